@@ -9,9 +9,24 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         .filter_map(|entry| Some(entry.ok()?.path()))
         .collect();
 
+    // servers are only needed for the mock node of the `testing` feature
+    let build_server = env::var("CARGO_FEATURE_TESTING").is_ok();
+
     // services
 
-    let config = tonic_build::configure().build_server(false);
+    let mut config = tonic_build::configure().build_server(build_server);
+    // re-exported enums, written in JSON under their proto names, e.g. `SUCCESS`
+    for name in [
+        "ResponseCodeEnum",
+        "TokenFreezeStatus",
+        "TokenKycStatus",
+        "TokenPauseStatus",
+    ] {
+        config = config.type_attribute(
+            format!(".proto.{}", name),
+            "#[derive(serde::Serialize)] #[serde(rename_all = \"SCREAMING_SNAKE_CASE\")]",
+        );
+    }
     config.compile(&services_protos, &[Path::new("proto/services/").to_owned()])?;
 
     // sdk
@@ -36,16 +51,21 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     create_dir_all(&mirror_out_dir)?;
 
     tonic_build::configure()
-        .build_server(false)
+        .build_server(build_server)
         .extern_path(".proto.Timestamp", "crate::proto::services::Timestamp")
         .extern_path(".proto.TopicID", "crate::proto::services::TopicId")
+        .extern_path(".proto.FileID", "crate::proto::services::FileId")
+        .extern_path(".proto.NodeAddress", "crate::proto::services::NodeAddress")
         .extern_path(
             ".proto.ConsensusMessageChunkInfo",
             "crate::proto::services::ConsensusMessageChunkInfo",
         )
         .out_dir(&mirror_out_dir)
         .compile(
-            &["proto/mirror/consensus_service.proto"],
+            &[
+                "proto/mirror/consensus_service.proto",
+                "proto/mirror/mirror_network_service.proto",
+            ],
             &["proto/mirror/", "proto/services/"],
         )?;
 
@@ -172,9 +192,19 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         )
         .extern_path(".proto.TokenType", "crate::proto::services::TokenType")
         .extern_path(".proto.ContractID", "crate::proto::services::ContractId")
+        .extern_path(".proto.Transaction", "crate::proto::services::Transaction")
+        .extern_path(
+            ".proto.TransactionRecord",
+            "crate::proto::services::TransactionRecord",
+        )
         .out_dir(&streams_out_dir)
         .compile(
-            &["proto/streams/account_balance_file.proto"],
+            &[
+                "proto/streams/account_balance_file.proto",
+                "proto/streams/record_stream_file.proto",
+                "proto/streams/signature_file.proto",
+                "proto/streams/sidecar_file.proto",
+            ],
             &["proto/streams/", "proto/services/"],
         )?;
 
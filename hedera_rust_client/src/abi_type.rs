@@ -0,0 +1,261 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::HederaError;
+
+/// A Solidity ABI type, e.g. `uint128`, `bytes32[]` or `(address,string)[2]`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AbiType {
+    Address,
+    Bool,
+    /// `intN`, with `N` in bits.
+    Int(usize),
+    /// `uintN`, with `N` in bits.
+    Uint(usize),
+    /// `bytesN`, with `N` in bytes.
+    FixedBytes(usize),
+    Bytes,
+    String,
+    /// An address followed by a function selector.
+    Function,
+    /// `T[]`
+    Array(Box<AbiType>),
+    /// `T[N]`
+    FixedArray(Box<AbiType>, usize),
+    /// `(T1,T2,...)`, a Solidity struct.
+    Tuple(Vec<AbiType>),
+}
+
+impl AbiType {
+    /// Whether values of this type are encoded after the head, at an offset.
+    pub fn is_dynamic(&self) -> bool {
+        match self {
+            AbiType::Bytes | AbiType::String | AbiType::Array(_) => true,
+            AbiType::FixedArray(ty, size) => *size > 0 && ty.is_dynamic(),
+            AbiType::Tuple(types) => types.iter().any(AbiType::is_dynamic),
+            _ => false,
+        }
+    }
+
+    /// Bytes taken in the head of an encoding: an offset for dynamic types,
+    /// the whole value otherwise.
+    pub(crate) fn head_size(&self) -> usize {
+        if self.is_dynamic() {
+            return 32;
+        }
+        match self {
+            AbiType::FixedArray(ty, size) => ty.head_size() * size,
+            AbiType::Tuple(types) => types.iter().map(AbiType::head_size).sum(),
+            _ => 32,
+        }
+    }
+
+    /// Resolves a JSON ABI `type`, where `tuple` stands for the `components`.
+    pub(crate) fn with_components(
+        ty: &str,
+        components: Option<Vec<AbiType>>,
+    ) -> Result<AbiType, HederaError> {
+        match ty.strip_prefix("tuple") {
+            Some(suffixes) => {
+                let components = components.ok_or_else(|| {
+                    HederaError::InvalidAbi(format!("tuple type `{}` without components", ty))
+                })?;
+                with_array_suffixes(AbiType::Tuple(components), suffixes)
+            }
+            None => ty.parse(),
+        }
+    }
+}
+
+fn invalid_type(ty: &str) -> HederaError {
+    HederaError::InvalidAbi(format!("unknown type `{}`", ty))
+}
+
+// bit sizes of `intN` and `uintN`
+fn int_size(size: &str, ty: &str) -> Result<usize, HederaError> {
+    if size.is_empty() {
+        return Ok(256);
+    }
+    match size.parse::<usize>() {
+        Ok(bits) if bits > 0 && bits <= 256 && bits % 8 == 0 => Ok(bits),
+        _ => Err(invalid_type(ty)),
+    }
+}
+
+fn elementary(ty: &str) -> Result<AbiType, HederaError> {
+    let parsed = match ty {
+        "address" => AbiType::Address,
+        "bool" => AbiType::Bool,
+        "string" => AbiType::String,
+        "bytes" => AbiType::Bytes,
+        "function" => AbiType::Function,
+        "byte" => AbiType::FixedBytes(1),
+        _ => {
+            if let Some(bits) = ty.strip_prefix("uint") {
+                AbiType::Uint(int_size(bits, ty)?)
+            } else if let Some(bits) = ty.strip_prefix("int") {
+                AbiType::Int(int_size(bits, ty)?)
+            } else if let Some(size) = ty.strip_prefix("bytes") {
+                match size.parse::<usize>() {
+                    Ok(size) if size > 0 && size <= 32 => AbiType::FixedBytes(size),
+                    _ => return Err(invalid_type(ty)),
+                }
+            } else {
+                return Err(invalid_type(ty));
+            }
+        }
+    };
+    Ok(parsed)
+}
+
+// wraps `ty` in the arrays of `suffixes`, e.g. `[2][]`
+fn with_array_suffixes(mut ty: AbiType, mut suffixes: &str) -> Result<AbiType, HederaError> {
+    while !suffixes.is_empty() {
+        let end = match (suffixes.starts_with('['), suffixes.find(']')) {
+            (true, Some(end)) => end,
+            _ => return Err(invalid_type(suffixes)),
+        };
+        ty = match &suffixes[1..end] {
+            "" => AbiType::Array(Box::new(ty)),
+            size => AbiType::FixedArray(
+                Box::new(ty),
+                size.parse().map_err(|_| invalid_type(suffixes))?,
+            ),
+        };
+        suffixes = &suffixes[end + 1..];
+    }
+    Ok(ty)
+}
+
+// splits `a,(b,c),d` on the commas outside of parentheses
+fn split_top_level(s: &str) -> Result<Vec<&str>, HederaError> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+        if depth < 0 {
+            return Err(invalid_type(s));
+        }
+    }
+    if depth != 0 {
+        return Err(invalid_type(s));
+    }
+    parts.push(&s[start..]);
+    Ok(parts)
+}
+
+/// Parses the type list of a signature, e.g. `address,(uint8,bool)[]`.
+pub(crate) fn parse_type_list(s: &str) -> Result<Vec<AbiType>, HederaError> {
+    if s.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    split_top_level(s)?.into_iter().map(str::parse).collect()
+}
+
+impl FromStr for AbiType {
+    type Err = HederaError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.starts_with('(') {
+            let close = match s.rfind(')') {
+                Some(close) => close,
+                None => return Err(invalid_type(s)),
+            };
+            let components = parse_type_list(&s[1..close])?;
+            return with_array_suffixes(AbiType::Tuple(components), &s[close + 1..]);
+        }
+        match s.find('[') {
+            Some(start) => with_array_suffixes(elementary(&s[..start])?, &s[start..]),
+            None => elementary(s),
+        }
+    }
+}
+
+impl fmt::Display for AbiType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AbiType::Address => write!(f, "address"),
+            AbiType::Bool => write!(f, "bool"),
+            AbiType::Int(bits) => write!(f, "int{}", bits),
+            AbiType::Uint(bits) => write!(f, "uint{}", bits),
+            AbiType::FixedBytes(size) => write!(f, "bytes{}", size),
+            AbiType::Bytes => write!(f, "bytes"),
+            AbiType::String => write!(f, "string"),
+            AbiType::Function => write!(f, "function"),
+            AbiType::Array(ty) => write!(f, "{}[]", ty),
+            AbiType::FixedArray(ty, size) => write!(f, "{}[{}]", ty, size),
+            AbiType::Tuple(types) => {
+                write!(f, "(")?;
+                for (i, ty) in types.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", ty)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_abi_type() {
+        assert_eq!("uint".parse::<AbiType>().unwrap(), AbiType::Uint(256));
+        assert_eq!("int16".parse::<AbiType>().unwrap(), AbiType::Int(16));
+        assert_eq!(
+            "bytes10[2][]".parse::<AbiType>().unwrap(),
+            AbiType::Array(Box::new(AbiType::FixedArray(
+                Box::new(AbiType::FixedBytes(10)),
+                2
+            )))
+        );
+        assert_eq!(
+            "(address,(bool,string)[])[3]".parse::<AbiType>().unwrap(),
+            AbiType::FixedArray(
+                Box::new(AbiType::Tuple(vec![
+                    AbiType::Address,
+                    AbiType::Array(Box::new(AbiType::Tuple(vec![
+                        AbiType::Bool,
+                        AbiType::String
+                    ])))
+                ])),
+                3
+            )
+        );
+        assert!("uint7".parse::<AbiType>().is_err());
+        assert!("bytes33".parse::<AbiType>().is_err());
+        assert!("(uint8".parse::<AbiType>().is_err());
+    }
+
+    #[test]
+    fn test_abi_type_display_roundtrip() {
+        for ty in ["uint128[]", "(address,(bool,string)[])[3]", "bytes32", "()"] {
+            assert_eq!(ty.parse::<AbiType>().unwrap().to_string(), ty);
+        }
+    }
+
+    #[test]
+    fn test_abi_type_is_dynamic() {
+        assert!(!"uint256[2]".parse::<AbiType>().unwrap().is_dynamic());
+        assert!("string[2]".parse::<AbiType>().unwrap().is_dynamic());
+        assert!("(uint8,bytes)".parse::<AbiType>().unwrap().is_dynamic());
+        assert_eq!(
+            "(uint8,bytes32)[2]".parse::<AbiType>().unwrap().head_size(),
+            128
+        );
+    }
+}
@@ -0,0 +1,755 @@
+use num::bigint::{BigInt, BigUint, Sign};
+use num::{One, ToPrimitive};
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::abi_type::AbiType;
+use crate::error::HederaError;
+
+/// A value of a Solidity ABI type.
+///
+/// Arrays, fixed size or not, are `Array`; structs are `Tuple`. The type the
+/// value is encoded as decides the rest, e.g. an `Uint` fits an `int64` as
+/// long as it's in range.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AbiValue {
+    Address([u8; 20]),
+    Bool(bool),
+    Int(BigInt),
+    Uint(BigUint),
+    FixedBytes(Vec<u8>),
+    Bytes(Vec<u8>),
+    String(String),
+    Function([u8; 24]),
+    Array(Vec<AbiValue>),
+    Tuple(Vec<AbiValue>),
+}
+
+impl AbiValue {
+    /// An address from its 40 hex characters, with or without `0x`.
+    pub fn address(hex: &str) -> Result<AbiValue, HederaError> {
+        let hex = hex.strip_prefix("0x").unwrap_or(hex);
+        if hex.len() != 40 {
+            return Err(HederaError::ContractAddressLength(hex.to_string()));
+        }
+        let mut address = [0; 20];
+        address.copy_from_slice(&hex::decode(hex)?);
+        Ok(AbiValue::Address(address))
+    }
+
+    pub fn as_address(&self) -> Option<[u8; 20]> {
+        match self {
+            AbiValue::Address(address) => Some(*address),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            AbiValue::Bool(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<&BigInt> {
+        match self {
+            AbiValue::Int(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_uint(&self) -> Option<&BigUint> {
+        match self {
+            AbiValue::Uint(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// An `Int` or `Uint` that fits an `i64`.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            AbiValue::Int(value) => value.to_i64(),
+            AbiValue::Uint(value) => value.to_i64(),
+            _ => None,
+        }
+    }
+
+    /// An `Int` or `Uint` that fits an `u64`.
+    pub fn as_u64(&self) -> Option<u64> {
+        match self {
+            AbiValue::Int(value) => value.to_u64(),
+            AbiValue::Uint(value) => value.to_u64(),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            AbiValue::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The bytes of `Bytes` or `FixedBytes`.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            AbiValue::Bytes(bytes) | AbiValue::FixedBytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// The elements of an `Array` or the fields of a `Tuple`.
+    pub fn as_slice(&self) -> Option<&[AbiValue]> {
+        match self {
+            AbiValue::Array(values) | AbiValue::Tuple(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    fn expected(self, ty: &str) -> HederaError {
+        HederaError::AbiTypeMismatch(ty.to_string(), self.to_string())
+    }
+
+    pub fn into_address(self) -> Result<[u8; 20], HederaError> {
+        match self {
+            AbiValue::Address(address) => Ok(address),
+            other => Err(other.expected("address")),
+        }
+    }
+
+    pub fn into_bool(self) -> Result<bool, HederaError> {
+        match self {
+            AbiValue::Bool(value) => Ok(value),
+            other => Err(other.expected("bool")),
+        }
+    }
+
+    /// An `Uint` as any integer it fits, e.g. `u64` or `BigUint`.
+    pub fn into_uint<T: TryFrom<BigUint>>(self) -> Result<T, HederaError> {
+        match self {
+            AbiValue::Uint(value) => {
+                T::try_from(value.clone()).map_err(|_| AbiValue::Uint(value).expected("uint"))
+            }
+            other => Err(other.expected("uint")),
+        }
+    }
+
+    /// An `Int` as any integer it fits, e.g. `i64` or `BigInt`.
+    pub fn into_int<T: TryFrom<BigInt>>(self) -> Result<T, HederaError> {
+        match self {
+            AbiValue::Int(value) => {
+                T::try_from(value.clone()).map_err(|_| AbiValue::Int(value).expected("int"))
+            }
+            other => Err(other.expected("int")),
+        }
+    }
+
+    /// The bytes of `Bytes` or `FixedBytes`.
+    pub fn into_bytes(self) -> Result<Vec<u8>, HederaError> {
+        match self {
+            AbiValue::Bytes(bytes) | AbiValue::FixedBytes(bytes) => Ok(bytes),
+            other => Err(other.expected("bytes")),
+        }
+    }
+
+    /// `FixedBytes` of exactly `N` bytes.
+    pub fn into_fixed_bytes<const N: usize>(self) -> Result<[u8; N], HederaError> {
+        match self {
+            AbiValue::FixedBytes(bytes) if bytes.len() == N => {
+                let mut fixed = [0; N];
+                fixed.copy_from_slice(&bytes);
+                Ok(fixed)
+            }
+            other => Err(other.expected(&format!("bytes{}", N))),
+        }
+    }
+
+    pub fn into_string(self) -> Result<String, HederaError> {
+        match self {
+            AbiValue::String(value) => Ok(value),
+            other => Err(other.expected("string")),
+        }
+    }
+
+    pub fn into_function(self) -> Result<[u8; 24], HederaError> {
+        match self {
+            AbiValue::Function(function) => Ok(function),
+            other => Err(other.expected("function")),
+        }
+    }
+
+    pub fn into_array(self) -> Result<Vec<AbiValue>, HederaError> {
+        match self {
+            AbiValue::Array(values) => Ok(values),
+            other => Err(other.expected("array")),
+        }
+    }
+
+    /// The fields of a `Tuple` of `len` fields.
+    pub fn into_tuple(self, len: usize) -> Result<Vec<AbiValue>, HederaError> {
+        match self {
+            AbiValue::Tuple(values) if values.len() == len => Ok(values),
+            AbiValue::Tuple(values) => Err(HederaError::AbiValueCount(len, values.len())),
+            other => Err(other.expected("tuple")),
+        }
+    }
+}
+
+macro_rules! impl_from_uint {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for AbiValue {
+                fn from(value: $ty) -> AbiValue {
+                    AbiValue::Uint(BigUint::from(value))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! impl_from_int {
+    ($($ty:ty),*) => {
+        $(
+            impl From<$ty> for AbiValue {
+                fn from(value: $ty) -> AbiValue {
+                    AbiValue::Int(BigInt::from(value))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_uint!(u8, u16, u32, u64, u128, BigUint);
+impl_from_int!(i8, i16, i32, i64, i128, BigInt);
+
+impl From<bool> for AbiValue {
+    fn from(value: bool) -> AbiValue {
+        AbiValue::Bool(value)
+    }
+}
+
+impl From<String> for AbiValue {
+    fn from(value: String) -> AbiValue {
+        AbiValue::String(value)
+    }
+}
+
+impl From<&str> for AbiValue {
+    fn from(value: &str) -> AbiValue {
+        AbiValue::String(value.to_string())
+    }
+}
+
+impl From<Vec<u8>> for AbiValue {
+    fn from(value: Vec<u8>) -> AbiValue {
+        AbiValue::Bytes(value)
+    }
+}
+
+impl From<Vec<AbiValue>> for AbiValue {
+    fn from(values: Vec<AbiValue>) -> AbiValue {
+        AbiValue::Array(values)
+    }
+}
+
+macro_rules! impl_from_vec {
+    ($($ty:ty),*) => {
+        $(
+            impl From<Vec<$ty>> for AbiValue {
+                fn from(values: Vec<$ty>) -> AbiValue {
+                    AbiValue::Array(values.into_iter().map(Into::into).collect())
+                }
+            }
+        )*
+    };
+}
+
+// `Vec<u8>` is `bytes` rather than `uint8[]`
+impl_from_vec!(u16, u32, u64, u128, i8, i16, i32, i64, i128, BigUint, BigInt, bool, String, &str);
+
+impl fmt::Display for AbiValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AbiValue::Address(address) => write!(f, "0x{}", hex::encode(address)),
+            AbiValue::Bool(value) => write!(f, "{}", value),
+            AbiValue::Int(value) => write!(f, "{}", value),
+            AbiValue::Uint(value) => write!(f, "{}", value),
+            AbiValue::FixedBytes(bytes) | AbiValue::Bytes(bytes) => {
+                write!(f, "0x{}", hex::encode(bytes))
+            }
+            AbiValue::String(value) => write!(f, "{:?}", value),
+            AbiValue::Function(function) => write!(f, "0x{}", hex::encode(function)),
+            AbiValue::Array(values) | AbiValue::Tuple(values) => {
+                let (open, close) = match self {
+                    AbiValue::Array(_) => ("[", "]"),
+                    _ => ("(", ")"),
+                };
+                write!(f, "{}", open)?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", value)?;
+                }
+                write!(f, "{}", close)
+            }
+        }
+    }
+}
+
+/// ABI encodes `values` as the sequence of `types`, as for the arguments of a
+/// function call without its selector.
+pub fn abi_encode(types: &[AbiType], values: &[AbiValue]) -> Result<Vec<u8>, HederaError> {
+    if types.len() != values.len() {
+        return Err(HederaError::AbiValueCount(types.len(), values.len()));
+    }
+    encode_sequence(types.iter(), values)
+}
+
+/// Decodes ABI encoded `data` as the sequence of `types`, as for the return
+/// values of a function. Malformed or truncated data is an error, never a
+/// panic.
+pub fn abi_decode(types: &[AbiType], data: &[u8]) -> Result<Vec<AbiValue>, HederaError> {
+    decode_sequence(types.iter(), data)
+}
+
+fn mismatch(ty: &AbiType, value: &AbiValue) -> HederaError {
+    HederaError::AbiTypeMismatch(ty.to_string(), value.to_string())
+}
+
+fn word(value: usize) -> [u8; 32] {
+    let mut word = [0; 32];
+    word[24..].copy_from_slice(&(value as u64).to_be_bytes());
+    word
+}
+
+// `bytes` followed by zeros up to a multiple of 32 bytes
+fn pad_right(bytes: &[u8]) -> Vec<u8> {
+    let mut padded = bytes.to_vec();
+    padded.resize(bytes.len() + (32 - bytes.len() % 32) % 32, 0);
+    padded
+}
+
+fn encode_sequence<'a, I>(types: I, values: &[AbiValue]) -> Result<Vec<u8>, HederaError>
+where
+    I: Iterator<Item = &'a AbiType> + Clone,
+{
+    let head_size: usize = types.clone().map(AbiType::head_size).sum();
+    let mut head = Vec::with_capacity(head_size);
+    let mut tail = Vec::new();
+    for (ty, value) in types.zip(values) {
+        let encoded = encode_value(ty, value)?;
+        if ty.is_dynamic() {
+            head.extend_from_slice(&word(head_size + tail.len()));
+            tail.extend(encoded);
+        } else {
+            head.extend(encoded);
+        }
+    }
+    head.extend(tail);
+    Ok(head)
+}
+
+fn encode_uint(ty: &AbiType, bits: usize, value: &BigUint) -> Result<Vec<u8>, HederaError> {
+    if value.bits() as usize > bits {
+        return Err(HederaError::AbiTypeMismatch(
+            ty.to_string(),
+            value.to_string(),
+        ));
+    }
+    let bytes = value.to_bytes_be();
+    let mut encoded = vec![0; 32 - bytes.len()];
+    encoded.extend(bytes);
+    Ok(encoded)
+}
+
+fn encode_int(ty: &AbiType, bits: usize, value: &BigInt) -> Result<Vec<u8>, HederaError> {
+    let limit = BigInt::one() << (bits - 1);
+    if *value >= limit || *value < -limit {
+        return Err(HederaError::AbiTypeMismatch(
+            ty.to_string(),
+            value.to_string(),
+        ));
+    }
+    let bytes = value.to_signed_bytes_be();
+    let fill = if value.sign() == Sign::Minus { 0xff } else { 0 };
+    let mut encoded = vec![fill; 32 - bytes.len()];
+    encoded.extend(bytes);
+    Ok(encoded)
+}
+
+fn encode_value(ty: &AbiType, value: &AbiValue) -> Result<Vec<u8>, HederaError> {
+    let encoded = match (ty, value) {
+        (AbiType::Address, AbiValue::Address(address)) => {
+            let mut encoded = vec![0; 12];
+            encoded.extend_from_slice(address);
+            encoded
+        }
+        (AbiType::Bool, AbiValue::Bool(value)) => word(*value as usize).to_vec(),
+        (AbiType::Uint(bits), AbiValue::Uint(value)) => encode_uint(ty, *bits, value)?,
+        (AbiType::Uint(bits), AbiValue::Int(int)) => match int.to_biguint() {
+            Some(uint) => encode_uint(ty, *bits, &uint)?,
+            None => return Err(mismatch(ty, value)),
+        },
+        (AbiType::Int(bits), AbiValue::Int(value)) => encode_int(ty, *bits, value)?,
+        (AbiType::Int(bits), AbiValue::Uint(value)) => {
+            encode_int(ty, *bits, &BigInt::from(value.clone()))?
+        }
+        (AbiType::FixedBytes(size), AbiValue::FixedBytes(bytes))
+        | (AbiType::FixedBytes(size), AbiValue::Bytes(bytes)) => {
+            if bytes.len() != *size {
+                return Err(mismatch(ty, value));
+            }
+            pad_right(bytes)
+        }
+        (AbiType::Function, AbiValue::Function(function)) => pad_right(function),
+        (AbiType::Bytes, AbiValue::Bytes(bytes))
+        | (AbiType::Bytes, AbiValue::FixedBytes(bytes)) => {
+            let mut encoded = word(bytes.len()).to_vec();
+            encoded.extend(pad_right(bytes));
+            encoded
+        }
+        (AbiType::String, AbiValue::String(value)) => {
+            let mut encoded = word(value.len()).to_vec();
+            encoded.extend(pad_right(value.as_bytes()));
+            encoded
+        }
+        (AbiType::Array(element), AbiValue::Array(values)) => {
+            let mut encoded = word(values.len()).to_vec();
+            encoded.extend(encode_sequence(
+                (0..values.len()).map(|_| &**element),
+                values,
+            )?);
+            encoded
+        }
+        (AbiType::FixedArray(element, size), AbiValue::Array(values)) => {
+            if values.len() != *size {
+                return Err(HederaError::AbiValueCount(*size, values.len()));
+            }
+            encode_sequence((0..*size).map(|_| &**element), values)?
+        }
+        (AbiType::Tuple(types), AbiValue::Tuple(values)) => {
+            if values.len() != types.len() {
+                return Err(HederaError::AbiValueCount(types.len(), values.len()));
+            }
+            encode_sequence(types.iter(), values)?
+        }
+        _ => return Err(mismatch(ty, value)),
+    };
+    Ok(encoded)
+}
+
+fn read_word(data: &[u8], at: usize) -> Result<&[u8], HederaError> {
+    at.checked_add(32)
+        .and_then(|end| data.get(at..end))
+        .ok_or_else(|| HederaError::AbiDecode(format!("no word at offset {}", at)))
+}
+
+// a word holding a length or an offset
+fn read_usize(data: &[u8], at: usize) -> Result<usize, HederaError> {
+    let word = read_word(data, at)?;
+    if word[..24].iter().any(|byte| *byte != 0) {
+        return Err(HederaError::AbiDecode(format!(
+            "offset or length at {} is too large",
+            at
+        )));
+    }
+    let mut value = [0; 8];
+    value.copy_from_slice(&word[24..]);
+    usize::try_from(u64::from_be_bytes(value))
+        .map_err(|_| HederaError::AbiDecode(format!("offset or length at {} is too large", at)))
+}
+
+fn read_bytes(data: &[u8], at: usize, len: usize) -> Result<&[u8], HederaError> {
+    at.checked_add(len)
+        .and_then(|end| data.get(at..end))
+        .ok_or_else(|| HederaError::AbiDecode(format!("{} bytes past the end of the data", len)))
+}
+
+fn decode_sequence<'a, I>(types: I, data: &[u8]) -> Result<Vec<AbiValue>, HederaError>
+where
+    I: Iterator<Item = &'a AbiType>,
+{
+    let mut values = Vec::new();
+    let mut head = 0;
+    for ty in types {
+        let value = if ty.is_dynamic() {
+            let offset = read_usize(data, head)?;
+            let tail = data.get(offset..).ok_or_else(|| {
+                HederaError::AbiDecode(format!("offset {} past the end of the data", offset))
+            })?;
+            decode_value(ty, tail)?
+        } else {
+            decode_value(ty, &data[head.min(data.len())..])?
+        };
+        values.push(value);
+        head += ty.head_size();
+    }
+    Ok(values)
+}
+
+fn decode_value(ty: &AbiType, data: &[u8]) -> Result<AbiValue, HederaError> {
+    let value = match ty {
+        AbiType::Address => {
+            let mut address = [0; 20];
+            address.copy_from_slice(&read_word(data, 0)?[12..]);
+            AbiValue::Address(address)
+        }
+        AbiType::Bool => match read_usize(data, 0)? {
+            0 => AbiValue::Bool(false),
+            1 => AbiValue::Bool(true),
+            other => return Err(HederaError::AbiDecode(format!("invalid bool {}", other))),
+        },
+        AbiType::Uint(bits) => {
+            let value = BigUint::from_bytes_be(read_word(data, 0)?);
+            if value.bits() as usize > *bits {
+                return Err(HederaError::AbiDecode(format!(
+                    "{} overflows {}",
+                    value, ty
+                )));
+            }
+            AbiValue::Uint(value)
+        }
+        AbiType::Int(bits) => {
+            let value = BigInt::from_signed_bytes_be(read_word(data, 0)?);
+            let limit = BigInt::one() << (bits - 1);
+            if value >= limit || value < -limit {
+                return Err(HederaError::AbiDecode(format!(
+                    "{} overflows {}",
+                    value, ty
+                )));
+            }
+            AbiValue::Int(value)
+        }
+        AbiType::FixedBytes(size) => AbiValue::FixedBytes(read_word(data, 0)?[..*size].to_vec()),
+        AbiType::Function => {
+            let mut function = [0; 24];
+            function.copy_from_slice(&read_word(data, 0)?[..24]);
+            AbiValue::Function(function)
+        }
+        AbiType::Bytes => {
+            let len = read_usize(data, 0)?;
+            AbiValue::Bytes(read_bytes(data, 32, len)?.to_vec())
+        }
+        AbiType::String => {
+            let len = read_usize(data, 0)?;
+            let bytes = read_bytes(data, 32, len)?.to_vec();
+            AbiValue::String(
+                String::from_utf8(bytes)
+                    .map_err(|_| HederaError::AbiDecode("string is not utf-8".to_string()))?,
+            )
+        }
+        AbiType::Array(element) => {
+            let len = read_usize(data, 0)?;
+            // every element takes at least one word, so a length larger than
+            // the data is malformed rather than a reason to allocate
+            if len > data.len() / 32 {
+                return Err(HederaError::AbiDecode(format!(
+                    "array length {} past the end of the data",
+                    len
+                )));
+            }
+            AbiValue::Array(decode_sequence((0..len).map(|_| &**element), &data[32..])?)
+        }
+        AbiType::FixedArray(element, size) => {
+            AbiValue::Array(decode_sequence((0..*size).map(|_| &**element), data)?)
+        }
+        AbiType::Tuple(types) => AbiValue::Tuple(decode_sequence(types.iter(), data)?),
+    };
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi_type::parse_type_list;
+
+    fn encode(types: &str, values: Vec<AbiValue>) -> String {
+        hex::encode(abi_encode(&parse_type_list(types).unwrap(), &values).unwrap())
+    }
+
+    fn words(words: &[&str]) -> String {
+        words
+            .iter()
+            .map(|word| format!("{:0>64}", word))
+            .collect::<String>()
+    }
+
+    #[test]
+    fn test_abi_encode_static() {
+        assert_eq!(
+            encode("uint32,bool", vec![69u32.into(), true.into()]),
+            words(&["45", "1"])
+        );
+        assert_eq!(
+            encode("int16,int256", vec![(-1i16).into(), (-2i64).into()]),
+            "f".repeat(64) + &"f".repeat(63) + "e"
+        );
+        assert_eq!(
+            encode("uint128", vec![u128::MAX.into()]),
+            words(&[&"f".repeat(32)])
+        );
+    }
+
+    #[test]
+    fn test_abi_encode_dynamic() {
+        // from the examples of the Solidity ABI specification
+        assert_eq!(
+            encode(
+                "bytes,bool,uint256[]",
+                vec![
+                    b"dave".to_vec().into(),
+                    true.into(),
+                    vec![1u64, 2, 3].into(),
+                ]
+            ),
+            words(&[
+                "60",
+                "1",
+                "a0",
+                "4",
+                &format!("{:0<64}", hex::encode("dave")),
+                "3",
+                "1",
+                "2",
+                "3",
+            ])
+        );
+        assert_eq!(
+            encode(
+                "uint256[][],string[]",
+                vec![
+                    AbiValue::Array(vec![vec![1u64, 2].into(), vec![3u64].into()]),
+                    vec!["one", "two", "three"].into(),
+                ]
+            ),
+            words(&[
+                "40",
+                "140",
+                "2",
+                "40",
+                "a0",
+                "2",
+                "1",
+                "2",
+                "1",
+                "3",
+                "3",
+                "60",
+                "a0",
+                "e0",
+                "3",
+                &format!("{:0<64}", hex::encode("one")),
+                "3",
+                &format!("{:0<64}", hex::encode("two")),
+                "5",
+                &format!("{:0<64}", hex::encode("three")),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_abi_encode_tuple() {
+        let value = AbiValue::Tuple(vec![7u8.into(), "hi".into()]);
+        assert_eq!(
+            encode(
+                "(uint8,string),bool[2]",
+                vec![value, vec![true, false].into()]
+            ),
+            words(&[
+                "60",
+                "1",
+                "0",
+                "7",
+                "40",
+                "2",
+                &format!("{:0<64}", hex::encode("hi")),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_abi_encode_mismatch() {
+        let types = parse_type_list("int8").unwrap();
+        assert!(matches!(
+            abi_encode(&types, &[128u32.into()]),
+            Err(HederaError::AbiTypeMismatch(..))
+        ));
+        assert!(matches!(
+            abi_encode(&types, &["1".into()]),
+            Err(HederaError::AbiTypeMismatch(..))
+        ));
+        assert!(matches!(
+            abi_encode(&types, &[]),
+            Err(HederaError::AbiValueCount(1, 0))
+        ));
+        let types = parse_type_list("uint8").unwrap();
+        assert!(abi_encode(&types, &[(-1i8).into()]).is_err());
+    }
+
+    #[test]
+    fn test_abi_decode_roundtrip() {
+        let types = parse_type_list("int16,(uint8,string)[],bytes3,bool[2],uint128").unwrap();
+        let values = vec![
+            (-300i16).into(),
+            AbiValue::Array(vec![
+                AbiValue::Tuple(vec![7u8.into(), "hi".into()]),
+                AbiValue::Tuple(vec![8u8.into(), "".into()]),
+            ]),
+            AbiValue::FixedBytes(vec![1, 2, 3]),
+            vec![true, false].into(),
+            u128::MAX.into(),
+        ];
+        let encoded = abi_encode(&types, &values).unwrap();
+        assert_eq!(abi_decode(&types, &encoded).unwrap(), values);
+    }
+
+    #[test]
+    fn test_abi_decode_malformed() {
+        let types = parse_type_list("string").unwrap();
+        let encoded = abi_encode(&types, &["hello".into()]).unwrap();
+        for len in 0..encoded.len() - 32 {
+            assert!(abi_decode(&types, &encoded[..len]).is_err());
+        }
+
+        let types = parse_type_list("uint8,bool").unwrap();
+        assert!(abi_decode(
+            &types,
+            &abi_encode(
+                &parse_type_list("uint16,bool").unwrap(),
+                &[256u16.into(), true.into()]
+            )
+            .unwrap()
+        )
+        .is_err());
+        let mut bad_bool = [0u8; 64];
+        bad_bool[63] = 2;
+        assert!(abi_decode(&types, &bad_bool).is_err());
+
+        let types = parse_type_list("uint256[]").unwrap();
+        let mut huge = words(&["20", "ffffffffffff"]);
+        huge.truncate(128);
+        assert!(abi_decode(&types, &hex::decode(huge).unwrap()).is_err());
+    }
+
+    #[test]
+    fn test_abi_value_into() {
+        assert_eq!(AbiValue::from(300u64).into_uint::<u16>().unwrap(), 300);
+        assert!(AbiValue::from(300u64).into_uint::<u8>().is_err());
+        assert_eq!(AbiValue::from(-3i8).into_int::<i64>().unwrap(), -3);
+        assert!(AbiValue::from(true).into_string().is_err());
+        assert_eq!(
+            AbiValue::FixedBytes(vec![1, 2])
+                .into_fixed_bytes::<2>()
+                .unwrap(),
+            [1, 2]
+        );
+        assert!(AbiValue::FixedBytes(vec![1, 2])
+            .into_fixed_bytes::<3>()
+            .is_err());
+        assert!(matches!(
+            AbiValue::Tuple(vec![true.into()]).into_tuple(2),
+            Err(HederaError::AbiValueCount(2, 1))
+        ));
+    }
+}
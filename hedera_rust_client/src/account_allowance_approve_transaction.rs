@@ -0,0 +1,223 @@
+use crate::entity_id::validate_option_id_checksum;
+
+use crate::hbar_allowance::HbarAllowance;
+use crate::token_allowance::TokenAllowance;
+use crate::token_nft_allowance::TokenNftAllowance;
+use crate::transaction::Transaction;
+use crate::AccountId;
+use crate::Client;
+use crate::Hbar;
+use crate::HederaError;
+use crate::NftId;
+use crate::TokenId;
+use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
+
+#[derive(TransactionSchedule, TransactionExecute, Debug, Clone, PartialEq)]
+#[hedera_rust_client_derive(service(
+    method_service_name = "crypto",
+    method_service_fn = "approve_allowances"
+))]
+pub struct AccountAllowanceApproveTransaction {
+    transaction: Transaction,
+    services: Proto,
+}
+
+impl AccountAllowanceApproveTransaction {
+    pub fn new() -> AccountAllowanceApproveTransaction {
+        let transaction = Transaction::with_max_transaction_fee(Hbar::new(2.0));
+        let services = Proto::new();
+        AccountAllowanceApproveTransaction {
+            transaction,
+            services,
+        }
+    }
+
+    fn validate_network_on_ids(&self, client: &Client) -> Result<(), HederaError> {
+        for ap in self.services.crypto_allowances.iter() {
+            validate_option_id_checksum(&ap.owner_account_id, client)?;
+            validate_option_id_checksum(&ap.spender_account_id, client)?;
+        }
+        for ap in self.services.token_allowances.iter() {
+            validate_option_id_checksum(&ap.token_id, client)?;
+            validate_option_id_checksum(&ap.owner_account_id, client)?;
+            validate_option_id_checksum(&ap.spender_account_id, client)?;
+        }
+        for ap in self.services.nft_allowances.iter() {
+            validate_option_id_checksum(&ap.token_id, client)?;
+            validate_option_id_checksum(&ap.owner_account_id, client)?;
+            validate_option_id_checksum(&ap.spender_account_id, client)?;
+            validate_option_id_checksum(&ap.delegating_spender, client)?;
+        }
+        Ok(())
+    }
+
+    pub fn hbar_approvals(&self) -> Vec<HbarAllowance> {
+        self.services.crypto_allowances.clone()
+    }
+
+    pub fn token_approvals(&self) -> Vec<TokenAllowance> {
+        self.services.token_allowances.clone()
+    }
+
+    pub fn token_nft_approvals(&self) -> Vec<TokenNftAllowance> {
+        self.services.nft_allowances.clone()
+    }
+
+    pub fn approve_hbar_allowance(
+        &mut self,
+        owner_account_id: AccountId,
+        spender_account_id: AccountId,
+        amount: Hbar,
+    ) -> Result<&mut Self, HederaError> {
+        self.require_not_frozen()?;
+        self.services.crypto_allowances.push(HbarAllowance::new(
+            Some(owner_account_id),
+            Some(spender_account_id),
+            amount,
+        ));
+        Ok(self)
+    }
+
+    pub fn approve_token_allowance(
+        &mut self,
+        token_id: TokenId,
+        owner_account_id: AccountId,
+        spender_account_id: AccountId,
+        amount: i64,
+    ) -> Result<&mut Self, HederaError> {
+        self.require_not_frozen()?;
+        self.services.token_allowances.push(TokenAllowance::new(
+            Some(token_id),
+            Some(owner_account_id),
+            Some(spender_account_id),
+            amount,
+        ));
+        Ok(self)
+    }
+
+    pub fn approve_token_nft_allowance(
+        &mut self,
+        nft_id: NftId,
+        owner_account_id: AccountId,
+        spender_account_id: AccountId,
+    ) -> Result<&mut Self, HederaError> {
+        self.approve_token_nft_allowance_serial(nft_id, owner_account_id, spender_account_id, None)
+    }
+
+    pub fn approve_token_nft_allowance_with_delegating_spender(
+        &mut self,
+        nft_id: NftId,
+        owner_account_id: AccountId,
+        spender_account_id: AccountId,
+        delegating_spender: AccountId,
+    ) -> Result<&mut Self, HederaError> {
+        self.approve_token_nft_allowance_serial(
+            nft_id,
+            owner_account_id,
+            spender_account_id,
+            Some(delegating_spender),
+        )
+    }
+
+    pub fn approve_token_nft_allowance_all_serials(
+        &mut self,
+        token_id: TokenId,
+        owner_account_id: AccountId,
+        spender_account_id: AccountId,
+    ) -> Result<&mut Self, HederaError> {
+        self.set_token_nft_allowance_all_serials(
+            token_id,
+            owner_account_id,
+            spender_account_id,
+            true,
+        )
+    }
+
+    pub fn delete_token_nft_allowance_all_serials(
+        &mut self,
+        token_id: TokenId,
+        owner_account_id: AccountId,
+        spender_account_id: AccountId,
+    ) -> Result<&mut Self, HederaError> {
+        self.set_token_nft_allowance_all_serials(
+            token_id,
+            owner_account_id,
+            spender_account_id,
+            false,
+        )
+    }
+
+    // serials approved to the same spender by the same owner share a single allowance entry
+    fn approve_token_nft_allowance_serial(
+        &mut self,
+        nft_id: NftId,
+        owner_account_id: AccountId,
+        spender_account_id: AccountId,
+        delegating_spender: Option<AccountId>,
+    ) -> Result<&mut Self, HederaError> {
+        self.require_not_frozen()?;
+        let existing = self.services.nft_allowances.iter_mut().find(|ap| {
+            ap.token_id == Some(nft_id.token_id)
+                && ap.owner_account_id == Some(owner_account_id)
+                && ap.spender_account_id == Some(spender_account_id)
+                && ap.delegating_spender == delegating_spender
+                && ap.all_serials.is_none()
+        });
+        match existing {
+            Some(ap) => ap.serial_numbers.push(nft_id.serial_number),
+            None => self.services.nft_allowances.push(TokenNftAllowance::new(
+                Some(nft_id.token_id),
+                Some(owner_account_id),
+                Some(spender_account_id),
+                vec![nft_id.serial_number],
+                None,
+                delegating_spender,
+            )),
+        }
+        Ok(self)
+    }
+
+    fn set_token_nft_allowance_all_serials(
+        &mut self,
+        token_id: TokenId,
+        owner_account_id: AccountId,
+        spender_account_id: AccountId,
+        all_serials: bool,
+    ) -> Result<&mut Self, HederaError> {
+        self.require_not_frozen()?;
+        self.services.nft_allowances.push(TokenNftAllowance::new(
+            Some(token_id),
+            Some(owner_account_id),
+            Some(spender_account_id),
+            Vec::new(),
+            Some(all_serials),
+            None,
+        ));
+        Ok(self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
+#[hedera_rust_client_derive(proto(
+    proto_enum = "CryptoApproveAllowance",
+    proto_type = "CryptoApproveAllowanceTransactionBody"
+))]
+struct Proto {
+    #[hedera_rust_client_derive(to_proto_vec = "CryptoAllowance")]
+    pub crypto_allowances: Vec<HbarAllowance>,
+    #[hedera_rust_client_derive(to_proto_vec = "NftAllowance")]
+    pub nft_allowances: Vec<TokenNftAllowance>,
+    #[hedera_rust_client_derive(to_proto_vec = "TokenAllowance")]
+    pub token_allowances: Vec<TokenAllowance>,
+}
+
+impl Proto {
+    pub fn new() -> Self {
+        Proto {
+            crypto_allowances: Vec::new(),
+            nft_allowances: Vec::new(),
+            token_allowances: Vec::new(),
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use crate::entity_id::validate_option_id_checksum;
+
+use crate::proto::{services, ToProto};
+use crate::token_nft_allowance::TokenNftAllowance;
+use crate::transaction::Transaction;
+use crate::AccountId;
+use crate::Client;
+use crate::Hbar;
+use crate::HederaError;
+use crate::NftId;
+use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
+
+#[derive(TransactionSchedule, TransactionExecute, Debug, Clone, PartialEq)]
+#[hedera_rust_client_derive(service(
+    method_service_name = "crypto",
+    method_service_fn = "delete_allowances"
+))]
+pub struct AccountAllowanceDeleteTransaction {
+    transaction: Transaction,
+    services: Proto,
+}
+
+impl AccountAllowanceDeleteTransaction {
+    pub fn new() -> AccountAllowanceDeleteTransaction {
+        let transaction = Transaction::with_max_transaction_fee(Hbar::new(2.0));
+        let services = Proto::new();
+        AccountAllowanceDeleteTransaction {
+            transaction,
+            services,
+        }
+    }
+
+    fn validate_network_on_ids(&self, client: &Client) -> Result<(), HederaError> {
+        for ap in self.services.nft_allowances.iter() {
+            validate_option_id_checksum(&ap.token_id, client)?;
+            validate_option_id_checksum(&ap.owner_account_id, client)?;
+        }
+        Ok(())
+    }
+
+    pub fn token_nft_allowance_deletions(&self) -> Vec<TokenNftAllowance> {
+        self.services.nft_allowances.clone()
+    }
+
+    // removes every spender's allowance on the serial; serials of the same token and owner are grouped
+    pub fn delete_all_token_nft_allowances(
+        &mut self,
+        nft_id: NftId,
+        owner_account_id: AccountId,
+    ) -> Result<&mut Self, HederaError> {
+        self.require_not_frozen()?;
+        let existing = self.services.nft_allowances.iter_mut().find(|ap| {
+            ap.token_id == Some(nft_id.token_id) && ap.owner_account_id == Some(owner_account_id)
+        });
+        match existing {
+            Some(ap) => ap.serial_numbers.push(nft_id.serial_number),
+            None => self.services.nft_allowances.push(TokenNftAllowance::new(
+                Some(nft_id.token_id),
+                Some(owner_account_id),
+                None,
+                vec![nft_id.serial_number],
+                None,
+                None,
+            )),
+        }
+        Ok(self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
+#[hedera_rust_client_derive(proto(
+    proto_enum = "CryptoDeleteAllowance",
+    proto_type = "CryptoDeleteAllowanceTransactionBody"
+))]
+struct Proto {
+    #[hedera_rust_client_derive(
+        to_proto_with_fn = "token_nft_allowances_to_remove_proto_vec",
+        from_proto_with_fn = "token_nft_allowances_from_remove_proto_vec"
+    )]
+    pub nft_allowances: Vec<TokenNftAllowance>,
+}
+
+impl Proto {
+    pub fn new() -> Self {
+        Proto {
+            nft_allowances: Vec::new(),
+        }
+    }
+}
+
+fn token_nft_allowances_to_remove_proto_vec(
+    allowances: &[TokenNftAllowance],
+) -> Result<Vec<services::NftRemoveAllowance>, HederaError> {
+    allowances.iter().map(|x| x.to_proto()).collect()
+}
+
+fn token_nft_allowances_from_remove_proto_vec(
+    allowances: Vec<services::NftRemoveAllowance>,
+) -> Result<Vec<TokenNftAllowance>, HederaError> {
+    allowances
+        .into_iter()
+        .map(TokenNftAllowance::try_from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TokenId;
+
+    #[test]
+    fn test_delete_all_token_nft_allowances_grouping() -> Result<(), HederaError> {
+        let (token_a, token_b) = (TokenId::simple(5), TokenId::simple(6));
+        let (alice, bob) = (AccountId::simple(1001), AccountId::simple(1002));
+        let mut tx = AccountAllowanceDeleteTransaction::new();
+        tx.delete_all_token_nft_allowances(NftId::new(token_a, 1), alice)?
+            .delete_all_token_nft_allowances(NftId::new(token_b, 1), alice)?
+            .delete_all_token_nft_allowances(NftId::new(token_a, 2), bob)?
+            .delete_all_token_nft_allowances(NftId::new(token_a, 3), alice)?;
+
+        let groups = tx
+            .token_nft_allowance_deletions()
+            .into_iter()
+            .map(|ap| (ap.token_id, ap.owner_account_id, ap.serial_numbers))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            groups,
+            vec![
+                (Some(token_a), Some(alice), vec![1, 3]),
+                (Some(token_b), Some(alice), vec![1]),
+                (Some(token_a), Some(bob), vec![2]),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_delete_allowance_proto() -> Result<(), HederaError> {
+        let token_id = TokenId::simple(5);
+        let owner = AccountId::simple(1001);
+        let mut tx = AccountAllowanceDeleteTransaction::new();
+        tx.delete_all_token_nft_allowances(NftId::new(token_id, 1), owner)?
+            .delete_all_token_nft_allowances(NftId::new(token_id, 4), owner)?;
+
+        let data = tx.services.proto()?;
+        let body = match &data {
+            services::transaction_body::Data::CryptoDeleteAllowance(body) => body,
+            other => panic!("expected a delete allowance body, got {:?}", other),
+        };
+        assert_eq!(
+            body.nft_allowances,
+            vec![services::NftRemoveAllowance {
+                token_id: Some(token_id.to_proto()?),
+                owner: Some(owner.to_proto()?),
+                serial_numbers: vec![1, 4],
+            }]
+        );
+        assert_eq!(Proto::from_data(data)?, tx.services);
+        Ok(())
+    }
+}
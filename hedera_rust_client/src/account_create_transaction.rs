@@ -1,5 +1,6 @@
 use chrono::Duration;
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
 use std::convert::TryFrom;
 
 use crate::account_id::AccountId;
@@ -50,7 +51,7 @@ impl AccountCreateTransaction {
     gen_transaction_staked_id_option_fns!();
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "CryptoCreateAccount",
     proto_type = "CryptoCreateTransactionBody"
@@ -65,6 +66,7 @@ struct Proto {
     pub receive_record_threshold: u64, // depreciated
     pub receiver_sig_required: bool,
     #[hedera_rust_client_derive(to_option_proto)]
+    #[serde(serialize_with = "crate::json::option_duration")]
     pub auto_renew_period: Option<Duration>,
     #[hedera_rust_client_derive(to_option_proto)]
     pub shard_id: Option<ShardId>,
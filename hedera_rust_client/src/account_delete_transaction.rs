@@ -5,6 +5,7 @@ use crate::Client;
 use crate::Hbar;
 use crate::HederaError;
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
 
 #[derive(TransactionSchedule, TransactionExecute, Debug, Clone, PartialEq)]
 #[hedera_rust_client_derive(service(
@@ -37,7 +38,7 @@ impl AccountDeleteTransaction {
     gen_transaction_transfer_account_id_fns!();
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "CryptoDelete",
     proto_type = "CryptoDeleteTransactionBody"
@@ -13,7 +13,7 @@ use crate::proto::services::AccountId as ProtoAccountId;
 use crate::proto::ToProto;
 use crate::PublicKey;
 
-#[derive(IdPartialEq, Debug, Clone, Copy, Hash, Serialize, Deserialize)]
+#[derive(IdPartialEq, Debug, Clone, Copy, Hash)]
 #[hedera_rust_client_derive(field_name = "account")]
 pub struct AccountId {
     shard_num: i64,
@@ -128,6 +128,8 @@ impl std::str::FromStr for AccountId {
     }
 }
 
+serde_from_str!(AccountId);
+
 #[derive(Debug, Clone, PartialEq, Eq, Copy, Hash, Serialize, Deserialize)]
 pub enum Account {
     AccountNum(i64),
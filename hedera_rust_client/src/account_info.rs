@@ -1,4 +1,5 @@
 use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
 use std::collections::HashMap;
 use std::convert::TryFrom;
 
@@ -11,7 +12,7 @@ use crate::AccountId;
 use crate::Hbar;
 use crate::TokenId;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct AccountInfo {
     pub account_id: AccountId,
     pub contract_account_id: String,
@@ -22,7 +23,9 @@ pub struct AccountInfo {
     pub balance: Hbar,
     pub receiver_sig_required: bool,
     pub expiration_time: Option<DateTime<Utc>>,
+    #[serde(serialize_with = "crate::json::option_duration")]
     pub auto_renew_period: Option<Duration>,
+    #[serde(serialize_with = "crate::json::sorted_values")]
     pub token_relationships: HashMap<TokenId, TokenRelationship>,
     pub memo: String,
 }
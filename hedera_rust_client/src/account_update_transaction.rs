@@ -1,5 +1,6 @@
 use chrono::{DateTime, Duration, Utc};
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
 
 use crate::entity_id::validate_option_id_checksum;
 use crate::proto::services;
@@ -45,7 +46,7 @@ impl AccountUpdateTransaction {
     gen_transaction_staked_id_option_fns!();
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "CryptoUpdateAccount",
     proto_type = "CryptoUpdateTransactionBody"
@@ -59,16 +60,20 @@ struct Proto {
     pub proxy_account_id: Option<AccountId>,
     pub proxy_fraction: i32,
     #[hedera_rust_client_derive(to_option_proto)]
+    #[serde(serialize_with = "crate::json::option_duration")]
     pub auto_renew_period: Option<Duration>,
     #[hedera_rust_client_derive(to_option_proto)]
     pub expiration_time: Option<DateTime<Utc>>,
     pub memo: Option<String>,
     pub max_automatic_token_associations: Option<i32>,
     pub decline_reward: Option<bool>,
+    #[serde(skip)]
     pub send_record_threshold_field:
         Option<services::crypto_update_transaction_body::SendRecordThresholdField>,
+    #[serde(skip)]
     pub receive_record_threshold_field:
         Option<services::crypto_update_transaction_body::ReceiveRecordThresholdField>,
+    #[serde(serialize_with = "receiver_sig_required_json")]
     pub receiver_sig_required_field:
         Option<services::crypto_update_transaction_body::ReceiverSigRequiredField>,
     #[hedera_rust_client_derive(to_option_proto)]
@@ -94,3 +99,17 @@ impl Proto {
         }
     }
 }
+
+fn receiver_sig_required_json<S: serde::Serializer>(
+    field: &Option<services::crypto_update_transaction_body::ReceiverSigRequiredField>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    use services::crypto_update_transaction_body::ReceiverSigRequiredField;
+    match field {
+        Some(ReceiverSigRequiredField::ReceiverSigRequired(required))
+        | Some(ReceiverSigRequiredField::ReceiverSigRequiredWrapper(required)) => {
+            serializer.serialize_some(required)
+        }
+        None => serializer.serialize_none(),
+    }
+}
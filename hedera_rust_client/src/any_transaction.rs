@@ -0,0 +1,297 @@
+use chrono::Duration;
+use serde::ser::Error;
+use serde::{Serialize, Serializer};
+use std::collections::HashMap;
+
+use crate::error::HederaError;
+use crate::proto::services::transaction_body::Data as ProtoData;
+use crate::signer::Signer;
+use crate::transaction::Transaction;
+use crate::*;
+
+macro_rules! any_transaction {
+    ($($variant:ident($ty:ident) => $data:ident,)*) => {
+        /// A transaction of any type, decoded from bytes.
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum AnyTransaction {
+            $($variant($ty),)*
+        }
+
+        $(
+            impl From<$ty> for AnyTransaction {
+                fn from(transaction: $ty) -> AnyTransaction {
+                    AnyTransaction::$variant(transaction)
+                }
+            }
+        )*
+
+        impl AnyTransaction {
+            fn from_transaction(transaction: Transaction) -> Result<AnyTransaction, HederaError> {
+                let data = match transaction.chunk_data()?.into_iter().next() {
+                    Some((_, data)) => data,
+                    None => return Err(HederaError::UnsupportedTransactionBodyType),
+                };
+                match data {
+                    $(ProtoData::$data(_) => Ok(AnyTransaction::$variant($ty::from_transaction(transaction)?)),)*
+                    _ => Err(HederaError::UnsupportedTransactionBodyType),
+                }
+            }
+
+            pub fn to_bytes(&mut self) -> Result<Vec<u8>, HederaError> {
+                match self {
+                    $(AnyTransaction::$variant(tx) => tx.to_bytes(),)*
+                }
+            }
+
+            pub fn transaction_hash(&mut self) -> Result<Vec<u8>, HederaError> {
+                match self {
+                    $(AnyTransaction::$variant(tx) => tx.transaction_hash(),)*
+                }
+            }
+
+            pub fn transaction_id(&self) -> Result<TransactionId, HederaError> {
+                match self {
+                    $(AnyTransaction::$variant(tx) => tx.transaction_id(),)*
+                }
+            }
+
+            pub fn node_account_ids(&self) -> Vec<AccountId> {
+                match self {
+                    $(AnyTransaction::$variant(tx) => tx.node_account_ids(),)*
+                }
+            }
+
+            pub fn max_transaction_fee(&self) -> Hbar {
+                match self {
+                    $(AnyTransaction::$variant(tx) => tx.max_transaction_fee(),)*
+                }
+            }
+
+            pub fn transaction_memo(&self) -> String {
+                match self {
+                    $(AnyTransaction::$variant(tx) => tx.transaction_memo(),)*
+                }
+            }
+
+            pub fn transaction_valid_duration(&self) -> Option<Duration> {
+                match self {
+                    $(AnyTransaction::$variant(tx) => tx.transaction_valid_duration(),)*
+                }
+            }
+
+            pub fn signatures(
+                &self,
+            ) -> Result<HashMap<AccountId, HashMap<PublicKey, Vec<u8>>>, HederaError> {
+                match self {
+                    $(AnyTransaction::$variant(tx) => tx.signatures(),)*
+                }
+            }
+
+            pub fn key_requirement(
+                &self,
+                key: &Key,
+            ) -> Result<HashMap<AccountId, KeyRequirement>, HederaError> {
+                match self {
+                    $(AnyTransaction::$variant(tx) => tx.key_requirement(key),)*
+                }
+            }
+
+            pub fn sign(&mut self, private_key: &PrivateKey) -> Result<&mut Self, HederaError> {
+                match self {
+                    $(AnyTransaction::$variant(tx) => {
+                        tx.sign(private_key)?;
+                    })*
+                }
+                Ok(self)
+            }
+
+            pub async fn sign_with_signer(
+                &mut self,
+                signer: &dyn Signer,
+            ) -> Result<&mut Self, HederaError> {
+                match self {
+                    $(AnyTransaction::$variant(tx) => {
+                        tx.sign_with_signer(signer).await?;
+                    })*
+                }
+                Ok(self)
+            }
+
+            pub fn describe(&self) -> Result<TransactionDescription, HederaError> {
+                match self {
+                    $(AnyTransaction::$variant(tx) => tx.describe(),)*
+                }
+            }
+        }
+
+        impl Serialize for AnyTransaction {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                match self {
+                    $(AnyTransaction::$variant(tx) => tx.serialize(serializer),)*
+                }
+            }
+        }
+
+        /// Writes the data of a transaction body as the fields of its type.
+        pub(crate) struct AnyTransactionData<'a>(pub(crate) &'a ProtoData);
+
+        impl Serialize for AnyTransactionData<'_> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                match self.0 {
+                    $(ProtoData::$data(_) => $ty::serialize_data(self.0.clone(), serializer),)*
+                    _ => Err(S::Error::custom("unsupported transaction body type")),
+                }
+            }
+        }
+
+        pub(crate) fn transaction_type_name(data: &ProtoData) -> Option<&'static str> {
+            match data {
+                $(ProtoData::$data(_) => Some(stringify!($ty)),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+any_transaction! {
+    AccountAllowanceApprove(AccountAllowanceApproveTransaction) => CryptoApproveAllowance,
+    AccountAllowanceDelete(AccountAllowanceDeleteTransaction) => CryptoDeleteAllowance,
+    AccountCreate(AccountCreateTransaction) => CryptoCreateAccount,
+    AccountDelete(AccountDeleteTransaction) => CryptoDelete,
+    AccountUpdate(AccountUpdateTransaction) => CryptoUpdateAccount,
+    ContractCreate(ContractCreateTransaction) => ContractCreateInstance,
+    ContractDelete(ContractDeleteTransaction) => ContractDeleteInstance,
+    ContractExecute(ContractExecuteTransaction) => ContractCall,
+    ContractUpdate(ContractUpdateTransaction) => ContractUpdateInstance,
+    Ethereum(EthereumTransaction) => EthereumTransaction,
+    FileAppend(FileAppendTransaction) => FileAppend,
+    FileCreate(FileCreateTransaction) => FileCreate,
+    FileDelete(FileDeleteTransaction) => FileDelete,
+    FileUpdate(FileUpdateTransaction) => FileUpdate,
+    LiveHashAdd(LiveHashAddTransaction) => CryptoAddLiveHash,
+    LiveHashDelete(LiveHashDeleteTransaction) => CryptoDeleteLiveHash,
+    Prng(PrngTransaction) => UtilPrng,
+    ScheduleCreate(ScheduleCreateTransaction) => ScheduleCreate,
+    ScheduleDelete(ScheduleDeleteTransaction) => ScheduleDelete,
+    ScheduleSign(ScheduleSignTransaction) => ScheduleSign,
+    TokenAssociate(TokenAssociateTransaction) => TokenAssociate,
+    TokenBurn(TokenBurnTransaction) => TokenBurn,
+    TokenCreate(TokenCreateTransaction) => TokenCreation,
+    TokenDelete(TokenDeleteTransaction) => TokenDeletion,
+    TokenDissociate(TokenDissociateTransaction) => TokenDissociate,
+    TokenFeeScheduleUpdate(TokenFeeScheduleUpdateTransaction) => TokenFeeScheduleUpdate,
+    TokenFreeze(TokenFreezeTransaction) => TokenFreeze,
+    TokenGrantKyc(TokenGrantKycTransaction) => TokenGrantKyc,
+    TokenMint(TokenMintTransaction) => TokenMint,
+    TokenPause(TokenPauseTransaction) => TokenPause,
+    TokenRevokeKyc(TokenRevokeKycTransaction) => TokenRevokeKyc,
+    TokenUnfreeze(TokenUnfreezeTransaction) => TokenUnfreeze,
+    TokenUnpause(TokenUnpauseTransaction) => TokenUnpause,
+    TokenUpdate(TokenUpdateTransaction) => TokenUpdate,
+    TokenWipe(TokenWipeTransaction) => TokenWipe,
+    TopicCreate(TopicCreateTransaction) => ConsensusCreateTopic,
+    TopicDelete(TopicDeleteTransaction) => ConsensusDeleteTopic,
+    TopicMessageSubmit(TopicMessageSubmitTransaction) => ConsensusSubmitMessage,
+    TopicUpdate(TopicUpdateTransaction) => ConsensusUpdateTopic,
+    Transfer(TransferTransaction) => CryptoTransfer,
+}
+
+impl AnyTransaction {
+    /// Decodes the `to_bytes` of a frozen transaction, from this or another
+    /// SDK, into its type. Signatures, node and transaction ids are kept, so
+    /// `to_bytes` gives back the same bytes until it is signed again.
+    pub fn from_bytes(bytes: &[u8]) -> Result<AnyTransaction, HederaError> {
+        AnyTransaction::from_transaction(Transaction::from_bytes(bytes.to_vec())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::sdk::TransactionList as ProtoTransactionList;
+    use crate::proto::services;
+    use crate::utils::test_utils::*;
+    use prost::Message;
+
+    async fn signed_transfer(private_key: &PrivateKey) -> Result<TransferTransaction, HederaError> {
+        let mut tx = TransferTransaction::new();
+        tx.add_hbar_transfer(AccountId::simple(2), Hbar::from_tinybar(-10))?
+            .add_hbar_transfer(AccountId::simple(3), Hbar::from_tinybar(10))?
+            .set_transaction_memo("memo".to_string())?
+            .set_transaction_id(mock_transaction_id())?
+            .set_node_account_ids(vec![AccountId::simple(3), AccountId::simple(4)])?
+            .freeze()
+            .await?;
+        tx.sign(private_key)?;
+        Ok(tx)
+    }
+
+    #[tokio::test]
+    async fn test_any_transaction_from_bytes() -> Result<(), HederaError> {
+        let private_key = PrivateKey::new();
+        let mut tx = signed_transfer(&private_key).await?;
+        let bytes = tx.to_bytes()?;
+
+        let mut decoded = AnyTransaction::from_bytes(&bytes)?;
+        assert_eq!(decoded.transaction_id()?, tx.transaction_id()?);
+        assert_eq!(
+            decoded.node_account_ids(),
+            vec![AccountId::simple(3), AccountId::simple(4)]
+        );
+        assert_eq!(decoded.transaction_memo(), "memo");
+        assert_eq!(decoded.signatures()?, tx.signatures()?);
+        assert_eq!(decoded.to_bytes()?, bytes);
+        match &decoded {
+            AnyTransaction::Transfer(transfer) => {
+                assert_eq!(transfer.hbar_transfers()?, tx.hbar_transfers()?)
+            }
+            other => panic!("unexpected transaction {:?}", other),
+        }
+
+        let mut message = TopicMessageSubmitTransaction::new();
+        message
+            .set_topic_id(TopicId::simple(5))?
+            .set_message(vec![7; 2500])?
+            .set_transaction_id(mock_transaction_id())?
+            .set_node_account_ids(vec![AccountId::simple(3)])?
+            .freeze()
+            .await?;
+        match AnyTransaction::from_bytes(&message.to_bytes()?)? {
+            AnyTransaction::TopicMessageSubmit(decoded) => {
+                assert_eq!(decoded.message()?, vec![7; 2500]);
+                assert_eq!(decoded.topic_id()?, TopicId::simple(5));
+            }
+            other => panic!("unexpected transaction {:?}", other),
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_any_transaction_from_legacy_bytes() -> Result<(), HederaError> {
+        let private_key = PrivateKey::new();
+        let mut tx = signed_transfer(&private_key).await?;
+        let list = ProtoTransactionList::decode(&tx.to_bytes()?[..]).unwrap();
+        let signed_transaction = services::SignedTransaction::decode(
+            &list.transaction_list[0].signed_transaction_bytes[..],
+        )
+        .unwrap();
+        // a lone `Transaction` carrying the body and signatures directly
+        #[allow(deprecated)]
+        let legacy = services::Transaction {
+            body_bytes: signed_transaction.body_bytes,
+            sig_map: signed_transaction.sig_map,
+            ..Default::default()
+        };
+
+        let decoded = AnyTransaction::from_bytes(&legacy.encode_to_vec())?;
+        assert_eq!(decoded.node_account_ids(), vec![AccountId::simple(3)]);
+        assert!(decoded.signatures()?[&AccountId::simple(3)].contains_key(&private_key.public()));
+        match decoded {
+            AnyTransaction::Transfer(transfer) => {
+                assert_eq!(transfer.hbar_transfers()?, tx.hbar_transfers()?)
+            }
+            other => panic!("unexpected transaction {:?}", other),
+        }
+        Ok(())
+    }
+}
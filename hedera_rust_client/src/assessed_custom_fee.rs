@@ -1,10 +1,12 @@
+use serde::Serialize;
+
 use crate::error::HederaError;
 use crate::proto::services::{self};
 use crate::AccountId;
 use crate::TokenId;
 use std::convert::TryFrom;
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct AssessedCustomFee {
     pub amount: i64,
     pub token_id: Option<TokenId>,
@@ -4,10 +4,11 @@ use crate::proto::services::{
     file_service_client::FileServiceClient, freeze_service_client::FreezeServiceClient,
     network_service_client::NetworkServiceClient, schedule_service_client::ScheduleServiceClient,
     smart_contract_service_client::SmartContractServiceClient,
-    token_service_client::TokenServiceClient,
+    token_service_client::TokenServiceClient, util_service_client::UtilServiceClient,
 };
 use http::{uri::Authority, Uri};
-use tonic::transport::{Certificate, Channel as TonicChannel, ClientTlsConfig};
+use std::time::Duration;
+use tonic::transport::{Certificate, Channel as TonicChannel, ClientTlsConfig, Endpoint};
 
 #[derive(Debug, Clone)]
 pub struct Channel {
@@ -29,21 +30,45 @@ impl Channel {
         Ok(uri)
     }
 
-    pub fn tonic_channel(authority: &str) -> Result<TonicChannel, HederaError> {
+    fn endpoint(
+        authority: &str,
+        connect_timeout: Option<Duration>,
+    ) -> Result<Endpoint, HederaError> {
         let uri = Channel::build_uri(authority)?;
-        Ok(TonicChannel::builder(uri).connect_lazy())
+        let endpoint = TonicChannel::builder(uri);
+        Ok(match connect_timeout {
+            Some(timeout) => endpoint.connect_timeout(timeout),
+            None => endpoint,
+        })
     }
 
-    pub fn from_authority(authority: &str) -> Result<Channel, HederaError> {
-        let tonic_channel = Self::tonic_channel(authority)?;
+    /// `connect_timeout` bounds how long establishing the connection may take,
+    /// the channel is still connected lazily on first use.
+    pub fn tonic_channel(
+        authority: &str,
+        connect_timeout: Option<Duration>,
+    ) -> Result<TonicChannel, HederaError> {
+        Ok(Channel::endpoint(authority, connect_timeout)?.connect_lazy())
+    }
+
+    pub fn from_authority(
+        authority: &str,
+        connect_timeout: Option<Duration>,
+    ) -> Result<Channel, HederaError> {
+        let tonic_channel = Self::tonic_channel(authority, connect_timeout)?;
         Ok(Channel::new(tonic_channel))
     }
 
-    pub fn from_authority_tls(authority: &str, cert: &[u8]) -> Result<Channel, HederaError> {
-        let uri = Channel::build_uri(authority)?;
+    pub fn from_authority_tls(
+        authority: &str,
+        cert: &[u8],
+        connect_timeout: Option<Duration>,
+    ) -> Result<Channel, HederaError> {
         let ca = Certificate::from_pem(cert);
         let tls = ClientTlsConfig::new().ca_certificate(ca);
-        let tonic_channel = TonicChannel::builder(uri).tls_config(tls)?.connect_lazy();
+        let tonic_channel = Channel::endpoint(authority, connect_timeout)?
+            .tls_config(tls)?
+            .connect_lazy();
         Ok(Channel::new(tonic_channel))
     }
 
@@ -78,4 +103,8 @@ impl Channel {
     pub fn schedule(&mut self) -> ScheduleServiceClient<TonicChannel> {
         ScheduleServiceClient::new(self.channel.clone())
     }
+
+    pub fn util(&mut self) -> UtilServiceClient<TonicChannel> {
+        UtilServiceClient::new(self.channel.clone())
+    }
 }
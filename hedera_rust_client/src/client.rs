@@ -1,21 +1,39 @@
+use chrono::{DateTime, TimeZone, Utc};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::fmt;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::task::JoinHandle;
+use tokio::time::MissedTickBehavior;
+use tracing::warn;
 
 use crate::crypto::{PrivateKey, PublicKey, Signature};
 use crate::error::HederaError;
+use crate::exchange_rate::ExchangeRateSet;
+use crate::execute_middleware::{ExecuteHooks, ExecuteMiddleware};
+use crate::fee_estimator::FeeEstimator;
+use crate::fee_schedules::FeeSchedules;
 use crate::hbar::Hbar;
 use crate::ledger_id::LedgerId;
 use crate::managed_network::ArcNetworkNode;
 use crate::mirror_network::MirrorNetwork;
+use crate::mirror_rest_client::MirrorRestClient;
 use crate::network::Network;
 use crate::network_name::NetworkName;
+use crate::node_address_book::NodeAddressBook;
+use crate::node_address_book_query::NodeAddressBookQuery;
 use crate::proto::services;
+use crate::retry_policy::RetryPolicy;
+use crate::signer::{LocalSigner, Signer};
+use crate::system_file_cache::SystemFileCache;
+use crate::throttle_definitions::ThrottleDefinitions;
 use crate::AccountBalance;
 use crate::AccountBalanceQuery;
 use crate::AccountId;
+use crate::FileContentsQuery;
+use crate::FileId;
 
 #[derive(Debug, Clone, PartialEq, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -47,6 +65,8 @@ pub struct ClientConfig {
     // #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub mirror_network: Option<MirrorNetworkConfig>,
+    #[serde(default)]
+    pub mirror_rest_url: Option<String>,
 }
 
 impl ClientConfig {
@@ -61,15 +81,21 @@ impl ClientConfig {
 pub struct Operator {
     pub account_id: AccountId,
     pub public_key: PublicKey,
-    pub transaction_signer: Arc<dyn Fn(&Vec<u8>) -> Signature + Send + Sync + 'static>,
+    pub transaction_signer: Arc<dyn Signer>,
 }
 
 impl Operator {
     pub fn new(account_id: AccountId, private_key: PrivateKey) -> Operator {
+        Operator::with_signer(account_id, Arc::new(LocalSigner::new(private_key)))
+    }
+
+    /// An operator whose key is held by `signer`, e.g. an HSM or a
+    /// `RemoteSigner`.
+    pub fn with_signer(account_id: AccountId, signer: Arc<dyn Signer>) -> Operator {
         Operator {
             account_id,
-            public_key: private_key.public(),
-            transaction_signer: Arc::new(move |b| private_key.sign(b)),
+            public_key: signer.public_key(),
+            transaction_signer: signer,
         }
     }
 }
@@ -89,6 +115,9 @@ impl std::fmt::Display for Operator {
     }
 }
 
+const DEFAULT_GRPC_DEADLINE: Duration = Duration::from_secs(10);
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
 #[derive(Builder, Debug, Clone)]
 pub struct Client {
     operator: Operator,
@@ -98,6 +127,8 @@ pub struct Client {
     max_query_payment: Hbar,
     network: Network,
     mirror_network: MirrorNetwork,
+    #[builder(default = "None")]
+    mirror_rest_client: Option<MirrorRestClient>,
     #[builder(default = "false")]
     auto_validate_checksums: bool,
     #[builder(default = "None")]
@@ -106,6 +137,14 @@ pub struct Client {
     min_backoff: u64,
     #[builder(default = "8000")]
     max_backoff: u64,
+    #[builder(default = "DEFAULT_GRPC_DEADLINE")]
+    grpc_deadline: Duration,
+    #[builder(default = "DEFAULT_REQUEST_TIMEOUT")]
+    request_timeout: Duration,
+    #[builder(setter(skip))]
+    hooks: ExecuteHooks,
+    #[builder(setter(skip))]
+    system_files: SystemFileCache,
 }
 
 impl Client {
@@ -116,10 +155,15 @@ impl Client {
             max_query_payment,
             network: Network::new(),
             mirror_network: MirrorNetwork::new(),
+            mirror_rest_client: None,
             auto_validate_checksums: false,
             max_attempts: None,
             min_backoff: 250,
             max_backoff: 8000,
+            grpc_deadline: DEFAULT_GRPC_DEADLINE,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+            hooks: ExecuteHooks::default(),
+            system_files: SystemFileCache::default(),
         }
     }
 
@@ -147,6 +191,54 @@ impl Client {
         self.min_backoff
     }
 
+    /// Sets how long a single attempt against one node may take before the
+    /// node is marked unhealthy and the next node is tried.
+    pub fn set_grpc_deadline(&mut self, deadline: Duration) {
+        self.grpc_deadline = deadline;
+    }
+
+    pub fn grpc_deadline(&self) -> Duration {
+        self.grpc_deadline
+    }
+
+    /// Sets how long `execute` may take in total, across all attempts.
+    pub fn set_request_timeout(&mut self, timeout: Duration) {
+        self.request_timeout = timeout;
+    }
+
+    pub fn request_timeout(&self) -> Duration {
+        self.request_timeout
+    }
+
+    /// Sets how long connecting to a node may take. Open channels are
+    /// dropped and reconnected with the new timeout.
+    pub async fn set_connect_timeout(&mut self, timeout: Option<Duration>) {
+        self.network.set_connect_timeout(timeout).await;
+    }
+
+    pub fn connect_timeout(&self) -> Option<Duration> {
+        self.network.connect_timeout()
+    }
+
+    /// Sets the retry policy for every request, unless the request sets its own.
+    pub fn set_retry_policy(&mut self, retry_policy: Arc<dyn RetryPolicy>) {
+        self.hooks.retry_policy = Some(retry_policy);
+    }
+
+    pub fn retry_policy(&self) -> Option<Arc<dyn RetryPolicy>> {
+        self.hooks.retry_policy.clone()
+    }
+
+    /// Adds middleware that runs for every request, before any middleware
+    /// added to the request itself.
+    pub fn add_middleware(&mut self, middleware: Arc<dyn ExecuteMiddleware>) {
+        self.hooks.middleware.push(middleware);
+    }
+
+    pub(crate) fn hooks(&self) -> &ExecuteHooks {
+        &self.hooks
+    }
+
     pub fn set_max_attempts(&mut self, max_attempts: u8) {
         self.max_attempts = Some(max_attempts);
     }
@@ -192,6 +284,16 @@ impl Client {
         self.mirror_network = mirror_network;
     }
 
+    pub fn set_mirror_rest_client(&mut self, mirror_rest_client: MirrorRestClient) {
+        self.mirror_rest_client = Some(mirror_rest_client);
+    }
+
+    pub fn mirror_rest_client(&self) -> Result<&MirrorRestClient, HederaError> {
+        self.mirror_rest_client
+            .as_ref()
+            .ok_or_else(|| HederaError::ValueNotSet("mirror_rest_client".to_string()))
+    }
+
     pub async fn set_transport_security(&mut self, tls: bool) {
         self.network.set_transport_security(tls).await;
         self.mirror_network.set_transport_security(tls).await;
@@ -271,8 +373,137 @@ impl Client {
         self.mirror_network.get_next_mirror_node().await
     }
 
-    pub fn sign_with_operator(&self, bytes: &Vec<u8>) -> Signature {
-        (self.operator.transaction_signer)(bytes)
+    /// Fetches the current address book from the mirror network and applies it
+    /// to the consensus node list.
+    pub async fn update_network(&self) -> Result<(), HederaError> {
+        let address_book = NodeAddressBookQuery::new().execute(self).await?;
+        self.network.set_address_book(&address_book).await
+    }
+
+    /// Spawns a task that calls `update_network` every `period`, starting one
+    /// period from now. Clones of this client share the refreshed node list.
+    /// Abort the returned handle to stop refreshing.
+    pub fn start_network_update(&self, period: Duration) -> JoinHandle<()> {
+        let client = self.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            // the first tick completes immediately
+            interval.tick().await;
+            loop {
+                interval.tick().await;
+                if let Err(e) = client.update_network().await {
+                    warn!("network update failed: {}", e);
+                }
+            }
+        })
+    }
+
+    /// The address book in file `0.0.101`.
+    pub async fn address_book(&self) -> Result<NodeAddressBook, HederaError> {
+        self.system_file(
+            FileId::ADDRESS_BOOK,
+            NodeAddressBook::from_proto_bytes,
+            |_| None,
+        )
+        .await
+    }
+
+    /// The address book with node details in file `0.0.102`.
+    pub async fn node_details(&self) -> Result<NodeAddressBook, HederaError> {
+        self.system_file(
+            FileId::NODE_DETAILS,
+            NodeAddressBook::from_proto_bytes,
+            |_| None,
+        )
+        .await
+    }
+
+    /// The fee schedules in file `0.0.111`, refreshed once the current one
+    /// expires.
+    pub async fn fee_schedules(&self) -> Result<FeeSchedules, HederaError> {
+        self.system_file(
+            FileId::FEE_SCHEDULE,
+            FeeSchedules::from_proto_bytes,
+            |schedules| schedules.current.as_ref().and_then(|s| s.expiry_time),
+        )
+        .await
+    }
+
+    /// The exchange rates in file `0.0.112`, refreshed once the current rate
+    /// expires.
+    pub async fn exchange_rates(&self) -> Result<ExchangeRateSet, HederaError> {
+        self.system_file(
+            FileId::EXCHANGE_RATES,
+            ExchangeRateSet::from_proto_bytes,
+            |rates| {
+                rates
+                    .current_rate
+                    .as_ref()
+                    .and_then(|rate| Utc.timestamp_opt(rate.expiration_time, 0).single())
+            },
+        )
+        .await
+    }
+
+    /// The throttle definitions in file `0.0.123`.
+    pub async fn throttle_definitions(&self) -> Result<ThrottleDefinitions, HederaError> {
+        self.system_file(
+            FileId::THROTTLE_DEFINITIONS,
+            ThrottleDefinitions::from_proto_bytes,
+            |_| None,
+        )
+        .await
+    }
+
+    /// A fee estimator for the fee schedule and exchange rate in effect now.
+    pub async fn fee_estimator(&self) -> Result<FeeEstimator, HederaError> {
+        let now = Utc::now();
+        let schedules = self.fee_schedules().await?;
+        let rates = self.exchange_rates().await?;
+        let schedule = schedules
+            .schedule_at(now)
+            .ok_or_else(|| HederaError::FeeScheduleNotFound("current".to_string()))?;
+        let rate = rates
+            .rate_at(now)
+            .ok_or_else(|| HederaError::MissingInProto("current_rate".to_string()))?;
+        Ok(FeeEstimator::new(schedule.clone(), rate.clone()))
+    }
+
+    /// How long system files without an expiry of their own are cached,
+    /// one hour by default.
+    pub fn set_system_file_ttl(&mut self, ttl: Duration) {
+        self.system_files.set_ttl(ttl);
+    }
+
+    /// Drops cached system files, so the next read fetches them again.
+    pub fn clear_system_file_cache(&self) {
+        self.system_files.clear();
+    }
+
+    async fn system_file<T>(
+        &self,
+        file_id: FileId,
+        decode: fn(Vec<u8>) -> Result<T, HederaError>,
+        expiry: fn(&T) -> Option<DateTime<Utc>>,
+    ) -> Result<T, HederaError> {
+        let now = Utc::now();
+        if let Some(contents) = self.system_files.get(&file_id, now) {
+            return decode(contents);
+        }
+        let contents = FileContentsQuery::new()
+            .set_file_id(file_id)?
+            .execute(self)
+            .await?
+            .ok_or_else(|| HederaError::MissingInProto("file_contents".to_string()))?;
+        let value = decode(contents.clone())?;
+        self.system_files
+            .insert(file_id, contents, expiry(&value), now);
+        Ok(value)
+    }
+
+    pub async fn sign_with_operator(&self, bytes: &[u8]) -> Result<Signature, HederaError> {
+        self.operator.transaction_signer.sign(bytes).await
     }
 
     pub fn to_signature_pair_protobuf(&self, signature: &Signature) -> services::SignaturePair {
@@ -285,9 +516,7 @@ impl Client {
         &self.operator.public_key
     }
 
-    pub fn operator_transaction_signer(
-        &self,
-    ) -> Arc<dyn Fn(&Vec<u8>) -> Signature + Send + Sync + 'static> {
+    pub fn operator_transaction_signer(&self) -> Arc<dyn Signer> {
         self.operator.transaction_signer.clone()
     }
 
@@ -323,12 +552,13 @@ impl ClientBuilder {
 
     pub fn from_config(cfg: ClientConfig) -> Result<ClientBuilder, HederaError> {
         let mut builder = ClientBuilder::default();
-        let (network, mirror_network) = match cfg.network {
+        let (network, mirror_network, mirror_rest_client) = match cfg.network {
             NetworkConfig::Str(network_name) => {
                 let network_name = NetworkName::from_str(&network_name)?;
                 (
                     Network::for_network_name(&network_name)?,
                     MirrorNetwork::for_network_name(&network_name)?,
+                    MirrorRestClient::for_network_name(&network_name).ok(),
                 )
             }
             NetworkConfig::Map(nodes) => {
@@ -339,17 +569,21 @@ impl ClientBuilder {
                 (
                     Network::from_network(network)?,
                     MirrorNetwork::from_network(Vec::new())?,
+                    None,
                 )
             }
         };
         builder.network = Some(network);
         builder.mirror_network = Some(mirror_network);
+        builder.mirror_rest_client = Some(mirror_rest_client);
 
         // if mirror network defined then override
         if let Some(mirror_network_cfg) = cfg.mirror_network {
             let mirror_network = match mirror_network_cfg {
                 MirrorNetworkConfig::Str(network_name) => {
                     let network_name = NetworkName::from_str(&network_name)?;
+                    builder.mirror_rest_client =
+                        Some(MirrorRestClient::for_network_name(&network_name).ok());
                     MirrorNetwork::for_network_name(&network_name)?
                 }
                 MirrorNetworkConfig::StrVec(nodes) => MirrorNetwork::from_network(nodes)?,
@@ -357,6 +591,15 @@ impl ClientBuilder {
             builder.mirror_network = Some(mirror_network);
         }
 
+        // an explicit rest url wins, e.g. to point at a local stub server
+        if let Some(url) = cfg.mirror_rest_url {
+            let mut mirror_rest_client = MirrorRestClient::new(&url);
+            if let Some(Some(current)) = &builder.mirror_rest_client {
+                mirror_rest_client.set_ledger_id(current.ledger_id());
+            }
+            builder.mirror_rest_client = Some(Some(mirror_rest_client));
+        }
+
         if let Some(operator_cfg) = cfg.operator {
             let operator = Operator::new(
                 AccountId::from_str(&operator_cfg.account_id)?,
@@ -374,6 +617,7 @@ impl ClientBuilder {
     ) -> Result<&mut Self, HederaError> {
         self.network = Some(Network::for_network_name(&network_name)?);
         self.mirror_network = Some(MirrorNetwork::for_network_name(&network_name)?);
+        self.mirror_rest_client = Some(MirrorRestClient::for_network_name(&network_name).ok());
         Ok(self)
     }
 }
@@ -409,6 +653,16 @@ mod tests {
         "mirrorNetwork": "mainnet"
     }"#;
 
+    const CONFIG_WITH_MIRROR_REST_URL: &str = r#"
+    {
+        "network": "testnet",
+        "operator": {
+            "accountId": "0.0.36",
+            "privateKey": "302e020100300506032b657004220420db484b828e64b2d8f12ce3c0a0e93a0b8cce7af1bb8f39c97732394482538e10"
+        },
+        "mirrorRestUrl": "http://127.0.0.1:5551"
+    }"#;
+
     #[test]
     fn deserialize_client_config() {
         let _cfg: ClientConfig = serde_json::from_str(CONFIG_WITH_OPERATOR).unwrap();
@@ -437,4 +691,13 @@ mod tests {
         let client = builder.build();
         assert!(client.is_err(), "should fail with no operator");
     }
+
+    #[test]
+    fn from_config_with_mirror_rest_url() {
+        let cfg: ClientConfig = serde_json::from_str(CONFIG_WITH_MIRROR_REST_URL).unwrap();
+        let client = ClientBuilder::from_config(cfg).unwrap().build().unwrap();
+        let mirror_rest_client = client.mirror_rest_client().unwrap();
+        assert_eq!(mirror_rest_client.base_url(), "http://127.0.0.1:5551");
+        assert_eq!(mirror_rest_client.ledger_id(), LedgerId::for_testnet());
+    }
 }
@@ -0,0 +1,627 @@
+use serde::Deserialize;
+use sha3::{Digest, Keccak256};
+
+use crate::abi_type::AbiType;
+use crate::abi_value::{abi_decode, abi_encode, AbiValue};
+use crate::contract_log_info::ContractLogInfo;
+use crate::contract_revert::ContractRevert;
+use crate::error::HederaError;
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum RawAbiFile {
+    Entries(Vec<RawAbiEntry>),
+    // a compiler artifact, e.g. from Hardhat or Truffle
+    Artifact { abi: Vec<RawAbiEntry> },
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawAbiEntry {
+    #[serde(rename = "type", default = "default_entry_type")]
+    kind: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    inputs: Vec<RawAbiParam>,
+    #[serde(default)]
+    outputs: Vec<RawAbiParam>,
+    state_mutability: Option<String>,
+    #[serde(default)]
+    constant: bool,
+    #[serde(default)]
+    payable: bool,
+    #[serde(default)]
+    anonymous: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawAbiParam {
+    #[serde(default)]
+    name: String,
+    #[serde(rename = "type")]
+    kind: String,
+    components: Option<Vec<RawAbiParam>>,
+    internal_type: Option<String>,
+    #[serde(default)]
+    indexed: bool,
+}
+
+fn default_entry_type() -> String {
+    "function".to_string()
+}
+
+impl RawAbiParam {
+    fn kind(&self) -> Result<AbiType, HederaError> {
+        let components = match &self.components {
+            Some(components) => Some(
+                components
+                    .iter()
+                    .map(RawAbiParam::kind)
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            None => None,
+        };
+        AbiType::with_components(&self.kind, components)
+    }
+
+    fn into_param(self) -> Result<AbiParam, HederaError> {
+        Ok(AbiParam {
+            kind: self.kind()?,
+            name: self.name,
+            internal_type: self.internal_type,
+            indexed: self.indexed,
+        })
+    }
+}
+
+fn into_params(raw: Vec<RawAbiParam>) -> Result<Vec<AbiParam>, HederaError> {
+    raw.into_iter().map(RawAbiParam::into_param).collect()
+}
+
+/// Whether a function reads or changes state, and whether it accepts hbar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StateMutability {
+    Pure,
+    View,
+    NonPayable,
+    Payable,
+}
+
+impl StateMutability {
+    fn from_raw(entry: &RawAbiEntry) -> Result<StateMutability, HederaError> {
+        match entry.state_mutability.as_deref() {
+            Some("pure") => Ok(StateMutability::Pure),
+            Some("view") => Ok(StateMutability::View),
+            Some("nonpayable") => Ok(StateMutability::NonPayable),
+            Some("payable") => Ok(StateMutability::Payable),
+            Some(other) => Err(HederaError::InvalidAbi(format!(
+                "unknown state mutability `{}`",
+                other
+            ))),
+            // ABIs from before solidity 0.5
+            None if entry.constant => Ok(StateMutability::View),
+            None if entry.payable => Ok(StateMutability::Payable),
+            None => Ok(StateMutability::NonPayable),
+        }
+    }
+}
+
+/// A named input or output of a function, event or error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbiParam {
+    pub name: String,
+    pub kind: AbiType,
+    /// The solidity type, e.g. `struct Pool.Order`, when the compiler gave it.
+    pub internal_type: Option<String>,
+    /// Whether an event parameter is stored in a log topic.
+    pub indexed: bool,
+}
+
+/// A function of a contract ABI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbiFunction {
+    pub name: String,
+    pub inputs: Vec<AbiParam>,
+    pub outputs: Vec<AbiParam>,
+    pub state_mutability: StateMutability,
+}
+
+impl AbiFunction {
+    /// The canonical signature, e.g. `transfer(address,uint256)`.
+    pub fn signature(&self) -> String {
+        signature(&self.name, &self.inputs)
+    }
+
+    /// The first four bytes of the keccak256 hash of the signature.
+    pub fn selector(&self) -> [u8; 4] {
+        let mut selector = [0; 4];
+        selector.copy_from_slice(&Keccak256::digest(self.signature().as_bytes())[..4]);
+        selector
+    }
+
+    /// Whether calling the function doesn't change state, so that a
+    /// `ContractCallQuery` is enough.
+    pub fn is_read_only(&self) -> bool {
+        matches!(
+            self.state_mutability,
+            StateMutability::Pure | StateMutability::View
+        )
+    }
+
+    /// The call data: the selector followed by the encoded arguments, for
+    /// `ContractExecuteTransaction::set_function_parameters` or
+    /// `ContractCallQuery::set_function_parameters`.
+    pub fn encode_input(&self, args: &[AbiValue]) -> Result<Vec<u8>, HederaError> {
+        let mut data = self.selector().to_vec();
+        data.extend(abi_encode(&param_kinds(&self.inputs), args)?);
+        Ok(data)
+    }
+
+    /// Decodes the return values, e.g. `ContractFunctionResult::contract_call_result`.
+    pub fn decode_output(&self, data: &[u8]) -> Result<Vec<AbiValue>, HederaError> {
+        abi_decode(&param_kinds(&self.outputs), data)
+    }
+}
+
+/// The constructor of a contract ABI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbiConstructor {
+    pub inputs: Vec<AbiParam>,
+    pub state_mutability: StateMutability,
+}
+
+impl AbiConstructor {
+    /// The encoded arguments, for
+    /// `ContractCreateTransaction::set_constructor_parameters_raw`.
+    pub fn encode_input(&self, args: &[AbiValue]) -> Result<Vec<u8>, HederaError> {
+        abi_encode(&param_kinds(&self.inputs), args)
+    }
+}
+
+/// An event of a contract ABI.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbiEvent {
+    pub name: String,
+    pub inputs: Vec<AbiParam>,
+    /// Anonymous events don't have their topic as first log topic.
+    pub anonymous: bool,
+}
+
+impl AbiEvent {
+    /// The canonical signature, e.g. `Transfer(address,address,uint256)`.
+    pub fn signature(&self) -> String {
+        signature(&self.name, &self.inputs)
+    }
+
+    /// The keccak256 hash of the signature, the first topic of its logs.
+    pub fn topic(&self) -> [u8; 32] {
+        let mut topic = [0; 32];
+        topic.copy_from_slice(&Keccak256::digest(self.signature().as_bytes()));
+        topic
+    }
+
+    /// Decodes the indexed parameters from the log topics and the others from
+    /// the log data.
+    ///
+    /// Indexed strings, bytes, arrays and structs are logged as the keccak256
+    /// hash of their encoding, so they decode to that hash as `FixedBytes`.
+    pub fn decode_log(&self, log: &ContractLogInfo) -> Result<DecodedEvent, HederaError> {
+        let mut topics = log.topic.iter();
+        if !self.anonymous {
+            match topics.next() {
+                Some(topic) if topic[..] == self.topic()[..] => {}
+                _ => {
+                    return Err(HederaError::AbiDecode(format!(
+                        "log is not a {} event",
+                        self.name
+                    )))
+                }
+            }
+        }
+        let indexed = self.inputs.iter().filter(|param| param.indexed).count();
+        if topics.len() != indexed {
+            return Err(HederaError::AbiDecode(format!(
+                "expected {} indexed topics, got {}",
+                indexed,
+                topics.len()
+            )));
+        }
+
+        let data_kinds = self
+            .inputs
+            .iter()
+            .filter(|param| !param.indexed)
+            .map(|param| param.kind.clone())
+            .collect::<Vec<_>>();
+        let mut data = abi_decode(&data_kinds, &log.data)?.into_iter();
+
+        let mut params = Vec::with_capacity(self.inputs.len());
+        for param in &self.inputs {
+            let value = if !param.indexed {
+                data.next()
+            } else {
+                let topic = topics.next().map(Vec::as_slice).unwrap_or_default();
+                match param.kind {
+                    AbiType::Tuple(_)
+                    | AbiType::Array(_)
+                    | AbiType::FixedArray(..)
+                    | AbiType::Bytes
+                    | AbiType::String => Some(AbiValue::FixedBytes(topic.to_vec())),
+                    _ => abi_decode(std::slice::from_ref(&param.kind), topic)?.pop(),
+                }
+            };
+            if let Some(value) = value {
+                params.push((param.name.clone(), value));
+            }
+        }
+        Ok(DecodedEvent {
+            name: self.name.clone(),
+            params,
+        })
+    }
+}
+
+/// An event decoded from a contract log, with its named parameters in
+/// declaration order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedEvent {
+    pub name: String,
+    pub params: Vec<(String, AbiValue)>,
+}
+
+impl DecodedEvent {
+    /// The parameter called `name`.
+    pub fn get(&self, name: &str) -> Option<&AbiValue> {
+        self.params
+            .iter()
+            .find(|(param, _)| param == name)
+            .map(|(_, value)| value)
+    }
+}
+
+/// A custom error of a contract ABI, e.g. `error InsufficientBalance(uint256 available)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AbiError {
+    pub name: String,
+    pub inputs: Vec<AbiParam>,
+}
+
+impl AbiError {
+    pub fn signature(&self) -> String {
+        signature(&self.name, &self.inputs)
+    }
+
+    pub fn selector(&self) -> [u8; 4] {
+        let mut selector = [0; 4];
+        selector.copy_from_slice(&Keccak256::digest(self.signature().as_bytes())[..4]);
+        selector
+    }
+}
+
+fn param_kinds(params: &[AbiParam]) -> Vec<AbiType> {
+    params.iter().map(|param| param.kind.clone()).collect()
+}
+
+fn signature(name: &str, params: &[AbiParam]) -> String {
+    let kinds = param_kinds(params)
+        .iter()
+        .map(AbiType::to_string)
+        .collect::<Vec<_>>();
+    format!("{}({})", name, kinds.join(","))
+}
+
+/// The ABI of a Solidity contract, as emitted by the compiler.
+///
+/// ```ignore
+/// let abi = ContractAbi::from_json(include_str!("Pool.abi.json"))?;
+/// let data = abi
+///     .function("deposit")?
+///     .encode_input(&[AbiValue::Tuple(vec![1u64.into(), "memo".into()])])?;
+///
+/// ContractExecuteTransaction::new()
+///     .set_contract_id(contract_id)?
+///     .set_gas(100_000)?
+///     .set_function_parameters(data)?
+///     .execute(&client)
+///     .await?;
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContractAbi {
+    pub constructor: Option<AbiConstructor>,
+    pub functions: Vec<AbiFunction>,
+    pub events: Vec<AbiEvent>,
+    pub errors: Vec<AbiError>,
+}
+
+impl ContractAbi {
+    /// Parses a JSON ABI, either the bare entry array or a compiler artifact
+    /// with an `abi` field.
+    pub fn from_json(json: &str) -> Result<ContractAbi, HederaError> {
+        let raw: RawAbiFile =
+            serde_json::from_str(json).map_err(|e| HederaError::InvalidAbi(e.to_string()))?;
+        let entries = match raw {
+            RawAbiFile::Entries(entries) => entries,
+            RawAbiFile::Artifact { abi } => abi,
+        };
+
+        let mut abi = ContractAbi::default();
+        for entry in entries {
+            let state_mutability = StateMutability::from_raw(&entry)?;
+            match entry.kind.as_str() {
+                "function" => abi.functions.push(AbiFunction {
+                    name: entry.name,
+                    inputs: into_params(entry.inputs)?,
+                    outputs: into_params(entry.outputs)?,
+                    state_mutability,
+                }),
+                "constructor" => {
+                    abi.constructor = Some(AbiConstructor {
+                        inputs: into_params(entry.inputs)?,
+                        state_mutability,
+                    })
+                }
+                "event" => abi.events.push(AbiEvent {
+                    name: entry.name,
+                    inputs: into_params(entry.inputs)?,
+                    anonymous: entry.anonymous,
+                }),
+                "error" => abi.errors.push(AbiError {
+                    name: entry.name,
+                    inputs: into_params(entry.inputs)?,
+                }),
+                "fallback" | "receive" => {}
+                other => {
+                    return Err(HederaError::InvalidAbi(format!(
+                        "unknown entry type `{}`",
+                        other
+                    )))
+                }
+            }
+        }
+        Ok(abi)
+    }
+
+    /// The first function called `name`; see `function_by_signature` for
+    /// overloaded functions.
+    pub fn function(&self, name: &str) -> Result<&AbiFunction, HederaError> {
+        self.functions
+            .iter()
+            .find(|function| function.name == name)
+            .ok_or_else(|| HederaError::AbiFunctionNotFound(name.to_string()))
+    }
+
+    /// The function with the given signature, e.g. `transfer(address,uint256)`.
+    /// Whitespace and type aliases such as `uint` are accepted.
+    pub fn function_by_signature(&self, signature: &str) -> Result<&AbiFunction, HederaError> {
+        let not_found = || HederaError::AbiFunctionNotFound(signature.to_string());
+        let open = signature.find('(').ok_or_else(not_found)?;
+        let close = signature.rfind(')').ok_or_else(not_found)?;
+        let name = signature[..open].trim();
+        let kinds = crate::abi_type::parse_type_list(&signature[open + 1..close])?;
+        self.functions
+            .iter()
+            .find(|function| function.name == name && param_kinds(&function.inputs) == kinds)
+            .ok_or_else(not_found)
+    }
+
+    /// Encodes the constructor arguments; a contract without a constructor
+    /// takes none.
+    pub fn encode_constructor(&self, args: &[AbiValue]) -> Result<Vec<u8>, HederaError> {
+        match &self.constructor {
+            Some(constructor) => constructor.encode_input(args),
+            None => abi_encode(&[], args),
+        }
+    }
+
+    /// The first event called `name`.
+    pub fn event(&self, name: &str) -> Option<&AbiEvent> {
+        self.events.iter().find(|event| event.name == name)
+    }
+
+    /// Decodes a log of one of the (non anonymous) events, found by its first
+    /// topic.
+    pub fn decode_log(&self, log: &ContractLogInfo) -> Result<DecodedEvent, HederaError> {
+        let topic = log.topic.first().map(Vec::as_slice).unwrap_or_default();
+        self.events
+            .iter()
+            .find(|event| !event.anonymous && event.topic()[..] == *topic)
+            .ok_or_else(|| HederaError::AbiEventNotFound(hex::encode(topic)))?
+            .decode_log(log)
+    }
+
+    /// Decodes revert data, including the custom errors of the contract.
+    pub fn decode_revert(&self, data: &[u8]) -> ContractRevert {
+        if data.len() >= 4 {
+            let (selector, args) = data.split_at(4);
+            for error in &self.errors {
+                if error.selector()[..] != *selector {
+                    continue;
+                }
+                if let Ok(values) = abi_decode(&param_kinds(&error.inputs), args) {
+                    return ContractRevert::Custom {
+                        name: error.name.clone(),
+                        params: error
+                            .inputs
+                            .iter()
+                            .map(|param| param.name.clone())
+                            .zip(values)
+                            .collect(),
+                    };
+                }
+            }
+        }
+        ContractRevert::from_bytes(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ABI: &str = r#"[
+        {"type": "constructor", "stateMutability": "nonpayable",
+         "inputs": [{"name": "owner", "type": "address"}]},
+        {"type": "function", "name": "baz", "stateMutability": "pure",
+         "inputs": [{"name": "x", "type": "uint32"}, {"name": "y", "type": "bool"}],
+         "outputs": [{"name": "", "type": "bool"}]},
+        {"type": "function", "name": "baz", "stateMutability": "nonpayable",
+         "inputs": [{"name": "x", "type": "int16"}], "outputs": []},
+        {"type": "function", "name": "place", "stateMutability": "payable",
+         "inputs": [{"name": "orders", "type": "tuple[]", "internalType": "struct Pool.Order[]",
+                     "components": [{"name": "amount", "type": "uint128"},
+                                    {"name": "flags", "type": "bool[]"}]}],
+         "outputs": []},
+        {"type": "event", "name": "Placed", "anonymous": false,
+         "inputs": [{"name": "id", "type": "uint256", "indexed": true},
+                    {"name": "memo", "type": "string", "indexed": true},
+                    {"name": "amounts", "type": "uint128[]", "indexed": false},
+                    {"name": "owner", "type": "address", "indexed": false}]},
+        {"type": "error", "name": "InsufficientBalance",
+         "inputs": [{"name": "available", "type": "uint256"},
+                    {"name": "required", "type": "uint256"}]}
+    ]"#;
+
+    #[test]
+    fn test_contract_abi_from_json() {
+        let abi = ContractAbi::from_json(ABI).unwrap();
+        assert_eq!(abi.functions.len(), 3);
+        assert_eq!(
+            abi.constructor.as_ref().unwrap().inputs[0].kind,
+            AbiType::Address
+        );
+
+        let place = abi.function("place").unwrap();
+        assert_eq!(place.signature(), "place((uint128,bool[])[])");
+        assert_eq!(place.state_mutability, StateMutability::Payable);
+        assert_eq!(
+            place.inputs[0].internal_type.as_deref(),
+            Some("struct Pool.Order[]")
+        );
+
+        let artifact = format!(r#"{{"contractName": "Pool", "abi": {}}}"#, ABI);
+        assert_eq!(ContractAbi::from_json(&artifact).unwrap(), abi);
+        assert!(matches!(
+            abi.function("missing"),
+            Err(HederaError::AbiFunctionNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_abi_function_encode_input() {
+        let abi = ContractAbi::from_json(ABI).unwrap();
+        let baz = abi.function_by_signature("baz(uint32, bool)").unwrap();
+        assert!(baz.is_read_only());
+        assert_eq!(hex::encode(baz.selector()), "cdcd77c0");
+        assert_eq!(
+            hex::encode(baz.encode_input(&[69u32.into(), true.into()]).unwrap()),
+            format!("cdcd77c0{:0>64}{:0>64}", "45", "1")
+        );
+
+        let overload = abi.function_by_signature("baz(int16)").unwrap();
+        assert!(!overload.is_read_only());
+        assert!(overload.encode_input(&[40_000u32.into()]).is_err());
+
+        let owner = AbiValue::address("0x00000000000000000000000000000000000004d2").unwrap();
+        assert_eq!(
+            hex::encode(abi.encode_constructor(&[owner]).unwrap()),
+            format!("{:0>64}", "4d2")
+        );
+    }
+
+    #[test]
+    fn test_contract_abi_decode_log() {
+        let abi = ContractAbi::from_json(ABI).unwrap();
+        let placed = abi.event("Placed").unwrap();
+        assert_eq!(
+            placed.signature(),
+            "Placed(uint256,string,uint128[],address)"
+        );
+
+        let owner = AbiValue::address("0x00000000000000000000000000000000000004d2").unwrap();
+        let amounts: AbiValue = vec![5u64, 6].into();
+        let log = ContractLogInfo {
+            contract_id: crate::ContractId::simple(1001),
+            bloom: Vec::new(),
+            topic: vec![
+                placed.topic().to_vec(),
+                abi_encode(&[AbiType::Uint(256)], &[42u8.into()]).unwrap(),
+                vec![0xab; 32],
+            ],
+            data: abi_encode(
+                &[
+                    AbiType::Array(Box::new(AbiType::Uint(128))),
+                    AbiType::Address,
+                ],
+                &[amounts.clone(), owner.clone()],
+            )
+            .unwrap(),
+        };
+
+        let event = abi.decode_log(&log).unwrap();
+        assert_eq!(event.name, "Placed");
+        assert_eq!(event.get("id").and_then(AbiValue::as_u64), Some(42));
+        assert_eq!(
+            event.get("memo"),
+            Some(&AbiValue::FixedBytes(vec![0xab; 32]))
+        );
+        assert_eq!(event.get("amounts"), Some(&amounts));
+        assert_eq!(event.get("owner"), Some(&owner));
+
+        let mut truncated = log.clone();
+        truncated.topic.pop();
+        assert!(abi.decode_log(&truncated).is_err());
+        truncated.topic.clear();
+        assert!(matches!(
+            abi.decode_log(&truncated),
+            Err(HederaError::AbiEventNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_contract_abi_decode_revert() {
+        let abi = ContractAbi::from_json(ABI).unwrap();
+        let error = &abi.errors[0];
+        let mut data = error.selector().to_vec();
+        data.extend(
+            abi_encode(
+                &[AbiType::Uint(256), AbiType::Uint(256)],
+                &[1u8.into(), 2u8.into()],
+            )
+            .unwrap(),
+        );
+
+        let revert = abi.decode_revert(&data);
+        assert_eq!(
+            revert,
+            ContractRevert::Custom {
+                name: "InsufficientBalance".to_string(),
+                params: vec![
+                    ("available".to_string(), 1u8.into()),
+                    ("required".to_string(), 2u8.into()),
+                ],
+            }
+        );
+        assert_eq!(
+            revert.to_string(),
+            "reverted with InsufficientBalance(available: 1, required: 2)"
+        );
+        assert_eq!(
+            abi.decode_revert(&data[..4]),
+            ContractRevert::Unknown(data[..4].to_vec())
+        );
+    }
+
+    #[test]
+    fn test_abi_function_decode_output() {
+        let abi = ContractAbi::from_json(ABI).unwrap();
+        let baz = abi.function("baz").unwrap();
+        assert_eq!(
+            baz.decode_output(&abi_encode(&[AbiType::Bool], &[true.into()]).unwrap())
+                .unwrap(),
+            vec![AbiValue::Bool(true)]
+        );
+        assert!(baz.decode_output(&[0; 31]).is_err());
+    }
+}
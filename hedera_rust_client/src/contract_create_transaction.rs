@@ -1,5 +1,6 @@
 use chrono::Duration;
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
 use std::convert::TryFrom;
 
 use crate::entity_id::validate_option_id_checksum;
@@ -77,7 +78,7 @@ impl ContractCreateTransaction {
     gen_transaction_staked_id_option_fns!();
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "ContractCreateInstance",
     proto_type = "ContractCreateTransactionBody"
@@ -90,7 +91,9 @@ struct Proto {
     #[hedera_rust_client_derive(to_option_proto)]
     pub proxy_account_id: Option<AccountId>,
     #[hedera_rust_client_derive(to_option_proto)]
+    #[serde(serialize_with = "crate::json::option_duration")]
     pub auto_renew_period: Option<Duration>,
+    #[serde(serialize_with = "crate::json::hex")]
     pub constructor_parameters: Vec<u8>,
     #[hedera_rust_client_derive(to_option_proto)]
     pub shard_id: Option<ShardId>,
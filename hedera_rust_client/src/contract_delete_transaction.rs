@@ -1,4 +1,5 @@
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
 
 use crate::entity_id::{validate_id_checksum, validate_option_id_checksum};
 use crate::proto::services::contract_delete_transaction_body::Obtainers as ProtoObtainers;
@@ -88,7 +89,7 @@ impl ContractDeleteTransaction {
     );
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "ContractDeleteInstance",
     proto_type = "ContractDeleteTransactionBody"
@@ -96,7 +97,10 @@ impl ContractDeleteTransaction {
 struct Proto {
     #[hedera_rust_client_derive(to_option_proto)]
     pub contract_id: Option<ContractId>,
-    #[hedera_rust_client_derive(to_proto_with_fn = "obtainer_to_proto")]
+    #[hedera_rust_client_derive(
+        to_proto_with_fn = "obtainer_to_proto",
+        from_proto_with_fn = "obtainer_from_proto"
+    )]
     pub obtainers: Option<Obtainers>,
     pub permanent_removal: bool,
 }
@@ -111,7 +115,8 @@ impl Proto {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 enum Obtainers {
     TransferAccountId(AccountId),
     TransferContractId(ContractId),
@@ -133,3 +138,16 @@ fn obtainer_to_proto(o: &Option<Obtainers>) -> Result<Option<ProtoObtainers>, He
         None => Ok(None),
     }
 }
+
+fn obtainer_from_proto(o: Option<ProtoObtainers>) -> Result<Option<Obtainers>, HederaError> {
+    let obtainers = match o {
+        Some(ProtoObtainers::TransferAccountId(id)) => {
+            Some(Obtainers::TransferAccountId(AccountId::try_from(id)?))
+        }
+        Some(ProtoObtainers::TransferContractId(id)) => {
+            Some(Obtainers::TransferContractId(ContractId::from(id)))
+        }
+        None => None,
+    };
+    Ok(obtainers)
+}
@@ -1,4 +1,5 @@
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
 use std::convert::TryFrom;
 
 use crate::entity_id::validate_option_id_checksum;
@@ -51,7 +52,7 @@ impl ContractExecuteTransaction {
     );
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "ContractCall",
     proto_type = "ContractCallTransactionBody"
@@ -61,6 +62,7 @@ struct Proto {
     pub contract_id: Option<ContractId>,
     pub gas: i64,
     pub amount: i64,
+    #[serde(serialize_with = "crate::json::hex")]
     pub function_parameters: Vec<u8>,
 }
 
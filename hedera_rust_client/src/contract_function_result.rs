@@ -1,18 +1,25 @@
 use byteorder::{BigEndian, ByteOrder};
 use bytes::Buf;
+use serde::Serialize;
 use std::convert::TryFrom;
 
+use crate::abi_type::AbiType;
+use crate::abi_value::{abi_decode, AbiValue};
+use crate::contract_abi::AbiFunction;
 use crate::contract_log_info::ContractLogInfo;
+use crate::contract_revert::ContractRevert;
 use crate::error::HederaError;
 use crate::proto::services;
 use crate::utils;
 use crate::ContractId;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct ContractFunctionResult {
     pub contract_id: ContractId,
+    #[serde(serialize_with = "crate::json::hex")]
     pub contract_call_result: Vec<u8>,
     pub error_message: String,
+    #[serde(serialize_with = "crate::json::hex")]
     pub bloom: Vec<u8>,
     pub gas_used: u64,
     pub log_info: Vec<ContractLogInfo>,
@@ -20,91 +27,129 @@ pub struct ContractFunctionResult {
 }
 
 impl ContractFunctionResult {
+    // the bytes in [start, start + len) of the result, failing when it is short
+    fn slice(&self, start: usize, len: usize) -> Result<&[u8], HederaError> {
+        start
+            .checked_add(len)
+            .and_then(|end| self.contract_call_result.get(start..end))
+            .ok_or_else(|| {
+                HederaError::AbiDecode(format!(
+                    "no {} bytes at offset {} of a {} byte result",
+                    len,
+                    start,
+                    self.contract_call_result.len()
+                ))
+            })
+    }
+
+    // the 32 byte word at the given index
+    fn word(&self, index: usize) -> Result<&[u8], HederaError> {
+        let start = index
+            .checked_mul(32)
+            .ok_or_else(|| HederaError::AbiDecode(format!("no word at index {}", index)))?;
+        self.slice(start, 32)
+    }
+
     // gets a solidity bool from the result at the given index
-    pub fn bool(&self, index: usize) -> bool {
-        self.u32(index) == 1
+    pub fn bool(&self, index: usize) -> Result<bool, HederaError> {
+        Ok(self.u32(index)? == 1)
     }
 
     // gets a solidity address from the result at the given index
-    pub fn address(&self, index: usize) -> [u8; 20] {
+    pub fn address(&self, index: usize) -> Result<[u8; 20], HederaError> {
         let mut res = [0; 20];
-        res.copy_from_slice(&self.contract_call_result[(index * 32) + 12..(index * 32) + 32]);
-        res
+        res.copy_from_slice(&self.word(index)?[12..]);
+        Ok(res)
     }
 
     // gets a solidity int8 from the result at the given index
-    pub fn i8(&self, index: usize) -> i8 {
-        self.u8(index) as i8
+    pub fn i8(&self, index: usize) -> Result<i8, HederaError> {
+        Ok(self.u8(index)? as i8)
     }
 
     // gets a solidity int32 from the result at the given index
-    pub fn i32(&self, index: usize) -> i32 {
-        let buf =
-            BigEndian::read_u32(&self.contract_call_result[index * 32 + 28..(index + 1) * 32]);
-        buf as i32
+    pub fn i32(&self, index: usize) -> Result<i32, HederaError> {
+        Ok(self.u32(index)? as i32)
     }
 
     // gets a solidity int64 from the result at the given index
-    pub fn i64(&self, index: usize) -> i64 {
-        let buf =
-            BigEndian::read_u64(&self.contract_call_result[index * 32 + 24..(index + 1) * 32]);
-        buf as i64
+    pub fn i64(&self, index: usize) -> Result<i64, HederaError> {
+        Ok(self.u64(index)? as i64)
     }
 
     // gets a solidity int256 from the result at the given index
-    pub fn i256(&self, index: usize) -> [u8; 32] {
-        let mut res = [0; 32];
-        res.copy_from_slice(&self.contract_call_result[index * 32..index * 32 + 32]);
-        res
+    pub fn i256(&self, index: usize) -> Result<[u8; 32], HederaError> {
+        self.u256(index)
     }
 
     // gets a solidity uint8 from the result at the given index
-    pub fn u8(&self, index: usize) -> u8 {
-        self.contract_call_result[index * 32 + 31]
+    pub fn u8(&self, index: usize) -> Result<u8, HederaError> {
+        Ok(self.word(index)?[31])
     }
 
     // gets a solidity uint32 from the result at the given index
-    pub fn u32(&self, index: usize) -> u32 {
-        let buf =
-            BigEndian::read_u32(&self.contract_call_result[index * 32 + 28..(index + 1) * 32]);
-        buf
+    pub fn u32(&self, index: usize) -> Result<u32, HederaError> {
+        Ok(BigEndian::read_u32(&self.word(index)?[28..]))
     }
 
     // gets a solidity uint64 from the result at the given index
-    pub fn u64(&self, index: usize) -> u64 {
-        let buf =
-            BigEndian::read_u64(&self.contract_call_result[index * 32 + 24..(index + 1) * 32]);
-        buf
+    pub fn u64(&self, index: usize) -> Result<u64, HederaError> {
+        Ok(BigEndian::read_u64(&self.word(index)?[24..]))
     }
 
     // gets a solidity int256 from the result at the given index
-    pub fn u256(&self, index: usize) -> [u8; 32] {
+    pub fn u256(&self, index: usize) -> Result<[u8; 32], HederaError> {
         let mut res = [0; 32];
-        res.copy_from_slice(&self.contract_call_result[index * 32..index * 32 + 32]);
-        res
+        res.copy_from_slice(self.word(index)?);
+        Ok(res)
     }
 
-    pub fn bytes_32(&self, index: usize) -> Vec<u8> {
-        self.contract_call_result[index * 32..index * 32 + 32].to_vec()
+    pub fn bytes_32(&self, index: usize) -> Result<Vec<u8>, HederaError> {
+        Ok(self.word(index)?.to_vec())
     }
 
     // gets a string from the result at the given index
-    pub fn string(&self, index: usize) -> String {
-        String::from_utf8_lossy(&self.bytes(index)).to_string()
+    pub fn string(&self, index: usize) -> Result<String, HederaError> {
+        Ok(String::from_utf8_lossy(&self.bytes(index)?).to_string())
     }
 
     // gets a byte array from the result at the given index
-    pub fn bytes(&self, index: usize) -> Vec<u8> {
-        let offset = self.u64(index) as usize;
-        let mut buf = &self.contract_call_result[offset + 24..offset + 32];
-        let length = buf.get_u64() as usize;
-        self.contract_call_result[offset + 32..offset + 32 + length].to_vec()
+    pub fn bytes(&self, index: usize) -> Result<Vec<u8>, HederaError> {
+        let offset = usize::try_from(self.u64(index)?)
+            .map_err(|_| HederaError::AbiDecode(format!("bytes offset at index {}", index)))?;
+        let mut buf = &self.slice(offset, 32)?[24..];
+        let length = usize::try_from(buf.get_u64())
+            .map_err(|_| HederaError::AbiDecode(format!("bytes length at offset {}", offset)))?;
+        Ok(self.slice(offset + 32, length)?.to_vec())
     }
 
     // AsBytes returns the raw bytes of the ContractCallResult
     pub fn as_bytes(&self) -> Vec<u8> {
         self.contract_call_result.clone()
     }
+
+    // decodes the result as the given types, failing on malformed output
+    pub fn decode(&self, types: &[AbiType]) -> Result<Vec<AbiValue>, HederaError> {
+        abi_decode(types, &self.contract_call_result)
+    }
+
+    // decodes the result as the outputs of the called function
+    pub fn decode_output(&self, function: &AbiFunction) -> Result<Vec<AbiValue>, HederaError> {
+        function.decode_output(&self.contract_call_result)
+    }
+
+    // the revert data, which the error message holds as 0x prefixed hex
+    pub fn revert_data(&self) -> Option<Vec<u8>> {
+        let data = self.error_message.trim().strip_prefix("0x")?;
+        hex::decode(data).ok()
+    }
+
+    // the decoded revert reason, if the call reverted with data; use
+    // ContractAbi::decode_revert for the custom errors of a contract
+    pub fn revert(&self) -> Option<ContractRevert> {
+        self.revert_data()
+            .map(|data| ContractRevert::from_bytes(&data))
+    }
 }
 
 impl TryFrom<services::ContractFunctionResult> for ContractFunctionResult {
@@ -134,3 +179,94 @@ impl TryFrom<services::ContractFunctionResult> for ContractFunctionResult {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi_value::abi_encode;
+    use crate::contract_revert::ERROR_SELECTOR;
+
+    fn result(contract_call_result: Vec<u8>, error_message: &str) -> ContractFunctionResult {
+        ContractFunctionResult {
+            contract_id: ContractId::simple(1001),
+            contract_call_result,
+            error_message: error_message.to_string(),
+            bloom: Vec::new(),
+            gas_used: 0,
+            log_info: Vec::new(),
+            created_contract_ids: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_contract_function_result_decode() {
+        let types = [AbiType::String, AbiType::Array(Box::new(AbiType::Int(16)))];
+        let values = vec!["hello".into(), vec![-1i16, 2].into()];
+        let res = result(abi_encode(&types, &values).unwrap(), "");
+        assert_eq!(res.decode(&types).unwrap(), values);
+        assert!(result(vec![0; 40], "").decode(&types).is_err());
+        assert_eq!(res.revert(), None);
+    }
+
+    #[test]
+    fn test_contract_function_result_accessors() {
+        let types = [AbiType::Uint(64), AbiType::Address, AbiType::String];
+        let address = [7u8; 20];
+        let values = vec![
+            AbiValue::Uint(42u64.into()),
+            AbiValue::Address(address),
+            "hello".into(),
+        ];
+        let res = result(abi_encode(&types, &values).unwrap(), "");
+        assert_eq!(res.u64(0).unwrap(), 42);
+        assert_eq!(res.i8(0).unwrap(), 42);
+        assert_eq!(res.address(1).unwrap(), address);
+        assert_eq!(res.string(2).unwrap(), "hello");
+        assert_eq!(res.bytes(2).unwrap(), b"hello".to_vec());
+        assert!(res.u64(5).is_err());
+        assert!(res.u64(usize::MAX).is_err());
+    }
+
+    #[test]
+    fn test_contract_function_result_short_output() {
+        let short = result(vec![0; 31], "");
+        assert!(short.bool(0).is_err());
+        assert!(short.u8(0).is_err());
+        assert!(short.u64(0).is_err());
+        assert!(short.u256(0).is_err());
+        assert!(short.address(0).is_err());
+        assert!(short.bytes_32(0).is_err());
+        assert!(short.string(0).is_err());
+
+        // an offset past the end, then a length past the end
+        let mut data = vec![0; 64];
+        data[31] = 64;
+        assert!(result(data.clone(), "").bytes(0).is_err());
+        data.extend(vec![0; 32]);
+        data[95] = 10;
+        assert!(result(data.clone(), "").string(0).is_err());
+        data.extend(vec![b'a'; 10]);
+        assert_eq!(result(data, "").string(0).unwrap(), "aaaaaaaaaa");
+
+        // an offset that doesn't fit in memory
+        let mut data = vec![0xff; 32];
+        data[..24].fill(0);
+        assert!(result(data, "").bytes(0).is_err());
+    }
+
+    #[test]
+    fn test_contract_function_result_revert() {
+        let mut data = ERROR_SELECTOR.to_vec();
+        data.extend(abi_encode(&[AbiType::String], &["too late".into()]).unwrap());
+        let res = result(Vec::new(), &format!("0x{}", hex::encode(&data)));
+        assert_eq!(res.revert_data(), Some(data));
+        assert_eq!(
+            res.revert(),
+            Some(ContractRevert::Error("too late".to_string()))
+        );
+        assert_eq!(
+            result(Vec::new(), "CONTRACT_REVERT_EXECUTED").revert(),
+            None
+        );
+    }
+}
@@ -1,12 +1,11 @@
 use hedera_rust_client_derive::{Id, IdPartialEq, IdValidateChecksum};
-use serde::{Deserialize, Serialize};
 
 use crate::error::HederaError;
 use crate::id::IdChecksum;
 use crate::proto::services::{contract_id::Contract, ContractId as ProtoContractId};
 use crate::proto::ToProto;
 
-#[derive(Id, IdPartialEq, IdValidateChecksum, Debug, Clone, Copy, Hash, Serialize, Deserialize)]
+#[derive(Id, IdPartialEq, IdValidateChecksum, Debug, Clone, Copy, Hash)]
 #[hedera_rust_client_derive(field_name = "contract_num")]
 pub struct ContractId {
     shard_num: i64,
@@ -1,14 +1,18 @@
+use serde::Serialize;
 use std::convert::TryFrom;
 
 use crate::proto::services;
 use crate::utils;
 use crate::ContractId;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct ContractLogInfo {
     pub contract_id: ContractId,
+    #[serde(serialize_with = "crate::json::hex")]
     pub bloom: Vec<u8>,
+    #[serde(serialize_with = "crate::json::hex_vec")]
     pub topic: Vec<Vec<u8>>,
+    #[serde(serialize_with = "crate::json::hex")]
     pub data: Vec<u8>,
 }
 
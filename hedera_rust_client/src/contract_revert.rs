@@ -0,0 +1,128 @@
+use num::bigint::BigUint;
+use num::ToPrimitive;
+use std::fmt;
+
+use crate::abi_type::AbiType;
+use crate::abi_value::{abi_decode, AbiValue};
+
+/// Selector of `Error(string)`, raised by `require` and `revert("...")`.
+pub const ERROR_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+/// Selector of `Panic(uint256)`, raised by failing asserts, overflows and
+/// the like.
+pub const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+/// Why a contract call reverted, decoded from its revert data.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ContractRevert {
+    #[error("reverted: {0}")]
+    Error(String),
+
+    #[error("panicked with code {:#x}: {}", .0, panic_reason(.0))]
+    Panic(BigUint),
+
+    /// A custom `error` of the contract ABI, with its named arguments.
+    #[error("reverted with {name}{}", format_params(.params))]
+    Custom {
+        name: String,
+        params: Vec<(String, AbiValue)>,
+    },
+
+    /// Revert data that isn't a known error, possibly empty.
+    #[error("reverted with data 0x{}", hex::encode(.0))]
+    Unknown(Vec<u8>),
+}
+
+impl ContractRevert {
+    /// Decodes `Error(string)` and `Panic(uint256)` revert data; anything else
+    /// is `Unknown`.
+    pub fn from_bytes(data: &[u8]) -> ContractRevert {
+        if data.len() >= 4 {
+            let (selector, args) = data.split_at(4);
+            if selector == ERROR_SELECTOR {
+                if let Ok(values) = abi_decode(&[AbiType::String], args) {
+                    if let Some(AbiValue::String(message)) = values.into_iter().next() {
+                        return ContractRevert::Error(message);
+                    }
+                }
+            } else if selector == PANIC_SELECTOR {
+                if let Ok(values) = abi_decode(&[AbiType::Uint(256)], args) {
+                    if let Some(AbiValue::Uint(code)) = values.into_iter().next() {
+                        return ContractRevert::Panic(code);
+                    }
+                }
+            }
+        }
+        ContractRevert::Unknown(data.to_vec())
+    }
+}
+
+fn panic_reason(code: &BigUint) -> &'static str {
+    match code.to_u64() {
+        Some(0x00) => "generic compiler panic",
+        Some(0x01) => "assertion failed",
+        Some(0x11) => "arithmetic overflow or underflow",
+        Some(0x12) => "division or modulo by zero",
+        Some(0x21) => "invalid enum value",
+        Some(0x22) => "invalid storage byte array encoding",
+        Some(0x31) => "pop on empty array",
+        Some(0x32) => "array index out of bounds",
+        Some(0x41) => "out of memory",
+        Some(0x51) => "call to zero-initialized internal function",
+        _ => "unknown panic code",
+    }
+}
+
+struct Params<'a>(&'a [(String, AbiValue)]);
+
+impl fmt::Display for Params<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "(")?;
+        for (i, (name, value)) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            if name.is_empty() {
+                write!(f, "{}", value)?;
+            } else {
+                write!(f, "{}: {}", name, value)?;
+            }
+        }
+        write!(f, ")")
+    }
+}
+
+fn format_params(params: &[(String, AbiValue)]) -> Params<'_> {
+    Params(params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi_value::abi_encode;
+
+    #[test]
+    fn test_contract_revert_from_bytes() {
+        let mut data = ERROR_SELECTOR.to_vec();
+        data.extend(abi_encode(&[AbiType::String], &["not owner".into()]).unwrap());
+        let revert = ContractRevert::from_bytes(&data);
+        assert_eq!(revert, ContractRevert::Error("not owner".to_string()));
+        assert_eq!(revert.to_string(), "reverted: not owner");
+
+        let mut data = PANIC_SELECTOR.to_vec();
+        data.extend(abi_encode(&[AbiType::Uint(256)], &[0x11u8.into()]).unwrap());
+        assert_eq!(
+            ContractRevert::from_bytes(&data).to_string(),
+            "panicked with code 0x11: arithmetic overflow or underflow"
+        );
+
+        // truncated
+        assert_eq!(
+            ContractRevert::from_bytes(&data[..20]),
+            ContractRevert::Unknown(data[..20].to_vec())
+        );
+        assert_eq!(
+            ContractRevert::from_bytes(&[]),
+            ContractRevert::Unknown(Vec::new())
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Duration, Utc};
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
 
 use crate::entity_id::validate_option_id_checksum;
 use crate::memo::check_memo_length;
@@ -85,7 +86,7 @@ impl ContractUpdateTransaction {
     gen_transaction_staked_id_option_fns!();
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "ContractUpdateInstance",
     proto_type = "ContractUpdateTransactionBody"
@@ -100,6 +101,7 @@ struct Proto {
     #[hedera_rust_client_derive(to_option_proto)]
     pub proxy_account_id: Option<AccountId>,
     #[hedera_rust_client_derive(to_option_proto)]
+    #[serde(serialize_with = "crate::json::option_duration")]
     pub auto_renew_period: Option<Duration>,
     #[hedera_rust_client_derive(to_option_proto)]
     pub file_id: Option<FileId>,
@@ -107,7 +109,10 @@ struct Proto {
     #[hedera_rust_client_derive(to_option_proto)]
     pub auto_renew_account_id: Option<AccountId>,
     pub decline_reward: Option<bool>,
-    #[hedera_rust_client_derive(to_proto_with_fn = "memo_proto")]
+    #[hedera_rust_client_derive(
+        to_proto_with_fn = "memo_proto",
+        from_proto_with_fn = "memo_from_proto"
+    )]
     pub memo_field: Option<String>,
     #[hedera_rust_client_derive(to_option_proto)]
     pub staked_id: Option<StakedId>,
@@ -139,3 +144,16 @@ fn memo_proto(
         .map(|x| services::contract_update_transaction_body::MemoField::MemoWrapper(x.clone()));
     Ok(f)
 }
+
+fn memo_from_proto(
+    memo_field: Option<services::contract_update_transaction_body::MemoField>,
+) -> Result<Option<String>, HederaError> {
+    use services::contract_update_transaction_body::MemoField;
+    Ok(match memo_field {
+        Some(MemoField::MemoWrapper(memo)) => Some(memo),
+        // older clients set the deprecated field, empty meaning unchanged
+        #[allow(deprecated)]
+        Some(MemoField::Memo(memo)) if !memo.is_empty() => Some(memo),
+        _ => None,
+    })
+}
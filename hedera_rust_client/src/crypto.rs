@@ -1,13 +1,15 @@
 use bip39::{Language, Mnemonic, MnemonicType, Seed};
+use k256::ecdsa::signature::hazmat::{PrehashSigner, PrehashVerifier};
 use once_cell::sync::Lazy;
 use rand_chacha::{ChaCha20Core, ChaCha20Rng};
-use rand_core::{OsRng, SeedableRng};
+use rand_core::{OsRng, RngCore, SeedableRng};
 use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
 use serde::ser::{Serialize, SerializeTupleStruct, Serializer};
+use sha3::{Digest, Keccak256};
 #[allow(unused_imports)]
 use simple_asn1::{
-    der_decode, der_encode, oid, to_der, ASN1Block, ASN1Class, ASN1DecodeErr, ASN1EncodeErr,
-    BigUint, FromASN1, ToASN1, OID,
+    der_decode, der_encode, from_der, oid, to_der, ASN1Block, ASN1Class, ASN1DecodeErr,
+    ASN1EncodeErr, BigUint, FromASN1, ToASN1, OID,
 };
 use std::convert::TryFrom;
 use std::fmt::{self, Debug, Display};
@@ -41,6 +43,34 @@ pub enum Asn1Error {
 // [https://tools.ietf.org/id/draft-ietf-curdle-pkix-01.html#rfc.section.3]
 static OID_ED25519: Lazy<OID> = Lazy::new(|| oid!(1, 3, 101, 112));
 
+// [https://tools.ietf.org/html/rfc5480#section-2.1.1]
+static OID_EC_PUBLIC_KEY: Lazy<OID> = Lazy::new(|| oid!(1, 2, 840, 10045, 2, 1));
+
+// [https://www.secg.org/sec2-v2.pdf]
+static OID_SECP256K1: Lazy<OID> = Lazy::new(|| oid!(1, 3, 132, 0, 10));
+
+const ECDSA_SECP256K1_PRIVATE_KEY_LENGTH: usize = 32;
+const ECDSA_SECP256K1_PUBLIC_KEY_LENGTH: usize = 33;
+const ECDSA_SECP256K1_UNCOMPRESSED_PUBLIC_KEY_LENGTH: usize = 65;
+
+const ASN1_SEQUENCE_TAG: u8 = 0x30;
+
+/// The signature algorithm of a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyAlgorithm {
+    Ed25519,
+    EcdsaSecp256k1,
+}
+
+impl Display for KeyAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            KeyAlgorithm::Ed25519 => f.write_str("ed25519"),
+            KeyAlgorithm::EcdsaSecp256k1 => f.write_str("ecdsa_secp256k1"),
+        }
+    }
+}
+
 // [https://www.ietf.org/rfc/rfc3280.txt]
 // AlgorithmIdentifier ::= SEQUENCE {
 //      algorithm               OBJECT IDENTIFIER,
@@ -49,15 +79,60 @@ static OID_ED25519: Lazy<OID> = Lazy::new(|| oid!(1, 3, 101, 112));
 #[derive(Debug)]
 struct AlgorithmIdentifier {
     algorithm: OID,
+    parameters: Option<OID>,
+}
+
+impl AlgorithmIdentifier {
+    fn ed25519() -> Self {
+        Self {
+            algorithm: OID_ED25519.clone(),
+            parameters: None,
+        }
+    }
+
+    fn ecdsa_secp256k1() -> Self {
+        Self {
+            algorithm: OID_EC_PUBLIC_KEY.clone(),
+            parameters: Some(OID_SECP256K1.clone()),
+        }
+    }
+
+    fn key_algorithm(&self) -> Option<KeyAlgorithm> {
+        if self.algorithm == *OID_ED25519 {
+            return Some(KeyAlgorithm::Ed25519);
+        }
+        // NOTE: older Hedera SDKs encode secp256k1 keys with the curve OID as the
+        //       algorithm and no parameters
+        if self.algorithm == *OID_SECP256K1
+            || (self.algorithm == *OID_EC_PUBLIC_KEY
+                && self.parameters.as_ref() == Some(&*OID_SECP256K1))
+        {
+            return Some(KeyAlgorithm::EcdsaSecp256k1);
+        }
+        None
+    }
+
+    fn to_asn1_block(&self) -> ASN1Block {
+        // FIXME: Rewrite or improve the ASN.1 lib to remove allocation requirement
+        let mut blocks = vec![ASN1Block::ObjectIdentifier(0, self.algorithm.clone())];
+        if let Some(parameters) = &self.parameters {
+            blocks.push(ASN1Block::ObjectIdentifier(0, parameters.clone()));
+        }
+        ASN1Block::Sequence(0, blocks)
+    }
 }
 
 impl FromASN1 for AlgorithmIdentifier {
     type Error = Asn1Error;
 
     fn from_asn1(v: &[ASN1Block]) -> Result<(Self, &[ASN1Block]), Asn1Error> {
-        let algorithm = if let Some(ASN1Block::Sequence(_, blocks)) = v.get(0) {
+        let (algorithm, parameters) = if let Some(ASN1Block::Sequence(_, blocks)) = v.first() {
             if let Some(ASN1Block::ObjectIdentifier(_, id)) = blocks.get(0) {
-                id
+                let parameters = match blocks.get(1) {
+                    Some(ASN1Block::ObjectIdentifier(_, parameters)) => Some(parameters),
+                    _ => None,
+                };
+                (id, parameters)
             } else {
                 return Err(Asn1Error::UnexpectedType {
                     expected: "OBJECT IDENTIFIER",
@@ -75,6 +150,7 @@ impl FromASN1 for AlgorithmIdentifier {
             Self {
                 // FIXME: Rewrite or improve the ASN.1 lib to remove allocation requirement
                 algorithm: algorithm.clone(),
+                parameters: parameters.cloned(),
             },
             &v[1..],
         ))
@@ -100,14 +176,7 @@ impl ToASN1 for SubjectPublicKeyInfo {
             0,
             vec![
                 // AlgorithmIdentifier
-                ASN1Block::Sequence(
-                    0,
-                    vec![
-                        // Algorithm
-                        // FIXME: Rewrite or improve the ASN.1 lib to remove allocation requirement
-                        ASN1Block::ObjectIdentifier(0, self.algorithm.algorithm.clone()),
-                    ],
-                ),
+                self.algorithm.to_asn1_block(),
                 // subjectPublicKey
                 ASN1Block::BitString(
                     0,
@@ -161,6 +230,8 @@ impl FromASN1 for SubjectPublicKeyInfo {
 //      privateKeyAlgorithm       AlgorithmIdentifier,
 //      privateKey                OCTET STRING,
 //      attributes           [0]  IMPLICIT Attributes OPTIONAL }
+//
+// NOTE: `private_key` holds the DER encoded contents of the privateKey OCTET STRING
 
 struct PrivateKeyInfo {
     algorithm: AlgorithmIdentifier,
@@ -177,20 +248,10 @@ impl ToASN1 for PrivateKeyInfo {
                 // Version
                 ASN1Block::Integer(0, 0.into()),
                 // AlgorithmIdentifier
-                ASN1Block::Sequence(
-                    0,
-                    vec![
-                        // Algorithm
-                        // FIXME: Rewrite or improve the ASN.1 lib to remove allocation requirement
-                        ASN1Block::ObjectIdentifier(0, self.algorithm.algorithm.clone()),
-                    ],
-                ),
+                self.algorithm.to_asn1_block(),
                 // PrivateKey
-                ASN1Block::OctetString(
-                    0,
-                    // FIXME: Rewrite or improve the ASN.1 lib to remove allocation requirement
-                    to_der(&ASN1Block::OctetString(0, self.private_key.clone()))?,
-                ),
+                // FIXME: Rewrite or improve the ASN.1 lib to remove allocation requirement
+                ASN1Block::OctetString(0, self.private_key.clone()),
             ],
         )])
     }
@@ -232,35 +293,169 @@ impl FromASN1 for PrivateKeyInfo {
     }
 }
 
-/// An ed25519 public key.
+// [https://www.ietf.org/rfc/rfc5915.txt]
+// ECPrivateKey ::= SEQUENCE {
+//      version        INTEGER { ecPrivkeyVer1(1) } (ecPrivkeyVer1),
+//      privateKey     OCTET STRING,
+//      parameters [0] ECParameters {{ NamedCurve }} OPTIONAL,
+//      publicKey  [1] BIT STRING OPTIONAL }
+
+struct EcPrivateKey {
+    private_key: Vec<u8>,
+}
+
+impl ToASN1 for EcPrivateKey {
+    type Error = Asn1Error;
+
+    fn to_asn1_class(&self, _c: ASN1Class) -> Result<Vec<ASN1Block>, Asn1Error> {
+        Ok(vec![ASN1Block::Sequence(
+            0,
+            vec![
+                // Version
+                ASN1Block::Integer(0, 1.into()),
+                // PrivateKey
+                // FIXME: Rewrite or improve the ASN.1 lib to remove allocation requirement
+                ASN1Block::OctetString(0, self.private_key.clone()),
+            ],
+        )])
+    }
+}
+
+impl FromASN1 for EcPrivateKey {
+    type Error = Asn1Error;
+
+    fn from_asn1(v: &[ASN1Block]) -> Result<(Self, &[ASN1Block]), Asn1Error> {
+        let key = if let Some(ASN1Block::Sequence(_, blocks)) = v.first() {
+            // Parse: version
+            match blocks.first() {
+                Some(ASN1Block::Integer(_, version)) if *version == 1.into() => {}
+                block => {
+                    return Err(Asn1Error::UnexpectedType {
+                        expected: "INTEGER 1",
+                        found: format!("{:?}", block),
+                    })
+                }
+            }
+
+            // Parse: private_key
+            if let Some(ASN1Block::OctetString(_, bytes)) = blocks.get(1) {
+                bytes
+            } else {
+                return Err(Asn1Error::UnexpectedType {
+                    expected: "OCTET STRING",
+                    found: format!("{:?}", blocks.get(1)),
+                });
+            }
+        } else {
+            return Err(Asn1Error::UnexpectedType {
+                expected: "SEQUENCE",
+                found: format!("{:?}", v.first()),
+            });
+        };
+
+        Ok((
+            Self {
+                // FIXME: Rewrite or improve the ASN.1 lib to remove allocation requirement
+                private_key: key.clone(),
+            },
+            &v[1..],
+        ))
+    }
+}
+
+// The privateKey of a secp256k1 PKCS#8 document is either an ECPrivateKey or,
+// as written by older Hedera SDKs, the raw key wrapped in an OCTET STRING.
+fn decode_ecdsa_private_key_contents(contents: &[u8]) -> Result<Vec<u8>, HederaError> {
+    let blocks = from_der(contents).map_err(Asn1Error::from)?;
+    match blocks.first() {
+        Some(ASN1Block::OctetString(_, bytes)) => Ok(bytes.clone()),
+        Some(ASN1Block::Sequence(..)) => {
+            let (key, _): (EcPrivateKey, _) = FromASN1::from_asn1(&blocks)?;
+            Ok(key.private_key)
+        }
+        block => Err(Asn1Error::UnexpectedType {
+            expected: "OCTET STRING or SEQUENCE",
+            found: format!("{:?}", block),
+        }
+        .into()),
+    }
+}
+
+// Decode a hex string, allowing the `0x` prefix used for EVM keys.
+fn decode_hex(s: &str) -> Result<Vec<u8>, HederaError> {
+    Ok(hex::decode(s.strip_prefix("0x").unwrap_or(s).as_bytes())?)
+}
+
 #[derive(Clone, Copy)]
-pub struct PublicKey(ed25519_dalek::PublicKey);
+enum PublicKeyData {
+    Ed25519(ed25519_dalek::PublicKey),
+    // NOTE: the compressed encoding is kept alongside the key so `as_bytes` can
+    //       return a reference
+    EcdsaSecp256k1(
+        k256::ecdsa::VerifyingKey,
+        [u8; ECDSA_SECP256K1_PUBLIC_KEY_LENGTH],
+    ),
+}
+
+/// An ed25519 or ECDSA (secp256k1) public key.
+#[derive(Clone, Copy)]
+pub struct PublicKey(PublicKeyData);
 
 impl PublicKey {
+    fn from_verifying_key(key: k256::ecdsa::VerifyingKey) -> Self {
+        let mut compressed = [0u8; ECDSA_SECP256K1_PUBLIC_KEY_LENGTH];
+        compressed.copy_from_slice(key.to_encoded_point(true).as_bytes());
+        PublicKey(PublicKeyData::EcdsaSecp256k1(key, compressed))
+    }
+
     /// Construct a `PublicKey` from a slice of bytes.
     /// Bytes are expected to be either a raw key or encoded in ASN.1.
+    ///
+    /// Raw keys of 32 bytes are read as ed25519, raw keys of 33 (compressed) or
+    /// 65 (uncompressed) bytes are read as ECDSA (secp256k1).
     pub fn from_bytes(bytes: impl AsRef<[u8]>) -> Result<Self, HederaError> {
         let bytes = bytes.as_ref();
 
-        if bytes.len() == ed25519_dalek::PUBLIC_KEY_LENGTH {
+        match bytes.len() {
             // If the buffer is exactly the length of a public key; assume that this is
             // a raw key and return it directly
-            return Ok(PublicKey(ed25519_dalek::PublicKey::from_bytes(bytes)?));
+            ed25519_dalek::PUBLIC_KEY_LENGTH => Self::from_bytes_ed25519(bytes),
+            ECDSA_SECP256K1_PUBLIC_KEY_LENGTH | ECDSA_SECP256K1_UNCOMPRESSED_PUBLIC_KEY_LENGTH => {
+                Self::from_bytes_ecdsa(bytes)
+            }
+            _ => Self::from_bytes_der(bytes),
         }
+    }
 
-        let info: SubjectPublicKeyInfo = der_decode(&bytes)?;
+    /// Construct an ed25519 `PublicKey` from its raw bytes.
+    pub fn from_bytes_ed25519(bytes: impl AsRef<[u8]>) -> Result<Self, HederaError> {
+        Ok(PublicKey(PublicKeyData::Ed25519(
+            ed25519_dalek::PublicKey::from_bytes(bytes.as_ref())?,
+        )))
+    }
 
-        if info.algorithm.algorithm != *OID_ED25519 {
-            return Err(HederaError::UnknownPublicKeyAlgorithm);
-        }
+    /// Construct an ECDSA (secp256k1) `PublicKey` from its compressed or
+    /// uncompressed SEC1 bytes.
+    pub fn from_bytes_ecdsa(bytes: impl AsRef<[u8]>) -> Result<Self, HederaError> {
+        Ok(Self::from_verifying_key(
+            k256::ecdsa::VerifyingKey::from_sec1_bytes(bytes.as_ref())?,
+        ))
+    }
 
-        if info.subject_public_key.len() != ed25519_dalek::PUBLIC_KEY_LENGTH {
-            return Err(HederaError::InvalidPublicKeyLength);
-        }
+    /// Construct a `PublicKey` from a slice of bytes encoded in ASN.1.
+    pub fn from_bytes_der(bytes: impl AsRef<[u8]>) -> Result<Self, HederaError> {
+        let info: SubjectPublicKeyInfo = der_decode(bytes.as_ref())?;
 
-        Ok(PublicKey(ed25519_dalek::PublicKey::from_bytes(
-            &info.subject_public_key,
-        )?))
+        match info.algorithm.key_algorithm() {
+            Some(KeyAlgorithm::Ed25519) => {
+                if info.subject_public_key.len() != ed25519_dalek::PUBLIC_KEY_LENGTH {
+                    return Err(HederaError::InvalidPublicKeyLength);
+                }
+                Self::from_bytes_ed25519(&info.subject_public_key)
+            }
+            Some(KeyAlgorithm::EcdsaSecp256k1) => Self::from_bytes_ecdsa(&info.subject_public_key),
+            None => Err(HederaError::UnknownPublicKeyAlgorithm),
+        }
     }
 
     pub fn from_hex_bytes(bytes: Vec<u8>) -> Result<Self, HederaError> {
@@ -273,10 +468,41 @@ impl PublicKey {
         }
     }
 
+    /// Construct an ed25519 `PublicKey` from a hex representation of its raw bytes.
+    pub fn from_str_ed25519(s: &str) -> Result<Self, HederaError> {
+        Self::from_bytes_ed25519(decode_hex(s)?)
+    }
+
+    /// Construct an ECDSA (secp256k1) `PublicKey` from a hex representation of its
+    /// compressed or uncompressed bytes.
+    pub fn from_str_ecdsa(s: &str) -> Result<Self, HederaError> {
+        Self::from_bytes_ecdsa(decode_hex(s)?)
+    }
+
+    /// Return the signature algorithm of this `PublicKey`.
+    pub fn algorithm(&self) -> KeyAlgorithm {
+        match &self.0 {
+            PublicKeyData::Ed25519(_) => KeyAlgorithm::Ed25519,
+            PublicKeyData::EcdsaSecp256k1(..) => KeyAlgorithm::EcdsaSecp256k1,
+        }
+    }
+
+    pub fn is_ed25519(&self) -> bool {
+        self.algorithm() == KeyAlgorithm::Ed25519
+    }
+
+    pub fn is_ecdsa(&self) -> bool {
+        self.algorithm() == KeyAlgorithm::EcdsaSecp256k1
+    }
+
     /// Return the `PublicKey` as raw bytes.
+    /// ECDSA (secp256k1) keys are returned in their compressed form.
     #[inline]
-    pub fn as_bytes(&self) -> &[u8; ed25519_dalek::PUBLIC_KEY_LENGTH] {
-        self.0.as_bytes()
+    pub fn as_bytes(&self) -> &[u8] {
+        match &self.0 {
+            PublicKeyData::Ed25519(key) => key.as_bytes(),
+            PublicKeyData::EcdsaSecp256k1(_, compressed) => compressed,
+        }
     }
 
     #[inline]
@@ -286,10 +512,12 @@ impl PublicKey {
 
     /// Format a `PublicKey` as a vec of bytes in ASN.1 format.
     pub fn to_encoded_bytes(&self) -> Vec<u8> {
+        let algorithm = match self.algorithm() {
+            KeyAlgorithm::Ed25519 => AlgorithmIdentifier::ed25519(),
+            KeyAlgorithm::EcdsaSecp256k1 => AlgorithmIdentifier::ecdsa_secp256k1(),
+        };
         der_encode(&SubjectPublicKeyInfo {
-            algorithm: AlgorithmIdentifier {
-                algorithm: OID_ED25519.clone(),
-            },
+            algorithm,
             subject_public_key: self.as_bytes_vec(),
         })
         // NOTE: Not possible to fail. Only fail case the library has is if OIDs are
@@ -297,30 +525,61 @@ impl PublicKey {
         .unwrap()
     }
 
+    /// Derive the 20 byte EVM address of an ECDSA (secp256k1) `PublicKey`.
+    pub fn to_evm_address(&self) -> Result<[u8; 20], HederaError> {
+        match &self.0 {
+            PublicKeyData::EcdsaSecp256k1(key, _) => {
+                let uncompressed = key.to_encoded_point(false);
+                let hash = Keccak256::digest(&uncompressed.as_bytes()[1..]);
+                let mut address = [0u8; 20];
+                address.copy_from_slice(&hash[12..]);
+                Ok(address)
+            }
+            PublicKeyData::Ed25519(_) => Err(HederaError::UnsupportedKeyType),
+        }
+    }
+
     /// Verify a signature on a message with this `PublicKey`.
+    ///
+    /// ECDSA (secp256k1) signatures are verified against the keccak256 hash of the
+    /// message.
     pub fn verify(
         &self,
         message: impl AsRef<[u8]>,
         signature: &Signature,
     ) -> Result<bool, HederaError> {
-        match self.0.verify_strict(message.as_ref(), &signature.0) {
-            Ok(_) => Ok(true),
-            Err(error) => {
-                if error.to_string() == "Verification equation was not satisfied" {
-                    Ok(false)
-                } else {
-                    Err(error.into())
+        match &self.0 {
+            PublicKeyData::Ed25519(key) => {
+                match key.verify_strict(message.as_ref(), &signature.to_ed25519()?) {
+                    Ok(_) => Ok(true),
+                    Err(error) => {
+                        if error.to_string() == "Verification equation was not satisfied" {
+                            Ok(false)
+                        } else {
+                            Err(error.into())
+                        }
+                    }
                 }
             }
+            PublicKeyData::EcdsaSecp256k1(key, _) => {
+                let hash = Keccak256::digest(message.as_ref());
+                Ok(key.verify_prehash(&hash, &signature.to_ecdsa()?).is_ok())
+            }
         }
     }
 
     pub fn to_signature_pair_protobuf(&self, signature: &Signature) -> services::SignaturePair {
+        let signature = match self.algorithm() {
+            KeyAlgorithm::Ed25519 => {
+                services::signature_pair::Signature::Ed25519(signature.as_bytes_vec())
+            }
+            KeyAlgorithm::EcdsaSecp256k1 => {
+                services::signature_pair::Signature::EcdsaSecp256k1(signature.as_bytes_vec())
+            }
+        };
         services::SignaturePair {
             pub_key_prefix: self.as_bytes_vec(),
-            signature: Some(services::signature_pair::Signature::Ed25519(
-                signature.as_bytes_vec(),
-            )),
+            signature: Some(signature),
         }
     }
 }
@@ -331,7 +590,7 @@ impl FromStr for PublicKey {
     type Err = HederaError;
     #[inline]
     fn from_str(s: &str) -> Result<Self, HederaError> {
-        Self::from_bytes(&hex::decode(s.as_bytes())?)
+        Self::from_bytes(&decode_hex(s)?)
     }
 }
 
@@ -362,14 +621,13 @@ impl PartialEq for PublicKey {
 
 impl Eq for PublicKey {}
 
+// DER hex, as `Display`
 impl Serialize for PublicKey {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
-        let mut ts = serializer.serialize_tuple_struct("PublicKey", 1)?;
-        ts.serialize_field(&self.to_string())?;
-        ts.end()
+        serializer.collect_str(self)
     }
 }
 impl<'de> Deserialize<'de> for PublicKey {
@@ -383,22 +641,31 @@ impl<'de> Deserialize<'de> for PublicKey {
             type Value = PublicKey;
 
             fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-                formatter.write_str("struct PublicKey")
+                formatter.write_str("a DER hex encoded public key")
             }
 
+            // the one element tuple struct written before; formats without
+            // framing for tuples, like bincode, read it as the string itself
             fn visit_seq<V>(self, mut seq: V) -> Result<Self::Value, V::Error>
             where
                 V: SeqAccess<'de>,
             {
-                let s = seq
+                let s: String = seq
                     .next_element()?
                     .ok_or_else(|| de::Error::invalid_length(0, &self))?;
 
-                Ok(PublicKey::from_str(s).map_err(de::Error::custom)?)
+                PublicKey::from_str(&s).map_err(de::Error::custom)
+            }
+
+            fn visit_str<E>(self, s: &str) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                PublicKey::from_str(s).map_err(de::Error::custom)
             }
         }
 
-        deserializer.deserialize_seq(PublicKeyVisitor)
+        deserializer.deserialize_str(PublicKeyVisitor)
     }
 }
 
@@ -412,6 +679,7 @@ impl TryFrom<services::Key> for PublicKey {
         };
         match key {
             services::key::Key::Ed25519(bytes) => Self::from_hex_bytes(bytes),
+            services::key::Key::EcdsaSecp256k1(bytes) => Self::from_bytes_ecdsa(bytes),
             _ => Err(HederaError::UnsupportedKeyType),
         }
     }
@@ -419,18 +687,46 @@ impl TryFrom<services::Key> for PublicKey {
 
 impl ToProto<services::key::Key> for PublicKey {
     fn to_proto(&self) -> Result<services::key::Key, HederaError> {
-        Ok(services::key::Key::Ed25519(self.as_bytes_vec()))
+        match self.algorithm() {
+            KeyAlgorithm::Ed25519 => Ok(services::key::Key::Ed25519(self.as_bytes_vec())),
+            KeyAlgorithm::EcdsaSecp256k1 => {
+                Ok(services::key::Key::EcdsaSecp256k1(self.as_bytes_vec()))
+            }
+        }
     }
 }
 
-/// An EdDSA secret key.
-pub struct PrivateKey(ed25519_dalek::SecretKey);
+enum PrivateKeyData {
+    Ed25519(ed25519_dalek::SecretKey),
+    EcdsaSecp256k1(k256::ecdsa::SigningKey),
+}
+
+/// An EdDSA (ed25519) or ECDSA (secp256k1) secret key.
+pub struct PrivateKey(PrivateKeyData);
 
 impl PrivateKey {
     pub fn new() -> Self {
         let mut rng = OsRng {};
-        PrivateKey(ed25519_dalek::SecretKey::generate(&mut rng))
+        PrivateKey(PrivateKeyData::Ed25519(ed25519_dalek::SecretKey::generate(
+            &mut rng,
+        )))
     }
+
+    /// Generate an ECDSA (secp256k1) `PrivateKey` using a cryptographically secure
+    /// random number generator.
+    pub fn generate_ecdsa() -> Self {
+        let mut rng = OsRng {};
+        let mut bytes = [0u8; ECDSA_SECP256K1_PRIVATE_KEY_LENGTH];
+        loop {
+            rng.fill_bytes(&mut bytes);
+            // NOTE: Random bytes fall outside the curve order with negligible
+            //       probability; draw again if they do.
+            if let Ok(key) = k256::ecdsa::SigningKey::from_slice(&bytes) {
+                return PrivateKey(PrivateKeyData::EcdsaSecp256k1(key));
+            }
+        }
+    }
+
     /// Generate a `PrivateKey` with a BIP-39 mnemonic using a cryptographically
     /// secure random number generator.
     ///
@@ -449,33 +745,89 @@ impl PrivateKey {
         seed.copy_from_slice(&Seed::new(&mnemonic, password).as_bytes()[0..32]);
 
         let mut rng = ChaCha20Rng::from(ChaCha20Core::from_seed(seed));
-        PrivateKey(ed25519_dalek::SecretKey::generate(&mut rng))
+        PrivateKey(PrivateKeyData::Ed25519(ed25519_dalek::SecretKey::generate(
+            &mut rng,
+        )))
     }
 
     /// Construct a `PrivateKey` from a slice of bytes.
     /// Bytes are expected to be either a raw key or encoded in ASN.1.
+    ///
+    /// Raw keys are read as ed25519, use `from_bytes_ecdsa` for raw ECDSA
+    /// (secp256k1) keys.
     pub fn from_bytes(bytes: impl AsRef<[u8]>) -> Result<Self, HederaError> {
         let bytes = bytes.as_ref();
 
         if bytes.len() == ed25519_dalek::SECRET_KEY_LENGTH + ed25519_dalek::PUBLIC_KEY_LENGTH
             || bytes.len() == ed25519_dalek::SECRET_KEY_LENGTH
         {
+            // NOTE: a PKCS#8 encoded secp256k1 key is also 64 bytes long
+            if bytes[0] == ASN1_SEQUENCE_TAG {
+                if let Ok(key) = Self::from_bytes_der(bytes) {
+                    return Ok(key);
+                }
+            }
+            return Self::from_bytes_ed25519(bytes);
+        }
+
+        Self::from_bytes_der(bytes)
+    }
+
+    /// Construct an ed25519 `PrivateKey` from its raw bytes.
+    pub fn from_bytes_ed25519(bytes: impl AsRef<[u8]>) -> Result<Self, HederaError> {
+        let bytes = bytes.as_ref();
+
+        if bytes.len() == ed25519_dalek::SECRET_KEY_LENGTH + ed25519_dalek::PUBLIC_KEY_LENGTH {
             // If the buffer looks like a {secret}{public} byte string; just pull the secret
             // key bytes off of it
-            return Ok(PrivateKey(ed25519_dalek::SecretKey::from_bytes(
-                &bytes[..ed25519_dalek::SECRET_KEY_LENGTH],
-            )?));
+            return Self::from_bytes_ed25519(&bytes[..ed25519_dalek::SECRET_KEY_LENGTH]);
         }
 
-        let info: PrivateKeyInfo = der_decode(&bytes)?;
+        Ok(PrivateKey(PrivateKeyData::Ed25519(
+            ed25519_dalek::SecretKey::from_bytes(bytes)?,
+        )))
+    }
+
+    /// Construct an ECDSA (secp256k1) `PrivateKey` from its raw 32 bytes.
+    pub fn from_bytes_ecdsa(bytes: impl AsRef<[u8]>) -> Result<Self, HederaError> {
+        Ok(PrivateKey(PrivateKeyData::EcdsaSecp256k1(
+            k256::ecdsa::SigningKey::from_slice(bytes.as_ref())?,
+        )))
+    }
+
+    /// Construct a `PrivateKey` from a slice of bytes encoded in ASN.1, either as
+    /// PKCS#8 or, for ECDSA (secp256k1) keys, as an RFC 5915 `ECPrivateKey`.
+    pub fn from_bytes_der(bytes: impl AsRef<[u8]>) -> Result<Self, HederaError> {
+        let bytes = bytes.as_ref();
+
+        let info: PrivateKeyInfo = match der_decode(bytes) {
+            Ok(info) => info,
+            Err(error) => {
+                return match der_decode::<EcPrivateKey>(bytes) {
+                    Ok(key) => Self::from_bytes_ecdsa(&key.private_key),
+                    Err(_) => Err(error.into()),
+                }
+            }
+        };
 
-        if info.algorithm.algorithm != *OID_ED25519 {
-            return Err(HederaError::PkcsUnknownPublicKeyAlgorithm);
+        match info.algorithm.key_algorithm() {
+            Some(KeyAlgorithm::Ed25519) => Self::from_bytes_ed25519(&info.private_key[2..]),
+            Some(KeyAlgorithm::EcdsaSecp256k1) => {
+                Self::from_bytes_ecdsa(decode_ecdsa_private_key_contents(&info.private_key)?)
+            }
+            None => Err(HederaError::PkcsUnknownPublicKeyAlgorithm),
         }
+    }
+
+    /// Construct an ed25519 `PrivateKey` from a hex representation of its raw bytes.
+    pub fn from_str_ed25519(s: &str) -> Result<Self, HederaError> {
+        Self::from_bytes_ed25519(decode_hex(s)?)
+    }
 
-        Ok(PrivateKey(ed25519_dalek::SecretKey::from_bytes(
-            &info.private_key[2..],
-        )?))
+    /// Construct an ECDSA (secp256k1) `PrivateKey` from a hex representation of its
+    /// raw bytes.
+    pub fn from_str_ecdsa(s: &str) -> Result<Self, HederaError> {
+        Self::from_bytes_ecdsa(decode_hex(s)?)
     }
 
     /// Re-construct a `PrivateKey` from the supplied mnemonic and password.
@@ -486,10 +838,21 @@ impl PrivateKey {
         Ok(Self::generate_with_mnemonic(&mnemonic, password))
     }
 
+    /// Return the signature algorithm of this `PrivateKey`.
+    pub fn algorithm(&self) -> KeyAlgorithm {
+        match &self.0 {
+            PrivateKeyData::Ed25519(_) => KeyAlgorithm::Ed25519,
+            PrivateKeyData::EcdsaSecp256k1(_) => KeyAlgorithm::EcdsaSecp256k1,
+        }
+    }
+
     /// Return the `PrivateKey` as raw bytes.
     #[inline]
-    pub fn as_bytes(&self) -> &[u8; ed25519_dalek::PUBLIC_KEY_LENGTH] {
-        self.0.as_bytes()
+    pub fn as_bytes(&self) -> [u8; ed25519_dalek::SECRET_KEY_LENGTH] {
+        match &self.0 {
+            PrivateKeyData::Ed25519(key) => *key.as_bytes(),
+            PrivateKeyData::EcdsaSecp256k1(key) => key.to_bytes().into(),
+        }
     }
 
     #[inline]
@@ -499,50 +862,80 @@ impl PrivateKey {
 
     /// Format a `PrivateKey` as a vec of bytes in ASN.1 format.
     pub fn to_encoded_bytes(&self) -> Vec<u8> {
-        der_encode(&PrivateKeyInfo {
-            algorithm: AlgorithmIdentifier {
-                algorithm: OID_ED25519.clone(),
-            },
-            private_key: self.as_bytes_vec(),
-        })
         // NOTE: Not possible to fail. Only fail case the library has is if OIDs are
         //       given incorrectly.
+        let (algorithm, private_key) = match self.algorithm() {
+            KeyAlgorithm::Ed25519 => (
+                AlgorithmIdentifier::ed25519(),
+                to_der(&ASN1Block::OctetString(0, self.as_bytes_vec())).unwrap(),
+            ),
+            KeyAlgorithm::EcdsaSecp256k1 => (
+                AlgorithmIdentifier::ecdsa_secp256k1(),
+                der_encode(&EcPrivateKey {
+                    private_key: self.as_bytes_vec(),
+                })
+                .unwrap(),
+            ),
+        };
+        der_encode(&PrivateKeyInfo {
+            algorithm,
+            private_key,
+        })
         .unwrap()
     }
 
     /// Derive a `PublicKey` from this `PrivateKey`.
     #[inline]
     pub fn public(&self) -> PublicKey {
-        PublicKey(ed25519_dalek::PublicKey::from(&self.0))
+        match &self.0 {
+            PrivateKeyData::Ed25519(key) => {
+                PublicKey(PublicKeyData::Ed25519(ed25519_dalek::PublicKey::from(key)))
+            }
+            PrivateKeyData::EcdsaSecp256k1(key) => {
+                PublicKey::from_verifying_key(*key.verifying_key())
+            }
+        }
     }
 
     /// Sign a message with this `PrivateKey`.
+    ///
+    /// ECDSA (secp256k1) keys sign the keccak256 hash of the message.
     #[inline]
     pub fn sign(&self, message: impl AsRef<[u8]>) -> Signature {
-        Signature(
-            ed25519_dalek::ExpandedSecretKey::from(&self.0)
-                .sign(message.as_ref(), &self.public().0),
-        )
+        match &self.0 {
+            PrivateKeyData::Ed25519(key) => Signature(SignatureData::Ed25519(
+                ed25519_dalek::ExpandedSecretKey::from(key)
+                    .sign(message.as_ref(), &ed25519_dalek::PublicKey::from(key)),
+            )),
+            PrivateKeyData::EcdsaSecp256k1(key) => {
+                let hash = Keccak256::digest(message.as_ref());
+                // NOTE: Not possible to fail. The prehash is always a 32 byte digest.
+                let signature: k256::ecdsa::Signature = key.sign_prehash(&hash).unwrap();
+                Signature(SignatureData::EcdsaSecp256k1(
+                    signature.normalize_s().unwrap_or(signature),
+                ))
+            }
+        }
     }
 
     pub async fn sign_transaction(&self, transaction: &mut Transaction) -> Result<(), HederaError> {
-        transaction.require_one_node_account_id()?;
-
         if !transaction.is_frozen() {
             transaction.freeze().await?;
         }
-
-        let signature = self.sign(&transaction.signed_transactions[0].body_bytes);
-        transaction.add_signature(self.public(), signature)?;
-
-        Ok(())
+        // one signature per body, so every node's copy is signed
+        transaction.sign(self)
     }
 }
 
 impl Clone for PrivateKey {
     #[inline]
     fn clone(&self) -> Self {
-        Self::from_bytes(self.0.as_bytes()).unwrap()
+        match &self.0 {
+            PrivateKeyData::Ed25519(key) => Self::from_bytes_ed25519(key.as_bytes()).unwrap(),
+            PrivateKeyData::EcdsaSecp256k1(key) => {
+                PrivateKey(PrivateKeyData::EcdsaSecp256k1(key.clone()))
+            }
+        }
     }
 }
 
@@ -552,7 +945,7 @@ impl FromStr for PrivateKey {
     type Err = crate::error::HederaError;
     #[inline]
     fn from_str(s: &str) -> Result<Self, HederaError> {
-        Self::from_bytes(&hex::decode(s.as_bytes())?)
+        Self::from_bytes(&decode_hex(s)?)
     }
 }
 
@@ -611,32 +1004,79 @@ impl<'de> Deserialize<'de> for PrivateKey {
     }
 }
 
-/// An EdDSA signature.
-#[derive(Debug)]
-pub struct Signature(ed25519_dalek::Signature);
+#[derive(Debug, Clone, Copy)]
+enum SignatureData {
+    Ed25519(ed25519_dalek::Signature),
+    EcdsaSecp256k1(k256::ecdsa::Signature),
+}
+
+/// An EdDSA (ed25519) or ECDSA (secp256k1) signature.
+#[derive(Debug, Clone, Copy)]
+pub struct Signature(SignatureData);
 
 impl Signature {
-    /// Construct a `Signature` from a slice of bytes.
+    /// Construct an ed25519 `Signature` from a slice of bytes.
     #[inline]
     pub fn from_bytes(bytes: impl AsRef<[u8]>) -> Result<Self, HederaError> {
-        Ok(Signature(ed25519_dalek::Signature::try_from(
-            bytes.as_ref(),
-        )?))
+        Ok(Signature(SignatureData::Ed25519(
+            ed25519_dalek::Signature::try_from(bytes.as_ref())?,
+        )))
+    }
+
+    /// Construct an ECDSA (secp256k1) `Signature` from a slice of `r || s` bytes.
+    #[inline]
+    pub fn from_bytes_ecdsa(bytes: impl AsRef<[u8]>) -> Result<Self, HederaError> {
+        Ok(Signature(SignatureData::EcdsaSecp256k1(
+            k256::ecdsa::Signature::from_slice(bytes.as_ref())?,
+        )))
+    }
+
+    /// Return the signature algorithm of this `Signature`.
+    pub fn algorithm(&self) -> KeyAlgorithm {
+        match &self.0 {
+            SignatureData::Ed25519(_) => KeyAlgorithm::Ed25519,
+            SignatureData::EcdsaSecp256k1(_) => KeyAlgorithm::EcdsaSecp256k1,
+        }
     }
 
     /// Return the `Signature` as raw bytes.
     #[inline]
     pub fn to_bytes(&self) -> [u8; ed25519_dalek::SIGNATURE_LENGTH] {
-        self.0.to_bytes()
+        match &self.0 {
+            SignatureData::Ed25519(signature) => signature.to_bytes(),
+            SignatureData::EcdsaSecp256k1(signature) => {
+                let mut bytes = [0u8; ed25519_dalek::SIGNATURE_LENGTH];
+                bytes.copy_from_slice(&signature.to_bytes());
+                bytes
+            }
+        }
     }
 
     #[inline]
     pub fn as_bytes_vec(&self) -> Vec<u8> {
         self.to_bytes().to_vec()
     }
+
+    // Signatures parsed from bytes cannot be told apart by length, so they are
+    // re-read for the algorithm of the verifying key.
+    fn to_ed25519(self) -> Result<ed25519_dalek::Signature, HederaError> {
+        match self.0 {
+            SignatureData::Ed25519(signature) => Ok(signature),
+            SignatureData::EcdsaSecp256k1(_) => {
+                Ok(ed25519_dalek::Signature::try_from(&self.to_bytes()[..])?)
+            }
+        }
+    }
+
+    fn to_ecdsa(self) -> Result<k256::ecdsa::Signature, HederaError> {
+        match self.0 {
+            SignatureData::EcdsaSecp256k1(signature) => Ok(signature),
+            SignatureData::Ed25519(_) => Ok(k256::ecdsa::Signature::from_slice(&self.to_bytes())?),
+        }
+    }
 }
 
-/// Construct a `Signature` from a hex representation of the signature.
+/// Construct an ed25519 `Signature` from a hex representation of the signature.
 impl FromStr for Signature {
     type Err = crate::error::HederaError;
     #[inline]
@@ -654,6 +1094,10 @@ impl Display for Signature {
 
 impl ToProto<services::Signature> for Signature {
     fn to_proto(&self) -> Result<services::Signature, HederaError> {
+        // NOTE: the deprecated `Signature` message has no secp256k1 variant
+        if self.algorithm() != KeyAlgorithm::Ed25519 {
+            return Err(HederaError::UnsupportedKeyType);
+        }
         Ok(services::Signature {
             signature: Some(services::signature::Signature::Ed25519(self.as_bytes_vec())),
         })
@@ -662,7 +1106,12 @@ impl ToProto<services::Signature> for Signature {
 
 #[cfg(test)]
 mod tests {
-    use super::{PrivateKey, PublicKey, Signature};
+    use super::{KeyAlgorithm, PrivateKey, PublicKey, Signature};
+    use crate::key::Key;
+    use crate::proto::{services, ToProto};
+    use serde::de::{self, Deserialize};
+    use serde::ser::{Serialize, SerializeTupleStruct, Serializer};
+    use std::convert::TryFrom;
 
     const KEY_PUBLIC_ASN1_HEX: &str =
         "302a300506032b6570032100e0c8ec2758a5879ffac226a13c0c516b799e72e35141a0dd828f94d37988a4b7";
@@ -675,6 +1124,21 @@ mod tests {
     const KEY_SECRET_HEX: &str = "db484b828e64b2d8f12ce3c0a0e93a0b8cce7af1bb8f39c97732394482538e10\
                                   e0c8ec2758a5879ffac226a13c0c516b799e72e35141a0dd828f94d37988a4b7";
 
+    const ECDSA_KEY_SECRET_HEX: &str =
+        "0000000000000000000000000000000000000000000000000000000000000001";
+
+    // the secp256k1 generator point; the public key of the secret `1`
+    const ECDSA_KEY_PUBLIC_HEX: &str =
+        "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+    const ECDSA_KEY_PUBLIC_ASN1_HEX_PREFIX: &str = "3036301006072a8648ce3d020106052b8104000a032200";
+
+    const ECDSA_KEY_PUBLIC_LEGACY_ASN1_HEX_PREFIX: &str = "302d300706052b8104000a032200";
+
+    const ECDSA_KEY_SECRET_LEGACY_ASN1_HEX_PREFIX: &str = "3030020100300706052b8104000a04220420";
+
+    const ECDSA_EVM_ADDRESS_HEX: &str = "7e5f4552091a69125d5dfcb7b8c2659029395bdf";
+
     const MESSAGE: &str = "This is a message about the world.";
     const SIGNATURE: &str = "73bea53f31ca9c42a422ecb7516ec08d0bbd1a6bfd630ccf10ec1872454814d29f4a8011129cd007eab544af01a75f508285b591e5bed24b68f927751e49e30e";
 
@@ -687,7 +1151,7 @@ mod tests {
         let secret_key2: PrivateKey = KEY_SECRET_HEX.parse().unwrap();
 
         assert_eq!(public_key1, public_key2);
-        assert_eq!(secret_key1.0.as_bytes(), secret_key2.0.as_bytes());
+        assert_eq!(secret_key1.as_bytes(), secret_key2.as_bytes());
         assert_eq!(public_key1, secret_key1.public());
         assert_eq!(public_key2, secret_key2.public());
         assert_eq!(secret_key2.public(), secret_key1.public());
@@ -731,6 +1195,40 @@ mod tests {
         assert_eq!(secret_key1.as_bytes(), secret_key2.as_bytes());
     }
 
+    #[test]
+    fn test_public_key_serde() {
+        struct LegacyPublicKey(String);
+
+        impl Serialize for LegacyPublicKey {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut ts = serializer.serialize_tuple_struct("PublicKey", 1)?;
+                ts.serialize_field(&self.0)?;
+                ts.end()
+            }
+        }
+
+        for key in [
+            PrivateKey::new().public(),
+            PrivateKey::generate_ecdsa().public(),
+        ] {
+            let json = serde_json::to_string(&key).unwrap();
+            assert_eq!(json, format!("\"{}\"", key));
+            assert_eq!(serde_json::from_str::<PublicKey>(&json).unwrap(), key);
+
+            let bytes = bincode::serialize(&key).unwrap();
+            assert_eq!(bincode::deserialize::<PublicKey>(&bytes).unwrap(), key);
+            let legacy = bincode::serialize(&LegacyPublicKey(key.to_string())).unwrap();
+            assert_eq!(bincode::deserialize::<PublicKey>(&legacy).unwrap(), key);
+
+            // formats that hand the legacy tuple over as a sequence
+            let seq = de::value::SeqDeserializer::<_, de::value::Error>::new(
+                vec![key.to_string()].into_iter(),
+            );
+            assert_eq!(PublicKey::deserialize(seq).unwrap(), key);
+        }
+        assert!(serde_json::from_str::<PublicKey>("\"302a\"").is_err());
+    }
+
     #[test]
     fn test_reconstruct() {
         let (secret1, mnemonic) = PrivateKey::generate("this-is-not-a-password");
@@ -738,4 +1236,94 @@ mod tests {
 
         assert_eq!(secret1.as_bytes(), secret2.as_bytes());
     }
+
+    #[test]
+    fn test_ecdsa_parse() {
+        let secret_key = PrivateKey::from_str_ecdsa(ECDSA_KEY_SECRET_HEX).unwrap();
+        let public_key = PublicKey::from_str_ecdsa(ECDSA_KEY_PUBLIC_HEX).unwrap();
+
+        assert_eq!(secret_key.algorithm(), KeyAlgorithm::EcdsaSecp256k1);
+        assert_eq!(public_key.algorithm(), KeyAlgorithm::EcdsaSecp256k1);
+        assert_eq!(secret_key.public(), public_key);
+        assert_eq!(hex::encode(public_key.as_bytes()), ECDSA_KEY_PUBLIC_HEX);
+        assert_eq!(
+            hex::encode(public_key.to_evm_address().unwrap()),
+            ECDSA_EVM_ADDRESS_HEX
+        );
+
+        // raw compressed keys are detected by length
+        let public_key2: PublicKey = ECDSA_KEY_PUBLIC_HEX.parse().unwrap();
+        assert_eq!(public_key, public_key2);
+    }
+
+    #[test]
+    fn test_ecdsa_parse_der() {
+        let secret_key = PrivateKey::from_str_ecdsa(ECDSA_KEY_SECRET_HEX).unwrap();
+
+        let public_asn1 = secret_key.public().to_string();
+        assert!(public_asn1.starts_with(ECDSA_KEY_PUBLIC_ASN1_HEX_PREFIX));
+
+        let legacy_public_asn1 = format!(
+            "{}{}",
+            ECDSA_KEY_PUBLIC_LEGACY_ASN1_HEX_PREFIX, ECDSA_KEY_PUBLIC_HEX
+        );
+        let legacy_secret_asn1 = format!(
+            "{}{}",
+            ECDSA_KEY_SECRET_LEGACY_ASN1_HEX_PREFIX, ECDSA_KEY_SECRET_HEX
+        );
+
+        let public_key1: PublicKey = public_asn1.parse().unwrap();
+        let public_key2: PublicKey = legacy_public_asn1.parse().unwrap();
+        let secret_key1: PrivateKey = secret_key.to_string().parse().unwrap();
+        let secret_key2: PrivateKey = legacy_secret_asn1.parse().unwrap();
+
+        assert_eq!(public_key1, secret_key.public());
+        assert_eq!(public_key2, secret_key.public());
+        assert_eq!(secret_key1.as_bytes(), secret_key.as_bytes());
+        assert_eq!(secret_key2.as_bytes(), secret_key.as_bytes());
+        assert_eq!(secret_key1.algorithm(), KeyAlgorithm::EcdsaSecp256k1);
+        assert_eq!(secret_key2.algorithm(), KeyAlgorithm::EcdsaSecp256k1);
+    }
+
+    #[test]
+    fn test_ecdsa_sign_verify() {
+        let key = PrivateKey::generate_ecdsa();
+        let signature = key.sign(MESSAGE.as_bytes());
+        assert_eq!(signature.algorithm(), KeyAlgorithm::EcdsaSecp256k1);
+        assert!(key.public().verify(MESSAGE.as_bytes(), &signature).unwrap());
+        assert!(!key.public().verify("tampered", &signature).unwrap());
+
+        let signature = Signature::from_bytes_ecdsa(signature.to_bytes()).unwrap();
+        assert!(key.public().verify(MESSAGE.as_bytes(), &signature).unwrap());
+
+        let other = PrivateKey::generate_ecdsa();
+        assert!(!other
+            .public()
+            .verify(MESSAGE.as_bytes(), &signature)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_ecdsa_key_proto() {
+        let key = PrivateKey::generate_ecdsa();
+        let proto: services::Key = Key::from(key.clone()).to_proto().unwrap();
+        assert_eq!(
+            proto.key,
+            Some(services::key::Key::EcdsaSecp256k1(
+                key.public().as_bytes_vec()
+            ))
+        );
+        assert_eq!(
+            Key::try_from(proto).unwrap(),
+            Key::EcdsaSecp256k1(key.public())
+        );
+
+        let pair = key
+            .public()
+            .to_signature_pair_protobuf(&key.sign(MESSAGE.as_bytes()));
+        assert!(matches!(
+            pair.signature,
+            Some(services::signature_pair::Signature::EcdsaSecp256k1(_))
+        ));
+    }
 }
@@ -1,3 +1,4 @@
+use serde::Serialize;
 use std::convert::{TryFrom, TryInto};
 
 use crate::account_id::AccountId;
@@ -11,7 +12,7 @@ use crate::proto::services::{custom_fee::Fee as ProtoFee, CustomFee as ProtoCust
 use crate::proto::ToProto;
 use crate::utils::optional_account_id;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct CustomFee {
     pub fee_collector_account_id: Option<AccountId>,
     pub fee: Option<Fee>,
@@ -54,7 +55,8 @@ impl ToProto<ProtoCustomFee> for CustomFee {
     }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Fee {
     CustomFixedFee(CustomFixedFee),
     CustomFractionalFee(CustomFractionalFee),
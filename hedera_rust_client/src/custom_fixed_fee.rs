@@ -1,3 +1,4 @@
+use serde::Serialize;
 use std::convert::TryFrom;
 
 use crate::error::HederaError;
@@ -6,7 +7,7 @@ use crate::proto::services::FixedFee as ProtoFixedFee;
 use crate::proto::ToProto;
 use crate::token_id::TokenId;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct CustomFixedFee {
     pub amount: Hbar,
     pub denominating_token_id: Option<TokenId>,
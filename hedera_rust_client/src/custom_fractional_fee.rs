@@ -1,3 +1,4 @@
+use serde::Serialize;
 use std::convert::TryFrom;
 
 use crate::error::HederaError;
@@ -5,7 +6,7 @@ use crate::fraction::Fraction;
 use crate::proto::services::FractionalFee as ProtoFractionalFee;
 use crate::proto::ToProto;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct CustomFractionalFee {
     pub fractional_amount: Option<Fraction>,
     pub minimum_amount: i64,
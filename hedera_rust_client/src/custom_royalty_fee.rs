@@ -1,3 +1,4 @@
+use serde::Serialize;
 use std::convert::{TryFrom, TryInto};
 
 use crate::custom_fixed_fee::CustomFixedFee;
@@ -6,7 +7,7 @@ use crate::fraction::Fraction;
 use crate::proto::services::RoyaltyFee as ProtoRoyaltyFee;
 use crate::proto::ToProto;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct CustomRoyaltyFee {
     pub exchange_value_fraction: Option<Fraction>,
     pub fallback_fee: Option<CustomFixedFee>,
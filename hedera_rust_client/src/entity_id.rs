@@ -1,4 +1,5 @@
 use bytes::{BufMut, BytesMut};
+use serde::Serialize;
 use std::cmp::min;
 use std::convert::AsMut;
 
@@ -149,7 +150,7 @@ pub fn validate_id_checksum<T: ValidateChecksum>(
     id.validate_checksum(client)
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
 pub struct ShardId {
     pub shard_num: i64,
 }
@@ -162,7 +163,15 @@ impl ToProto<ProtoShardId> for ShardId {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+impl From<ProtoShardId> for ShardId {
+    fn from(services: ProtoShardId) -> ShardId {
+        ShardId {
+            shard_num: services.shard_num,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
 pub struct RealmId {
     pub shard_num: i64,
     pub realm_num: i64,
@@ -177,6 +186,15 @@ impl ToProto<ProtoRealmId> for RealmId {
     }
 }
 
+impl From<ProtoRealmId> for RealmId {
+    fn from(services: ProtoRealmId) -> RealmId {
+        RealmId {
+            shard_num: services.shard_num,
+            realm_num: services.realm_num,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,7 +1,11 @@
+use serde::Serialize;
+
 use crate::proto::services::{self};
 
-#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Entropy {
+    #[serde(serialize_with = "crate::json::hex")]
     PrngBytes(Vec<u8>),
     PrngNumber(i32),
 }
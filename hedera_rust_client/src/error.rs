@@ -8,6 +8,7 @@ use thiserror::Error;
 
 use crate::client::ClientBuilderError;
 use crate::crypto::Asn1Error;
+use crate::AccountId;
 use crate::Hbar;
 use crate::Status;
 use crate::TransactionId;
@@ -42,6 +43,9 @@ pub enum HederaError {
     #[error("unable to deserialize node address book")]
     NodeAddressBookDeserialize,
 
+    #[error("node address book has no usable endpoints")]
+    EmptyNodeAddressBook,
+
     #[error("no node returned")]
     NoNode,
 
@@ -73,13 +77,13 @@ pub enum HederaError {
     #[error("unreacahble: function name must be non-nil at this point")]
     Unreacahble,
 
-    #[error("ed25519: unknown public key algorithm")]
+    #[error("unknown public key algorithm")]
     UnknownPublicKeyAlgorithm,
 
-    #[error("ed25519: public key length mismatch")]
+    #[error("public key length mismatch")]
     InvalidPublicKeyLength,
 
-    #[error("ed25519: PKCS#8 wrapping contained private key with unknown algorithm")]
+    #[error("PKCS#8 wrapping contained private key with unknown algorithm")]
     PkcsUnknownPublicKeyAlgorithm,
 
     #[error("unable to serialize key")]
@@ -139,6 +143,9 @@ pub enum HederaError {
     #[error("exceeded max attemps {0:?}")]
     MaxAttempsExceeded(u8),
 
+    #[error("execute timed out after {0:?}")]
+    ExecuteTimeout(std::time::Duration),
+
     #[error("max chunks exceeded, required chunks: {0}, max chunks: {1}")]
     MaxChunksExceeded(usize, usize),
 
@@ -217,9 +224,78 @@ pub enum HederaError {
     #[error("expected format [seconds].[nanos]")]
     UnknownTimestampFormat,
 
-    #[error("Only ed25519 public keys are currently supported")]
+    #[error("unsupported key type")]
     UnsupportedKeyType,
 
+    #[error("invalid ethereum transaction data: {0}")]
+    InvalidEthereumData(String),
+
+    #[error("mirror node rest request failed with status {0}: {1}")]
+    MirrorRestStatus(u16, String),
+
+    #[error("unable to decode mirror node rest response: {0}")]
+    MirrorRestDecode(String),
+
+    #[error("invalid record stream file: {0}")]
+    InvalidRecordStreamFile(String),
+
+    #[error("record stream hash mismatch: {0}")]
+    RecordStreamHashMismatch(String),
+
+    #[error("invalid record stream signature: {0}")]
+    InvalidRecordStreamSignature(String),
+
+    #[error("record stream file has {0} valid node signatures, {1} required")]
+    InsufficientRecordStreamSignatures(usize, usize),
+
+    #[error("invalid abi: {0}")]
+    InvalidAbi(String),
+
+    #[error("abi function `{0}` not found")]
+    AbiFunctionNotFound(String),
+
+    #[error("abi value {1} does not match type `{0}`")]
+    AbiTypeMismatch(String, String),
+
+    #[error("expected {0} abi values, got {1}")]
+    AbiValueCount(usize, usize),
+
+    #[error("unable to abi decode: {0}")]
+    AbiDecode(String),
+
+    #[error("abi event with topic `{0}` not found")]
+    AbiEventNotFound(String),
+
+    #[error("no fee schedule for {0}")]
+    FeeScheduleNotFound(String),
+
+    #[error("invalid exchange rate: {0} hbar to {1} cents")]
+    InvalidExchangeRate(i32, i32),
+
+    #[error("unable to deserialize {0}: {1}")]
+    ProtoDeserialize(&'static str, DecodeError),
+
+    #[error("transaction has no body for node {0}")]
+    TransactionNodeNotFound(AccountId),
+
+    #[error("signature by {0} does not match the transaction body")]
+    InvalidTransactionSignature(String),
+
+    #[error("signed transaction {0} has a different body")]
+    TransactionBodyMismatch(usize),
+
+    #[error("signer failed: {0}")]
+    SignerError(String),
+
+    #[error("chunked topic message {0} expired with {1} of {2} chunks received")]
+    TopicMessageChunksExpired(TransactionId, usize, usize),
+
+    #[error("topic message sequence number {1} received, expected {0}")]
+    TopicMessageSequenceGap(u64, u64),
+
+    #[error("checkpoint store failed: {0}")]
+    CheckpointStore(String),
+
     #[error(transparent)]
     ClientBuilder(#[from] ClientBuilderError),
 
@@ -229,6 +305,9 @@ pub enum HederaError {
     #[error(transparent)]
     Crypto(#[from] ed25519::Error),
 
+    #[error(transparent)]
+    Ecdsa(#[from] k256::ecdsa::Error),
+
     #[error(transparent)]
     Config(#[from] ConfigError),
 
@@ -238,12 +317,18 @@ pub enum HederaError {
     #[error(transparent)]
     Int(#[from] ParseIntError),
 
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+
     #[error(transparent)]
     Hex(#[from] FromHexError),
 
     #[error(transparent)]
     Http(#[from] http::Error),
 
+    #[error(transparent)]
+    Reqwest(#[from] reqwest::Error),
+
     #[error(transparent)]
     Tonic(#[from] tonic::transport::Error),
 
@@ -0,0 +1,228 @@
+use crate::ethereum_transaction::EthereumTransaction;
+use crate::file_append_transaction::FileAppendTransaction;
+use crate::file_create_transaction::FileCreateTransaction;
+use crate::key_list::KeyList;
+use crate::rlp::RlpItem;
+use crate::AccountId;
+use crate::Client;
+use crate::FileId;
+use crate::Hbar;
+use crate::HederaError;
+use crate::TransactionResponse;
+
+// ethereum_data larger than this is split, with the call data moved into a file
+pub const MAX_ETHEREUM_DATA_SIZE: usize = 5120;
+const FILE_CREATE_CHUNK_SIZE: usize = 4096;
+const FILE_APPEND_CHUNK_SIZE: usize = 1024;
+
+/// Submits a signed ethereum transaction, first uploading its call data
+/// through the file service when the encoded transaction is too large
+/// for a single `EthereumTransaction`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EthereumFlow {
+    ethereum_data: Vec<u8>,
+    call_data_file_id: Option<FileId>,
+    max_gas_allowance: Option<Hbar>,
+    node_account_ids: Vec<AccountId>,
+}
+
+impl EthereumFlow {
+    pub fn new() -> EthereumFlow {
+        EthereumFlow {
+            ethereum_data: Vec::new(),
+            call_data_file_id: None,
+            max_gas_allowance: None,
+            node_account_ids: Vec::new(),
+        }
+    }
+
+    pub fn ethereum_data(&self) -> Vec<u8> {
+        self.ethereum_data.clone()
+    }
+
+    pub fn set_ethereum_data(&mut self, ethereum_data: Vec<u8>) -> &mut Self {
+        self.ethereum_data = ethereum_data;
+        self
+    }
+
+    pub fn call_data_file_id(&self) -> Option<FileId> {
+        self.call_data_file_id
+    }
+
+    pub fn set_call_data_file_id(&mut self, file_id: FileId) -> &mut Self {
+        self.call_data_file_id = Some(file_id);
+        self
+    }
+
+    pub fn max_gas_allowance(&self) -> Option<Hbar> {
+        self.max_gas_allowance
+    }
+
+    pub fn set_max_gas_allowance(&mut self, max_gas_allowance: Hbar) -> &mut Self {
+        self.max_gas_allowance = Some(max_gas_allowance);
+        self
+    }
+
+    pub fn node_account_ids(&self) -> Vec<AccountId> {
+        self.node_account_ids.clone()
+    }
+
+    pub fn set_node_account_ids(&mut self, node_account_ids: Vec<AccountId>) -> &mut Self {
+        self.node_account_ids = node_account_ids;
+        self
+    }
+
+    pub async fn execute(&self, client: &Client) -> Result<TransactionResponse, HederaError> {
+        if self.ethereum_data.is_empty() {
+            return Err(HederaError::ValueNotSet("ethereum_data".to_string()));
+        }
+
+        let mut tx = EthereumTransaction::new();
+        if !self.node_account_ids.is_empty() {
+            tx.set_node_account_ids(self.node_account_ids.clone())?;
+        }
+        if let Some(max_gas_allowance) = self.max_gas_allowance {
+            tx.set_max_gas_allowance(max_gas_allowance)?;
+        }
+
+        if let Some(file_id) = self.call_data_file_id {
+            tx.set_ethereum_data(self.ethereum_data.clone())?
+                .set_call_data_file_id(file_id)?;
+        } else if self.ethereum_data.len() <= MAX_ETHEREUM_DATA_SIZE {
+            tx.set_ethereum_data(self.ethereum_data.clone())?;
+        } else {
+            let (ethereum_data, call_data) = split_call_data(&self.ethereum_data)?;
+            let file_id = self.create_call_data_file(client, call_data).await?;
+            tx.set_ethereum_data(ethereum_data)?
+                .set_call_data_file_id(file_id)?;
+        }
+
+        tx.execute(client).await
+    }
+
+    async fn create_call_data_file(
+        &self,
+        client: &Client,
+        call_data: Vec<u8>,
+    ) -> Result<FileId, HederaError> {
+        // the network expects the call data file to hold hex encoded bytes
+        let contents = hex::encode(call_data).into_bytes();
+        let first_chunk = contents.len().min(FILE_CREATE_CHUNK_SIZE);
+
+        let mut file_create = FileCreateTransaction::new();
+        if !self.node_account_ids.is_empty() {
+            file_create.set_node_account_ids(self.node_account_ids.clone())?;
+        }
+        let receipt = file_create
+            .set_keys(KeyList::of(vec![client.operator_public_key().into()]))?
+            .set_contents(contents[..first_chunk].to_vec())?
+            .execute(client)
+            .await?
+            .get_receipt(client)
+            .await?;
+        let file_id = receipt.file_id.ok_or(HederaError::NoFileProvided)?;
+
+        if first_chunk < contents.len() {
+            let remaining = contents[first_chunk..].to_vec();
+            let max_chunks = remaining.chunks(FILE_APPEND_CHUNK_SIZE).len();
+            let mut file_append = FileAppendTransaction::new();
+            if !self.node_account_ids.is_empty() {
+                file_append.set_node_account_ids(self.node_account_ids.clone())?;
+            }
+            file_append
+                .set_file_id(file_id)?
+                .set_max_chunks(max_chunks)?
+                .set_contents(remaining)?
+                .execute_async(client)
+                .await?
+                .get_receipt(client)
+                .await?;
+        }
+        Ok(file_id)
+    }
+}
+
+// Returns the transaction re-encoded with empty call data, and the call data
+// that was removed from it.
+fn split_call_data(ethereum_data: &[u8]) -> Result<(Vec<u8>, Vec<u8>), HederaError> {
+    let (type_prefix, payload, call_data_index) = match ethereum_data.first() {
+        Some(0x01) => (Some(0x01u8), &ethereum_data[1..], 6),
+        Some(0x02) => (Some(0x02u8), &ethereum_data[1..], 7),
+        Some(b) if *b >= 0xc0 => (None, ethereum_data, 5),
+        _ => {
+            return Err(HederaError::InvalidEthereumData(
+                "unknown ethereum transaction type".to_string(),
+            ))
+        }
+    };
+
+    let mut fields = match RlpItem::decode(payload)? {
+        RlpItem::List(fields) => fields,
+        RlpItem::Bytes(_) => {
+            return Err(HederaError::InvalidEthereumData(
+                "expected rlp list".to_string(),
+            ))
+        }
+    };
+    let call_data = match fields.get_mut(call_data_index) {
+        Some(RlpItem::Bytes(bytes)) => std::mem::take(bytes),
+        _ => {
+            return Err(HederaError::InvalidEthereumData(
+                "missing call data".to_string(),
+            ))
+        }
+    };
+
+    let mut out = type_prefix.into_iter().collect::<Vec<u8>>();
+    out.extend(RlpItem::List(fields).encode());
+    Ok((out, call_data))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eip1559_transaction(call_data: Vec<u8>) -> Vec<u8> {
+        let fields = vec![
+            RlpItem::Bytes(vec![0x01, 0x2a]),
+            RlpItem::Bytes(vec![0x02]),
+            RlpItem::Bytes(vec![0x2f]),
+            RlpItem::Bytes(vec![0x01, 0x00]),
+            RlpItem::Bytes(vec![0x98, 0x96, 0x80]),
+            RlpItem::Bytes(vec![0x11; 20]),
+            RlpItem::Bytes(Vec::new()),
+            RlpItem::Bytes(call_data),
+            RlpItem::List(Vec::new()),
+            RlpItem::Bytes(vec![0x01]),
+            RlpItem::Bytes(vec![0x22; 32]),
+            RlpItem::Bytes(vec![0x33; 32]),
+        ];
+        let mut out = vec![0x02];
+        out.extend(RlpItem::List(fields).encode());
+        out
+    }
+
+    #[test]
+    fn test_rlp_round_trip() {
+        let bytes = eip1559_transaction(vec![0xab; MAX_ETHEREUM_DATA_SIZE]);
+        let item = RlpItem::decode(&bytes[1..]).unwrap();
+        assert_eq!(item.encode(), bytes[1..].to_vec());
+    }
+
+    #[test]
+    fn test_split_call_data() {
+        let call_data = vec![0xab; MAX_ETHEREUM_DATA_SIZE];
+        let bytes = eip1559_transaction(call_data.clone());
+
+        let (stripped, removed) = split_call_data(&bytes).unwrap();
+        assert_eq!(removed, call_data);
+        assert_eq!(stripped, eip1559_transaction(Vec::new()));
+        assert!(stripped.len() <= MAX_ETHEREUM_DATA_SIZE);
+    }
+
+    #[test]
+    fn test_split_call_data_invalid() {
+        assert!(split_call_data(&[0x05, 0xc0]).is_err());
+        assert!(split_call_data(&[0x02, 0xc0]).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+use crate::entity_id::validate_option_id_checksum;
+
+use crate::transaction::Transaction;
+use crate::Client;
+use crate::FileId;
+use crate::Hbar;
+use crate::HederaError;
+use hedera_rust_client_derive::{TransactionExecute, TransactionProto};
+use serde::Serialize;
+
+#[derive(TransactionExecute, Debug, Clone, PartialEq)]
+#[hedera_rust_client_derive(service(
+    method_service_name = "contract",
+    method_service_fn = "call_ethereum"
+))]
+pub struct EthereumTransaction {
+    transaction: Transaction,
+    services: Proto,
+}
+
+impl EthereumTransaction {
+    pub fn new() -> EthereumTransaction {
+        let transaction = Transaction::with_max_transaction_fee(Hbar::new(2.0));
+        let services = Proto::new();
+        EthereumTransaction {
+            transaction,
+            services,
+        }
+    }
+
+    fn validate_network_on_ids(&self, client: &Client) -> Result<(), HederaError> {
+        validate_option_id_checksum(&self.services.call_data, client)?;
+        Ok(())
+    }
+
+    // ethereum_data, the raw signed RLP encoded transaction
+    gen_transaction_get_set_pb_fns!(ethereum_data, Vec<u8>, ethereum_data, set_ethereum_data);
+
+    // call_data, file holding the hex encoded call data when too large for ethereum_data
+    gen_transaction_get_set_pb_option_fns!(
+        call_data,
+        FileId,
+        call_data_file_id,
+        set_call_data_file_id
+    );
+
+    // max_gas_allowance
+    gen_transaction_get_set_with_hbar_i64!(
+        max_gas_allowance,
+        max_gas_allowance,
+        set_max_gas_allowance
+    );
+}
+
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
+#[hedera_rust_client_derive(proto(
+    proto_enum = "EthereumTransaction",
+    proto_type = "EthereumTransactionBody"
+))]
+struct Proto {
+    #[serde(serialize_with = "crate::json::hex")]
+    pub ethereum_data: Vec<u8>,
+    #[hedera_rust_client_derive(to_option_proto)]
+    pub call_data: Option<FileId>,
+    pub max_gas_allowance: i64,
+}
+
+impl Proto {
+    pub fn new() -> Self {
+        Proto {
+            ethereum_data: Vec::new(),
+            call_data: None,
+            max_gas_allowance: 0,
+        }
+    }
+}
@@ -1,9 +1,13 @@
-use std::convert::TryFrom;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use prost::Message;
+use serde::Serialize;
+use std::convert::{TryFrom, TryInto};
 
 use crate::error::HederaError;
 use crate::proto::services;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct ExchangeRate {
     pub hbars: i32,
     pub cents: i32,
@@ -23,3 +27,52 @@ impl TryFrom<services::ExchangeRate> for ExchangeRate {
         })
     }
 }
+
+impl ExchangeRate {
+    pub fn is_expired_at(&self, time: DateTime<Utc>) -> bool {
+        time.timestamp() >= self.expiration_time
+    }
+}
+
+/// The current and next exchange rates, as kept in file `0.0.112`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ExchangeRateSet {
+    pub current_rate: Option<ExchangeRate>,
+    pub next_rate: Option<ExchangeRate>,
+}
+
+impl ExchangeRateSet {
+    pub fn from_proto_bytes(bytes: Vec<u8>) -> Result<ExchangeRateSet, HederaError> {
+        match services::ExchangeRateSet::decode(Bytes::from(bytes)) {
+            Ok(proto) => proto.try_into(),
+            Err(e) => Err(HederaError::ProtoDeserialize("ExchangeRateSet", e)),
+        }
+    }
+
+    /// The rate in effect at `time`: the current one until it expires, then
+    /// the next.
+    pub fn rate_at(&self, time: DateTime<Utc>) -> Option<&ExchangeRate> {
+        match &self.current_rate {
+            Some(rate) if !rate.is_expired_at(time) => Some(rate),
+            _ => self.next_rate.as_ref(),
+        }
+    }
+}
+
+impl TryFrom<services::ExchangeRateSet> for ExchangeRateSet {
+    type Error = HederaError;
+    fn try_from(services: services::ExchangeRateSet) -> Result<ExchangeRateSet, HederaError> {
+        let current_rate = match services.current_rate {
+            Some(x) => Some(x.try_into()?),
+            None => None,
+        };
+        let next_rate = match services.next_rate {
+            Some(x) => Some(x.try_into()?),
+            None => None,
+        };
+        Ok(ExchangeRateSet {
+            current_rate,
+            next_rate,
+        })
+    }
+}
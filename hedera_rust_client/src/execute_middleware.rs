@@ -0,0 +1,69 @@
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::HederaError;
+use crate::executor::{ProtoRequest, Response};
+use crate::retry_policy::RetryPolicy;
+use crate::AccountId;
+
+/// The attempt a middleware hook is called for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecuteContext {
+    /// starting at 1
+    pub attempt: u8,
+    pub max_attempts: u8,
+    pub node_account_id: AccountId,
+    /// time since `execute` was called
+    pub elapsed: Duration,
+}
+
+/// Hooks around every request `execute` sends to a node.
+pub trait ExecuteMiddleware: Send + Sync + fmt::Debug {
+    /// Called right before the request is sent. Returning an error aborts
+    /// `execute` with that error.
+    fn before_request(
+        &self,
+        _context: &ExecuteContext,
+        _request: &mut ProtoRequest,
+    ) -> Result<(), HederaError> {
+        Ok(())
+    }
+
+    /// Called with the node's response, or the gRPC status if the call failed.
+    fn after_response(
+        &self,
+        _context: &ExecuteContext,
+        _response: Result<&Response, &tonic::Status>,
+    ) {
+    }
+}
+
+/// Retry policy and middleware set on a `Client` or on a single request.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ExecuteHooks {
+    pub retry_policy: Option<Arc<dyn RetryPolicy>>,
+    pub middleware: Vec<Arc<dyn ExecuteMiddleware>>,
+}
+
+fn same_arc<T: ?Sized>(a: &Arc<T>, b: &Arc<T>) -> bool {
+    Arc::as_ptr(a) as *const () == Arc::as_ptr(b) as *const ()
+}
+
+// hooks are compared by identity, so requests stay comparable
+impl PartialEq for ExecuteHooks {
+    fn eq(&self, other: &Self) -> bool {
+        let same_policy = match (&self.retry_policy, &other.retry_policy) {
+            (Some(a), Some(b)) => same_arc(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        same_policy
+            && self.middleware.len() == other.middleware.len()
+            && self
+                .middleware
+                .iter()
+                .zip(other.middleware.iter())
+                .all(|(a, b)| same_arc(a, b))
+    }
+}
@@ -1,16 +1,17 @@
-use num::pow;
-use std::cmp::min;
 use std::future::Future;
 use std::pin::Pin;
-use std::time::Duration;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tracing::{debug, instrument, trace};
 
 use crate::channel::Channel;
 use crate::client::Client;
 use crate::error::HederaError;
+use crate::execute_middleware::{ExecuteContext, ExecuteMiddleware};
 use crate::proto::services;
 use crate::query::Query;
 use crate::query_response::QueryResponse;
+use crate::retry_policy::{DefaultRetryPolicy, RetryContext};
 use crate::status::Status;
 use crate::transaction::Transaction;
 use crate::transaction_response::TransactionResponse;
@@ -200,6 +201,19 @@ pub async fn execute(
         },
     };
 
+    let mut grpc_deadline = client.grpc_deadline();
+    let mut request_timeout = client.request_timeout();
+    match &request {
+        Request::Query(query) => {
+            grpc_deadline = query.grpc_deadline.unwrap_or(grpc_deadline);
+            request_timeout = query.request_timeout.unwrap_or(request_timeout);
+        }
+        Request::Transaction(transaction) => {
+            grpc_deadline = transaction.grpc_deadline.unwrap_or(grpc_deadline);
+            request_timeout = transaction.request_timeout.unwrap_or(request_timeout);
+        }
+    }
+
     let mut min_backoff = client.min_backoff();
     let mut max_backoff = client.max_backoff();
     match &request {
@@ -221,103 +235,167 @@ pub async fn execute(
         }
     }
 
+    let started = Instant::now();
+    let request_hooks = match &request {
+        Request::Query(query) => &query.hooks,
+        Request::Transaction(transaction) => &transaction.hooks,
+    };
+    // the request's retry policy wins, middleware of both runs client first
+    let retry_policy = request_hooks
+        .retry_policy
+        .clone()
+        .or_else(|| client.hooks().retry_policy.clone())
+        .unwrap_or_else(|| Arc::new(DefaultRetryPolicy));
+    let middleware: Vec<Arc<dyn ExecuteMiddleware>> = client
+        .hooks()
+        .middleware
+        .iter()
+        .chain(request_hooks.middleware.iter())
+        .cloned()
+        .collect();
+
     trace!(
         "max_attempts: {}, min_backoff: {}, max_backoff: {}",
         max_attempts,
         min_backoff,
         max_backoff
     );
-    for attempt in 1..max_attempts {
-        trace!("attempt {}/{}", attempt, max_attempts);
-        let node_account_id = get_node_account_id(&request)?;
-
-        let node = client.node_for_account_id(&node_account_id).await?;
-
-        let mut node_lock = node.write().await;
-        node_lock.in_use();
-
-        if !node_lock.is_healthy() {
-            let wait = node_lock.wait();
-            drop(node_lock);
-            debug!("node {} was not healthy", node_account_id);
-            tokio::time::sleep(wait).await;
-        } else {
-            drop(node_lock);
-        }
+    let attempts = async {
+        for attempt in 1..max_attempts {
+            trace!("attempt {}/{}", attempt, max_attempts);
+            let node_account_id = get_node_account_id(&request)?;
+
+            let node = client.node_for_account_id(&node_account_id).await?;
+
+            let mut node_lock = node.write().await;
+            node_lock.in_use();
+
+            if !node_lock.is_healthy() {
+                let wait = node_lock.wait();
+                drop(node_lock);
+                debug!("node {} was not healthy", node_account_id);
+                tokio::time::sleep(wait).await;
+            } else {
+                drop(node_lock);
+            }
 
-        let mut node_write = node.write().await;
-        let channel = node_write.node_channel();
-        drop(node_write);
+            let mut node_write = node.write().await;
+            let channel = node_write.node_channel();
+            drop(node_write);
 
-        let mut channel = channel?;
+            let mut channel = channel?;
 
-        let method = get_method(&request, &mut channel);
-        let method = match method {
-            Ok(v) => v,
-            Err(_) => {
-                continue;
+            let method = get_method(&request, &mut channel);
+            let method = match method {
+                Ok(v) => v,
+                Err(_) => {
+                    continue;
+                }
+            };
+
+            let mut proto_request = make_request(&mut request)?;
+            advance_request(&mut request)?;
+
+            let context = ExecuteContext {
+                attempt,
+                max_attempts,
+                node_account_id,
+                elapsed: started.elapsed(),
+            };
+            for m in middleware.iter() {
+                m.before_request(&context, &mut proto_request)?;
             }
-        };
 
-        let proto_request = make_request(&mut request)?;
-        advance_request(&mut request)?;
-
-        let proto_response = match method {
-            Method::Query(func) => {
-                let proto_query = proto_request.get_query()?;
-                match func(proto_query.clone()).await {
-                    Ok(v) => Ok(Response::Query(v.into_inner())),
-                    Err(status) => Err(status),
+            let proto_response = match method {
+                Method::Query(func) => {
+                    let proto_query = proto_request.get_query()?;
+                    match tokio::time::timeout(grpc_deadline, func(proto_query.clone())).await {
+                        Ok(Ok(v)) => Ok(Response::Query(v.into_inner())),
+                        Ok(Err(status)) => Err(status),
+                        Err(_) => Err(deadline_exceeded(grpc_deadline)),
+                    }
                 }
-            }
-            Method::Transaction(func) => {
-                let proto_tx = proto_request.get_transaction()?;
-                match func(proto_tx.clone()).await {
-                    Ok(v) => Ok(Response::Transaction(v.into_inner())),
-                    Err(status) => Err(status),
+                Method::Transaction(func) => {
+                    let proto_tx = proto_request.get_transaction()?;
+                    match tokio::time::timeout(grpc_deadline, func(proto_tx.clone())).await {
+                        Ok(Ok(v)) => Ok(Response::Transaction(v.into_inner())),
+                        Ok(Err(status)) => Err(status),
+                        Err(_) => Err(deadline_exceeded(grpc_deadline)),
+                    }
                 }
+            };
+
+            for m in middleware.iter() {
+                m.after_response(&context, proto_response.as_ref());
             }
-        };
-
-        let response = match proto_response {
-            Ok(v) => v,
-            Err(status) => {
-                let code = status.code();
-                if code != tonic::Code::Ok
-                    && (code == tonic::Code::Unavailable || code == tonic::Code::ResourceExhausted)
-                {
-                    let mut node_write = node.write().await;
-                    node_write.increase_delay();
-                    drop(node_write);
+
+            let mut retry_context = RetryContext {
+                attempt,
+                max_attempts,
+                node_account_id,
+                code: None,
+                status: None,
+                retryable: false,
+                min_backoff: Duration::from_millis(min_backoff),
+                max_backoff: Duration::from_millis(max_backoff),
+                elapsed: started.elapsed(),
+            };
+
+            let response = match proto_response {
+                Ok(v) => v,
+                Err(status) => {
+                    let code = status.code();
+                    retry_context.code = Some(code);
+                    // timed out attempts count as a node failure, like unavailable nodes
+                    retry_context.retryable = code == tonic::Code::Unavailable
+                        || code == tonic::Code::ResourceExhausted
+                        || code == tonic::Code::DeadlineExceeded;
+                    if retry_context.retryable {
+                        let mut node_write = node.write().await;
+                        node_write.increase_delay();
+                        drop(node_write);
+                    }
+                    if let Some(delay) = retry_policy.retry_delay(&retry_context) {
+                        trace!("will retry on attempt {}/{}", attempt, max_attempts);
+                        tokio::time::sleep(delay).await;
+                        continue;
+                    }
+                    return Err(HederaError::ProtoClientFailed(code));
+                }
+            };
+
+            let mut node_write = node.write().await;
+            node_write.decrease_delay();
+            let account_id = node_write.account_id()?;
+            drop(node_write);
+
+            let response_status = map_response_status(&request, &response)?;
+            let is_ok = response_status == Status::Ok || response_status == Status::Success;
+
+            retry_context.retryable = should_retry(&response_status, &response);
+            if retry_context.retryable || !is_ok {
+                retry_context.status = Some(response_status);
+                if let Some(delay) = retry_policy.retry_delay(&retry_context) {
+                    trace!("will retry on attempt {}/{}", attempt, max_attempts);
+                    tokio::time::sleep(delay).await;
                     continue;
                 }
-                return Err(HederaError::ProtoClientFailed(code));
+                if !is_ok {
+                    return Err(HederaError::FailedPreCheck(response_status));
+                }
+                return Err(HederaError::MaxAttempsExceeded(attempt));
             }
-        };
 
-        let mut node_write = node.write().await;
-        node_write.decrease_delay();
-        let account_id = node_write.account_id()?;
-        drop(node_write);
-
-        let response_status = map_response_status(&request, &response)?;
-
-        if should_retry(&response_status, &response) && attempt <= max_attempts {
-            trace!("will retry on attempt {}/{}", attempt, max_attempts);
-            delay_for_attempt(min_backoff, max_backoff, attempt).await;
-            continue;
-        }
-
-        if response_status != Status::Ok && response_status != Status::Success {
-            return Err(HederaError::FailedPreCheck(response_status));
+            return map_response(request, response, account_id, proto_request);
         }
-
-        return map_response(request, response, account_id, proto_request);
+        Err(HederaError::MaxAttempsExceeded(max_attempts))
+    };
+    match tokio::time::timeout(request_timeout, attempts).await {
+        Ok(result) => result,
+        Err(_) => Err(HederaError::ExecuteTimeout(request_timeout)),
     }
-    return Err(HederaError::MaxAttempsExceeded(max_attempts));
 }
 
-pub async fn delay_for_attempt(min_backoff: u64, max_backoff: u64, attempt: u8) {
-    let delay = min(min_backoff * pow(u64::from(attempt), 2), max_backoff);
-    tokio::time::sleep(Duration::from_millis(delay)).await;
+fn deadline_exceeded(grpc_deadline: Duration) -> tonic::Status {
+    tonic::Status::deadline_exceeded(format!("no response within {:?}", grpc_deadline))
 }
@@ -4,7 +4,7 @@ use crate::error::HederaError;
 use crate::proto::services::FeeComponents as ProtoFeeComponents;
 use crate::proto::ToProto;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub struct FeeComponents {
     pub min: i64,
     pub max: i64,
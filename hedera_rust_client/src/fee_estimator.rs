@@ -0,0 +1,748 @@
+use chrono::{DateTime, Utc};
+use prost::Message;
+
+use crate::error::HederaError;
+use crate::exchange_rate::ExchangeRate;
+use crate::fee_components::FeeComponents;
+use crate::fee_data::{FeeData, FeeDataType};
+use crate::fee_schedule::FeeSchedule;
+use crate::fee_schedules::FeeSchedules;
+use crate::proto::services::{self, transaction_body::Data as TxData};
+use crate::transaction::Transaction;
+use crate::transaction_body::TransactionBody;
+use crate::transaction_fee_schedule::HederaFunctionality;
+use crate::{AccountId, Hbar, TransactionId};
+
+// schedule prices are in thousandths of a tinycent
+const FEE_DIVISOR_FACTOR: i128 = 1000;
+const TINYCENTS_PER_USD: f64 = 10_000_000_000.0;
+
+const HOUR_SEC: i64 = 3600;
+const RECEIPT_STORAGE_TIME_SEC: i64 = 180;
+// used when a body doesn't say how long the entity lives
+const DEFAULT_LIFETIME_SEC: i64 = 7_890_000;
+const SCHEDULE_LIFETIME_SEC: i64 = 1800;
+
+// entity sizes in bytes, as used by the network's fee model
+const LONG_SIZE: i64 = 8;
+const INT_SIZE: i64 = 4;
+const BOOL_SIZE: i64 = 4;
+const BASIC_ENTITY_ID_SIZE: i64 = 3 * LONG_SIZE;
+const BASIC_RECEIPT_SIZE: i64 = INT_SIZE + BASIC_ENTITY_ID_SIZE + 6 * LONG_SIZE;
+const BASIC_ACCOUNT_SIZE: i64 = 8 * LONG_SIZE + BOOL_SIZE;
+const BASIC_FILE_SIZE: i64 = 2 * LONG_SIZE + BOOL_SIZE;
+const BASIC_TOPIC_SIZE: i64 = BASIC_ENTITY_ID_SIZE + 3 * LONG_SIZE + 48;
+const BASIC_TOKEN_SIZE: i64 = 2 * BASIC_ENTITY_ID_SIZE + 7 * LONG_SIZE + 3 * BOOL_SIZE;
+const BASIC_NFT_SIZE: i64 = BASIC_ENTITY_ID_SIZE + 2 * LONG_SIZE;
+// an ed25519 signature pair with its full public key prefix
+const SIGNATURE_PAIR_SIZE: usize = 102;
+
+/// Estimated cost of a transaction, split the way the network charges it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FeeEstimate {
+    pub node_fee: Hbar,
+    pub network_fee: Hbar,
+    pub service_fee: Hbar,
+    /// Total cost in tinycents (1/100,000,000 of a US cent).
+    pub tinycents: i64,
+}
+
+impl FeeEstimate {
+    pub fn total(&self) -> Hbar {
+        Hbar::from_tinybar(
+            self.node_fee.as_tinybar()
+                + self.network_fee.as_tinybar()
+                + self.service_fee.as_tinybar(),
+        )
+    }
+
+    pub fn usd(&self) -> f64 {
+        self.tinycents as f64 / TINYCENTS_PER_USD
+    }
+
+    fn add(&self, other: &FeeEstimate) -> FeeEstimate {
+        let sum =
+            |a: Hbar, b: Hbar| Hbar::from_tinybar(a.as_tinybar().saturating_add(b.as_tinybar()));
+        FeeEstimate {
+            node_fee: sum(self.node_fee, other.node_fee),
+            network_fee: sum(self.network_fee, other.network_fee),
+            service_fee: sum(self.service_fee, other.service_fee),
+            tinycents: self.tinycents.saturating_add(other.tinycents),
+        }
+    }
+}
+
+/// Computes transaction fees offline from a fee schedule and exchange rate.
+///
+/// Usage (bytes, signatures, RAM and storage byte-hours, gas) is derived
+/// from the transaction body, so the result is an estimate of what the
+/// network will charge, not a quote.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeEstimator {
+    schedule: FeeSchedule,
+    exchange_rate: ExchangeRate,
+}
+
+impl FeeEstimator {
+    pub fn new(schedule: FeeSchedule, exchange_rate: ExchangeRate) -> FeeEstimator {
+        FeeEstimator {
+            schedule,
+            exchange_rate,
+        }
+    }
+
+    /// Uses the current schedule of `schedules`.
+    pub fn from_schedules(
+        schedules: &FeeSchedules,
+        exchange_rate: ExchangeRate,
+    ) -> Result<FeeEstimator, HederaError> {
+        match &schedules.current {
+            Some(schedule) => Ok(FeeEstimator::new(schedule.clone(), exchange_rate)),
+            None => Err(HederaError::FeeScheduleNotFound("current".to_string())),
+        }
+    }
+
+    pub fn schedule(&self) -> &FeeSchedule {
+        &self.schedule
+    }
+
+    pub fn exchange_rate(&self) -> &ExchangeRate {
+        &self.exchange_rate
+    }
+
+    /// Estimates the fee of `transaction` once it carries `signatures`
+    /// signatures. Frozen transactions are measured as signed so far, one
+    /// body per chunk; for unfrozen ones the transaction id and node
+    /// account id are stubbed.
+    pub(crate) fn estimate(
+        &self,
+        transaction: &Transaction,
+        signatures: usize,
+    ) -> Result<FeeEstimate, HederaError> {
+        if transaction.signed_transactions.is_empty() {
+            return self.estimate_unsigned(transaction.body(), signatures);
+        }
+        // every node is sent a copy of each chunk, the network charges for one
+        let mut chunk_ids = Vec::new();
+        let mut total: Option<FeeEstimate> = None;
+        for signed in &transaction.signed_transactions {
+            let body = TransactionBody::try_from_proto_bytes(signed.body_bytes.clone())?;
+            if chunk_ids.contains(&body.transaction_id) {
+                continue;
+            }
+            chunk_ids.push(body.transaction_id.clone());
+            let present = signed.sig_map.as_ref().map_or(0, |m| m.sig_pair.len());
+            let missing = signatures.saturating_sub(present);
+            let bytes = signed.to_proto_bytes()?.len() + missing * SIGNATURE_PAIR_SIZE;
+            let estimate =
+                self.estimate_body(&body, bytes as i64, (present + missing).max(1) as i64)?;
+            total = Some(total.map_or(estimate, |t| t.add(&estimate)));
+        }
+        total.ok_or(HederaError::UnsupportedTransactionBodyType)
+    }
+
+    /// Estimates the unfrozen chunks of a chunked transaction, summed.
+    pub(crate) fn estimate_chunks(
+        &self,
+        chunks: &[Transaction],
+        signatures: usize,
+    ) -> Result<FeeEstimate, HederaError> {
+        let mut total: Option<FeeEstimate> = None;
+        for chunk in chunks {
+            let estimate = self.estimate(chunk, signatures)?;
+            total = Some(total.map_or(estimate, |t| t.add(&estimate)));
+        }
+        total.ok_or(HederaError::UnsupportedTransactionBodyType)
+    }
+
+    fn estimate_unsigned(
+        &self,
+        body: &TransactionBody,
+        signatures: usize,
+    ) -> Result<FeeEstimate, HederaError> {
+        let mut body = body.clone();
+        if body.node_account_id.is_none() {
+            body.node_account_id = Some(AccountId::simple(3));
+        }
+        if body.transaction_id.is_none() {
+            body.transaction_id = Some(TransactionId::generate(AccountId::simple(1001)));
+        }
+        // SignedTransaction framing around the body
+        let bytes = body.to_proto_bytes()?.len() + 3 + signatures * SIGNATURE_PAIR_SIZE;
+        self.estimate_body(&body, bytes as i64, signatures.max(1) as i64)
+    }
+
+    fn estimate_body(
+        &self,
+        body: &TransactionBody,
+        bytes: i64,
+        signatures: i64,
+    ) -> Result<FeeEstimate, HederaError> {
+        let data = body
+            .data
+            .as_ref()
+            .ok_or(HederaError::UnsupportedTransactionBodyType)?;
+        let functionality = functionality(data);
+        let valid_start = body
+            .transaction_id
+            .as_ref()
+            .and_then(|id| id.transaction_valid_start)
+            .unwrap_or_else(Utc::now);
+        let (fee_data_type, service) = service_usage(data, valid_start);
+        let prices = self.prices(functionality, fee_data_type)?;
+
+        let node = FeeComponents {
+            constant: 1,
+            transaction_bandwidth_byte: bytes,
+            transaction_verification: 1,
+            response_memory_byte: INT_SIZE,
+            ..Default::default()
+        };
+        let network = FeeComponents {
+            constant: 1,
+            transaction_bandwidth_byte: bytes,
+            transaction_verification: signatures,
+            transaction_ram_byte_hour: byte_hours(BASIC_RECEIPT_SIZE, RECEIPT_STORAGE_TIME_SEC),
+            ..Default::default()
+        };
+
+        let node_fee = component_fee(prices.node_data.as_ref(), &node);
+        let network_fee = component_fee(prices.network_data.as_ref(), &network);
+        let service_fee = component_fee(prices.service_data.as_ref(), &service);
+        Ok(FeeEstimate {
+            node_fee: self.to_hbar(node_fee)?,
+            network_fee: self.to_hbar(network_fee)?,
+            service_fee: self.to_hbar(service_fee)?,
+            tinycents: clamp_i64(node_fee + network_fee + service_fee),
+        })
+    }
+
+    // exact sub type, then the default one, then whatever the schedule has
+    fn prices(
+        &self,
+        functionality: HederaFunctionality,
+        fee_data_type: FeeDataType,
+    ) -> Result<FeeData, HederaError> {
+        let schedule = self
+            .schedule
+            .transaction_fee_schedule
+            .iter()
+            .find(|s| s.hedera_functionality == functionality)
+            .ok_or_else(|| HederaError::FeeScheduleNotFound(format!("{:?}", functionality)))?;
+        schedule
+            .fees
+            .iter()
+            .find(|f| f.fee_data_type == fee_data_type)
+            .or_else(|| {
+                schedule
+                    .fees
+                    .iter()
+                    .find(|f| f.fee_data_type == FeeDataType::Default)
+            })
+            .or_else(|| schedule.fees.first())
+            .or(schedule.fee_data.as_ref())
+            .copied()
+            .ok_or_else(|| HederaError::FeeScheduleNotFound(format!("{:?}", functionality)))
+    }
+
+    fn to_hbar(&self, tinycents: i128) -> Result<Hbar, HederaError> {
+        let rate = &self.exchange_rate;
+        if rate.hbars <= 0 || rate.cents <= 0 {
+            return Err(HederaError::InvalidExchangeRate(rate.hbars, rate.cents));
+        }
+        let tinybars = tinycents * rate.hbars as i128 / rate.cents as i128;
+        Ok(Hbar::from_tinybar(clamp_i64(tinybars)))
+    }
+}
+
+// fee in tinycents of one component, clamped to the schedule's bounds
+fn component_fee(price: Option<&FeeComponents>, usage: &FeeComponents) -> i128 {
+    let price = match price {
+        Some(v) => v,
+        None => return 0,
+    };
+    let products = [
+        (price.constant, usage.constant),
+        (
+            price.transaction_bandwidth_byte,
+            usage.transaction_bandwidth_byte,
+        ),
+        (
+            price.transaction_verification,
+            usage.transaction_verification,
+        ),
+        (
+            price.transaction_ram_byte_hour,
+            usage.transaction_ram_byte_hour,
+        ),
+        (
+            price.transaction_storage_byte_hour,
+            usage.transaction_storage_byte_hour,
+        ),
+        (
+            price.contract_transaction_gas,
+            usage.contract_transaction_gas,
+        ),
+        (price.transfer_volume_hbar, usage.transfer_volume_hbar),
+        (price.response_memory_byte, usage.response_memory_byte),
+        (price.response_disc_byte, usage.response_disc_byte),
+    ];
+    let fee: i128 = products
+        .iter()
+        .map(|(price, usage)| *price as i128 * *usage as i128)
+        .sum();
+    let fee = fee.max(price.min as i128);
+    let fee = if price.max > price.min {
+        fee.min(price.max as i128)
+    } else {
+        fee
+    };
+    fee / FEE_DIVISOR_FACTOR
+}
+
+fn clamp_i64(value: i128) -> i64 {
+    value.max(i64::MIN as i128).min(i64::MAX as i128) as i64
+}
+
+fn byte_hours(bytes: i64, seconds: i64) -> i64 {
+    (bytes * seconds.max(0) + HOUR_SEC - 1) / HOUR_SEC
+}
+
+fn key_size(key: &Option<services::Key>) -> i64 {
+    key.as_ref().map_or(0, |k| k.encoded_len() as i64)
+}
+
+fn duration_sec(duration: &Option<services::Duration>, default: i64) -> i64 {
+    duration.as_ref().map_or(default, |d| d.seconds)
+}
+
+fn lifetime_sec(expiration: &Option<services::Timestamp>, valid_start: DateTime<Utc>) -> i64 {
+    expiration.as_ref().map_or(DEFAULT_LIFETIME_SEC, |t| {
+        t.seconds - valid_start.timestamp()
+    })
+}
+
+fn token_sub_type(non_fungible: bool, custom_fees: bool) -> FeeDataType {
+    match (non_fungible, custom_fees) {
+        (false, false) => FeeDataType::TokenFungibleCommon,
+        (true, false) => FeeDataType::TokenNonFungibleUnique,
+        (false, true) => FeeDataType::TokenFungibleCommonWithCustomFees,
+        (true, true) => FeeDataType::TokenNonFungibleUniqueWithCustomFees,
+    }
+}
+
+// service usage beyond the flat constant: entity state kept for its
+// lifetime, file storage, gas, and the schedule sub type
+fn service_usage(data: &TxData, valid_start: DateTime<Utc>) -> (FeeDataType, FeeComponents) {
+    let mut usage = FeeComponents {
+        constant: 1,
+        ..Default::default()
+    };
+    let mut fee_data_type = FeeDataType::Default;
+    match data {
+        TxData::CryptoCreateAccount(body) => {
+            usage.transaction_ram_byte_hour = byte_hours(
+                BASIC_ACCOUNT_SIZE + key_size(&body.key) + body.memo.len() as i64,
+                duration_sec(&body.auto_renew_period, DEFAULT_LIFETIME_SEC),
+            );
+        }
+        TxData::CryptoTransfer(body) if !body.token_transfers.is_empty() => {
+            let non_fungible = body
+                .token_transfers
+                .iter()
+                .any(|t| !t.nft_transfers.is_empty());
+            fee_data_type = token_sub_type(non_fungible, false);
+        }
+        TxData::FileCreate(body) => {
+            let keys = body.keys.as_ref().map_or(0, |k| k.encoded_len() as i64);
+            usage.transaction_storage_byte_hour = byte_hours(
+                BASIC_FILE_SIZE + body.contents.len() as i64 + keys + body.memo.len() as i64,
+                lifetime_sec(&body.expiration_time, valid_start),
+            );
+        }
+        TxData::FileUpdate(body) => {
+            let keys = body.keys.as_ref().map_or(0, |k| k.encoded_len() as i64);
+            usage.transaction_storage_byte_hour = byte_hours(
+                body.contents.len() as i64 + keys,
+                lifetime_sec(&body.expiration_time, valid_start),
+            );
+        }
+        TxData::FileAppend(body) => {
+            usage.transaction_storage_byte_hour =
+                byte_hours(body.contents.len() as i64, DEFAULT_LIFETIME_SEC);
+        }
+        TxData::ConsensusCreateTopic(body) => {
+            usage.transaction_ram_byte_hour = byte_hours(
+                BASIC_TOPIC_SIZE
+                    + body.memo.len() as i64
+                    + key_size(&body.admin_key)
+                    + key_size(&body.submit_key),
+                duration_sec(&body.auto_renew_period, DEFAULT_LIFETIME_SEC),
+            );
+        }
+        TxData::TokenCreation(body) => {
+            let keys = [
+                &body.admin_key,
+                &body.kyc_key,
+                &body.freeze_key,
+                &body.wipe_key,
+                &body.supply_key,
+                &body.fee_schedule_key,
+                &body.pause_key,
+            ]
+            .iter()
+            .map(|k| key_size(k))
+            .sum::<i64>();
+            let custom_fees = body
+                .custom_fees
+                .iter()
+                .map(|f| f.encoded_len() as i64)
+                .sum::<i64>();
+            let lifetime = match &body.auto_renew_period {
+                Some(period) => period.seconds,
+                None => lifetime_sec(&body.expiry, valid_start),
+            };
+            usage.transaction_ram_byte_hour = byte_hours(
+                BASIC_TOKEN_SIZE
+                    + body.name.len() as i64
+                    + body.symbol.len() as i64
+                    + body.memo.len() as i64
+                    + keys
+                    + custom_fees,
+                lifetime,
+            );
+            let non_fungible = body.token_type == services::TokenType::NonFungibleUnique as i32;
+            fee_data_type = token_sub_type(non_fungible, !body.custom_fees.is_empty());
+        }
+        TxData::TokenMint(body) => {
+            fee_data_type = token_sub_type(!body.metadata.is_empty(), false);
+            let metadata = body.metadata.iter().map(|m| m.len() as i64).sum::<i64>();
+            usage.transaction_ram_byte_hour = byte_hours(
+                metadata + body.metadata.len() as i64 * BASIC_NFT_SIZE,
+                DEFAULT_LIFETIME_SEC,
+            );
+        }
+        TxData::TokenBurn(body) => {
+            fee_data_type = token_sub_type(!body.serial_numbers.is_empty(), false);
+        }
+        TxData::TokenWipe(body) => {
+            fee_data_type = token_sub_type(!body.serial_numbers.is_empty(), false);
+        }
+        TxData::ContractCall(body) => {
+            usage.contract_transaction_gas = body.gas;
+        }
+        TxData::ContractCreateInstance(body) => {
+            usage.contract_transaction_gas = body.gas;
+            usage.transaction_ram_byte_hour = byte_hours(
+                BASIC_ACCOUNT_SIZE + key_size(&body.admin_key) + body.memo.len() as i64,
+                duration_sec(&body.auto_renew_period, DEFAULT_LIFETIME_SEC),
+            );
+        }
+        TxData::ScheduleCreate(body) => {
+            let scheduled = body
+                .scheduled_transaction_body
+                .as_ref()
+                .map_or(0, |b| b.encoded_len() as i64);
+            let lifetime = match &body.expiration_time {
+                Some(_) => lifetime_sec(&body.expiration_time, valid_start),
+                None => SCHEDULE_LIFETIME_SEC,
+            };
+            usage.transaction_ram_byte_hour = byte_hours(
+                BASIC_ENTITY_ID_SIZE
+                    + scheduled
+                    + body.memo.len() as i64
+                    + key_size(&body.admin_key),
+                lifetime,
+            );
+        }
+        _ => {}
+    }
+    (fee_data_type, usage)
+}
+
+fn functionality(data: &TxData) -> HederaFunctionality {
+    match data {
+        TxData::ContractCall(_) => HederaFunctionality::ContractCall,
+        TxData::ContractCreateInstance(_) => HederaFunctionality::ContractCreate,
+        TxData::ContractUpdateInstance(_) => HederaFunctionality::ContractUpdate,
+        TxData::ContractDeleteInstance(_) => HederaFunctionality::ContractDelete,
+        TxData::EthereumTransaction(_) => HederaFunctionality::EthereumTransaction,
+        TxData::CryptoAddLiveHash(_) => HederaFunctionality::CryptoAddLiveHash,
+        TxData::CryptoApproveAllowance(_) => HederaFunctionality::CryptoApproveAllowance,
+        TxData::CryptoDeleteAllowance(_) => HederaFunctionality::CryptoDeleteAllowance,
+        TxData::CryptoCreateAccount(_) => HederaFunctionality::CryptoCreate,
+        TxData::CryptoDelete(_) => HederaFunctionality::CryptoDelete,
+        TxData::CryptoDeleteLiveHash(_) => HederaFunctionality::CryptoDeleteLiveHash,
+        TxData::CryptoTransfer(_) => HederaFunctionality::CryptoTransfer,
+        TxData::CryptoUpdateAccount(_) => HederaFunctionality::CryptoUpdate,
+        TxData::FileAppend(_) => HederaFunctionality::FileAppend,
+        TxData::FileCreate(_) => HederaFunctionality::FileCreate,
+        TxData::FileDelete(_) => HederaFunctionality::FileDelete,
+        TxData::FileUpdate(_) => HederaFunctionality::FileUpdate,
+        TxData::SystemDelete(_) => HederaFunctionality::SystemDelete,
+        TxData::SystemUndelete(_) => HederaFunctionality::SystemUndelete,
+        TxData::Freeze(_) => HederaFunctionality::Freeze,
+        TxData::ConsensusCreateTopic(_) => HederaFunctionality::ConsensusCreateTopic,
+        TxData::ConsensusUpdateTopic(_) => HederaFunctionality::ConsensusUpdateTopic,
+        TxData::ConsensusDeleteTopic(_) => HederaFunctionality::ConsensusDeleteTopic,
+        TxData::ConsensusSubmitMessage(_) => HederaFunctionality::ConsensusSubmitMessage,
+        TxData::UncheckedSubmit(_) => HederaFunctionality::UncheckedSubmit,
+        TxData::TokenCreation(_) => HederaFunctionality::TokenCreate,
+        TxData::TokenFreeze(_) => HederaFunctionality::TokenFreezeAccount,
+        TxData::TokenUnfreeze(_) => HederaFunctionality::TokenUnfreezeAccount,
+        TxData::TokenGrantKyc(_) => HederaFunctionality::TokenGrantKycToAccount,
+        TxData::TokenRevokeKyc(_) => HederaFunctionality::TokenRevokeKycFromAccount,
+        TxData::TokenDeletion(_) => HederaFunctionality::TokenDelete,
+        TxData::TokenUpdate(_) => HederaFunctionality::TokenUpdate,
+        TxData::TokenMint(_) => HederaFunctionality::TokenMint,
+        TxData::TokenBurn(_) => HederaFunctionality::TokenBurn,
+        TxData::TokenWipe(_) => HederaFunctionality::TokenAccountWipe,
+        TxData::TokenAssociate(_) => HederaFunctionality::TokenAssociateToAccount,
+        TxData::TokenDissociate(_) => HederaFunctionality::TokenDissociateFromAccount,
+        TxData::TokenFeeScheduleUpdate(_) => HederaFunctionality::TokenFeeScheduleUpdate,
+        TxData::TokenPause(_) => HederaFunctionality::TokenPause,
+        TxData::TokenUnpause(_) => HederaFunctionality::TokenUnpause,
+        TxData::ScheduleCreate(_) => HederaFunctionality::ScheduleCreate,
+        TxData::ScheduleDelete(_) => HederaFunctionality::ScheduleDelete,
+        TxData::ScheduleSign(_) => HederaFunctionality::ScheduleSign,
+        TxData::NodeStakeUpdate(_) => HederaFunctionality::NodeStakeUpdate,
+        TxData::UtilPrng(_) => HederaFunctionality::UtilPrng,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction_fee_schedule::TransactionFeeSchedule;
+    use crate::{
+        FileAppendTransaction, FileCreateTransaction, FileId, PrivateKey,
+        ScheduleCreateTransaction, TokenCreateTransaction, TokenId, TokenMintTransaction,
+        TokenType, TopicCreateTransaction, TopicId, TopicMessageSubmitTransaction,
+        TransferTransaction,
+    };
+
+    fn prices(constant: i64, bpt: i64, vpt: i64, rbh: i64) -> Option<FeeComponents> {
+        Some(FeeComponents {
+            min: 0,
+            max: 1_000_000_000_000_000,
+            constant,
+            transaction_bandwidth_byte: bpt,
+            transaction_verification: vpt,
+            transaction_ram_byte_hour: rbh,
+            ..Default::default()
+        })
+    }
+
+    fn fee_data(fee_data_type: FeeDataType, service_constant: i64) -> FeeData {
+        let mut service_data = prices(service_constant, 0, 0, 1_000);
+        if let Some(service) = service_data.as_mut() {
+            service.transaction_storage_byte_hour = 1_000;
+        }
+        FeeData {
+            node_data: prices(1_000_000, 1_000, 100_000, 0),
+            network_data: prices(2_000_000, 1_000, 100_000, 1_000),
+            service_data,
+            fee_data_type,
+        }
+    }
+
+    fn estimator_for(functionalities: &[HederaFunctionality]) -> FeeEstimator {
+        let schedule = FeeSchedule {
+            transaction_fee_schedule: functionalities
+                .iter()
+                .map(|f| TransactionFeeSchedule {
+                    hedera_functionality: *f,
+                    fee_data: None,
+                    fees: vec![
+                        fee_data(FeeDataType::Default, 50_000_000),
+                        fee_data(FeeDataType::TokenNonFungibleUnique, 80_000_000),
+                    ],
+                })
+                .collect(),
+            expiry_time: None,
+        };
+        let rate = ExchangeRate {
+            hbars: 1,
+            cents: 12,
+            expiration_time: 0,
+        };
+        FeeEstimator::from_schedules(
+            &FeeSchedules {
+                current: Some(schedule),
+                next: None,
+            },
+            rate,
+        )
+        .unwrap()
+    }
+
+    fn estimator() -> FeeEstimator {
+        estimator_for(&[HederaFunctionality::CryptoTransfer])
+    }
+
+    #[test]
+    fn test_component_fee() {
+        let usage = FeeComponents {
+            constant: 1,
+            transaction_bandwidth_byte: 200,
+            transaction_verification: 2,
+            ..Default::default()
+        };
+        // (1_000_000 + 200 * 1_000 + 2 * 100_000) / 1000
+        assert_eq!(
+            component_fee(prices(1_000_000, 1_000, 100_000, 0).as_ref(), &usage),
+            1_400
+        );
+        let mut bounded = prices(1_000_000, 1_000, 100_000, 0).unwrap();
+        bounded.max = 500_000;
+        assert_eq!(component_fee(Some(&bounded), &usage), 500);
+        assert_eq!(component_fee(None, &usage), 0);
+    }
+
+    #[test]
+    fn test_estimate_transfer() -> Result<(), HederaError> {
+        let estimator = estimator();
+        let mut tx = TransferTransaction::new();
+        tx.add_hbar_transfer(AccountId::simple(1001), Hbar::from_tinybar(-10))?
+            .add_hbar_transfer(AccountId::simple(1002), Hbar::from_tinybar(10))?;
+
+        let one = tx.estimate_fee(&estimator, 1)?;
+        let two = tx.estimate_fee(&estimator, 2)?;
+        // service fee is flat, each extra signature costs bytes and verification
+        assert_eq!(one.service_fee, two.service_fee);
+        assert_eq!(one.service_fee, Hbar::from_tinybar(50_000 / 12));
+        assert!(two.total().as_tinybar() > one.total().as_tinybar());
+        assert!(one.tinycents > 53_000);
+        assert!((one.usd() - one.tinycents as f64 / 1e10).abs() < f64::EPSILON);
+
+        let topic = crate::TopicCreateTransaction::new();
+        assert!(matches!(
+            topic.estimate_fee(&estimator, 1),
+            Err(HederaError::FeeScheduleNotFound(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_estimate_token() -> Result<(), HederaError> {
+        let estimator = estimator_for(&[
+            HederaFunctionality::TokenCreate,
+            HederaFunctionality::TokenMint,
+        ]);
+        let mut fungible = TokenCreateTransaction::new();
+        fungible.set_name("a".to_string())?;
+        let mut named = fungible.clone();
+        named.set_name("a much longer token name".repeat(4))?;
+        let mut non_fungible = fungible.clone();
+        non_fungible.set_token_type(TokenType::NonFungibleUnique)?;
+
+        let fungible = fungible.estimate_fee(&estimator, 1)?;
+        // the name is held for the token's lifetime
+        assert!(named.estimate_fee(&estimator, 1)?.service_fee > fungible.service_fee);
+        // priced with the sub type's schedule
+        assert!(non_fungible.estimate_fee(&estimator, 1)?.service_fee > fungible.service_fee);
+
+        let mut mint = TokenMintTransaction::new();
+        mint.set_token_id(TokenId::simple(7))?.set_amount(10)?;
+        let mut nft = TokenMintTransaction::new();
+        nft.set_token_id(TokenId::simple(7))?
+            .set_metadata(vec![1; 100])?;
+        let mut nfts = nft.clone();
+        nfts.add_metadata(vec![2; 100])?;
+        let fungible = mint.estimate_fee(&estimator, 1)?;
+        let nft = nft.estimate_fee(&estimator, 1)?;
+        assert!(nft.service_fee > fungible.service_fee);
+        assert!(nfts.estimate_fee(&estimator, 1)?.service_fee > nft.service_fee);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_estimate_file() -> Result<(), HederaError> {
+        let estimator = estimator_for(&[
+            HederaFunctionality::FileCreate,
+            HederaFunctionality::FileAppend,
+        ]);
+        let mut create = FileCreateTransaction::new();
+        create.set_contents(vec![1; 10])?;
+        let small = create.estimate_fee(&estimator, 1)?;
+        create.set_contents(vec![1; 1000])?;
+        assert!(create.estimate_fee(&estimator, 1)?.service_fee > small.service_fee);
+
+        let mut one = FileAppendTransaction::new();
+        one.set_file_id(FileId::simple(150))?
+            .set_contents(vec![1; 1024])?;
+        let mut three = one.clone();
+        three.set_contents(vec![1; 3 * 1024])?;
+        let one = one.estimate_fee(&estimator, 1)?;
+        let unfrozen = three.estimate_fee(&estimator, 1)?;
+        // three chunks, each charged as a transaction of its own
+        assert_eq!(
+            unfrozen.service_fee,
+            Hbar::from_tinybar(3 * one.service_fee.as_tinybar())
+        );
+        assert!(unfrozen.node_fee.as_tinybar() > 2 * one.node_fee.as_tinybar());
+
+        three
+            .set_transaction_id(TransactionId::generate(AccountId::simple(1001)))?
+            .set_node_account_ids(vec![AccountId::simple(3), AccountId::simple(4)])?
+            .freeze()
+            .await?;
+        let frozen = three.estimate_fee(&estimator, 1)?;
+        // one copy of each chunk per node, counted once
+        assert_eq!(frozen.service_fee, unfrozen.service_fee);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_estimate_topic() -> Result<(), HederaError> {
+        let estimator = estimator_for(&[
+            HederaFunctionality::ConsensusCreateTopic,
+            HederaFunctionality::ConsensusSubmitMessage,
+        ]);
+        let mut create = TopicCreateTransaction::new();
+        let open = create.estimate_fee(&estimator, 1)?;
+        create.set_submit_key(PrivateKey::new().public().into())?;
+        assert!(create.estimate_fee(&estimator, 1)?.service_fee > open.service_fee);
+
+        let mut one = TopicMessageSubmitTransaction::new();
+        one.set_topic_id(TopicId::simple(9))?
+            .set_message(vec![1; 100])?;
+        let mut three = one.clone();
+        three.set_message(vec![1; 2 * 1024 + 100])?;
+        let one = one.estimate_fee(&estimator, 1)?;
+        let unfrozen = three.estimate_fee(&estimator, 1)?;
+        assert_eq!(
+            unfrozen.service_fee,
+            Hbar::from_tinybar(3 * one.service_fee.as_tinybar())
+        );
+        assert!(unfrozen.network_fee.as_tinybar() > 2 * one.network_fee.as_tinybar());
+
+        three
+            .set_transaction_id(TransactionId::generate(AccountId::simple(1001)))?
+            .set_node_account_ids(vec![AccountId::simple(3)])?
+            .freeze()
+            .await?;
+        let frozen = three.estimate_fee(&estimator, 1)?;
+        assert_eq!(frozen.service_fee, unfrozen.service_fee);
+        // the chunks carry the same body bytes give or take a varint
+        assert!((frozen.tinycents - unfrozen.tinycents).abs() < unfrozen.tinycents / 100);
+        Ok(())
+    }
+
+    #[test]
+    fn test_estimate_schedule_create() -> Result<(), HederaError> {
+        let estimator = estimator_for(&[HederaFunctionality::ScheduleCreate]);
+        let scheduled = |memo: &str| services::SchedulableTransactionBody {
+            memo: memo.to_string(),
+            ..Default::default()
+        };
+        let mut small = ScheduleCreateTransaction::new();
+        small.set_schedulable_transaction_body(scheduled("short"))?;
+        let small = small.estimate_fee(&estimator, 1)?;
+        let mut large = ScheduleCreateTransaction::new();
+        large.set_schedulable_transaction_body(scheduled(&"a longer memo".repeat(8)))?;
+        // the scheduled body is held until the schedule expires
+        assert!(large.estimate_fee(&estimator, 1)?.service_fee > small.service_fee);
+        Ok(())
+    }
+}
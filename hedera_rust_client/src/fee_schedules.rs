@@ -1,3 +1,6 @@
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use prost::Message;
 use std::convert::{TryFrom, TryInto};
 
 use crate::error::HederaError;
@@ -11,6 +14,26 @@ pub struct FeeSchedules {
     pub next: Option<FeeSchedule>,
 }
 
+impl FeeSchedules {
+    pub fn from_proto_bytes(bytes: Vec<u8>) -> Result<FeeSchedules, HederaError> {
+        match CurrentAndNextFeeSchedule::decode(Bytes::from(bytes)) {
+            Ok(proto) => proto.try_into(),
+            Err(e) => Err(HederaError::ProtoDeserialize("FeeSchedules", e)),
+        }
+    }
+
+    /// The schedule in effect at `time`: the current one until it expires,
+    /// then the next.
+    pub fn schedule_at(&self, time: DateTime<Utc>) -> Option<&FeeSchedule> {
+        match &self.current {
+            Some(schedule) if schedule.expiry_time.map_or(true, |expiry| time < expiry) => {
+                Some(schedule)
+            }
+            _ => self.next.as_ref(),
+        }
+    }
+}
+
 impl TryFrom<CurrentAndNextFeeSchedule> for FeeSchedules {
     type Error = HederaError;
     fn try_from(services: CurrentAndNextFeeSchedule) -> Result<FeeSchedules, Self::Error> {
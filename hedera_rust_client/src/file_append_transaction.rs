@@ -6,6 +6,7 @@ use crate::FileId;
 use crate::Hbar;
 use crate::HederaError;
 use hedera_rust_client_derive::{TransactionChunked, TransactionChunkedSchedule, TransactionProto};
+use serde::Serialize;
 
 const CHUNK_SIZE: usize = 1024;
 
@@ -58,9 +59,14 @@ impl FileAppendTransaction {
         self.services.contents = self.data[start_index..end_index].to_vec();
         Ok(())
     }
+
+    fn on_decode_chunk(&mut self, services: Proto) {
+        self.data.extend_from_slice(&services.contents);
+        self.services = services;
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "FileAppend",
     proto_type = "FileAppendTransactionBody"
@@ -68,6 +74,7 @@ impl FileAppendTransaction {
 struct Proto {
     #[hedera_rust_client_derive(to_option_proto)]
     pub file_id: Option<FileId>,
+    #[serde(serialize_with = "crate::json::hex")]
     pub contents: Vec<u8>,
 }
 
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Utc};
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
 
 use crate::key_list::KeyList;
 use crate::transaction::Transaction;
@@ -49,7 +50,7 @@ impl FileCreateTransaction {
     gen_transaction_memo_fns!();
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "FileCreate",
     proto_type = "FileCreateTransactionBody"
@@ -59,6 +60,7 @@ struct Proto {
     pub expiration_time: Option<DateTime<Utc>>,
     #[hedera_rust_client_derive(to_option_proto)]
     pub keys: Option<KeyList>,
+    #[serde(serialize_with = "crate::json::hex")]
     pub contents: Vec<u8>,
     #[hedera_rust_client_derive(to_option_proto)]
     pub shard_id: Option<ShardId>,
@@ -1,11 +1,8 @@
 use hedera_rust_client_derive::{Id, IdPartialEq, IdProto, IdValidateChecksum};
-use serde::{Deserialize, Serialize};
 
 use crate::id::IdChecksum;
 
-#[derive(
-    Id, IdProto, IdPartialEq, IdValidateChecksum, Debug, Clone, Copy, Hash, Serialize, Deserialize,
-)]
+#[derive(Id, IdProto, IdPartialEq, IdValidateChecksum, Debug, Clone, Copy, Hash)]
 #[hedera_rust_client_derive(field_name = "file_num")]
 pub struct FileId {
     shard_num: i64,
@@ -13,3 +10,25 @@ pub struct FileId {
     file_num: i64,
     checksum: Option<IdChecksum>,
 }
+
+impl FileId {
+    /// `0.0.101`, the address book.
+    pub const ADDRESS_BOOK: FileId = FileId::system(101);
+    /// `0.0.102`, the address book with node details.
+    pub const NODE_DETAILS: FileId = FileId::system(102);
+    /// `0.0.111`, the current and next fee schedules.
+    pub const FEE_SCHEDULE: FileId = FileId::system(111);
+    /// `0.0.112`, the current and next exchange rates.
+    pub const EXCHANGE_RATES: FileId = FileId::system(112);
+    /// `0.0.123`, the throttle definitions.
+    pub const THROTTLE_DEFINITIONS: FileId = FileId::system(123);
+
+    const fn system(file_num: i64) -> FileId {
+        FileId {
+            shard_num: 0,
+            realm_num: 0,
+            file_num,
+            checksum: None,
+        }
+    }
+}
@@ -8,6 +8,7 @@ use crate::Hbar;
 use crate::HederaError;
 use chrono::{DateTime, Utc};
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
 
 #[derive(TransactionSchedule, TransactionExecute, Debug, Clone, PartialEq)]
 #[hedera_rust_client_derive(service(
@@ -50,7 +51,7 @@ impl FileUpdateTransaction {
     gen_transaction_optional_memo_fns!();
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "FileUpdate",
     proto_type = "FileUpdateTransactionBody"
@@ -62,6 +63,7 @@ struct Proto {
     pub expiration_time: Option<DateTime<Utc>>,
     #[hedera_rust_client_derive(to_option_proto)]
     pub keys: Option<KeyList>,
+    #[serde(serialize_with = "crate::json::hex")]
     pub contents: Vec<u8>,
     pub memo: Option<String>,
 }
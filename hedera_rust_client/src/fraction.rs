@@ -1,10 +1,11 @@
+use serde::Serialize;
 use std::convert::TryFrom;
 
 use crate::error::HederaError;
 use crate::proto::services::Fraction as ProtoFraction;
 use crate::proto::ToProto;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct Fraction {
     pub numerator: u32,
     pub denominator: u32,
@@ -1,4 +1,5 @@
 use crate::error::HederaError;
+use serde::{Serialize, Serializer};
 use std::convert::TryFrom;
 use std::fmt::{self, Debug};
 
@@ -103,3 +104,26 @@ impl fmt::Display for Hbar {
         write!(f, "{}{}", self.tinybar, HbarUnit::Tinybar.symbol())
     }
 }
+
+// in hbar, exactly, e.g. `1.5 ℏ`
+impl Serialize for Hbar {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let sign = if self.tinybar < 0 { "-" } else { "" };
+        let tinybar = self.tinybar.unsigned_abs();
+        let per_hbar = HbarUnit::Hbar.tinybar() as u64;
+        let fraction = format!("{:08}", tinybar % per_hbar);
+        let fraction = fraction.trim_end_matches('0');
+        let s = if fraction.is_empty() {
+            format!("{}{} {}", sign, tinybar / per_hbar, HbarUnit::Hbar.symbol())
+        } else {
+            format!(
+                "{}{}.{} {}",
+                sign,
+                tinybar / per_hbar,
+                fraction,
+                HbarUnit::Hbar.symbol()
+            )
+        };
+        serializer.serialize_str(&s)
+    }
+}
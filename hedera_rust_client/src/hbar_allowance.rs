@@ -0,0 +1,58 @@
+use serde::Serialize;
+use std::convert::TryFrom;
+
+use crate::error::HederaError;
+use crate::proto::{services, ToProto};
+use crate::utils;
+use crate::AccountId;
+use crate::Hbar;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct HbarAllowance {
+    pub owner_account_id: Option<AccountId>,
+    pub spender_account_id: Option<AccountId>,
+    pub amount: Hbar,
+}
+
+impl HbarAllowance {
+    pub fn new(
+        owner_account_id: Option<AccountId>,
+        spender_account_id: Option<AccountId>,
+        amount: Hbar,
+    ) -> Self {
+        HbarAllowance {
+            owner_account_id,
+            spender_account_id,
+            amount,
+        }
+    }
+}
+
+impl TryFrom<services::CryptoAllowance> for HbarAllowance {
+    type Error = HederaError;
+    fn try_from(services: services::CryptoAllowance) -> Result<HbarAllowance, Self::Error> {
+        Ok(HbarAllowance {
+            owner_account_id: utils::optional_account_id(services.owner)?,
+            spender_account_id: utils::optional_account_id(services.spender)?,
+            amount: Hbar::from_tinybar(services.amount),
+        })
+    }
+}
+
+impl ToProto<services::CryptoAllowance> for HbarAllowance {
+    fn to_proto(&self) -> Result<services::CryptoAllowance, HederaError> {
+        Ok(services::CryptoAllowance {
+            owner: self
+                .owner_account_id
+                .as_ref()
+                .map(|x| x.to_proto())
+                .transpose()?,
+            spender: self
+                .spender_account_id
+                .as_ref()
+                .map(|x| x.to_proto())
+                .transpose()?,
+            amount: self.amount.as_tinybar(),
+        })
+    }
+}
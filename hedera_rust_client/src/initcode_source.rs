@@ -10,6 +10,15 @@ pub enum InitcodeSource {
     FileId(FileId),
     Initcode(Vec<u8>),
 }
+impl From<ProtoInitcodeSource> for InitcodeSource {
+    fn from(services: ProtoInitcodeSource) -> InitcodeSource {
+        match services {
+            ProtoInitcodeSource::FileId(id) => InitcodeSource::FileId(FileId::from(id)),
+            ProtoInitcodeSource::Initcode(code) => InitcodeSource::Initcode(code),
+        }
+    }
+}
+
 impl ToProto<ProtoInitcodeSource> for InitcodeSource {
     fn to_proto(&self) -> Result<ProtoInitcodeSource, HederaError> {
         let pb = match &self {
@@ -0,0 +1,117 @@
+//! `serialize_with` helpers for the JSON representation of transactions,
+//! receipts and records. Durations are written in seconds, bytes in hex, and
+//! maps in key order so the same value always renders the same.
+use chrono::{DateTime, Duration, Utc};
+use serde::ser::Error;
+use serde::{Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+
+use crate::proto::services;
+use crate::token_transfer_list::AccountIdTokenTransferHashMap;
+use crate::transaction_body::TransactionBody;
+use crate::AccountId;
+use crate::Hbar;
+
+pub(crate) fn option_duration<S: Serializer>(
+    duration: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match duration {
+        Some(duration) => serializer.serialize_some(&duration.num_seconds()),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub(crate) fn hex<T: AsRef<[u8]>, S: Serializer>(
+    bytes: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&hex::encode(bytes))
+}
+
+pub(crate) fn hex_vec<S: Serializer>(bytes: &[Vec<u8>], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(bytes.iter().map(hex::encode))
+}
+
+pub(crate) fn option_timestamp<S: Serializer>(
+    timestamp: &Option<services::Timestamp>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match timestamp {
+        Some(timestamp) => {
+            let timestamp =
+                DateTime::<Utc>::try_from(timestamp.clone()).map_err(S::Error::custom)?;
+            serializer.serialize_some(&timestamp)
+        }
+        None => serializer.serialize_none(),
+    }
+}
+
+pub(crate) fn sorted_map<K, V, S>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    K: Ord + Serialize,
+    V: Serialize,
+    S: Serializer,
+{
+    serializer.collect_map(map.iter().collect::<BTreeMap<_, _>>())
+}
+
+/// Writes the values of a map whose values carry their key, e.g. transfers
+/// by account, as a list in key order.
+pub(crate) fn sorted_values<K, V, S>(map: &HashMap<K, V>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    K: Ord,
+    V: Serialize,
+    S: Serializer,
+{
+    serializer.collect_seq(map.iter().collect::<BTreeMap<_, _>>().values())
+}
+
+#[derive(Serialize)]
+struct HbarTransfer {
+    account_id: AccountId,
+    amount: Hbar,
+    is_approval: bool,
+}
+
+/// Writes hbar transfers, kept as `TokenTransfer`s of tinybar, in hbar.
+pub(crate) fn hbar_transfers<S: Serializer>(
+    transfers: &AccountIdTokenTransferHashMap,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(
+        transfers
+            .iter()
+            .collect::<BTreeMap<_, _>>()
+            .values()
+            .map(|transfer| HbarTransfer {
+                account_id: transfer.account_id,
+                amount: Hbar::from_tinybar(transfer.amount),
+                is_approval: transfer.is_approval,
+            }),
+    )
+}
+
+pub(crate) fn option_hbar_transfers<S: Serializer>(
+    transfers: &Option<AccountIdTokenTransferHashMap>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match transfers {
+        Some(transfers) => hbar_transfers(transfers, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub(crate) fn option_schedulable_transaction_body<S: Serializer>(
+    body: &Option<services::SchedulableTransactionBody>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match body {
+        Some(body) => {
+            let body = TransactionBody::try_from(body.clone()).map_err(S::Error::custom)?;
+            serializer.serialize_some(&body)
+        }
+        None => serializer.serialize_none(),
+    }
+}
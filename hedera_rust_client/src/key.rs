@@ -1,5 +1,8 @@
+use serde::Serialize;
+use std::collections::HashSet;
 use std::convert::TryFrom;
 
+use crate::crypto::KeyAlgorithm;
 use crate::error::HederaError;
 use crate::key_list::KeyList;
 use crate::proto::{services, ToProto};
@@ -7,19 +10,106 @@ use crate::ContractId;
 use crate::PrivateKey;
 use crate::PublicKey;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Key {
     Ed25519(PublicKey),
+    EcdsaSecp256k1(PublicKey),
     KeyList(KeyList),
     ThresholdKey(KeyList),
     ContractId(ContractId),
     DelegatableContractId(ContractId),
 }
 
+/// How far a set of signers is from satisfying a `Key`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyRequirement {
+    Satisfied,
+    /// The fewest further signers found that would satisfy the key.
+    Missing(Vec<PublicKey>),
+    /// No signatures can satisfy the key, e.g. a contract key.
+    Unsatisfiable,
+}
+
+impl KeyRequirement {
+    pub fn is_satisfied(&self) -> bool {
+        *self == KeyRequirement::Satisfied
+    }
+
+    fn from_missing(missing: Option<Vec<PublicKey>>) -> KeyRequirement {
+        match missing {
+            Some(missing) if missing.is_empty() => KeyRequirement::Satisfied,
+            Some(missing) => KeyRequirement::Missing(missing),
+            None => KeyRequirement::Unsatisfiable,
+        }
+    }
+}
+
+impl Key {
+    /// Whether signatures by `signers` satisfy the key. A key list needs all
+    /// of its keys, a threshold key `threshold` of them, and contract keys
+    /// are never satisfied by signatures.
+    pub fn is_satisfied_by(&self, signers: &HashSet<PublicKey>) -> bool {
+        self.requirement(signers).is_satisfied()
+    }
+
+    /// The signers still missing for `signers` to satisfy the key. Threshold
+    /// keys are met with the keys needing the fewest signers.
+    pub fn requirement(&self, signers: &HashSet<PublicKey>) -> KeyRequirement {
+        KeyRequirement::from_missing(self.missing_signers(signers))
+    }
+
+    // `None` if signatures can't satisfy the key
+    fn missing_signers(&self, signers: &HashSet<PublicKey>) -> Option<Vec<PublicKey>> {
+        match self {
+            Key::Ed25519(key) | Key::EcdsaSecp256k1(key) => match signers.contains(key) {
+                true => Some(Vec::new()),
+                false => Some(vec![*key]),
+            },
+            Key::KeyList(list) => {
+                if list.is_empty() {
+                    return None;
+                }
+                let mut missing = Vec::new();
+                for key in list.keys() {
+                    extend_unique(&mut missing, key.missing_signers(signers)?);
+                }
+                Some(missing)
+            }
+            Key::ThresholdKey(list) => {
+                let threshold = list.threshold().unwrap_or(list.keys().len() as u32) as usize;
+                let mut options = list
+                    .keys()
+                    .iter()
+                    .filter_map(|key| key.missing_signers(signers))
+                    .collect::<Vec<Vec<PublicKey>>>();
+                if options.len() < threshold {
+                    return None;
+                }
+                options.sort_by_key(|missing| missing.len());
+                let mut missing = Vec::new();
+                for option in options.into_iter().take(threshold) {
+                    extend_unique(&mut missing, option);
+                }
+                Some(missing)
+            }
+            Key::ContractId(_) | Key::DelegatableContractId(_) => None,
+        }
+    }
+}
+
+fn extend_unique(keys: &mut Vec<PublicKey>, other: Vec<PublicKey>) {
+    for key in other {
+        if !keys.contains(&key) {
+            keys.push(key);
+        }
+    }
+}
+
 impl ToProto<services::Key> for Key {
     fn to_proto(&self) -> Result<services::Key, HederaError> {
         let key = match &*self {
-            Key::Ed25519(key) => key.to_proto()?,
+            Key::Ed25519(key) | Key::EcdsaSecp256k1(key) => key.to_proto()?,
             Key::KeyList(key_list) => key_list.to_proto_key_list_key()?,
             Key::ThresholdKey(key_list) => key_list.to_proto_threshold_key()?,
             Key::ContractId(id) => services::key::Key::ContractId(id.to_proto()?),
@@ -40,6 +130,9 @@ impl TryFrom<services::Key> for Key {
                     services::key::Key::Ed25519(bytes) => {
                         Key::Ed25519(PublicKey::from_hex_bytes(bytes)?)
                     }
+                    services::key::Key::EcdsaSecp256k1(bytes) => {
+                        Key::EcdsaSecp256k1(PublicKey::from_bytes_ecdsa(bytes)?)
+                    }
                     services::key::Key::ThresholdKey(key) => {
                         Key::ThresholdKey(KeyList::try_from(key)?)
                     }
@@ -59,12 +152,63 @@ impl TryFrom<services::Key> for Key {
 
 impl From<PrivateKey> for Key {
     fn from(pk: PrivateKey) -> Key {
-        Key::Ed25519(pk.public())
+        Key::from(pk.public())
     }
 }
 
 impl From<PublicKey> for Key {
     fn from(pk: PublicKey) -> Key {
-        Key::Ed25519(pk)
+        match pk.algorithm() {
+            KeyAlgorithm::Ed25519 => Key::Ed25519(pk),
+            KeyAlgorithm::EcdsaSecp256k1 => Key::EcdsaSecp256k1(pk),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signers(keys: &[&PublicKey]) -> HashSet<PublicKey> {
+        keys.iter().map(|key| **key).collect()
+    }
+
+    #[test]
+    fn test_key_requirement_nested_threshold() {
+        let keys = (0..5)
+            .map(|_| PrivateKey::new().public())
+            .collect::<Vec<_>>();
+        // 2 of [a, b, all of (c, d)], and e
+        let mut inner = KeyList::new(None);
+        inner.add_all_public_keys(vec![keys[2], keys[3]]);
+        let mut threshold = KeyList::with_threshold(2);
+        threshold.add(Key::from(keys[0]));
+        threshold.add(Key::from(keys[1]));
+        threshold.add(Key::KeyList(inner));
+        let key = Key::KeyList(KeyList::of(vec![
+            Key::ThresholdKey(threshold),
+            Key::from(keys[4]),
+        ]));
+
+        assert_eq!(
+            key.requirement(&signers(&[])),
+            KeyRequirement::Missing(vec![keys[0], keys[1], keys[4]])
+        );
+        assert_eq!(
+            key.requirement(&signers(&[&keys[2], &keys[3], &keys[4]])),
+            KeyRequirement::Missing(vec![keys[0]])
+        );
+        assert!(key.is_satisfied_by(&signers(&[&keys[2], &keys[3], &keys[1], &keys[4]])));
+        assert!(!key.is_satisfied_by(&signers(&[&keys[0], &keys[1]])));
+
+        let contract = Key::ContractId(ContractId::simple(5));
+        assert_eq!(
+            contract.requirement(&signers(&keys.iter().collect::<Vec<_>>())),
+            KeyRequirement::Unsatisfiable
+        );
+        assert_eq!(
+            Key::KeyList(KeyList::new(None)).requirement(&signers(&[])),
+            KeyRequirement::Unsatisfiable
+        );
     }
 }
@@ -1,3 +1,4 @@
+use serde::Serialize;
 use std::convert::TryFrom;
 
 use crate::error::HederaError;
@@ -5,8 +6,9 @@ use crate::key::Key;
 use crate::proto::{services, ToProto};
 use crate::PublicKey;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
 pub struct KeyList {
+    #[serde(skip_serializing_if = "Option::is_none")]
     threshold: Option<u32>,
     keys: Vec<Key>,
 }
@@ -38,6 +40,10 @@ impl KeyList {
         self.threshold = Some(threshold);
     }
 
+    pub fn keys(&self) -> &[Key] {
+        &self.keys
+    }
+
     pub fn is_empty(&self) -> bool {
         self.keys.is_empty()
     }
@@ -58,7 +64,7 @@ impl KeyList {
 
     pub fn add_all_public_keys(&mut self, keys: Vec<PublicKey>) {
         for public_key in keys.into_iter() {
-            self.add(Key::Ed25519(public_key));
+            self.add(Key::from(public_key));
         }
     }
 
@@ -14,37 +14,31 @@ mod macros;
 mod account_info;
 mod channel;
 mod contract_function_parameters;
-mod contract_function_result;
 mod contract_function_selector;
 mod contract_info;
-mod contract_log_info;
 mod duration;
 mod endpoint;
 mod executor;
 mod fee_assessment_method;
-mod fee_components;
-mod fee_data;
-mod fee_schedule;
-mod fee_schedules;
 mod file_info;
 mod ipv4_address;
 mod ipv4_address_part;
-mod key_list;
+mod json;
 mod managed_network;
 mod managed_node;
 mod managed_node_address;
 mod mirror_node;
 mod network_version_info;
 mod node;
-mod node_address;
-mod node_address_book;
 mod proto;
 mod query;
 mod query_header;
 mod query_response;
 mod response_type;
+mod rlp;
 mod schedule_info;
 mod signed_transaction;
+mod system_file_cache;
 mod timestamp;
 mod token_info;
 mod token_relationship;
@@ -54,7 +48,6 @@ mod topic_message;
 mod topic_message_chunk;
 mod transaction;
 mod transaction_body;
-mod transaction_fee_schedule;
 mod transfer;
 mod utils;
 
@@ -79,6 +72,9 @@ pub use self::token_freeze_status::TokenFreezeStatus;
 mod token_kyc_status;
 pub use self::token_kyc_status::TokenKycStatus;
 
+mod token_pause_status;
+pub use self::token_pause_status::TokenPauseStatus;
+
 mod status;
 pub use self::status::Status;
 
@@ -86,7 +82,10 @@ mod memo;
 pub use self::memo::check_memo_length;
 
 mod key;
-pub use self::key::Key;
+pub use self::key::{Key, KeyRequirement};
+
+mod key_list;
+pub use self::key_list::KeyList;
 
 mod network_name;
 pub use self::network_name::NetworkName;
@@ -97,11 +96,78 @@ pub use self::network::Network;
 mod mirror_network;
 pub use self::mirror_network::MirrorNetwork;
 
+mod node_address;
+pub use self::node_address::NodeAddress;
+
+mod node_address_book;
+pub use self::node_address_book::NodeAddressBook;
+
+mod node_address_book_query;
+pub use self::node_address_book_query::NodeAddressBookQuery;
+
+// Execute
+mod execute_middleware;
+pub use self::execute_middleware::{ExecuteContext, ExecuteMiddleware};
+pub use self::executor::{ProtoRequest, Response as ProtoResponse};
+
+mod retry_policy;
+pub use self::retry_policy::{
+    DeadlineRetryPolicy, DefaultRetryPolicy, ExponentialJitterRetryPolicy, RetryContext,
+    RetryPolicy,
+};
+
+// Testing
+#[cfg(feature = "testing")]
+pub mod testing;
+
+// Record stream
+mod record_stream;
+pub use self::record_stream::{
+    RecordFile, RecordSignatureFile, RecordStreamItem, SidecarMetadata, SidecarType,
+};
+
+mod transaction_sidecar_record;
+pub use self::transaction_sidecar_record::{
+    ContractAction, ContractActionCaller, ContractActionRecipient, ContractActionResult,
+    ContractActionType, ContractBytecode, ContractStateChange, SidecarRecordData, StorageChange,
+    TransactionSidecarRecord,
+};
+
+mod mirror_rest_client;
+pub use self::mirror_rest_client::{MirrorRestClient, MirrorRestOrder, MirrorRestQuery};
+
+mod mirror_rest_response;
+pub use self::mirror_rest_response::{
+    MirrorContractResult, MirrorHbarTransfer, MirrorNftTransfer, MirrorTokenBalance,
+    MirrorTokenTransfer, MirrorTransaction,
+};
+
 mod account_balance;
 pub use self::account_balance::AccountBalance;
 
 mod exchange_rate;
-pub use self::exchange_rate::ExchangeRate;
+pub use self::exchange_rate::{ExchangeRate, ExchangeRateSet};
+
+mod fee_components;
+pub use self::fee_components::FeeComponents;
+
+mod fee_data;
+pub use self::fee_data::{FeeData, FeeDataType};
+
+mod fee_schedule;
+pub use self::fee_schedule::FeeSchedule;
+
+mod fee_schedules;
+pub use self::fee_schedules::FeeSchedules;
+
+mod transaction_fee_schedule;
+pub use self::transaction_fee_schedule::{HederaFunctionality, TransactionFeeSchedule};
+
+mod fee_estimator;
+pub use self::fee_estimator::{FeeEstimate, FeeEstimator};
+
+mod throttle_definitions;
+pub use self::throttle_definitions::{ThrottleBucket, ThrottleDefinitions, ThrottleGroup};
 
 mod client;
 pub use self::client::{
@@ -110,7 +176,16 @@ pub use self::client::{
 };
 
 mod crypto;
-pub use self::crypto::{Asn1Error, PrivateKey, PublicKey, Signature};
+pub use self::crypto::{Asn1Error, KeyAlgorithm, PrivateKey, PublicKey, Signature};
+
+mod signer;
+pub use self::signer::{LocalSigner, Signer};
+
+mod remote_signer;
+pub use self::remote_signer::RemoteSigner;
+
+// for implementing `Signer`
+pub use async_trait::async_trait;
 
 mod live_hash;
 pub use self::live_hash::LiveHash;
@@ -139,6 +214,29 @@ pub use self::file_id::FileId;
 mod contract_id;
 pub use self::contract_id::ContractId;
 
+mod abi_type;
+pub use self::abi_type::AbiType;
+
+mod abi_value;
+pub use self::abi_value::{abi_decode, abi_encode, AbiValue};
+pub use hedera_rust_client_derive::hedera_contract;
+pub use num::bigint::{BigInt, BigUint};
+
+mod contract_abi;
+pub use self::contract_abi::{
+    AbiConstructor, AbiError, AbiEvent, AbiFunction, AbiParam, ContractAbi, DecodedEvent,
+    StateMutability,
+};
+
+mod contract_function_result;
+pub use self::contract_function_result::ContractFunctionResult;
+
+mod contract_log_info;
+pub use self::contract_log_info::ContractLogInfo;
+
+mod contract_revert;
+pub use self::contract_revert::ContractRevert;
+
 mod token_association;
 pub use self::token_association::TokenAssociation;
 
@@ -157,6 +255,15 @@ pub use self::token_supply_type::TokenSupplyType;
 mod transaction_id;
 pub use self::transaction_id::TransactionId;
 
+mod serialized_transaction;
+pub use self::serialized_transaction::SerializedTransaction;
+
+mod any_transaction;
+pub use self::any_transaction::AnyTransaction;
+
+mod transaction_description;
+pub use self::transaction_description::{RequiredKey, TransactionDescription};
+
 mod transaction_response;
 pub use self::transaction_response::TransactionResponse;
 
@@ -199,11 +306,26 @@ pub use self::token_nft_info::TokenNftInfo;
 mod initcode_source;
 pub use self::initcode_source::InitcodeSource;
 
+mod hbar_allowance;
+pub use self::hbar_allowance::HbarAllowance;
+
+mod token_allowance;
+pub use self::token_allowance::TokenAllowance;
+
+mod token_nft_allowance;
+pub use self::token_nft_allowance::TokenNftAllowance;
+
 ////////////////////////////////////////////////////////////////////////////////
 // Transactions
 ////////////////////////////////////////////////////////////////////////////////
 
 // Account
+mod account_allowance_approve_transaction;
+pub use self::account_allowance_approve_transaction::AccountAllowanceApproveTransaction;
+
+mod account_allowance_delete_transaction;
+pub use self::account_allowance_delete_transaction::AccountAllowanceDeleteTransaction;
+
 mod account_create_transaction;
 pub use self::account_create_transaction::AccountCreateTransaction;
 
@@ -226,6 +348,13 @@ pub use self::contract_execute_transaction::ContractExecuteTransaction;
 mod contract_update_transaction;
 pub use self::contract_update_transaction::ContractUpdateTransaction;
 
+// Ethereum
+mod ethereum_transaction;
+pub use self::ethereum_transaction::EthereumTransaction;
+
+mod ethereum_flow;
+pub use self::ethereum_flow::EthereumFlow;
+
 // File
 mod file_append_transaction;
 pub use self::file_append_transaction::FileAppendTransaction;
@@ -246,6 +375,10 @@ pub use self::live_hash_add_transaction::LiveHashAddTransaction;
 mod live_hash_delete_transaction;
 pub use self::live_hash_delete_transaction::LiveHashDeleteTransaction;
 
+// Prng
+mod prng_transaction;
+pub use self::prng_transaction::PrngTransaction;
+
 // Schedule
 mod schedule_create_transaction;
 pub use self::schedule_create_transaction::ScheduleCreateTransaction;
@@ -284,12 +417,18 @@ pub use self::token_grant_kyc_transaction::TokenGrantKycTransaction;
 mod token_mint_transaction;
 pub use self::token_mint_transaction::TokenMintTransaction;
 
+mod token_pause_transaction;
+pub use self::token_pause_transaction::TokenPauseTransaction;
+
 mod token_revoke_kyc_transaction;
 pub use self::token_revoke_kyc_transaction::TokenRevokeKycTransaction;
 
 mod token_unfreeze_transaction;
 pub use self::token_unfreeze_transaction::TokenUnfreezeTransaction;
 
+mod token_unpause_transaction;
+pub use self::token_unpause_transaction::TokenUnpauseTransaction;
+
 mod token_update_transaction;
 pub use self::token_update_transaction::TokenUpdateTransaction;
 
@@ -380,7 +519,14 @@ mod topic_info_query;
 pub use self::topic_info_query::TopicInfoQuery;
 
 mod topic_message_query;
-pub use self::topic_message_query::TopicMessageQuery;
+pub use self::topic_message::TopicMessage;
+pub use self::topic_message_chunk::TopicMessageChunk;
+pub use self::topic_message_query::{SubscriptionHandle, TopicMessageQuery, TopicMessageStream};
+mod topic_consumer;
+pub use self::topic_consumer::{
+    Checkpoint, CheckpointStore, FileCheckpointStore, InMemoryCheckpointStore, ProcessingMode,
+    TopicConsumer, TopicMessageHandler,
+};
 
 // Transaction Receipt
 mod transaction_receipt_query;
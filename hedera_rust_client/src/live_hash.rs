@@ -1,4 +1,5 @@
 use chrono::Duration;
+use serde::Serialize;
 use std::convert::TryFrom;
 
 use crate::error::HederaError;
@@ -7,15 +8,17 @@ use crate::proto::{services, ToProto};
 use crate::utils;
 use crate::AccountId;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct LiveHash {
     /// The account to which the livehash is attached
     pub account_id: AccountId,
     /// The SHA-384 hash of a credential or certificate
+    #[serde(serialize_with = "crate::json::hex")]
     pub hash: Vec<u8>,
     /// A list of keys (primitive or threshold), all of which must sign to attach the livehash to an account, and any one of which can later delete it.
     pub keys: Option<KeyList>,
     /// The duration for which the livehash will remain valid
+    #[serde(serialize_with = "crate::json::option_duration")]
     pub duration: Option<Duration>,
 }
 
@@ -1,4 +1,5 @@
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto};
+use serde::Serialize;
 
 use crate::transaction::Transaction;
 use crate::Client;
@@ -34,7 +35,7 @@ impl LiveHashAddTransaction {
     gen_transaction_live_hash_fns!();
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "CryptoAddLiveHash",
     proto_type = "CryptoAddLiveHashTransactionBody"
@@ -1,4 +1,5 @@
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto};
+use serde::Serialize;
 
 use crate::entity_id::validate_option_id_checksum;
 
@@ -40,7 +41,7 @@ impl LiveHashDeleteTransaction {
     gen_transaction_live_hash_to_delete_fns!();
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "CryptoDeleteLiveHash",
     proto_type = "CryptoDeleteLiveHashTransactionBody"
@@ -48,6 +49,7 @@ impl LiveHashDeleteTransaction {
 struct Proto {
     #[hedera_rust_client_derive(to_option_proto)]
     pub account_of_live_hash: Option<AccountId>,
+    #[serde(serialize_with = "crate::json::hex")]
     pub live_hash_to_delete: Vec<u8>,
 }
 
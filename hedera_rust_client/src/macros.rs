@@ -53,6 +53,24 @@ macro_rules! non_optional_from {
 //     };
 // }
 
+// serializes an id through its `Display` form, e.g. `0.0.3`, and parses it back
+macro_rules! serde_from_str {
+    ($type:ty) => {
+        impl serde::Serialize for $type {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $type {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let s = <String as serde::Deserialize>::deserialize(deserializer)?;
+                s.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
 ////////////////////////////////////////////////////////////////////////////////
 // Common Get-Set
 ////////////////////////////////////////////////////////////////////////////////
@@ -14,7 +14,7 @@ use crate::channel::Channel;
 use crate::ledger_id::LedgerId;
 use crate::managed_node::ManagedNode;
 use crate::managed_node_address::ManagedNodeAddress;
-use crate::mirror_node::{ConsensusServiceClientChannel, MirrorNode};
+use crate::mirror_node::{ConsensusServiceClientChannel, MirrorNode, NetworkServiceClientChannel};
 use crate::node::Node;
 use crate::node_address::NodeAddress;
 
@@ -70,6 +70,12 @@ impl NetworkNode {
             NetworkNode::Mirror(n) => n.managed_node.max_backoff,
         }
     }
+    pub fn set_connect_timeout(&mut self, timeout: Option<Duration>) {
+        match self {
+            NetworkNode::Node(n) => n.set_connect_timeout(timeout),
+            NetworkNode::Mirror(_) => {}
+        }
+    }
     pub fn in_use(&mut self) {
         match self {
             NetworkNode::Node(n) => n.in_use(),
@@ -187,6 +193,12 @@ impl NetworkNode {
             NetworkNode::Mirror(n) => n.channel(),
         }
     }
+    pub fn mirror_network_channel(&mut self) -> Result<NetworkServiceClientChannel, HederaError> {
+        match self {
+            NetworkNode::Node(_) => Err(HederaError::InvalidNodeType),
+            NetworkNode::Mirror(n) => n.network_channel(),
+        }
+    }
 }
 
 pub type ArcNetworkNode = Arc<RwLock<NetworkNode>>;
@@ -204,6 +216,7 @@ pub struct ManagedNetwork {
     pub ledger_id: LedgerId,
     pub transport_security: bool,
     pub verify_certificate: bool,
+    pub connect_timeout: Option<Duration>,
 }
 
 impl ManagedNetwork {
@@ -221,6 +234,7 @@ impl ManagedNetwork {
             max_nodes_per_transaction: None,
             transport_security: false,
             verify_certificate: false,
+            connect_timeout: None,
         }
     }
 
@@ -280,6 +294,7 @@ impl ManagedNetwork {
         for mut f_node in from_nodes.into_iter() {
             f_node.set_min_backoff(self.min_backoff);
             f_node.set_max_backoff(self.max_backoff);
+            f_node.set_connect_timeout(self.connect_timeout);
             let address = f_node.address();
             let key = f_node.key();
             let node = Arc::new(RwLock::new(f_node));
@@ -344,8 +359,17 @@ impl ManagedNetwork {
         }
     }
 
+    pub async fn set_connect_timeout(&mut self, timeout: Option<Duration>) {
+        let nodes = self.nodes.read().await;
+        for node in nodes.iter() {
+            let mut n_w = node.write().await;
+            n_w.set_connect_timeout(timeout);
+        }
+        self.connect_timeout = timeout;
+    }
+
     pub async fn set_nodes_address_book(
-        &mut self,
+        &self,
         address_book: &HashMap<AccountId, NodeAddress>,
     ) -> Result<(), HederaError> {
         let nodes = self.nodes.read().await;
@@ -405,6 +429,19 @@ impl ManagedNetwork {
         return_nodes
     }
 
+    /// The healthiest node, the least used first.
+    pub async fn most_healthy_node(&self) -> Option<ArcNetworkNode> {
+        let nodes = self.nodes.read().await;
+        let mut healthiest: Option<(ManagedNodeHealth, &ArcNetworkNode)> = None;
+        for (i, node) in enumerate(&*nodes) {
+            let health = node.read().await.health(i);
+            if healthiest.as_ref().map_or(true, |(best, _)| health < *best) {
+                healthiest = Some((health, node));
+            }
+        }
+        healthiest.map(|(_, node)| node.clone())
+    }
+
     pub async fn node_for_execute(
         &self,
         node_account_id: &AccountId,
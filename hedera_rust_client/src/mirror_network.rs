@@ -61,10 +61,9 @@ impl MirrorNetwork {
     }
 
     pub async fn get_next_mirror_node(&self) -> Result<ArcNetworkNode, HederaError> {
-        let n = self.network.number_of_most_heathy_nodes(1).await;
-        if n.is_empty() {
-            return Err(HederaError::NoNode);
-        }
-        Ok(n[0].clone())
+        self.network
+            .most_healthy_node()
+            .await
+            .ok_or(HederaError::NoNode)
     }
 }
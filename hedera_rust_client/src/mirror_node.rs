@@ -5,13 +5,16 @@ use crate::channel::Channel;
 use crate::error::HederaError;
 use crate::managed_node::ManagedNode;
 use crate::proto::mirror::consensus_service_client::ConsensusServiceClient;
+use crate::proto::mirror::network_service_client::NetworkServiceClient;
 
 pub type ConsensusServiceClientChannel = ConsensusServiceClient<TonicChannel>;
+pub type NetworkServiceClientChannel = NetworkServiceClient<TonicChannel>;
 
 #[derive(Debug, Clone)]
 pub struct MirrorNode {
     pub managed_node: ManagedNode,
     pub channel: Option<ConsensusServiceClientChannel>,
+    pub network_channel: Option<NetworkServiceClientChannel>,
 }
 
 impl MirrorNode {
@@ -19,6 +22,7 @@ impl MirrorNode {
         MirrorNode {
             managed_node: ManagedNode::new(address, min_backoff),
             channel: None,
+            network_channel: None,
         }
     }
 
@@ -26,12 +30,22 @@ impl MirrorNode {
         if let Some(channel) = &self.channel {
             return Ok(channel.clone());
         }
-        let tonic_channel = Channel::tonic_channel(&self.managed_node.address.to_string())?;
+        let tonic_channel = Channel::tonic_channel(&self.managed_node.address.to_string(), None)?;
         let channel = ConsensusServiceClient::new(tonic_channel);
         self.channel = Some(channel.clone());
         Ok(channel)
     }
 
+    pub fn network_channel(&mut self) -> Result<NetworkServiceClientChannel, HederaError> {
+        if let Some(channel) = &self.network_channel {
+            return Ok(channel.clone());
+        }
+        let tonic_channel = Channel::tonic_channel(&self.managed_node.address.to_string(), None)?;
+        let channel = NetworkServiceClient::new(tonic_channel);
+        self.network_channel = Some(channel.clone());
+        Ok(channel)
+    }
+
     pub fn in_use(&mut self) {
         self.managed_node.in_use();
     }
@@ -56,6 +70,9 @@ impl MirrorNode {
         if self.channel.is_some() {
             self.channel = None;
         }
+        if self.network_channel.is_some() {
+            self.network_channel = None;
+        }
     }
 
     pub fn to_secure(&mut self) {
@@ -0,0 +1,428 @@
+use chrono::{DateTime, Utc};
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::HederaError;
+use crate::ledger_id::LedgerId;
+use crate::mirror_rest_response::{
+    format_transaction_id, nft_json_to_token_nft_info, AccountTokensPage, BalancesPage,
+    ContractResultsPage, MirrorContractResult, MirrorRestPage, MirrorTokenBalance,
+    MirrorTransaction, NftsPage, TransactionsPage,
+};
+use crate::network_name::NetworkName;
+use crate::AccountId;
+use crate::ContractId;
+use crate::TokenId;
+use crate::TokenNftInfo;
+use crate::TransactionId;
+
+pub fn mirror_rest_mainnet_url() -> String {
+    "https://mainnet-public.mirrornode.hedera.com".to_string()
+}
+
+pub fn mirror_rest_testnet_url() -> String {
+    "https://testnet.mirrornode.hedera.com".to_string()
+}
+
+pub fn mirror_rest_previewnet_url() -> String {
+    "https://previewnet.mirrornode.hedera.com".to_string()
+}
+
+const API_PREFIX: &str = "/api/v1";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MirrorRestOrder {
+    Asc,
+    Desc,
+}
+
+impl fmt::Display for MirrorRestOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MirrorRestOrder::Asc => write!(f, "asc"),
+            MirrorRestOrder::Desc => write!(f, "desc"),
+        }
+    }
+}
+
+/// Filters and paging options shared by the list endpoints
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MirrorRestQuery {
+    limit: Option<u32>,
+    order: Option<MirrorRestOrder>,
+    max_items: Option<usize>,
+    params: Vec<(String, String)>,
+}
+
+impl MirrorRestQuery {
+    pub fn new() -> MirrorRestQuery {
+        MirrorRestQuery::default()
+    }
+
+    /// Page size requested from the mirror node
+    pub fn set_limit(&mut self, limit: u32) -> &mut Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn set_order(&mut self, order: MirrorRestOrder) -> &mut Self {
+        self.order = Some(order);
+        self
+    }
+
+    /// Stop following `links.next` once this many items have been collected
+    pub fn set_max_items(&mut self, max_items: usize) -> &mut Self {
+        self.max_items = Some(max_items);
+        self
+    }
+
+    pub fn set_timestamp_gte(&mut self, timestamp: DateTime<Utc>) -> &mut Self {
+        self.add_timestamp_param("gte", timestamp)
+    }
+
+    pub fn set_timestamp_gt(&mut self, timestamp: DateTime<Utc>) -> &mut Self {
+        self.add_timestamp_param("gt", timestamp)
+    }
+
+    pub fn set_timestamp_lte(&mut self, timestamp: DateTime<Utc>) -> &mut Self {
+        self.add_timestamp_param("lte", timestamp)
+    }
+
+    pub fn set_timestamp_lt(&mut self, timestamp: DateTime<Utc>) -> &mut Self {
+        self.add_timestamp_param("lt", timestamp)
+    }
+
+    /// Adds a raw query parameter, e.g. `("transactiontype", "CRYPTOTRANSFER")`
+    pub fn add_param(&mut self, key: &str, value: &str) -> &mut Self {
+        self.params.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    fn add_timestamp_param(&mut self, op: &str, timestamp: DateTime<Utc>) -> &mut Self {
+        let value = format!(
+            "{}:{}.{:09}",
+            op,
+            timestamp.timestamp(),
+            timestamp.timestamp_subsec_nanos()
+        );
+        self.params.push(("timestamp".to_string(), value));
+        self
+    }
+
+    fn query_pairs(&self) -> Vec<(String, String)> {
+        let mut pairs = self.params.clone();
+        if let Some(limit) = self.limit {
+            pairs.push(("limit".to_string(), limit.to_string()));
+        }
+        if let Some(order) = self.order {
+            pairs.push(("order".to_string(), order.to_string()));
+        }
+        pairs
+    }
+}
+
+/// Typed client for the mirror node REST API. List endpoints follow
+/// `links.next` until the results are exhausted or `max_items` is reached.
+#[derive(Debug, Clone)]
+pub struct MirrorRestClient {
+    base_url: String,
+    ledger_id: LedgerId,
+    http: reqwest::Client,
+}
+
+impl MirrorRestClient {
+    pub fn new(base_url: &str) -> MirrorRestClient {
+        MirrorRestClient {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            ledger_id: LedgerId::from_network_name(NetworkName::Other),
+            http: reqwest::Client::new(),
+        }
+    }
+
+    pub fn for_network_name(network_name: &NetworkName) -> Result<Self, HederaError> {
+        let base_url = match network_name {
+            NetworkName::MainNet => mirror_rest_mainnet_url(),
+            NetworkName::TestNet => mirror_rest_testnet_url(),
+            NetworkName::PreviewNet => mirror_rest_previewnet_url(),
+            NetworkName::Other => return Err(HederaError::UnknownNetworkType("other".to_string())),
+        };
+        let mut client = Self::new(&base_url);
+        client.ledger_id = LedgerId::from_network_name(network_name.clone());
+        Ok(client)
+    }
+
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn ledger_id(&self) -> LedgerId {
+        self.ledger_id.clone()
+    }
+
+    pub fn set_ledger_id(&mut self, ledger_id: LedgerId) {
+        self.ledger_id = ledger_id;
+    }
+
+    pub async fn transaction(
+        &self,
+        transaction_id: &TransactionId,
+    ) -> Result<Vec<MirrorTransaction>, HederaError> {
+        let path = format!(
+            "{}/transactions/{}",
+            API_PREFIX,
+            format_transaction_id(transaction_id)?
+        );
+        self.get_all::<TransactionsPage>(&path, &MirrorRestQuery::new())
+            .await?
+            .into_iter()
+            .map(MirrorTransaction::try_from)
+            .collect()
+    }
+
+    /// Transactions across the network, typically narrowed with timestamp filters
+    pub async fn transactions(
+        &self,
+        query: &MirrorRestQuery,
+    ) -> Result<Vec<MirrorTransaction>, HederaError> {
+        let path = format!("{}/transactions", API_PREFIX);
+        self.get_all::<TransactionsPage>(&path, query)
+            .await?
+            .into_iter()
+            .map(MirrorTransaction::try_from)
+            .collect()
+    }
+
+    pub async fn account_transactions(
+        &self,
+        account_id: AccountId,
+        query: &MirrorRestQuery,
+    ) -> Result<Vec<MirrorTransaction>, HederaError> {
+        let mut query = query.clone();
+        query.add_param("account.id", &account_id.to_string());
+        self.transactions(&query).await
+    }
+
+    pub async fn account_token_balances(
+        &self,
+        account_id: AccountId,
+        query: &MirrorRestQuery,
+    ) -> Result<Vec<MirrorTokenBalance>, HederaError> {
+        let path = format!("{}/accounts/{}/tokens", API_PREFIX, account_id);
+        self.get_all::<AccountTokensPage>(&path, query)
+            .await?
+            .into_iter()
+            .map(|x| {
+                Ok(MirrorTokenBalance {
+                    account_id,
+                    token_id: TokenId::from_str(&x.token_id)?,
+                    balance: x.balance,
+                })
+            })
+            .collect()
+    }
+
+    pub async fn token_balances(
+        &self,
+        token_id: TokenId,
+        query: &MirrorRestQuery,
+    ) -> Result<Vec<MirrorTokenBalance>, HederaError> {
+        let path = format!("{}/tokens/{}/balances", API_PREFIX, token_id);
+        self.get_all::<BalancesPage>(&path, query)
+            .await?
+            .into_iter()
+            .map(|x| {
+                Ok(MirrorTokenBalance {
+                    account_id: AccountId::from_str(&x.account)?,
+                    token_id,
+                    balance: x.balance,
+                })
+            })
+            .collect()
+    }
+
+    pub async fn account_nfts(
+        &self,
+        account_id: AccountId,
+        query: &MirrorRestQuery,
+    ) -> Result<Vec<TokenNftInfo>, HederaError> {
+        let path = format!("{}/accounts/{}/nfts", API_PREFIX, account_id);
+        self.get_nfts(&path, query).await
+    }
+
+    pub async fn token_nfts(
+        &self,
+        token_id: TokenId,
+        query: &MirrorRestQuery,
+    ) -> Result<Vec<TokenNftInfo>, HederaError> {
+        let path = format!("{}/tokens/{}/nfts", API_PREFIX, token_id);
+        self.get_nfts(&path, query).await
+    }
+
+    pub async fn contract_results(
+        &self,
+        contract_id: ContractId,
+        query: &MirrorRestQuery,
+    ) -> Result<Vec<MirrorContractResult>, HederaError> {
+        let path = format!("{}/contracts/{}/results", API_PREFIX, contract_id);
+        self.get_all::<ContractResultsPage>(&path, query)
+            .await?
+            .into_iter()
+            .map(MirrorContractResult::try_from)
+            .collect()
+    }
+
+    async fn get_nfts(
+        &self,
+        path: &str,
+        query: &MirrorRestQuery,
+    ) -> Result<Vec<TokenNftInfo>, HederaError> {
+        self.get_all::<NftsPage>(path, query)
+            .await?
+            .into_iter()
+            .filter(|x| !x.deleted)
+            .map(|x| nft_json_to_token_nft_info(x, &self.ledger_id))
+            .collect()
+    }
+
+    async fn get_all<P: MirrorRestPage>(
+        &self,
+        path: &str,
+        query: &MirrorRestQuery,
+    ) -> Result<Vec<P::Item>, HederaError> {
+        let mut items = Vec::new();
+        let mut page: P = self.get(path, &query.query_pairs()).await?;
+        loop {
+            let next = page.next();
+            items.extend(page.into_items());
+            if let Some(max_items) = query.max_items {
+                if items.len() >= max_items {
+                    items.truncate(max_items);
+                    break;
+                }
+            }
+            match next {
+                // links.next already carries the original filters
+                Some(next) => page = self.get(&next, &[]).await?,
+                None => break,
+            }
+        }
+        Ok(items)
+    }
+
+    async fn get<P: MirrorRestPage>(
+        &self,
+        path_and_query: &str,
+        params: &[(String, String)],
+    ) -> Result<P, HederaError> {
+        let url = format!("{}{}", self.base_url, path_and_query);
+        let res = self.http.get(&url).query(params).send().await?;
+        let status = res.status();
+        if !status.is_success() {
+            let body = res.text().await.unwrap_or_default();
+            return Err(HederaError::MirrorRestStatus(status.as_u16(), body));
+        }
+        Ok(res.json::<P>().await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    // serves one canned body per connection, in order
+    async fn stub_server(bodies: Vec<String>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            for body in bodies {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0u8; 4096];
+                let _ = socket.read(&mut buf).await.unwrap();
+                let res = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                socket.write_all(res.as_bytes()).await.unwrap();
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_follows_links_next() {
+        let first = r#"{
+            "balances": [{"account": "0.0.1001", "balance": 10, "decimals": 2}],
+            "links": {"next": "/api/v1/tokens/0.0.5000/balances?account.id=gt:0.0.1001"}
+        }"#;
+        let second = r#"{
+            "balances": [{"account": "0.0.1002", "balance": 20, "decimals": 2}],
+            "links": {"next": null}
+        }"#;
+        let url = stub_server(vec![first.to_string(), second.to_string()]).await;
+        let client = MirrorRestClient::new(&url);
+
+        let token_id = TokenId::simple(5000);
+        let balances = client
+            .token_balances(token_id, &MirrorRestQuery::new())
+            .await
+            .unwrap();
+        assert_eq!(
+            balances,
+            vec![
+                MirrorTokenBalance {
+                    account_id: AccountId::simple(1001),
+                    token_id,
+                    balance: 10
+                },
+                MirrorTokenBalance {
+                    account_id: AccountId::simple(1002),
+                    token_id,
+                    balance: 20
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transactions() {
+        let body = r#"{
+            "transactions": [{
+                "consensus_timestamp": "1650000000.000000002",
+                "transaction_id": "0.0.2-1649999999-000000001",
+                "transaction_hash": "AQID",
+                "name": "CRYPTOTRANSFER",
+                "result": "SUCCESS",
+                "charged_tx_fee": 84000,
+                "memo_base64": "aGk=",
+                "node": "0.0.3",
+                "transfers": [
+                    {"account": "0.0.2", "amount": -100, "is_approval": false},
+                    {"account": "0.0.1001", "amount": 100, "is_approval": false}
+                ]
+            }],
+            "links": {"next": null}
+        }"#;
+        let url = stub_server(vec![body.to_string()]).await;
+        let client = MirrorRestClient::new(&url);
+
+        let mut query = MirrorRestQuery::new();
+        query.set_limit(1).set_max_items(1);
+        let transactions = client.transactions(&query).await.unwrap();
+        assert_eq!(transactions.len(), 1);
+
+        let tx = &transactions[0];
+        assert_eq!(tx.transaction_id.account_id, Some(AccountId::simple(2)));
+        assert_eq!(tx.transaction_hash, vec![1, 2, 3]);
+        assert_eq!(tx.memo, b"hi".to_vec());
+        assert_eq!(tx.node, Some(AccountId::simple(3)));
+        assert_eq!(tx.transfers[1].amount.as_tinybar(), 100);
+        assert_eq!(
+            format_transaction_id(&tx.transaction_id).unwrap(),
+            "0.0.2-1649999999-000000001"
+        );
+    }
+}
@@ -0,0 +1,389 @@
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+use crate::error::HederaError;
+use crate::ledger_id::LedgerId;
+use crate::timestamp::HederaTimestamp;
+use crate::AccountId;
+use crate::ContractId;
+use crate::Hbar;
+use crate::NftId;
+use crate::TokenId;
+use crate::TokenNftInfo;
+use crate::TransactionId;
+
+// Typed views of the mirror node REST responses. The raw `*Json` structs
+// mirror the wire format and are converted into crate domain types.
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub(crate) struct LinksJson {
+    pub next: Option<String>,
+}
+
+/// A single page of a paginated mirror node response
+pub(crate) trait MirrorRestPage: for<'de> Deserialize<'de> {
+    type Item;
+    fn next(&self) -> Option<String>;
+    fn into_items(self) -> Vec<Self::Item>;
+}
+
+macro_rules! gen_mirror_rest_page {
+    ($page:ident, $field:ident, $item:ty) => {
+        #[derive(Debug, Clone, PartialEq, Deserialize)]
+        pub(crate) struct $page {
+            #[serde(default)]
+            pub $field: Vec<$item>,
+            pub links: Option<LinksJson>,
+        }
+
+        impl MirrorRestPage for $page {
+            type Item = $item;
+
+            fn next(&self) -> Option<String> {
+                self.links.as_ref().and_then(|x| x.next.clone())
+            }
+
+            fn into_items(self) -> Vec<$item> {
+                self.$field
+            }
+        }
+    };
+}
+
+gen_mirror_rest_page!(TransactionsPage, transactions, TransactionJson);
+gen_mirror_rest_page!(BalancesPage, balances, BalanceJson);
+gen_mirror_rest_page!(AccountTokensPage, tokens, AccountTokenJson);
+gen_mirror_rest_page!(NftsPage, nfts, NftJson);
+gen_mirror_rest_page!(ContractResultsPage, results, ContractResultJson);
+
+pub(crate) fn parse_timestamp(s: &str) -> Result<DateTime<Utc>, HederaError> {
+    DateTime::<Utc>::try_from(HederaTimestamp::from_str(s)?)
+}
+
+/// Mirror node transaction ids are formatted `{account}-{seconds}-{nanos}`
+pub(crate) fn parse_transaction_id(s: &str) -> Result<TransactionId, HederaError> {
+    let parts: Vec<&str> = s.split('-').collect();
+    if parts.len() != 3 {
+        return Err(HederaError::InvalidTransactionIdFormat);
+    }
+    let account_id = AccountId::from_str(parts[0])?;
+    let valid_start = parse_timestamp(&format!("{}.{}", parts[1], parts[2]))?;
+    Ok(TransactionId::new(
+        Some(account_id),
+        Some(valid_start),
+        false,
+        None,
+    ))
+}
+
+pub(crate) fn format_transaction_id(transaction_id: &TransactionId) -> Result<String, HederaError> {
+    let account_id = transaction_id
+        .account_id
+        .ok_or_else(|| HederaError::ValueNotSet("account_id".to_string()))?;
+    let valid_start = transaction_id
+        .transaction_valid_start
+        .ok_or_else(|| HederaError::ValueNotSet("transaction_valid_start".to_string()))?;
+    Ok(format!(
+        "{}-{}-{:09}",
+        account_id,
+        valid_start.timestamp(),
+        valid_start.timestamp_subsec_nanos()
+    ))
+}
+
+fn parse_optional<T: FromStr<Err = HederaError>>(
+    s: &Option<String>,
+) -> Result<Option<T>, HederaError> {
+    s.as_deref().map(T::from_str).transpose()
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, HederaError> {
+    Ok(hex::decode(s.trim_start_matches("0x"))?)
+}
+
+fn decode_base64(s: &str) -> Result<Vec<u8>, HederaError> {
+    base64::decode(s).map_err(|e| HederaError::MirrorRestDecode(e.to_string()))
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub(crate) struct HbarTransferJson {
+    pub account: String,
+    pub amount: i64,
+    #[serde(default)]
+    pub is_approval: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub(crate) struct TokenTransferJson {
+    pub token_id: String,
+    pub account: String,
+    pub amount: i64,
+    #[serde(default)]
+    pub is_approval: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub(crate) struct NftTransferJson {
+    pub token_id: String,
+    pub sender_account_id: Option<String>,
+    pub receiver_account_id: Option<String>,
+    pub serial_number: i64,
+    #[serde(default)]
+    pub is_approval: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub(crate) struct TransactionJson {
+    pub consensus_timestamp: String,
+    pub transaction_id: String,
+    pub transaction_hash: Option<String>,
+    pub name: String,
+    pub result: String,
+    pub charged_tx_fee: i64,
+    pub memo_base64: Option<String>,
+    pub node: Option<String>,
+    #[serde(default)]
+    pub scheduled: bool,
+    #[serde(default)]
+    pub nonce: i32,
+    #[serde(default)]
+    pub transfers: Vec<HbarTransferJson>,
+    #[serde(default)]
+    pub token_transfers: Vec<TokenTransferJson>,
+    #[serde(default)]
+    pub nft_transfers: Vec<NftTransferJson>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub(crate) struct BalanceJson {
+    pub account: String,
+    pub balance: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub(crate) struct AccountTokenJson {
+    pub token_id: String,
+    pub balance: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub(crate) struct NftJson {
+    pub account_id: Option<String>,
+    pub created_timestamp: String,
+    #[serde(default)]
+    pub deleted: bool,
+    pub metadata: Option<String>,
+    pub serial_number: i64,
+    pub token_id: String,
+    pub spender: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub(crate) struct ContractResultJson {
+    pub contract_id: Option<String>,
+    pub timestamp: String,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    #[serde(default)]
+    pub amount: i64,
+    #[serde(default)]
+    pub gas_limit: u64,
+    #[serde(default)]
+    pub gas_used: u64,
+    pub call_result: Option<String>,
+    pub function_parameters: Option<String>,
+    pub error_message: Option<String>,
+    pub result: Option<String>,
+    pub hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MirrorHbarTransfer {
+    pub account_id: AccountId,
+    pub amount: Hbar,
+    pub is_approval: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MirrorTokenTransfer {
+    pub token_id: TokenId,
+    pub account_id: AccountId,
+    pub amount: i64,
+    pub is_approval: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MirrorNftTransfer {
+    pub nft_id: NftId,
+    pub sender_account_id: Option<AccountId>,
+    pub receiver_account_id: Option<AccountId>,
+    pub is_approval: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MirrorTransaction {
+    pub consensus_timestamp: DateTime<Utc>,
+    pub transaction_id: TransactionId,
+    pub transaction_hash: Vec<u8>,
+    /// The transaction type, e.g. `CRYPTOTRANSFER`
+    pub name: String,
+    /// The response code name, e.g. `SUCCESS`
+    pub result: String,
+    pub charged_tx_fee: Hbar,
+    pub memo: Vec<u8>,
+    pub node: Option<AccountId>,
+    pub transfers: Vec<MirrorHbarTransfer>,
+    pub token_transfers: Vec<MirrorTokenTransfer>,
+    pub nft_transfers: Vec<MirrorNftTransfer>,
+}
+
+impl TryFrom<TransactionJson> for MirrorTransaction {
+    type Error = HederaError;
+    fn try_from(json: TransactionJson) -> Result<MirrorTransaction, Self::Error> {
+        let mut transaction_id = parse_transaction_id(&json.transaction_id)?;
+        transaction_id.scheduled = json.scheduled;
+        if json.nonce != 0 {
+            transaction_id.nonce = Some(json.nonce);
+        }
+        Ok(MirrorTransaction {
+            consensus_timestamp: parse_timestamp(&json.consensus_timestamp)?,
+            transaction_id,
+            transaction_hash: json
+                .transaction_hash
+                .as_deref()
+                .map(decode_base64)
+                .transpose()?
+                .unwrap_or_default(),
+            name: json.name,
+            result: json.result,
+            charged_tx_fee: Hbar::from_tinybar(json.charged_tx_fee),
+            memo: json
+                .memo_base64
+                .as_deref()
+                .map(decode_base64)
+                .transpose()?
+                .unwrap_or_default(),
+            node: parse_optional(&json.node)?,
+            transfers: json
+                .transfers
+                .into_iter()
+                .map(|x| {
+                    Ok(MirrorHbarTransfer {
+                        account_id: AccountId::from_str(&x.account)?,
+                        amount: Hbar::from_tinybar(x.amount),
+                        is_approval: x.is_approval,
+                    })
+                })
+                .collect::<Result<Vec<MirrorHbarTransfer>, HederaError>>()?,
+            token_transfers: json
+                .token_transfers
+                .into_iter()
+                .map(|x| {
+                    Ok(MirrorTokenTransfer {
+                        token_id: TokenId::from_str(&x.token_id)?,
+                        account_id: AccountId::from_str(&x.account)?,
+                        amount: x.amount,
+                        is_approval: x.is_approval,
+                    })
+                })
+                .collect::<Result<Vec<MirrorTokenTransfer>, HederaError>>()?,
+            nft_transfers: json
+                .nft_transfers
+                .into_iter()
+                .map(|x| {
+                    Ok(MirrorNftTransfer {
+                        nft_id: NftId::new(TokenId::from_str(&x.token_id)?, x.serial_number),
+                        sender_account_id: parse_optional(&x.sender_account_id)?,
+                        receiver_account_id: parse_optional(&x.receiver_account_id)?,
+                        is_approval: x.is_approval,
+                    })
+                })
+                .collect::<Result<Vec<MirrorNftTransfer>, HederaError>>()?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MirrorTokenBalance {
+    pub account_id: AccountId,
+    pub token_id: TokenId,
+    pub balance: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MirrorContractResult {
+    pub contract_id: Option<ContractId>,
+    pub consensus_timestamp: DateTime<Utc>,
+    /// EVM address of the sender
+    pub from: Option<String>,
+    /// EVM address of the receiver
+    pub to: Option<String>,
+    pub amount: Hbar,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+    pub call_result: Vec<u8>,
+    pub function_parameters: Vec<u8>,
+    pub error_message: Option<String>,
+    pub result: Option<String>,
+    pub hash: Vec<u8>,
+}
+
+impl TryFrom<ContractResultJson> for MirrorContractResult {
+    type Error = HederaError;
+    fn try_from(json: ContractResultJson) -> Result<MirrorContractResult, Self::Error> {
+        Ok(MirrorContractResult {
+            contract_id: parse_optional(&json.contract_id)?,
+            consensus_timestamp: parse_timestamp(&json.timestamp)?,
+            from: json.from,
+            to: json.to,
+            amount: Hbar::from_tinybar(json.amount),
+            gas_limit: json.gas_limit,
+            gas_used: json.gas_used,
+            call_result: json
+                .call_result
+                .as_deref()
+                .map(decode_hex)
+                .transpose()?
+                .unwrap_or_default(),
+            function_parameters: json
+                .function_parameters
+                .as_deref()
+                .map(decode_hex)
+                .transpose()?
+                .unwrap_or_default(),
+            error_message: json.error_message,
+            result: json.result,
+            hash: json
+                .hash
+                .as_deref()
+                .map(decode_hex)
+                .transpose()?
+                .unwrap_or_default(),
+        })
+    }
+}
+
+pub(crate) fn nft_json_to_token_nft_info(
+    json: NftJson,
+    ledger_id: &LedgerId,
+) -> Result<TokenNftInfo, HederaError> {
+    let account_id = json
+        .account_id
+        .as_deref()
+        .ok_or_else(|| HederaError::MissingInProto("account_id".to_string()))?;
+    Ok(TokenNftInfo {
+        nft_id: NftId::new(TokenId::from_str(&json.token_id)?, json.serial_number),
+        account_id: AccountId::from_str(account_id)?,
+        creation_time: parse_timestamp(&json.created_timestamp)?,
+        metadata: json
+            .metadata
+            .as_deref()
+            .map(decode_base64)
+            .transpose()?
+            .unwrap_or_default(),
+        ledger_id: ledger_id.clone(),
+        spender_id: parse_optional(&json.spender)?,
+    })
+}
@@ -1,8 +1,10 @@
 use std::collections::HashMap;
+use std::time::Duration;
 
 use crate::error::HederaError;
 use crate::ledger_id::LedgerId;
 use crate::managed_network::{ArcNetworkNode, ManagedNetwork, NetworkNode};
+use crate::managed_node_address::ManagedNodeAddress;
 use crate::network_name::NetworkName;
 use crate::node::Node;
 use crate::node_address::NodeAddress;
@@ -135,6 +137,43 @@ impl Network {
         Ok(map)
     }
 
+    /// Replaces the node list with the endpoints from `address_book` when they
+    /// differ from the current nodes, then attaches each node's address book
+    /// entry. Nodes that are unchanged keep their health state.
+    pub async fn set_address_book(
+        &self,
+        address_book: &NodeAddressBook,
+    ) -> Result<(), HederaError> {
+        let mut network = HashMap::new();
+        let mut book = HashMap::new();
+        for node_address in address_book.node_addresses.iter() {
+            let account_id = match node_address.account_id {
+                Some(account_id) => account_id,
+                None => continue,
+            };
+            for endpoint in node_address.addresses.iter() {
+                let mut address: ManagedNodeAddress = endpoint.to_string().parse()?;
+                if address.is_transport_security() != self.network.transport_security {
+                    if self.network.transport_security {
+                        address.to_secure();
+                    } else {
+                        address.to_insecure();
+                    }
+                }
+                network.insert(address.to_string(), account_id);
+            }
+            book.insert(account_id, node_address.clone());
+        }
+        // an empty book would leave the client without any node to submit to
+        if network.is_empty() {
+            return Err(HederaError::EmptyNodeAddressBook);
+        }
+        if network != self.network().await? {
+            self.set_network(network).await?;
+        }
+        self.network.set_nodes_address_book(&book).await
+    }
+
     pub fn for_network_name(network_name: &NetworkName) -> Result<Self, HederaError> {
         let network = match network_name {
             NetworkName::MainNet => mainnet_nodes(),
@@ -215,6 +254,14 @@ impl Network {
         self.network.verify_certificate
     }
 
+    pub async fn set_connect_timeout(&mut self, timeout: Option<Duration>) {
+        self.network.set_connect_timeout(timeout).await;
+    }
+
+    pub fn connect_timeout(&self) -> Option<Duration> {
+        self.network.connect_timeout
+    }
+
     pub async fn node_account_ids_for_execute(&self) -> Vec<AccountId> {
         let nodes = self
             .network
@@ -238,8 +285,71 @@ impl Network {
     }
 }
 
-// #[cfg(test)]
-// mod tests {
-//     use super::*;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::proto::services;
 
-// }
+    fn node_address(num: i64, ip: &[u8], port: i32) -> services::NodeAddress {
+        #[allow(deprecated)]
+        services::NodeAddress {
+            node_account_id: Some(services::AccountId {
+                shard_num: 0,
+                realm_num: 0,
+                account: Some(services::account_id::Account::AccountNum(num)),
+            }),
+            node_id: num - 3,
+            service_endpoint: vec![services::ServiceEndpoint {
+                ip_address_v4: ip.to_vec(),
+                port,
+            }],
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_set_address_book() {
+        let network = Network::for_network_name(&NetworkName::TestNet).unwrap();
+        let address_book = NodeAddressBook::from(services::NodeAddressBook {
+            node_address: vec![
+                node_address(3, &[10, 0, 0, 1], 50211),
+                node_address(4, &[10, 0, 0, 2], 50211),
+            ],
+        });
+        network.set_address_book(&address_book).await.unwrap();
+
+        let mut expected = HashMap::new();
+        expected.insert("10.0.0.1:50211".to_string(), AccountId::simple(3));
+        expected.insert("10.0.0.2:50211".to_string(), AccountId::simple(4));
+        assert_eq!(network.network().await.unwrap(), expected);
+    }
+
+    #[tokio::test]
+    async fn test_set_empty_address_book() {
+        let network = Network::for_network_name(&NetworkName::TestNet).unwrap();
+        let before = network.network().await.unwrap();
+        let address_book = NodeAddressBook::from(services::NodeAddressBook {
+            node_address: vec![],
+        });
+        assert!(network.set_address_book(&address_book).await.is_err());
+        assert_eq!(network.network().await.unwrap(), before);
+    }
+
+    #[tokio::test]
+    async fn test_set_connect_timeout() {
+        let mut network = Network::for_network_name(&NetworkName::TestNet).unwrap();
+        let timeout = Some(Duration::from_secs(3));
+        network.set_connect_timeout(timeout).await;
+        assert_eq!(network.connect_timeout(), timeout);
+
+        let node = network
+            .node_for_account_id(&AccountId::simple(3))
+            .await
+            .unwrap();
+        let node = node.read().await;
+        match &*node {
+            NetworkNode::Node(n) => assert_eq!(n.connect_timeout, timeout),
+            NetworkNode::Mirror(_) => panic!("expected a consensus node"),
+        }
+    }
+}
@@ -1,12 +1,13 @@
-use serde::{Deserialize, Serialize};
 use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
 
 use crate::error::HederaError;
 use crate::proto::services::NftId as ProtoNftId;
 use crate::proto::ToProto;
 use crate::token_id::TokenId;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct NftId {
     pub token_id: TokenId,
     pub serial_number: i64,
@@ -21,6 +22,28 @@ impl NftId {
     }
 }
 
+impl fmt::Display for NftId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.token_id, self.serial_number)
+    }
+}
+
+// {token}/{serial}, or the older {serial}@{token}
+impl FromStr for NftId {
+    type Err = HederaError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((token_id, serial_number)) = s.split_once('/') {
+            Ok(NftId::new(token_id.parse()?, serial_number.parse()?))
+        } else if let Some((serial_number, token_id)) = s.split_once('@') {
+            Ok(NftId::new(token_id.parse()?, serial_number.parse()?))
+        } else {
+            Err(HederaError::UnknownIdFormat)
+        }
+    }
+}
+
+serde_from_str!(NftId);
+
 impl TryFrom<ProtoNftId> for NftId {
     type Error = HederaError;
     fn try_from(services: ProtoNftId) -> Result<NftId, Self::Error> {
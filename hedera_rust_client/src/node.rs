@@ -13,6 +13,7 @@ pub struct Node {
     pub channel: Option<Channel>,
     pub address_book: Option<NodeAddress>,
     pub verify_certificate: bool,
+    pub connect_timeout: Option<Duration>,
 }
 
 impl Node {
@@ -24,6 +25,7 @@ impl Node {
             channel: None,
             address_book: None,
             verify_certificate: true,
+            connect_timeout: None,
         }
     }
 
@@ -40,9 +42,10 @@ impl Node {
                     .as_ref()
                     .ok_or(HederaError::MissingNodeCertHash)?
                     .cert_hash,
+                self.connect_timeout,
             )?
         } else {
-            Channel::from_authority(&self.managed_node.address.to_string())?
+            Channel::from_authority(&self.managed_node.address.to_string(), self.connect_timeout)?
         };
 
         self.channel = Some(channel.clone());
@@ -69,6 +72,14 @@ impl Node {
         self.managed_node.wait()
     }
 
+    /// Drops the current channel, so the next one is built with `timeout`.
+    pub fn set_connect_timeout(&mut self, timeout: Option<Duration>) {
+        if self.connect_timeout != timeout {
+            self.connect_timeout = timeout;
+            self.close();
+        }
+    }
+
     pub fn close(&mut self) {
         if self.channel.is_some() {
             self.channel = None;
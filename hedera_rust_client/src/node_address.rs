@@ -18,15 +18,18 @@ impl From<PbNodeAddress> for NodeAddress {
         let mut addresses: Vec<Endpoint> =
             Vec::with_capacity(pb_node_address.service_endpoint.len());
         #[allow(deprecated)]
-        if pb_node_address.ip_address.is_empty() {
+        if let Some(ip_address_v4) = legacy_ip_address(&pb_node_address.ip_address) {
             let endpoint = ServiceEndpoint {
-                ip_address_v4: pb_node_address.ip_address,
+                ip_address_v4,
                 port: pb_node_address.portno,
             };
             addresses.push(endpoint.into());
         }
+        // endpoints without a v4 address can't be dialed
         for endpoint in pb_node_address.service_endpoint {
-            addresses.push(endpoint.into());
+            if endpoint.ip_address_v4.len() == 4 {
+                addresses.push(endpoint.into());
+            }
         }
 
         #[allow(deprecated)]
@@ -43,3 +46,15 @@ impl From<PbNodeAddress> for NodeAddress {
         }
     }
 }
+
+// the deprecated ip_address field holds either the raw octets or the dotted string
+fn legacy_ip_address(ip_address: &[u8]) -> Option<Vec<u8>> {
+    if ip_address.len() == 4 {
+        return Some(ip_address.to_vec());
+    }
+    std::str::from_utf8(ip_address)
+        .ok()?
+        .parse::<std::net::Ipv4Addr>()
+        .ok()
+        .map(|ip| ip.octets().to_vec())
+}
@@ -5,6 +5,7 @@ use crate::error::HederaError;
 use crate::node_address::NodeAddress;
 use crate::proto::services::NodeAddressBook as PbNodeAddressBook;
 
+#[derive(Debug, Clone, PartialEq)]
 pub struct NodeAddressBook {
     pub node_addresses: Vec<NodeAddress>,
 }
@@ -0,0 +1,104 @@
+use num::pow;
+use std::cmp::min;
+use std::time::Duration;
+
+use crate::client::Client;
+use crate::error::HederaError;
+use crate::mirror_node::NetworkServiceClientChannel;
+use crate::node_address::NodeAddress;
+use crate::node_address_book::NodeAddressBook;
+use crate::proto::{mirror, ToProto};
+use crate::FileId;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeAddressBookQuery {
+    file_id: FileId,
+    limit: i32,
+    max_attempts: u8,
+}
+
+impl Default for NodeAddressBookQuery {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NodeAddressBookQuery {
+    pub fn new() -> NodeAddressBookQuery {
+        NodeAddressBookQuery {
+            file_id: FileId::simple(102),
+            limit: 0,
+            max_attempts: 10,
+        }
+    }
+
+    pub fn file_id(&self) -> FileId {
+        self.file_id
+    }
+
+    pub fn set_file_id(&mut self, file_id: FileId) -> &mut Self {
+        self.file_id = file_id;
+        self
+    }
+
+    pub fn limit(&self) -> i32 {
+        self.limit
+    }
+
+    pub fn set_limit(&mut self, limit: i32) -> &mut Self {
+        self.limit = limit;
+        self
+    }
+
+    pub fn max_attempts(&self) -> u8 {
+        self.max_attempts
+    }
+
+    pub fn set_max_attempts(&mut self, max_attempts: u8) -> &mut Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub async fn execute(&self, client: &Client) -> Result<NodeAddressBook, HederaError> {
+        let query = mirror::AddressBookQuery {
+            file_id: Some(self.file_id.to_proto()?),
+            limit: self.limit,
+        };
+
+        let mut attempt = 0u8;
+        loop {
+            let node = client.next_mirror_node().await?;
+            let mut n_w = node.write().await;
+            let network_client = n_w.mirror_network_channel();
+            drop(n_w);
+
+            // the stream is restarted from the beginning on a retryable failure,
+            // so anything received before the failure is discarded
+            let code = match Self::get_nodes(network_client?, query.clone()).await {
+                Ok(node_addresses) => return Ok(NodeAddressBook { node_addresses }),
+                Err(status) => status.code(),
+            };
+            if code != tonic::Code::Unavailable && code != tonic::Code::ResourceExhausted {
+                return Err(HederaError::ProtoClientFailed(code));
+            }
+            if attempt >= self.max_attempts {
+                return Err(HederaError::MaxAttempsExceeded(self.max_attempts));
+            }
+            let delay = min(250 * pow(u64::from(attempt), 2), 8000);
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+            attempt += 1;
+        }
+    }
+
+    async fn get_nodes(
+        mut network_client: NetworkServiceClientChannel,
+        query: mirror::AddressBookQuery,
+    ) -> Result<Vec<NodeAddress>, tonic::Status> {
+        let mut stream = network_client.get_nodes(query).await?.into_inner();
+        let mut node_addresses = Vec::new();
+        while let Some(node_address) = stream.message().await? {
+            node_addresses.push(node_address.into());
+        }
+        Ok(node_addresses)
+    }
+}
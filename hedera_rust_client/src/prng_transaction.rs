@@ -0,0 +1,59 @@
+use serde::Serialize;
+use std::convert::TryFrom;
+
+use crate::transaction::Transaction;
+use crate::Client;
+use crate::Hbar;
+use crate::HederaError;
+use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+
+#[derive(TransactionSchedule, TransactionExecute, Debug, Clone, PartialEq)]
+#[hedera_rust_client_derive(service(method_service_name = "util", method_service_fn = "prng"))]
+pub struct PrngTransaction {
+    transaction: Transaction,
+    services: Proto,
+}
+
+impl PrngTransaction {
+    pub fn new() -> PrngTransaction {
+        let transaction = Transaction::with_max_transaction_fee(Hbar::new(5.0));
+        let services = Proto::new();
+        PrngTransaction {
+            transaction,
+            services,
+        }
+    }
+
+    fn validate_network_on_ids(&self, _client: &Client) -> Result<(), HederaError> {
+        Ok(())
+    }
+
+    // range, when unset the record holds 384 pseudorandom bits instead of a number in [0, range)
+    pub fn range(&self) -> Option<u32> {
+        match self.services.range {
+            0 => None,
+            range => u32::try_from(range).ok(),
+        }
+    }
+
+    pub fn set_range(&mut self, range: u32) -> Result<&mut Self, HederaError> {
+        self.require_not_frozen()?;
+        self.services.range = i32::try_from(range)?;
+        Ok(self)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
+#[hedera_rust_client_derive(proto(
+    proto_enum = "UtilPrng",
+    proto_type = "UtilPrngTransactionBody"
+))]
+struct Proto {
+    pub range: i32,
+}
+
+impl Proto {
+    pub fn new() -> Self {
+        Proto { range: 0 }
+    }
+}
@@ -9,6 +9,8 @@ pub mod sdk {
     tonic::include_proto!("sdk/proto");
 }
 
+// the mock mirror node's server names its stream types after the rpcs
+#[allow(non_camel_case_types)]
 pub mod mirror {
     tonic::include_proto!("mirror/com.hedera.mirror.api.proto");
 }
@@ -1,5 +1,8 @@
+use std::time::Duration;
+
 use crate::client::Client;
 use crate::error::HederaError;
+use crate::execute_middleware::ExecuteHooks;
 use crate::executor::{ProtoRequest, Request, Response};
 
 use crate::proto::{services, ToProto};
@@ -24,11 +27,11 @@ pub fn query_make_request_header(
     Ok(())
 }
 
-pub fn cost_query_make_request_header(
+pub async fn cost_query_make_request_header(
     query_header: &mut QueryHeader,
     client: &Client,
 ) -> Result<(), HederaError> {
-    let payment_transaction = query_make_payment_transaction(None, None, client, None)?;
+    let payment_transaction = query_make_payment_transaction(None, None, client, None).await?;
     query_header.payment = Some(payment_transaction);
     query_header.response_type = ResponseType::CostAnswer;
     Ok(())
@@ -78,7 +81,7 @@ pub fn cost_query_get_node_account_id(request: &Request) -> Result<AccountId, He
     Ok(account_id)
 }
 
-pub fn query_make_payment_transaction(
+pub async fn query_make_payment_transaction(
     transaction_id: Option<TransactionId>,
     node_account_id: Option<AccountId>,
     client: &Client,
@@ -114,7 +117,7 @@ pub fn query_make_payment_transaction(
     ));
 
     let body_bytes = body.to_proto_bytes()?;
-    let signature = client.sign_with_operator(&body_bytes);
+    let signature = client.sign_with_operator(&body_bytes).await?;
     let mut signed_transaction = SignedTransaction::with_body_bytes(body_bytes);
     signed_transaction.add_signature_pair(client.to_signature_pair_protobuf(&signature))?;
     let signed_transaction_bytes = signed_transaction.to_proto_bytes()?;
@@ -144,6 +147,9 @@ pub struct Query {
     pub max_retry: u8,
     pub min_backoff: Option<u64>,
     pub max_backoff: Option<u64>,
+    pub grpc_deadline: Option<Duration>,
+    pub request_timeout: Option<Duration>,
+    pub(crate) hooks: ExecuteHooks,
 
     pub is_payment_required: bool,
 }
@@ -162,6 +168,9 @@ impl Query {
             max_retry: 5,
             min_backoff: None,
             max_backoff: None,
+            grpc_deadline: None,
+            request_timeout: None,
+            hooks: ExecuteHooks::default(),
             is_payment_required,
         }
     }
@@ -172,7 +181,7 @@ impl Query {
         query
     }
 
-    pub fn generate_payments_for_node_account_ids(
+    pub async fn generate_payments_for_node_account_ids(
         &mut self,
         client: &Client,
         cost: Hbar,
@@ -191,7 +200,8 @@ impl Query {
                 Some(node_id.clone()),
                 &client,
                 Some(cost),
-            )?;
+            )
+            .await?;
             self.payment_transactions.push(transaction);
         }
         Ok(())
@@ -0,0 +1,770 @@
+use bytes::Bytes;
+use flate2::read::GzDecoder;
+use prost::Message;
+use rsa::pkcs8::DecodePublicKey;
+use rsa::{Pkcs1v15Sign, RsaPublicKey};
+use sha2::{Digest, Sha384};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::io::Read;
+use std::path::Path;
+
+use crate::error::HederaError;
+use crate::node_address_book::NodeAddressBook;
+use crate::proto::{services, streams};
+use crate::transaction::Transaction;
+use crate::transaction_sidecar_record::{sidecar_records_from_bytes, TransactionSidecarRecord};
+use crate::AccountId;
+use crate::SemanticVersion;
+use crate::TransactionRecord;
+
+// re export SidecarType in proto
+pub use crate::proto::streams::SidecarType;
+
+const RECORD_FILE_VERSION: i32 = 6;
+const SIGNATURE_FILE_VERSION: u8 = 6;
+
+// class ids and versions the platform mixes into every object and running hash
+const HASH_CLASS_ID: u64 = 0xf422da83a251741e;
+const HASH_CLASS_VERSION: i32 = 1;
+// id of the SHA-384 digest type written in every `Hash`
+const SHA384_DIGEST_TYPE: i32 = 0x58ff811b;
+const RECORD_STREAM_OBJECT_CLASS_ID: u64 = 0xe370929ba5429d8b;
+const RECORD_STREAM_OBJECT_CLASS_VERSION: i32 = 1;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+fn sha384(parts: &[&[u8]]) -> Vec<u8> {
+    let mut hasher = Sha384::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    hasher.finalize().to_vec()
+}
+
+/// Returns the file contents, gunzipped when `bytes` is gzip compressed.
+fn decompress(bytes: &[u8]) -> Result<Vec<u8>, HederaError> {
+    if !bytes.starts_with(&GZIP_MAGIC) {
+        return Ok(bytes.to_vec());
+    }
+    let mut decompressed = Vec::new();
+    GzDecoder::new(bytes)
+        .read_to_end(&mut decompressed)
+        .map_err(|e| HederaError::InvalidRecordStreamFile(e.to_string()))?;
+    Ok(decompressed)
+}
+
+fn read_varint(buf: &[u8], pos: &mut usize) -> Result<u64, HederaError> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *buf
+            .get(*pos)
+            .ok_or_else(|| HederaError::InvalidRecordStreamFile("truncated varint".to_string()))?;
+        *pos += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(HederaError::InvalidRecordStreamFile(
+        "varint overflow".to_string(),
+    ))
+}
+
+/// Returns the raw bytes of every length delimited field in a protobuf message.
+/// Hashes are computed over the bytes as written by the node, so they can't be
+/// taken from a decoded and re-encoded message.
+fn length_delimited_fields(buf: &[u8]) -> Result<Vec<(u64, &[u8])>, HederaError> {
+    let mut fields = Vec::new();
+    let mut pos = 0;
+    while pos < buf.len() {
+        let key = read_varint(buf, &mut pos)?;
+        let skip = match key & 0x7 {
+            0 => {
+                read_varint(buf, &mut pos)?;
+                0
+            }
+            1 => 8,
+            2 => {
+                let len = read_varint(buf, &mut pos)? as usize;
+                let end = pos
+                    .checked_add(len)
+                    .filter(|end| *end <= buf.len())
+                    .ok_or_else(|| {
+                        HederaError::InvalidRecordStreamFile("truncated field".to_string())
+                    })?;
+                fields.push((key >> 3, &buf[pos..end]));
+                len
+            }
+            5 => 4,
+            wire_type => {
+                return Err(HederaError::InvalidRecordStreamFile(format!(
+                    "unsupported wire type {}",
+                    wire_type
+                )))
+            }
+        };
+        pos += skip;
+    }
+    if pos != buf.len() {
+        return Err(HederaError::InvalidRecordStreamFile(
+            "truncated field".to_string(),
+        ));
+    }
+    Ok(fields)
+}
+
+fn hash_object_bytes(
+    hash: Option<streams::HashObject>,
+    name: &str,
+) -> Result<Vec<u8>, HederaError> {
+    match hash {
+        Some(hash) => Ok(hash.hash),
+        None => Err(HederaError::MissingInProto(name.to_string())),
+    }
+}
+
+/// Combines the previous running hash with the hash of the next object.
+/// Both are written as platform `Hash` objects: class id and version, then
+/// the digest type and the length prefixed digest.
+fn next_running_hash(running_hash: &[u8], object_hash: &[u8]) -> Vec<u8> {
+    let class_id = HASH_CLASS_ID.to_be_bytes();
+    let class_version = HASH_CLASS_VERSION.to_be_bytes();
+    let digest_type = SHA384_DIGEST_TYPE.to_be_bytes();
+    sha384(&[
+        &class_id,
+        &class_version,
+        &digest_type,
+        &(running_hash.len() as i32).to_be_bytes(),
+        running_hash,
+        &class_id,
+        &class_version,
+        &digest_type,
+        &(object_hash.len() as i32).to_be_bytes(),
+        object_hash,
+    ])
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordStreamItem {
+    pub transaction: Transaction,
+    pub record: TransactionRecord,
+    /// filled in by `RecordFile::add_sidecar_file`
+    pub sidecar_records: Vec<TransactionSidecarRecord>,
+    transaction_bytes: Vec<u8>,
+    record_bytes: Vec<u8>,
+}
+
+impl RecordStreamItem {
+    fn from_raw(transaction_bytes: &[u8], record_bytes: &[u8]) -> Result<Self, HederaError> {
+        let transaction = services::Transaction::decode(Bytes::copy_from_slice(transaction_bytes))
+            .map_err(HederaError::TransactionDeserializationError)?;
+        let record = services::TransactionRecord::decode(Bytes::copy_from_slice(record_bytes))
+            .map_err(|e| HederaError::InvalidRecordStreamFile(e.to_string()))?;
+        Ok(RecordStreamItem {
+            transaction: Transaction::from_proto_transactions(vec![transaction])?,
+            record: TransactionRecord::try_from(record)?,
+            sidecar_records: Vec::new(),
+            transaction_bytes: transaction_bytes.to_vec(),
+            record_bytes: record_bytes.to_vec(),
+        })
+    }
+
+    /// Hash of this item as it is folded into the running hash.
+    pub fn hash(&self) -> Vec<u8> {
+        sha384(&[
+            &RECORD_STREAM_OBJECT_CLASS_ID.to_be_bytes(),
+            &RECORD_STREAM_OBJECT_CLASS_VERSION.to_be_bytes(),
+            &(self.record_bytes.len() as i32).to_be_bytes(),
+            &self.record_bytes,
+            &(self.transaction_bytes.len() as i32).to_be_bytes(),
+            &self.transaction_bytes,
+        ])
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SidecarMetadata {
+    pub id: i32,
+    /// SHA-384 of the uncompressed sidecar file
+    pub hash: Vec<u8>,
+    pub types: Vec<SidecarType>,
+}
+
+impl TryFrom<streams::SidecarMetadata> for SidecarMetadata {
+    type Error = HederaError;
+    fn try_from(streams: streams::SidecarMetadata) -> Result<SidecarMetadata, Self::Error> {
+        Ok(SidecarMetadata {
+            id: streams.id,
+            hash: hash_object_bytes(streams.hash, "sidecar hash")?,
+            types: streams
+                .types
+                .into_iter()
+                .map(|t| SidecarType::from_i32(t).ok_or(HederaError::UnexpectedProtoType))
+                .collect::<Result<Vec<SidecarType>, HederaError>>()?,
+        })
+    }
+}
+
+/// A v6 record stream file (`.rcd` or `.rcd.gz`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordFile {
+    pub hapi_proto_version: SemanticVersion,
+    pub block_number: i64,
+    pub start_running_hash: Vec<u8>,
+    pub end_running_hash: Vec<u8>,
+    pub items: Vec<RecordStreamItem>,
+    pub sidecars: Vec<SidecarMetadata>,
+    file_hash: Vec<u8>,
+    metadata_hash: Vec<u8>,
+}
+
+impl RecordFile {
+    pub async fn from_file<P: AsRef<Path>>(path: P) -> Result<RecordFile, HederaError> {
+        let bytes = tokio::fs::read(path).await?;
+        Self::from_bytes(&bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<RecordFile, HederaError> {
+        let bytes = decompress(bytes)?;
+        if bytes.len() < 4 {
+            return Err(HederaError::InvalidRecordStreamFile(
+                "missing version".to_string(),
+            ));
+        }
+        let (version, body) = bytes.split_at(4);
+        let version = i32::from_be_bytes([version[0], version[1], version[2], version[3]]);
+        if version != RECORD_FILE_VERSION {
+            return Err(HederaError::InvalidRecordStreamFile(format!(
+                "unsupported version {}",
+                version
+            )));
+        }
+
+        let file = streams::RecordStreamFile::decode(Bytes::copy_from_slice(body))
+            .map_err(|e| HederaError::InvalidRecordStreamFile(e.to_string()))?;
+
+        let mut items = Vec::with_capacity(file.record_stream_items.len());
+        for (field, item) in length_delimited_fields(body)? {
+            if field != 3 {
+                continue;
+            }
+            let mut transaction_bytes: &[u8] = &[];
+            let mut record_bytes: &[u8] = &[];
+            for (field, value) in length_delimited_fields(item)? {
+                match field {
+                    1 => transaction_bytes = value,
+                    2 => record_bytes = value,
+                    _ => {}
+                }
+            }
+            items.push(RecordStreamItem::from_raw(transaction_bytes, record_bytes)?);
+        }
+
+        let hapi_proto_version = match file.hapi_proto_version {
+            Some(v) => SemanticVersion::try_from(v)?,
+            None => {
+                return Err(HederaError::MissingInProto(
+                    "hapi_proto_version".to_string(),
+                ))
+            }
+        };
+        let start_running_hash =
+            hash_object_bytes(file.start_object_running_hash, "start_object_running_hash")?;
+        let end_running_hash =
+            hash_object_bytes(file.end_object_running_hash, "end_object_running_hash")?;
+
+        // unlike the running hash, the metadata hash takes the start and end
+        // running hashes as the bare digests of their `HashObject`s, as the
+        // nodes' record stream writer and the mirror node reader do
+        let metadata_hash = sha384(&[
+            &version.to_be_bytes(),
+            &(hapi_proto_version.major as i32).to_be_bytes(),
+            &(hapi_proto_version.minor as i32).to_be_bytes(),
+            &(hapi_proto_version.patch as i32).to_be_bytes(),
+            &start_running_hash,
+            &end_running_hash,
+            &file.block_number.to_be_bytes(),
+        ]);
+
+        Ok(RecordFile {
+            hapi_proto_version,
+            block_number: file.block_number,
+            start_running_hash,
+            end_running_hash,
+            items,
+            sidecars: file
+                .sidecars
+                .into_iter()
+                .map(SidecarMetadata::try_from)
+                .collect::<Result<Vec<SidecarMetadata>, HederaError>>()?,
+            file_hash: sha384(&[&bytes]),
+            metadata_hash,
+        })
+    }
+
+    /// SHA-384 of the uncompressed file, as signed by the nodes.
+    pub fn file_hash(&self) -> &[u8] {
+        &self.file_hash
+    }
+
+    /// SHA-384 of the file metadata, as signed by the nodes.
+    pub fn metadata_hash(&self) -> &[u8] {
+        &self.metadata_hash
+    }
+
+    /// Checks the end running hash against the start running hash folded with
+    /// the hash of every item in the file.
+    pub fn verify_running_hash(&self) -> Result<(), HederaError> {
+        let running_hash = self
+            .items
+            .iter()
+            .fold(self.start_running_hash.clone(), |running_hash, item| {
+                next_running_hash(&running_hash, &item.hash())
+            });
+        if running_hash != self.end_running_hash {
+            return Err(HederaError::RecordStreamHashMismatch(format!(
+                "running hash of block {}",
+                self.block_number
+            )));
+        }
+        Ok(())
+    }
+
+    /// Checks that this file continues the running hash chain of `previous`.
+    pub fn verify_previous(&self, previous: &RecordFile) -> Result<(), HederaError> {
+        if self.start_running_hash != previous.end_running_hash {
+            return Err(HederaError::RecordStreamHashMismatch(format!(
+                "block {} does not follow block {}",
+                self.block_number, previous.block_number
+            )));
+        }
+        Ok(())
+    }
+
+    /// Verifies the running hash of every file and the links between them.
+    pub fn verify_chain(files: &[RecordFile]) -> Result<(), HederaError> {
+        for (i, file) in files.iter().enumerate() {
+            file.verify_running_hash()?;
+            if i > 0 {
+                file.verify_previous(&files[i - 1])?;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn add_sidecar_file_from_path<P: AsRef<Path>>(
+        &mut self,
+        id: i32,
+        path: P,
+    ) -> Result<(), HederaError> {
+        let bytes = tokio::fs::read(path).await?;
+        self.add_sidecar_file(id, &bytes)
+    }
+
+    /// Verifies the sidecar file `id` against the hash listed in this file and
+    /// attaches its records to the items with the same consensus timestamp.
+    pub fn add_sidecar_file(&mut self, id: i32, bytes: &[u8]) -> Result<(), HederaError> {
+        let bytes = decompress(bytes)?;
+        let metadata = self.sidecars.iter().find(|s| s.id == id).ok_or_else(|| {
+            HederaError::InvalidRecordStreamFile(format!("unknown sidecar id {}", id))
+        })?;
+        if metadata.hash != sha384(&[&bytes]) {
+            return Err(HederaError::RecordStreamHashMismatch(format!(
+                "sidecar {} of block {}",
+                id, self.block_number
+            )));
+        }
+
+        let mut items: HashMap<_, _> = self
+            .items
+            .iter_mut()
+            .filter_map(|item| item.record.consensus_timestamp.map(|ts| (ts, item)))
+            .collect();
+        for sidecar_record in sidecar_records_from_bytes(&bytes)? {
+            if let Some(item) = items.get_mut(&sidecar_record.consensus_timestamp) {
+                item.sidecar_records.push(sidecar_record);
+            }
+        }
+        Ok(())
+    }
+
+    /// Verifies the signature files against the RSA keys in `address_book` and
+    /// returns the nodes whose signatures are valid. At least a third of the
+    /// nodes in the address book have to have signed the file.
+    pub fn verify_signatures(
+        &self,
+        signatures: &HashMap<AccountId, RecordSignatureFile>,
+        address_book: &NodeAddressBook,
+    ) -> Result<Vec<AccountId>, HederaError> {
+        let mut node_count = 0usize;
+        let mut verified = Vec::new();
+        for node_address in address_book.node_addresses.iter() {
+            let account_id = match node_address.account_id {
+                Some(account_id) => account_id,
+                None => continue,
+            };
+            node_count += 1;
+            if let Some(signature) = signatures.get(&account_id) {
+                if signature.verify(self, &node_address.public_key).is_ok() {
+                    verified.push(account_id);
+                }
+            }
+        }
+        let required = (node_count + 2) / 3;
+        if verified.is_empty() || verified.len() < required {
+            return Err(HederaError::InsufficientRecordStreamSignatures(
+                verified.len(),
+                required,
+            ));
+        }
+        Ok(verified)
+    }
+}
+
+/// A v6 record stream signature file (`.rcd_sig`) written by a single node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordSignatureFile {
+    pub file_hash: Vec<u8>,
+    pub file_signature: Vec<u8>,
+    pub metadata_hash: Vec<u8>,
+    pub metadata_signature: Vec<u8>,
+}
+
+impl RecordSignatureFile {
+    pub async fn from_file<P: AsRef<Path>>(path: P) -> Result<RecordSignatureFile, HederaError> {
+        let bytes = tokio::fs::read(path).await?;
+        Self::from_bytes(&bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<RecordSignatureFile, HederaError> {
+        match bytes.first() {
+            Some(&SIGNATURE_FILE_VERSION) => {}
+            Some(version) => {
+                return Err(HederaError::InvalidRecordStreamFile(format!(
+                    "unsupported signature file version {}",
+                    version
+                )))
+            }
+            None => {
+                return Err(HederaError::InvalidRecordStreamFile(
+                    "missing version".to_string(),
+                ))
+            }
+        }
+        let file = streams::SignatureFile::decode(Bytes::copy_from_slice(&bytes[1..]))
+            .map_err(|e| HederaError::InvalidRecordStreamFile(e.to_string()))?;
+        let (file_hash, file_signature) = Self::signature_object(file.file_signature, "file")?;
+        let (metadata_hash, metadata_signature) =
+            Self::signature_object(file.metadata_signature, "metadata")?;
+        Ok(RecordSignatureFile {
+            file_hash,
+            file_signature,
+            metadata_hash,
+            metadata_signature,
+        })
+    }
+
+    fn signature_object(
+        signature: Option<streams::SignatureObject>,
+        name: &str,
+    ) -> Result<(Vec<u8>, Vec<u8>), HederaError> {
+        let signature =
+            signature.ok_or_else(|| HederaError::MissingInProto(format!("{}_signature", name)))?;
+        let hash = hash_object_bytes(signature.hash_object, &format!("{}_hash", name))?;
+        Ok((hash, signature.signature))
+    }
+
+    /// Checks that the signed hashes match `record_file` and that both
+    /// signatures were made by the node with the hex encoded DER `public_key`,
+    /// as found in `NodeAddress::public_key`.
+    pub fn verify(&self, record_file: &RecordFile, public_key: &str) -> Result<(), HederaError> {
+        if self.file_hash != record_file.file_hash() {
+            return Err(HederaError::RecordStreamHashMismatch(
+                "signed file hash".to_string(),
+            ));
+        }
+        if self.metadata_hash != record_file.metadata_hash() {
+            return Err(HederaError::RecordStreamHashMismatch(
+                "signed metadata hash".to_string(),
+            ));
+        }
+        let der = hex::decode(public_key.trim_start_matches("0x"))?;
+        let public_key = RsaPublicKey::from_public_key_der(&der)
+            .map_err(|e| HederaError::InvalidRecordStreamSignature(e.to_string()))?;
+        for (hash, signature) in [
+            (&self.file_hash, &self.file_signature),
+            (&self.metadata_hash, &self.metadata_signature),
+        ] {
+            public_key
+                .verify(
+                    Pkcs1v15Sign::new::<Sha384>(),
+                    &Sha384::digest(hash),
+                    signature,
+                )
+                .map_err(|e| HederaError::InvalidRecordStreamSignature(e.to_string()))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::node_address::NodeAddress;
+    use crate::proto::ToProto;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use rsa::pkcs8::DecodePrivateKey;
+    use rsa::RsaPrivateKey;
+    use std::io::Write;
+
+    const NODE_PRIVATE_KEY: &str = "30820275020100300d06092a864886f70d01010105000482025f3082025b02010002818100d773d112ee85a82723f80375eb482038f4db3ca5df2a41658d4cb3b99e454df3644b7f37d3029e5c9f8d1d31d75bd5feb71e01c57a33454b59a1d39fa7349142cb837a9eaf6a5936dfc9bbbd60616d76f48fc38038ed953aabe8a0f4eeb1fbcebecff690a317a2707a1af555a67431a40e0b2ff604e14e2ba5fda96fb87f1bb102030100010281800986f38dd4c250473acdf2c93a183c64885f51753c4310153f844ec63c7b7010fd346171c480ef23c56266121825d576a7fe49b1c3cf9496da65cf87d5cafb495615432587f9357120e5ecebb9d6431f685d8b17af3a81550eddf2d30b7d5e5a914a783b686ee786963c2a24a2e1c7092a83e25d46cd3b0bd95edbc9473d7a81024100eb959607aa76a6581295dc621a45f587c333f328743710de3bce528d88e8674e3875629669a6e1561af69e0a0a72d9732426871b6333c72459e4ff270c575aa9024100ea1f9b3346c92c09e14014367574f50c63a0693bd35c2ff31f73608376013eff68678470e3e72114ea7894b4fe32ff3dac7ea428710c3ce82a7742907ceaa5c902402e7e591bae32b8658d5572271254583eb0f1a1bd8c2209fc3205681972b99b19bedebbd43f89a5d31e83297185da60bbee9883f58dc07c3eda56ed5c417d43f102407b328282aafef51385da2848b7ea0180b17419ac077e7d24dc29e46188d903037736f1b44dd8b3c9c3d00bc069faffe0be422db3b239145bbeecdeb415678c31024032f2a4b9da27a567d62e4a80f4e6d06282376c5be1637a0bf8b305160dfb5b668ed6369c9ee310ab36a587cf1d8314030cd6ce3047e97529afd3a07efbd78c7c";
+
+    const NODE_PUBLIC_KEY: &str = "30819f300d06092a864886f70d010101050003818d0030818902818100d773d112ee85a82723f80375eb482038f4db3ca5df2a41658d4cb3b99e454df3644b7f37d3029e5c9f8d1d31d75bd5feb71e01c57a33454b59a1d39fa7349142cb837a9eaf6a5936dfc9bbbd60616d76f48fc38038ed953aabe8a0f4eeb1fbcebecff690a317a2707a1af555a67431a40e0b2ff604e14e2ba5fda96fb87f1bb10203010001";
+
+    fn stream_item(seconds: i64) -> streams::RecordStreamItem {
+        let transaction_id = services::TransactionId {
+            transaction_valid_start: Some(services::Timestamp {
+                seconds: seconds - 10,
+                nanos: 0,
+            }),
+            account_id: Some(AccountId::simple(1001).to_proto().unwrap()),
+            scheduled: false,
+            nonce: 0,
+        };
+        streams::RecordStreamItem {
+            transaction: Some(services::Transaction::default()),
+            record: Some(services::TransactionRecord {
+                receipt: Some(services::TransactionReceipt {
+                    status: services::ResponseCodeEnum::Success as i32,
+                    ..Default::default()
+                }),
+                consensus_timestamp: Some(services::Timestamp { seconds, nanos: 0 }),
+                transaction_id: Some(transaction_id),
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn hash_object(hash: Vec<u8>) -> Option<streams::HashObject> {
+        Some(streams::HashObject {
+            algorithm: streams::HashAlgorithm::Sha384 as i32,
+            length: hash.len() as i32,
+            hash,
+        })
+    }
+
+    fn encode_record_file(file: &streams::RecordStreamFile) -> Vec<u8> {
+        let mut bytes = RECORD_FILE_VERSION.to_be_bytes().to_vec();
+        bytes.extend(file.encode_to_vec());
+        bytes
+    }
+
+    // builds a file whose end running hash matches its items
+    fn record_file(
+        start_running_hash: Vec<u8>,
+        block_number: i64,
+        sidecars: Vec<streams::SidecarMetadata>,
+    ) -> Vec<u8> {
+        let mut file = streams::RecordStreamFile {
+            hapi_proto_version: Some(services::SemanticVersion {
+                major: 0,
+                minor: 30,
+                patch: 0,
+                ..Default::default()
+            }),
+            start_object_running_hash: hash_object(start_running_hash.clone()),
+            record_stream_items: vec![stream_item(1_000), stream_item(1_001)],
+            end_object_running_hash: hash_object(vec![0; 48]),
+            block_number,
+            sidecars,
+        };
+        let parsed = RecordFile::from_bytes(&encode_record_file(&file)).unwrap();
+        let end_running_hash = parsed.items.iter().fold(start_running_hash, |hash, item| {
+            next_running_hash(&hash, &item.hash())
+        });
+        file.end_object_running_hash = hash_object(end_running_hash);
+        encode_record_file(&file)
+    }
+
+    fn signature_file(record_file: &RecordFile, private_key: &str) -> RecordSignatureFile {
+        let key = RsaPrivateKey::from_pkcs8_der(&hex::decode(private_key).unwrap()).unwrap();
+        let sign = |hash: &[u8]| {
+            let signature = key
+                .sign(Pkcs1v15Sign::new::<Sha384>(), &Sha384::digest(hash))
+                .unwrap();
+            Some(streams::SignatureObject {
+                r#type: streams::SignatureType::Sha384WithRsa as i32,
+                length: signature.len() as i32,
+                checksum: 0,
+                signature,
+                hash_object: hash_object(hash.to_vec()),
+            })
+        };
+        let file = streams::SignatureFile {
+            file_signature: sign(record_file.file_hash()),
+            metadata_signature: sign(record_file.metadata_hash()),
+        };
+        let mut bytes = vec![SIGNATURE_FILE_VERSION];
+        bytes.extend(file.encode_to_vec());
+        RecordSignatureFile::from_bytes(&bytes).unwrap()
+    }
+
+    fn node_address(num: i64, public_key: &str) -> NodeAddress {
+        NodeAddress {
+            public_key: public_key.to_string(),
+            account_id: Some(AccountId::simple(num)),
+            node_id: num - 3,
+            cert_hash: Vec::new(),
+            addresses: Vec::new(),
+            description: String::new(),
+            stake: 0,
+        }
+    }
+
+    #[test]
+    fn test_running_hash() {
+        let bytes = record_file(vec![1; 48], 7, Vec::new());
+        let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+        gz.write_all(&bytes).unwrap();
+        let gz_bytes = gz.finish().unwrap();
+
+        let file = RecordFile::from_bytes(&bytes).unwrap();
+        let gz_file = RecordFile::from_bytes(&gz_bytes).unwrap();
+        assert_eq!(file, gz_file);
+        assert_eq!(file.block_number, 7);
+        assert_eq!(file.items.len(), 2);
+        assert_eq!(file.hapi_proto_version.minor, 30);
+        assert_eq!(
+            file.items[0].record.transaction_id.account_id,
+            Some(AccountId::simple(1001))
+        );
+        file.verify_running_hash().unwrap();
+
+        let mut tampered = file.clone();
+        tampered.items.swap(0, 1);
+        assert!(matches!(
+            tampered.verify_running_hash(),
+            Err(HederaError::RecordStreamHashMismatch(_))
+        ));
+    }
+
+    // end running hash of `record_file(vec![1; 48], ..)`, computed apart from
+    // this module following the platform's `Hash` and `RecordStreamObject`
+    // serialization
+    const KNOWN_END_RUNNING_HASH: &str = "a71262dcaedfd3033200fceb5ed7f5b053dd83a1135ab12bfad85b2ebfcb86598077a5057265989870d1404e77804433";
+
+    #[test]
+    fn test_known_running_hash() {
+        let mut file = streams::RecordStreamFile::decode(Bytes::from(
+            record_file(vec![1; 48], 7, Vec::new()).split_off(4),
+        ))
+        .unwrap();
+        file.end_object_running_hash = hash_object(hex::decode(KNOWN_END_RUNNING_HASH).unwrap());
+        let file = RecordFile::from_bytes(&encode_record_file(&file)).unwrap();
+        file.verify_running_hash().unwrap();
+    }
+
+    // metadata hash of that same file, computed apart from this module from
+    // the version, HAPI version, bare start and end running hashes and block
+    // number
+    const KNOWN_METADATA_HASH: &str = "79131f8fb928b94b8a5d4684eb91b35ddf82fedb71a85ec4d925a54d4b292f20b8713aa9ae7ee56ea9b87d1a26feab91";
+
+    #[test]
+    fn test_known_metadata_hash() {
+        let file = RecordFile::from_bytes(&record_file(vec![1; 48], 7, Vec::new())).unwrap();
+        assert_eq!(hex::encode(&file.end_running_hash), KNOWN_END_RUNNING_HASH);
+        assert_eq!(hex::encode(file.metadata_hash()), KNOWN_METADATA_HASH);
+    }
+
+    #[test]
+    fn test_verify_chain() {
+        let first = RecordFile::from_bytes(&record_file(vec![1; 48], 1, Vec::new())).unwrap();
+        let second =
+            RecordFile::from_bytes(&record_file(first.end_running_hash.clone(), 2, Vec::new()))
+                .unwrap();
+        let unrelated = RecordFile::from_bytes(&record_file(vec![2; 48], 3, Vec::new())).unwrap();
+
+        RecordFile::verify_chain(&[first.clone(), second]).unwrap();
+        assert!(RecordFile::verify_chain(&[first, unrelated]).is_err());
+    }
+
+    #[test]
+    fn test_verify_signatures() {
+        let file = RecordFile::from_bytes(&record_file(vec![1; 48], 1, Vec::new())).unwrap();
+        let address_book = NodeAddressBook {
+            node_addresses: vec![
+                node_address(3, NODE_PUBLIC_KEY),
+                node_address(4, NODE_PUBLIC_KEY),
+                node_address(5, NODE_PUBLIC_KEY),
+                node_address(6, NODE_PUBLIC_KEY),
+            ],
+        };
+        let signature = signature_file(&file, NODE_PRIVATE_KEY);
+        signature.verify(&file, NODE_PUBLIC_KEY).unwrap();
+
+        let mut signatures = HashMap::new();
+        signatures.insert(AccountId::simple(3), signature.clone());
+        assert!(matches!(
+            file.verify_signatures(&signatures, &address_book),
+            Err(HederaError::InsufficientRecordStreamSignatures(1, 2))
+        ));
+
+        signatures.insert(AccountId::simple(4), signature.clone());
+        let verified = file.verify_signatures(&signatures, &address_book).unwrap();
+        assert_eq!(verified, vec![AccountId::simple(3), AccountId::simple(4)]);
+
+        // signed for a different file
+        let other = RecordFile::from_bytes(&record_file(vec![2; 48], 1, Vec::new())).unwrap();
+        assert!(matches!(
+            signature.verify(&other, NODE_PUBLIC_KEY),
+            Err(HederaError::RecordStreamHashMismatch(_))
+        ));
+    }
+
+    #[test]
+    fn test_add_sidecar_file() {
+        let sidecar = streams::SidecarFile {
+            sidecar_records: vec![streams::TransactionSidecarRecord {
+                consensus_timestamp: Some(services::Timestamp {
+                    seconds: 1_001,
+                    nanos: 0,
+                }),
+                migration: false,
+                sidecar_records: Some(
+                    streams::transaction_sidecar_record::SidecarRecords::Bytecode(
+                        streams::ContractBytecode {
+                            contract_id: None,
+                            initcode: vec![0x60, 0x80],
+                            runtime_bytecode: vec![0x60, 0x40],
+                        },
+                    ),
+                ),
+            }],
+        }
+        .encode_to_vec();
+        let metadata = streams::SidecarMetadata {
+            hash: hash_object(sha384(&[&sidecar])),
+            id: 1,
+            types: vec![streams::SidecarType::ContractBytecode as i32],
+        };
+        let mut file =
+            RecordFile::from_bytes(&record_file(vec![1; 48], 1, vec![metadata])).unwrap();
+        assert_eq!(file.sidecars[0].types, vec![SidecarType::ContractBytecode]);
+
+        assert!(file.add_sidecar_file(1, &sidecar[1..]).is_err());
+        file.add_sidecar_file(1, &sidecar).unwrap();
+        assert!(file.items[0].sidecar_records.is_empty());
+        assert_eq!(
+            file.items[1].sidecar_records[0].data,
+            Some(crate::SidecarRecordData::Bytecode(
+                crate::ContractBytecode {
+                    contract_id: None,
+                    initcode: vec![0x60, 0x80],
+                    runtime_bytecode: vec![0x60, 0x40],
+                }
+            ))
+        );
+    }
+}
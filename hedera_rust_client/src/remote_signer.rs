@@ -0,0 +1,358 @@
+use async_trait::async_trait;
+use std::net::SocketAddr;
+#[cfg(any(test, feature = "testing"))]
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+#[cfg(any(test, feature = "testing"))]
+use tokio::net::TcpListener;
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+#[cfg(any(test, feature = "testing"))]
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use crate::crypto::{KeyAlgorithm, PublicKey, Signature};
+use crate::error::HederaError;
+use crate::signer::Signer;
+
+// Frames are `[tag: u8][length: u32 big endian][payload]`. Requests are tagged
+// with the operation, responses with whether it succeeded, the payload of a
+// failed one being the error message.
+const OP_PUBLIC_KEY: u8 = 0;
+const OP_SIGN: u8 = 1;
+const RESPONSE_OK: u8 = 0;
+const RESPONSE_ERR: u8 = 1;
+// far above the 6kb transaction size limit
+const MAX_FRAME_LENGTH: u32 = 1 << 20;
+
+async fn write_frame(stream: &mut TcpStream, tag: u8, payload: &[u8]) -> Result<(), HederaError> {
+    let mut frame = Vec::with_capacity(5 + payload.len());
+    frame.push(tag);
+    frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+    frame.extend_from_slice(payload);
+    stream.write_all(&frame).await?;
+    Ok(())
+}
+
+async fn read_frame(stream: &mut TcpStream) -> Result<(u8, Vec<u8>), HederaError> {
+    let tag = stream.read_u8().await?;
+    let length = stream.read_u32().await?;
+    if length > MAX_FRAME_LENGTH {
+        return Err(HederaError::SignerError(format!(
+            "frame of {} bytes is too long",
+            length
+        )));
+    }
+    let mut payload = vec![0; length as usize];
+    stream.read_exact(&mut payload).await?;
+    Ok((tag, payload))
+}
+
+/// A `Signer` whose key is held by a `RemoteSignerServer`, or another process
+/// speaking its protocol, at `addr`.
+///
+/// The connection is kept open between signatures and reopened once if a
+/// request on it fails with an I/O error. It is dropped on a malformed
+/// response, to be reopened by the next signature.
+#[derive(Debug)]
+pub struct RemoteSigner {
+    addr: SocketAddr,
+    public_key: PublicKey,
+    connection: Mutex<Option<TcpStream>>,
+}
+
+impl RemoteSigner {
+    /// Connects to the signer at `addr` and fetches its public key.
+    pub async fn connect(addr: SocketAddr) -> Result<RemoteSigner, HederaError> {
+        let mut stream = TcpStream::connect(addr).await?;
+        let bytes = RemoteSigner::request(&mut stream, OP_PUBLIC_KEY, &[])
+            .await?
+            .map_err(HederaError::SignerError)?;
+        let public_key = PublicKey::from_bytes_der(&bytes)?;
+        Ok(RemoteSigner {
+            addr,
+            public_key,
+            connection: Mutex::new(Some(stream)),
+        })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    // the signer's reply, which is the error message of a failed operation;
+    // an error means the stream can't be trusted to be at a frame boundary
+    async fn request(
+        stream: &mut TcpStream,
+        op: u8,
+        payload: &[u8],
+    ) -> Result<Result<Vec<u8>, String>, HederaError> {
+        write_frame(stream, op, payload).await?;
+        match read_frame(stream).await? {
+            (RESPONSE_OK, payload) => Ok(Ok(payload)),
+            (RESPONSE_ERR, message) => Ok(Err(String::from_utf8_lossy(&message).into_owned())),
+            (tag, _) => Err(HederaError::SignerError(format!(
+                "unknown response tag {}",
+                tag
+            ))),
+        }
+    }
+
+    async fn sign_bytes(&self, message: &[u8]) -> Result<Vec<u8>, HederaError> {
+        let mut connection = self.connection.lock().await;
+        // taken so that any failure leaves no connection behind
+        if let Some(mut stream) = connection.take() {
+            match RemoteSigner::request(&mut stream, OP_SIGN, message).await {
+                Ok(reply) => {
+                    *connection = Some(stream);
+                    return reply.map_err(HederaError::SignerError);
+                }
+                Err(HederaError::Io(e)) => {
+                    warn!("remote signer connection to {} failed: {}", self.addr, e);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        let mut stream = TcpStream::connect(self.addr).await?;
+        let reply = RemoteSigner::request(&mut stream, OP_SIGN, message).await?;
+        *connection = Some(stream);
+        reply.map_err(HederaError::SignerError)
+    }
+}
+
+#[async_trait]
+impl Signer for RemoteSigner {
+    fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    async fn sign(&self, message: &[u8]) -> Result<Signature, HederaError> {
+        let bytes = self.sign_bytes(message).await?;
+        match self.public_key.algorithm() {
+            KeyAlgorithm::Ed25519 => Signature::from_bytes(&bytes),
+            KeyAlgorithm::EcdsaSecp256k1 => Signature::from_bytes_ecdsa(&bytes),
+        }
+    }
+}
+
+/// Serves a `Signer` over TCP to `RemoteSigner`s, until dropped.
+///
+/// The protocol is neither authenticated nor encrypted and the server signs
+/// whatever it is sent, so it only listens on loopback addresses and is meant
+/// for tests. A production signing service needs its own authenticated
+/// channel in front of the key.
+#[cfg(any(test, feature = "testing"))]
+#[derive(Debug)]
+pub struct RemoteSignerServer {
+    local_addr: SocketAddr,
+    handle: JoinHandle<()>,
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl RemoteSignerServer {
+    /// Listens on `addr`, which must be a loopback address. Port 0 picks a
+    /// free port, see `local_addr`.
+    pub async fn bind(
+        addr: SocketAddr,
+        signer: Arc<dyn Signer>,
+    ) -> Result<RemoteSignerServer, HederaError> {
+        if !addr.ip().is_loopback() {
+            return Err(HederaError::SignerError(format!(
+                "remote signer server may only listen on a loopback address, not {}",
+                addr.ip()
+            )));
+        }
+        let listener = TcpListener::bind(addr).await?;
+        let local_addr = listener.local_addr()?;
+        let handle = tokio::spawn(async move {
+            loop {
+                let stream = match listener.accept().await {
+                    Ok((stream, _)) => stream,
+                    Err(e) => {
+                        warn!("remote signer server failed to accept: {}", e);
+                        continue;
+                    }
+                };
+                let signer = signer.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = RemoteSignerServer::serve(stream, signer).await {
+                        warn!("remote signer connection closed: {}", e);
+                    }
+                });
+            }
+        });
+        Ok(RemoteSignerServer { local_addr, handle })
+    }
+
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    async fn serve(mut stream: TcpStream, signer: Arc<dyn Signer>) -> Result<(), HederaError> {
+        loop {
+            let (op, payload) = match read_frame(&mut stream).await {
+                Ok(frame) => frame,
+                // the client hung up
+                Err(HederaError::Io(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                    return Ok(())
+                }
+                Err(e) => return Err(e),
+            };
+            let response = match op {
+                OP_PUBLIC_KEY => Ok(signer.public_key().to_encoded_bytes()),
+                OP_SIGN => signer
+                    .sign(&payload)
+                    .await
+                    .map(|signature| signature.as_bytes_vec()),
+                op => Err(HederaError::SignerError(format!(
+                    "unknown operation {}",
+                    op
+                ))),
+            };
+            match response {
+                Ok(bytes) => write_frame(&mut stream, RESPONSE_OK, &bytes).await?,
+                Err(e) => write_frame(&mut stream, RESPONSE_ERR, e.to_string().as_bytes()).await?,
+            }
+        }
+    }
+}
+
+#[cfg(any(test, feature = "testing"))]
+impl Drop for RemoteSignerServer {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::LocalSigner;
+    use crate::utils::test_utils::*;
+    use crate::{AccountId, Hbar, PrivateKey, TransferTransaction};
+
+    #[derive(Debug)]
+    struct FailingSigner(PublicKey);
+
+    #[async_trait]
+    impl Signer for FailingSigner {
+        fn public_key(&self) -> PublicKey {
+            self.0
+        }
+
+        async fn sign(&self, _message: &[u8]) -> Result<Signature, HederaError> {
+            Err(HederaError::SignerError("key is locked".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer() -> Result<(), HederaError> {
+        for private_key in vec![PrivateKey::new(), PrivateKey::generate_ecdsa()] {
+            let public_key = private_key.public();
+            let server = RemoteSignerServer::bind(
+                "127.0.0.1:0".parse().unwrap(),
+                Arc::new(LocalSigner::new(private_key)),
+            )
+            .await?;
+            let signer = RemoteSigner::connect(server.local_addr()).await?;
+            assert_eq!(signer.public_key(), public_key);
+            assert_eq!(signer.key_algorithm(), public_key.algorithm());
+
+            let signature = signer.sign(b"body").await?;
+            assert!(matches!(public_key.verify(b"body", &signature), Ok(true)));
+            // the connection is reused
+            let mut tx = TransferTransaction::new();
+            tx.add_hbar_transfer(AccountId::simple(2), Hbar::from_tinybar(-1))?
+                .add_hbar_transfer(AccountId::simple(3), Hbar::from_tinybar(1))?
+                .set_transaction_id(mock_transaction_id())?
+                .set_node_account_ids(vec![AccountId::simple(3), AccountId::simple(4)])?
+                .freeze()
+                .await?;
+            tx.sign_with_signer(&signer).await?;
+            let signatures = tx.signatures()?;
+            assert!(signatures[&AccountId::simple(3)].contains_key(&public_key));
+            assert!(signatures[&AccountId::simple(4)].contains_key(&public_key));
+        }
+
+        let public_key = PrivateKey::new().public();
+        let server = RemoteSignerServer::bind(
+            "127.0.0.1:0".parse().unwrap(),
+            Arc::new(FailingSigner(public_key)),
+        )
+        .await?;
+        let signer = RemoteSigner::connect(server.local_addr()).await?;
+        match signer.sign(b"body").await {
+            Err(HederaError::SignerError(message)) => assert!(message.contains("key is locked")),
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        let bound = RemoteSignerServer::bind(
+            "0.0.0.0:0".parse().unwrap(),
+            Arc::new(LocalSigner::new(PrivateKey::new())),
+        )
+        .await;
+        assert!(matches!(bound, Err(HederaError::SignerError(_))));
+        Ok(())
+    }
+
+    // answers signatures on its first connection with `first_reply`, a raw
+    // frame, and properly on the connections after it
+    async fn misbehaving_server(private_key: PrivateKey, first_reply: Vec<u8>) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut first = true;
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let private_key = private_key.clone();
+                let misbehave = std::mem::replace(&mut first, false);
+                let first_reply = first_reply.clone();
+                tokio::spawn(async move {
+                    while let Ok((op, payload)) = read_frame(&mut stream).await {
+                        let written = match op {
+                            OP_PUBLIC_KEY => {
+                                let key = private_key.public().to_encoded_bytes();
+                                write_frame(&mut stream, RESPONSE_OK, &key).await
+                            }
+                            _ if misbehave => stream
+                                .write_all(&first_reply)
+                                .await
+                                .map_err(HederaError::from),
+                            _ => {
+                                let signature = private_key.sign(&payload).as_bytes_vec();
+                                write_frame(&mut stream, RESPONSE_OK, &signature).await
+                            }
+                        };
+                        if written.is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer_drops_connection_on_bad_frame() -> Result<(), HederaError> {
+        let mut oversized = vec![RESPONSE_OK];
+        oversized.extend_from_slice(&(MAX_FRAME_LENGTH + 1).to_be_bytes());
+        let mut unknown_tag = vec![7];
+        unknown_tag.extend_from_slice(&0u32.to_be_bytes());
+        for first_reply in vec![unknown_tag, oversized] {
+            let private_key = PrivateKey::new();
+            let public_key = private_key.public();
+            let addr = misbehaving_server(private_key, first_reply).await;
+            let signer = RemoteSigner::connect(addr).await?;
+            assert!(matches!(
+                signer.sign(b"body").await,
+                Err(HederaError::SignerError(_))
+            ));
+            // the next signature is made over a new connection
+            let signature = signer.sign(b"body").await?;
+            assert!(matches!(public_key.verify(b"body", &signature), Ok(true)));
+            assert!(signer.connection.lock().await.is_some());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,197 @@
+use num::pow;
+use rand_core::{OsRng, RngCore};
+use std::cmp::min;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::status::Status;
+use crate::AccountId;
+
+/// What went wrong on an attempt, handed to a `RetryPolicy`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryContext {
+    /// attempt that just failed, starting at 1
+    pub attempt: u8,
+    pub max_attempts: u8,
+    pub node_account_id: AccountId,
+    /// set when the gRPC call itself failed
+    pub code: Option<tonic::Code>,
+    /// set when the node answered with a precheck status
+    pub status: Option<Status>,
+    /// whether the request type considers `code`/`status` retryable
+    pub retryable: bool,
+    pub min_backoff: Duration,
+    pub max_backoff: Duration,
+    /// time since `execute` was called
+    pub elapsed: Duration,
+}
+
+pub trait RetryPolicy: Send + Sync + fmt::Debug {
+    /// Returns how long to wait before the next attempt, or `None` to give up.
+    fn retry_delay(&self, context: &RetryContext) -> Option<Duration>;
+}
+
+/// The policy used when neither the client nor the request sets one.
+/// Failed gRPC calls move on to the next node right away, retryable precheck
+/// statuses back off quadratically between `min_backoff` and `max_backoff`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct DefaultRetryPolicy;
+
+impl RetryPolicy for DefaultRetryPolicy {
+    fn retry_delay(&self, context: &RetryContext) -> Option<Duration> {
+        if !context.retryable {
+            return None;
+        }
+        if context.code.is_some() {
+            return Some(Duration::ZERO);
+        }
+        let min_backoff = context.min_backoff.as_millis() as u64;
+        let max_backoff = context.max_backoff.as_millis() as u64;
+        let delay = min(
+            min_backoff * pow(u64::from(context.attempt), 2),
+            max_backoff,
+        );
+        Some(Duration::from_millis(delay))
+    }
+}
+
+/// Retries retryable failures after a random delay between zero and
+/// `base * 2^(attempt - 1)`, capped at `max`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExponentialJitterRetryPolicy {
+    base: Duration,
+    max: Duration,
+}
+
+impl ExponentialJitterRetryPolicy {
+    pub fn new(base: Duration, max: Duration) -> ExponentialJitterRetryPolicy {
+        ExponentialJitterRetryPolicy { base, max }
+    }
+
+    pub fn base(&self) -> Duration {
+        self.base
+    }
+
+    pub fn max(&self) -> Duration {
+        self.max
+    }
+
+    fn ceiling(&self, attempt: u8) -> Duration {
+        let factor = 1u32
+            .checked_shl(u32::from(attempt.saturating_sub(1)))
+            .unwrap_or(u32::MAX);
+        min(self.base.saturating_mul(factor), self.max)
+    }
+}
+
+impl RetryPolicy for ExponentialJitterRetryPolicy {
+    fn retry_delay(&self, context: &RetryContext) -> Option<Duration> {
+        if !context.retryable {
+            return None;
+        }
+        let ceiling = self.ceiling(context.attempt).as_millis() as u64;
+        let delay = OsRng.next_u64() % (ceiling + 1);
+        Some(Duration::from_millis(delay))
+    }
+}
+
+/// Wraps another policy and gives up once the next attempt would start after
+/// `deadline`, measured from the start of `execute`.
+#[derive(Debug, Clone)]
+pub struct DeadlineRetryPolicy {
+    deadline: Duration,
+    inner: Arc<dyn RetryPolicy>,
+}
+
+impl DeadlineRetryPolicy {
+    pub fn new(deadline: Duration) -> DeadlineRetryPolicy {
+        Self::with_inner(deadline, Arc::new(DefaultRetryPolicy))
+    }
+
+    pub fn with_inner(deadline: Duration, inner: Arc<dyn RetryPolicy>) -> DeadlineRetryPolicy {
+        DeadlineRetryPolicy { deadline, inner }
+    }
+
+    pub fn deadline(&self) -> Duration {
+        self.deadline
+    }
+}
+
+impl RetryPolicy for DeadlineRetryPolicy {
+    fn retry_delay(&self, context: &RetryContext) -> Option<Duration> {
+        let delay = self.inner.retry_delay(context)?;
+        if context.elapsed + delay >= self.deadline {
+            return None;
+        }
+        Some(delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(attempt: u8, elapsed: Duration) -> RetryContext {
+        RetryContext {
+            attempt,
+            max_attempts: 10,
+            node_account_id: AccountId::simple(3),
+            code: None,
+            status: Some(Status::Busy),
+            retryable: true,
+            min_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_millis(8000),
+            elapsed,
+        }
+    }
+
+    #[test]
+    fn test_default_retry_policy() {
+        let policy = DefaultRetryPolicy;
+        assert_eq!(
+            policy.retry_delay(&context(2, Duration::ZERO)),
+            Some(Duration::from_millis(1000))
+        );
+        assert_eq!(
+            policy.retry_delay(&context(9, Duration::ZERO)),
+            Some(Duration::from_millis(8000))
+        );
+
+        let mut unavailable = context(2, Duration::ZERO);
+        unavailable.code = Some(tonic::Code::Unavailable);
+        unavailable.status = None;
+        assert_eq!(policy.retry_delay(&unavailable), Some(Duration::ZERO));
+
+        let mut failed = context(1, Duration::ZERO);
+        failed.retryable = false;
+        assert_eq!(policy.retry_delay(&failed), None);
+    }
+
+    #[test]
+    fn test_exponential_jitter_retry_policy() {
+        let policy =
+            ExponentialJitterRetryPolicy::new(Duration::from_millis(100), Duration::from_secs(1));
+        for attempt in 1..20 {
+            let delay = policy
+                .retry_delay(&context(attempt, Duration::ZERO))
+                .unwrap();
+            assert!(delay <= policy.ceiling(attempt));
+        }
+        assert_eq!(policy.ceiling(3), Duration::from_millis(400));
+        assert_eq!(policy.ceiling(200), Duration::from_secs(1));
+    }
+
+    #[test]
+    fn test_deadline_retry_policy() {
+        let policy = DeadlineRetryPolicy::new(Duration::from_secs(5));
+        assert_eq!(
+            policy.retry_delay(&context(2, Duration::from_secs(1))),
+            Some(Duration::from_millis(1000))
+        );
+        assert_eq!(
+            policy.retry_delay(&context(2, Duration::from_secs(4))),
+            None
+        );
+    }
+}
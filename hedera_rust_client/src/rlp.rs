@@ -0,0 +1,116 @@
+use crate::error::HederaError;
+
+// Minimal Recursive Length Prefix codec, enough to pick apart and rebuild
+// signed ethereum transaction envelopes.
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RlpItem {
+    Bytes(Vec<u8>),
+    List(Vec<RlpItem>),
+}
+
+impl RlpItem {
+    pub fn decode(data: &[u8]) -> Result<RlpItem, HederaError> {
+        let (item, rest) = decode_item(data)?;
+        if !rest.is_empty() {
+            return Err(HederaError::InvalidEthereumData(
+                "trailing bytes after rlp item".to_string(),
+            ));
+        }
+        Ok(item)
+    }
+
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            RlpItem::Bytes(bytes) => {
+                if bytes.len() == 1 && bytes[0] < 0x80 {
+                    return bytes.clone();
+                }
+                let mut out = encode_length(bytes.len(), 0x80);
+                out.extend_from_slice(bytes);
+                out
+            }
+            RlpItem::List(items) => {
+                let payload: Vec<u8> = items.iter().flat_map(|x| x.encode()).collect();
+                let mut out = encode_length(payload.len(), 0xc0);
+                out.extend(payload);
+                out
+            }
+        }
+    }
+}
+
+fn encode_length(len: usize, offset: u8) -> Vec<u8> {
+    if len < 56 {
+        return vec![offset + len as u8];
+    }
+    let len_bytes: Vec<u8> = len
+        .to_be_bytes()
+        .iter()
+        .skip_while(|b| **b == 0)
+        .cloned()
+        .collect();
+    let mut out = vec![offset + 55 + len_bytes.len() as u8];
+    out.extend(len_bytes);
+    out
+}
+
+fn split_at_checked(data: &[u8], at: usize) -> Result<(&[u8], &[u8]), HederaError> {
+    if at > data.len() {
+        return Err(HederaError::InvalidEthereumData(
+            "rlp item length exceeds input".to_string(),
+        ));
+    }
+    Ok(data.split_at(at))
+}
+
+fn decode_long_length(data: &[u8], len_of_len: usize) -> Result<(usize, &[u8]), HederaError> {
+    let (len_bytes, rest) = split_at_checked(data, len_of_len)?;
+    if len_bytes.len() > std::mem::size_of::<usize>() {
+        return Err(HederaError::InvalidEthereumData(
+            "rlp length overflow".to_string(),
+        ));
+    }
+    let len = len_bytes
+        .iter()
+        .fold(0usize, |acc, b| (acc << 8) | *b as usize);
+    Ok((len, rest))
+}
+
+fn decode_item(data: &[u8]) -> Result<(RlpItem, &[u8]), HederaError> {
+    let prefix = *data.first().ok_or_else(|| {
+        HederaError::InvalidEthereumData("unexpected end of rlp input".to_string())
+    })?;
+    let data = &data[1..];
+    match prefix {
+        0x00..=0x7f => Ok((RlpItem::Bytes(vec![prefix]), data)),
+        0x80..=0xb7 => {
+            let (bytes, rest) = split_at_checked(data, (prefix - 0x80) as usize)?;
+            Ok((RlpItem::Bytes(bytes.to_vec()), rest))
+        }
+        0xb8..=0xbf => {
+            let (len, data) = decode_long_length(data, (prefix - 0xb7) as usize)?;
+            let (bytes, rest) = split_at_checked(data, len)?;
+            Ok((RlpItem::Bytes(bytes.to_vec()), rest))
+        }
+        0xc0..=0xf7 => {
+            let (payload, rest) = split_at_checked(data, (prefix - 0xc0) as usize)?;
+            Ok((RlpItem::List(decode_list(payload)?), rest))
+        }
+        0xf8..=0xff => {
+            let (len, data) = decode_long_length(data, (prefix - 0xf7) as usize)?;
+            let (payload, rest) = split_at_checked(data, len)?;
+            Ok((RlpItem::List(decode_list(payload)?), rest))
+        }
+    }
+}
+
+fn decode_list(mut payload: &[u8]) -> Result<Vec<RlpItem>, HederaError> {
+    let mut items = Vec::new();
+    while !payload.is_empty() {
+        let (item, rest) = decode_item(payload)?;
+        items.push(item);
+        payload = rest;
+    }
+    Ok(items)
+}
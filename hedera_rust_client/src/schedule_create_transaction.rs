@@ -1,4 +1,5 @@
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
 
 use crate::entity_id::validate_option_id_checksum;
 use crate::error::HederaError;
@@ -50,18 +51,20 @@ impl ScheduleCreateTransaction {
     gen_transaction_memo_fns!();
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "ScheduleCreate",
     proto_type = "ScheduleCreateTransactionBody"
 ))]
 struct Proto {
+    #[serde(serialize_with = "crate::json::option_schedulable_transaction_body")]
     pub scheduled_transaction_body: Option<services::SchedulableTransactionBody>,
     pub memo: String,
     #[hedera_rust_client_derive(to_option_proto)]
     pub admin_key: Option<Key>,
     #[hedera_rust_client_derive(to_option_proto)]
     pub payer_account_id: Option<AccountId>,
+    #[serde(serialize_with = "crate::json::option_timestamp")]
     pub expiration_time: Option<services::Timestamp>,
     pub wait_for_expiry: bool,
 }
@@ -1,4 +1,5 @@
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
 
 use crate::entity_id::validate_option_id_checksum;
 
@@ -36,7 +37,7 @@ impl ScheduleDeleteTransaction {
     gen_transaction_schedule_id_fns!();
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "ScheduleDelete",
     proto_type = "ScheduleDeleteTransactionBody"
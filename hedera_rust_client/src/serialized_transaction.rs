@@ -0,0 +1,288 @@
+use std::collections::HashMap;
+
+use crate::error::HederaError;
+use crate::transaction::Transaction;
+use crate::{AccountId, Key, KeyRequirement, PrivateKey, PublicKey, Signature, TransactionId};
+
+/// A frozen transaction in its `to_bytes()` form, passed between the parties
+/// that have to sign it.
+///
+/// Every node gets its own body, so each party signs all of them, or the
+/// bodies of a single node. The copies coming back are merged into one,
+/// and `signers` shows which keys are still missing for which node.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SerializedTransaction {
+    transaction: Transaction,
+}
+
+impl SerializedTransaction {
+    pub fn from_bytes(bytes: &[u8]) -> Result<SerializedTransaction, HederaError> {
+        let transaction = Transaction::from_bytes(bytes.to_vec())?;
+        if !transaction.is_frozen() {
+            return Err(HederaError::TransactionSignWithFailed);
+        }
+        Ok(SerializedTransaction { transaction })
+    }
+
+    pub fn to_bytes(&mut self) -> Result<Vec<u8>, HederaError> {
+        self.transaction.to_bytes()
+    }
+
+    pub fn transaction_id(&self) -> Result<TransactionId, HederaError> {
+        self.transaction.transaction_id()
+    }
+
+    pub fn node_account_ids(&self) -> Vec<AccountId> {
+        self.transaction.node_account_ids()
+    }
+
+    /// Signs the bodies of every node.
+    pub fn sign(&mut self, private_key: &PrivateKey) -> Result<&mut Self, HederaError> {
+        self.transaction.sign(private_key)?;
+        Ok(self)
+    }
+
+    pub fn sign_for_node(
+        &mut self,
+        node_account_id: AccountId,
+        private_key: &PrivateKey,
+    ) -> Result<&mut Self, HederaError> {
+        self.transaction
+            .sign_for_node(node_account_id, private_key)?;
+        Ok(self)
+    }
+
+    /// The bodies to sign for `node_account_id`, one per chunk.
+    pub fn body_bytes_for_node(
+        &self,
+        node_account_id: AccountId,
+    ) -> Result<Vec<Vec<u8>>, HederaError> {
+        self.transaction.body_bytes_for_node(node_account_id)
+    }
+
+    /// Adds a signature over one of the bodies of `node_account_id`, after
+    /// verifying it.
+    pub fn add_signature(
+        &mut self,
+        node_account_id: AccountId,
+        public_key: PublicKey,
+        signature: Signature,
+    ) -> Result<&mut Self, HederaError> {
+        self.transaction
+            .add_signature_for_node(node_account_id, public_key, signature)?;
+        Ok(self)
+    }
+
+    /// Takes the signatures of `other` that this copy lacks. Fails with
+    /// `TransactionBodyMismatch` if the two aren't copies of one transaction.
+    pub fn merge(&mut self, other: &SerializedTransaction) -> Result<&mut Self, HederaError> {
+        self.transaction.merge_signatures(&other.transaction)?;
+        Ok(self)
+    }
+
+    pub fn merge_bytes(&mut self, bytes: &[u8]) -> Result<&mut Self, HederaError> {
+        let other = SerializedTransaction::from_bytes(bytes)?;
+        self.merge(&other)
+    }
+
+    /// The keys that signed the bodies of each node.
+    pub fn signers(&self) -> Result<HashMap<AccountId, Vec<PublicKey>>, HederaError> {
+        self.transaction.signers()
+    }
+
+    /// Whether the valid signatures on the bodies of each node satisfy `key`.
+    pub fn key_requirement(
+        &self,
+        key: &Key,
+    ) -> Result<HashMap<AccountId, KeyRequirement>, HederaError> {
+        self.transaction.key_requirement(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use crate::{Hbar, TransferTransaction};
+
+    async fn frozen_bytes() -> Result<Vec<u8>, HederaError> {
+        let mut tx = TransferTransaction::new();
+        tx.add_hbar_transfer(AccountId::simple(2), Hbar::from_tinybar(-100))?
+            .add_hbar_transfer(AccountId::simple(3), Hbar::from_tinybar(100))?
+            .set_transaction_id(mock_transaction_id())?
+            .set_node_account_ids(vec![AccountId::simple(3), AccountId::simple(4)])?
+            .freeze()
+            .await?;
+        tx.to_bytes()
+    }
+
+    #[tokio::test]
+    async fn test_serialized_transaction_merge() -> Result<(), HederaError> {
+        let bytes = frozen_bytes().await?;
+        let alice = PrivateKey::new();
+        let bob = PrivateKey::new();
+        let carol = PrivateKey::new();
+
+        let mut alice_copy = SerializedTransaction::from_bytes(&bytes)?;
+        alice_copy.sign(&alice)?;
+
+        // bob signs node 0.0.4 offline
+        let mut bob_copy = SerializedTransaction::from_bytes(&bytes)?;
+        let body = bob_copy.body_bytes_for_node(AccountId::simple(4))?;
+        let signature = bob.sign(&body[0]);
+        assert!(matches!(
+            bob_copy.add_signature(AccountId::simple(3), bob.public(), signature),
+            Err(HederaError::InvalidTransactionSignature(_))
+        ));
+        bob_copy.add_signature(AccountId::simple(4), bob.public(), signature)?;
+
+        let mut carol_copy = SerializedTransaction::from_bytes(&bytes)?;
+        carol_copy.sign_for_node(AccountId::simple(3), &carol)?;
+
+        let mut merged = SerializedTransaction::from_bytes(&bytes)?;
+        merged
+            .merge(&alice_copy)?
+            .merge(&bob_copy)?
+            .merge_bytes(&carol_copy.to_bytes()?)?
+            // merging twice changes nothing
+            .merge(&alice_copy)?;
+
+        let signers = merged.signers()?;
+        assert_eq!(
+            signers[&AccountId::simple(3)],
+            vec![alice.public(), carol.public()]
+        );
+        assert_eq!(
+            signers[&AccountId::simple(4)],
+            vec![alice.public(), bob.public()]
+        );
+
+        let mut other = TransferTransaction::new();
+        other
+            .add_hbar_transfer(AccountId::simple(2), Hbar::from_tinybar(-1))?
+            .add_hbar_transfer(AccountId::simple(3), Hbar::from_tinybar(1))?
+            .set_transaction_id(mock_transaction_id())?
+            .set_node_account_ids(vec![AccountId::simple(3), AccountId::simple(4)])?
+            .freeze()
+            .await?;
+        assert!(matches!(
+            merged.merge_bytes(&other.to_bytes()?),
+            Err(HederaError::TransactionBodyMismatch(0))
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_serialized_transaction_merge_tampered() -> Result<(), HederaError> {
+        use crate::proto::services::signature_pair::Signature as ProtoSignature;
+
+        let bytes = frozen_bytes().await?;
+        let alice = PrivateKey::new();
+        let mut alice_copy = SerializedTransaction::from_bytes(&bytes)?;
+        alice_copy.sign(&alice)?;
+
+        // a blob carrying alice's key prefix with corrupted signatures
+        let mut tampered = alice_copy.clone();
+        for st in tampered.transaction.signed_transactions.iter_mut() {
+            for pair in st.sig_map.as_mut().unwrap().sig_pair.iter_mut() {
+                if let Some(ProtoSignature::Ed25519(bytes)) = pair.signature.as_mut() {
+                    bytes[0] ^= 0xff;
+                }
+            }
+        }
+        let tampered_bytes = tampered.to_bytes()?;
+
+        // invalid pairs aren't merged
+        let mut merged = SerializedTransaction::from_bytes(&bytes)?;
+        merged.merge_bytes(&tampered_bytes)?;
+        assert!(merged.signers()?[&AccountId::simple(3)].is_empty());
+
+        // and don't block the real signature
+        merged.merge(&alice_copy)?;
+        let key = Key::Ed25519(alice.public());
+        for requirement in merged.key_requirement(&key)?.values() {
+            assert_eq!(*requirement, KeyRequirement::Satisfied);
+        }
+
+        // a valid pair replaces an invalid one already held
+        let mut tampered = SerializedTransaction::from_bytes(&tampered_bytes)?;
+        assert!(matches!(
+            tampered.key_requirement(&key)?[&AccountId::simple(3)],
+            KeyRequirement::Missing(_)
+        ));
+        tampered.merge(&alice_copy)?;
+        assert_eq!(
+            tampered.signers()?[&AccountId::simple(3)],
+            vec![alice.public()]
+        );
+        for requirement in tampered.key_requirement(&key)?.values() {
+            assert_eq!(*requirement, KeyRequirement::Satisfied);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_serialized_transaction_truncated_prefix() -> Result<(), HederaError> {
+        let bytes = frozen_bytes().await?;
+        let alice = PrivateKey::new();
+        let bob = PrivateKey::new();
+        let mut tx = SerializedTransaction::from_bytes(&bytes)?;
+        tx.sign(&alice)?.sign(&bob)?;
+
+        // bob's pairs only carry the first bytes of his key
+        let bob_key = bob.public().as_bytes_vec();
+        for st in tx.transaction.signed_transactions.iter_mut() {
+            for pair in st.sig_map.as_mut().unwrap().sig_pair.iter_mut() {
+                if pair.pub_key_prefix == bob_key {
+                    pair.pub_key_prefix.truncate(6);
+                }
+            }
+        }
+
+        let signers = tx.signers()?;
+        assert_eq!(signers[&AccountId::simple(3)], vec![alice.public()]);
+        assert_eq!(signers[&AccountId::simple(4)], vec![alice.public()]);
+        let signatures = tx.transaction.signatures()?;
+        assert_eq!(signatures[&AccountId::simple(3)].len(), 1);
+        assert!(signatures[&AccountId::simple(3)].contains_key(&alice.public()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_serialized_transaction_key_requirement() -> Result<(), HederaError> {
+        let bytes = frozen_bytes().await?;
+        let keys = (0..3).map(|_| PrivateKey::new()).collect::<Vec<_>>();
+        let mut threshold = crate::KeyList::with_threshold(2);
+        threshold.add_all_public_keys(keys.iter().map(|key| key.public()).collect());
+        let key = Key::ThresholdKey(threshold);
+
+        let mut tx = SerializedTransaction::from_bytes(&bytes)?;
+        tx.sign(&keys[0])?;
+        let requirement = tx.key_requirement(&key)?;
+        assert_eq!(
+            requirement[&AccountId::simple(3)],
+            KeyRequirement::Missing(vec![keys[1].public()])
+        );
+
+        // a signature over another body doesn't count
+        let mut other = TransferTransaction::new();
+        other
+            .add_hbar_transfer(AccountId::simple(2), Hbar::from_tinybar(-1))?
+            .add_hbar_transfer(AccountId::simple(3), Hbar::from_tinybar(1))?
+            .set_transaction_id(mock_transaction_id())?
+            .set_node_account_ids(vec![AccountId::simple(3), AccountId::simple(4)])?
+            .freeze()
+            .await?;
+        let other = SerializedTransaction::from_bytes(&other.to_bytes()?)?;
+        let signature = keys[1].sign(&other.body_bytes_for_node(AccountId::simple(3))?[0]);
+        tx.transaction.signed_transactions[0]
+            .add_signature_pair(keys[1].public().to_signature_pair_protobuf(&signature))?;
+        assert!(!tx.key_requirement(&key)?[&AccountId::simple(3)].is_satisfied());
+
+        tx.sign_for_node(AccountId::simple(4), &keys[2])?;
+        let requirement = tx.key_requirement(&key)?;
+        assert!(requirement[&AccountId::simple(4)].is_satisfied());
+        assert!(!requirement[&AccountId::simple(3)].is_satisfied());
+        Ok(())
+    }
+}
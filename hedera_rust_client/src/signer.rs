@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use std::fmt;
+
+use crate::crypto::{KeyAlgorithm, PrivateKey, PublicKey, Signature};
+use crate::error::HederaError;
+
+/// Signs transaction bodies with a key that may live outside the process,
+/// e.g. in an HSM, a KMS or a remote signing service.
+#[async_trait]
+pub trait Signer: Send + Sync + fmt::Debug {
+    fn public_key(&self) -> PublicKey;
+
+    fn key_algorithm(&self) -> KeyAlgorithm {
+        self.public_key().algorithm()
+    }
+
+    /// Signs `message`, the bytes of a transaction body.
+    async fn sign(&self, message: &[u8]) -> Result<Signature, HederaError>;
+}
+
+/// A `Signer` over a `PrivateKey` held in memory.
+#[derive(Clone)]
+pub struct LocalSigner {
+    private_key: PrivateKey,
+    public_key: PublicKey,
+}
+
+impl LocalSigner {
+    pub fn new(private_key: PrivateKey) -> LocalSigner {
+        let public_key = private_key.public();
+        LocalSigner {
+            private_key,
+            public_key,
+        }
+    }
+}
+
+// keeps the private key out of logs
+impl fmt::Debug for LocalSigner {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LocalSigner")
+            .field("public_key", &self.public_key)
+            .finish()
+    }
+}
+
+impl From<PrivateKey> for LocalSigner {
+    fn from(private_key: PrivateKey) -> LocalSigner {
+        LocalSigner::new(private_key)
+    }
+}
+
+#[async_trait]
+impl Signer for LocalSigner {
+    fn public_key(&self) -> PublicKey {
+        self.public_key
+    }
+
+    async fn sign(&self, message: &[u8]) -> Result<Signature, HederaError> {
+        Ok(self.private_key.sign(message))
+    }
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 
 use crate::account_id::AccountId;
 use crate::error::HederaError;
@@ -86,3 +87,67 @@ impl ToProto<services::crypto_update_transaction_body::StakedId> for StakedId {
         Ok(pb)
     }
 }
+
+impl TryFrom<services::contract_create_transaction_body::StakedId> for StakedId {
+    type Error = HederaError;
+    fn try_from(
+        services: services::contract_create_transaction_body::StakedId,
+    ) -> Result<StakedId, Self::Error> {
+        Ok(match services {
+            services::contract_create_transaction_body::StakedId::StakedAccountId(id) => {
+                StakedId::StakedAccountId(AccountId::try_from(id)?)
+            }
+            services::contract_create_transaction_body::StakedId::StakedNodeId(id) => {
+                StakedId::StakedNodeId(id)
+            }
+        })
+    }
+}
+
+impl TryFrom<services::contract_update_transaction_body::StakedId> for StakedId {
+    type Error = HederaError;
+    fn try_from(
+        services: services::contract_update_transaction_body::StakedId,
+    ) -> Result<StakedId, Self::Error> {
+        Ok(match services {
+            services::contract_update_transaction_body::StakedId::StakedAccountId(id) => {
+                StakedId::StakedAccountId(AccountId::try_from(id)?)
+            }
+            services::contract_update_transaction_body::StakedId::StakedNodeId(id) => {
+                StakedId::StakedNodeId(id)
+            }
+        })
+    }
+}
+
+impl TryFrom<services::crypto_create_transaction_body::StakedId> for StakedId {
+    type Error = HederaError;
+    fn try_from(
+        services: services::crypto_create_transaction_body::StakedId,
+    ) -> Result<StakedId, Self::Error> {
+        Ok(match services {
+            services::crypto_create_transaction_body::StakedId::StakedAccountId(id) => {
+                StakedId::StakedAccountId(AccountId::try_from(id)?)
+            }
+            services::crypto_create_transaction_body::StakedId::StakedNodeId(id) => {
+                StakedId::StakedNodeId(id)
+            }
+        })
+    }
+}
+
+impl TryFrom<services::crypto_update_transaction_body::StakedId> for StakedId {
+    type Error = HederaError;
+    fn try_from(
+        services: services::crypto_update_transaction_body::StakedId,
+    ) -> Result<StakedId, Self::Error> {
+        Ok(match services {
+            services::crypto_update_transaction_body::StakedId::StakedAccountId(id) => {
+                StakedId::StakedAccountId(AccountId::try_from(id)?)
+            }
+            services::crypto_update_transaction_body::StakedId::StakedNodeId(id) => {
+                StakedId::StakedNodeId(id)
+            }
+        })
+    }
+}
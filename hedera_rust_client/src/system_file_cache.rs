@@ -0,0 +1,114 @@
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crate::FileId;
+
+// address books and throttles don't say when they change
+const DEFAULT_TTL: Duration = Duration::from_secs(3600);
+
+#[derive(Debug, Clone)]
+struct CachedFile {
+    contents: Vec<u8>,
+    expires_at: Option<DateTime<Utc>>,
+}
+
+/// System file contents shared by a client and its clones. A file is kept
+/// until the expiry of its contents, e.g. the current exchange rate, or the
+/// TTL, whichever comes first.
+#[derive(Debug, Clone)]
+pub(crate) struct SystemFileCache {
+    files: Arc<Mutex<HashMap<FileId, CachedFile>>>,
+    ttl: Duration,
+}
+
+impl Default for SystemFileCache {
+    fn default() -> Self {
+        SystemFileCache {
+            files: Arc::new(Mutex::new(HashMap::new())),
+            ttl: DEFAULT_TTL,
+        }
+    }
+}
+
+impl SystemFileCache {
+    pub fn set_ttl(&mut self, ttl: Duration) {
+        self.ttl = ttl;
+    }
+
+    pub fn get(&self, file_id: &FileId, now: DateTime<Utc>) -> Option<Vec<u8>> {
+        let mut files = self.files.lock().unwrap();
+        match files.get(file_id) {
+            Some(file) if file.expires_at.map_or(true, |expiry| now < expiry) => {
+                Some(file.contents.clone())
+            }
+            Some(_) => {
+                files.remove(file_id);
+                None
+            }
+            None => None,
+        }
+    }
+
+    pub fn insert(
+        &self,
+        file_id: FileId,
+        contents: Vec<u8>,
+        expiry: Option<DateTime<Utc>>,
+        now: DateTime<Utc>,
+    ) {
+        // a ttl too long to add never expires
+        let ttl_expiry = chrono::Duration::from_std(self.ttl)
+            .ok()
+            .and_then(|ttl| now.checked_add_signed(ttl));
+        let expires_at = match (expiry, ttl_expiry) {
+            (Some(expiry), Some(ttl_expiry)) => Some(expiry.min(ttl_expiry)),
+            (expiry, ttl_expiry) => expiry.or(ttl_expiry),
+        };
+        self.files.lock().unwrap().insert(
+            file_id,
+            CachedFile {
+                contents,
+                expires_at,
+            },
+        );
+    }
+
+    pub fn clear(&self) {
+        self.files.lock().unwrap().clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_system_file_cache_expiry() {
+        let cache = SystemFileCache::default();
+        let now = Utc::now();
+        cache.insert(
+            FileId::EXCHANGE_RATES,
+            vec![1],
+            Some(now + Duration::seconds(60)),
+            now,
+        );
+        cache.insert(FileId::ADDRESS_BOOK, vec![2], None, now);
+
+        assert_eq!(cache.get(&FileId::EXCHANGE_RATES, now), Some(vec![1]));
+        // the exchange rate expires first, the address book at the ttl
+        let later = now + Duration::seconds(61);
+        assert_eq!(cache.get(&FileId::EXCHANGE_RATES, later), None);
+        assert_eq!(cache.get(&FileId::ADDRESS_BOOK, later), Some(vec![2]));
+        assert_eq!(
+            cache.get(&FileId::ADDRESS_BOOK, now + Duration::seconds(3600)),
+            None
+        );
+
+        cache.insert(FileId::FEE_SCHEDULE, vec![3], None, now);
+        cache.clone().clear();
+        assert_eq!(cache.get(&FileId::FEE_SCHEDULE, now), None);
+    }
+}
@@ -0,0 +1,530 @@
+//! An in-process consensus node for exercising the SDK offline.
+//!
+//! A `MockNode` serves every consensus node gRPC service on `127.0.0.1` and
+//! answers each incoming request with the next queued `MockResponse`:
+//!
+//! ```ignore
+//! let node = MockNode::start(AccountId::simple(3)).await?;
+//! node.push_response(MockResponse::Precheck(Status::Ok));
+//! node.push_response(MockResponse::receipt(Status::Success));
+//!
+//! let client = ClientBuilder::default()
+//!     .operator(operator)
+//!     .network(Network::from_network(node.network())?)
+//!     .mirror_network(MirrorNetwork::from_network(Vec::new())?)
+//!     .build()?;
+//! ```
+//!
+//! A `LedgerEmulator` instead keeps ledger state and answers like a real
+//! network would.
+//!
+//! A `MockMirrorNode` answers topic subscriptions with queued messages.
+//!
+//! A `RemoteSignerServer` serves a `Signer` on loopback to `RemoteSigner`s.
+use std::collections::{HashMap, VecDeque};
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::Server;
+use tonic::{Request, Response};
+
+use crate::error::HederaError;
+use crate::proto::services::{
+    consensus_service_server::{ConsensusService, ConsensusServiceServer},
+    crypto_service_server::{CryptoService, CryptoServiceServer},
+    file_service_server::{FileService, FileServiceServer},
+    freeze_service_server::{FreezeService, FreezeServiceServer},
+    network_service_server::{NetworkService, NetworkServiceServer},
+    query, response,
+    schedule_service_server::{ScheduleService, ScheduleServiceServer},
+    smart_contract_service_server::{SmartContractService, SmartContractServiceServer},
+    token_service_server::{TokenService, TokenServiceServer},
+    util_service_server::{UtilService, UtilServiceServer},
+};
+use crate::status::Status;
+use crate::AccountId;
+
+/// The raw mirror protobuf types, to script topic messages.
+pub use crate::proto::mirror;
+/// The raw protobuf types, to script receipts, records and query answers.
+pub use crate::proto::services;
+
+/// How the mock node answers the next request.
+#[derive(Debug, Clone)]
+pub enum MockResponse {
+    /// Answers a transaction, or any query, with this precheck status.
+    Precheck(Status),
+    /// Answers a `COST_ANSWER` query with this cost in tinybars.
+    Cost(u64),
+    /// Answers a `TransactionReceiptQuery`.
+    Receipt(services::TransactionReceipt),
+    /// Answers a `TransactionRecordQuery`.
+    Record(services::TransactionRecord),
+    /// Answers a query with this response as is.
+    Query(services::response::Response),
+    /// Fails the gRPC call itself.
+    Error(tonic::Code),
+    /// Waits before answering with the inner response.
+    Delay(Duration, Box<MockResponse>),
+}
+
+impl MockResponse {
+    /// A receipt carrying only `status`.
+    pub fn receipt(status: Status) -> MockResponse {
+        MockResponse::Receipt(services::TransactionReceipt {
+            status: status as i32,
+            ..Default::default()
+        })
+    }
+
+    /// A record whose receipt carries only `status`.
+    pub fn record(status: Status) -> MockResponse {
+        MockResponse::Record(services::TransactionRecord {
+            receipt: Some(services::TransactionReceipt {
+                status: status as i32,
+                ..Default::default()
+            }),
+            ..Default::default()
+        })
+    }
+
+    pub fn delay(delay: Duration, response: MockResponse) -> MockResponse {
+        MockResponse::Delay(delay, Box::new(response))
+    }
+}
+
+/// A request received by the mock node.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MockRequest {
+    Transaction(services::Transaction),
+    Query(services::Query),
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    responses: VecDeque<MockResponse>,
+    requests: Vec<MockRequest>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct MockService {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockService {
+    fn next_response(&self, request: MockRequest) -> Option<MockResponse> {
+        let mut state = self.state.lock().unwrap();
+        state.requests.push(request);
+        state.responses.pop_front()
+    }
+
+    async fn transaction(
+        &self,
+        request: Request<services::Transaction>,
+    ) -> Result<Response<services::TransactionResponse>, tonic::Status> {
+        let next = self
+            .delayed(self.next_response(MockRequest::Transaction(request.into_inner())))
+            .await;
+        match next {
+            Some(MockResponse::Precheck(status)) => {
+                Ok(Response::new(services::TransactionResponse {
+                    node_transaction_precheck_code: status as i32,
+                    cost: 0,
+                }))
+            }
+            Some(MockResponse::Error(code)) => Err(tonic::Status::new(code, "mock error")),
+            Some(response) => Err(tonic::Status::internal(format!(
+                "mock response does not answer a transaction: {:?}",
+                response
+            ))),
+            None => Err(tonic::Status::failed_precondition(
+                "no mock response queued",
+            )),
+        }
+    }
+
+    async fn query(
+        &self,
+        request: Request<services::Query>,
+    ) -> Result<Response<services::Response>, tonic::Status> {
+        let query = request.into_inner();
+        let next = self
+            .delayed(self.next_response(MockRequest::Query(query.clone())))
+            .await;
+        let query = query
+            .query
+            .ok_or_else(|| tonic::Status::invalid_argument("empty query"))?;
+        let response = match next {
+            Some(MockResponse::Precheck(status)) => response_for_query(&query, header(status, 0)),
+            Some(MockResponse::Cost(cost)) => response_for_query(&query, header(Status::Ok, cost)),
+            Some(MockResponse::Receipt(receipt)) => {
+                response::Response::TransactionGetReceipt(services::TransactionGetReceiptResponse {
+                    header: Some(header(Status::Ok, 0)),
+                    receipt: Some(receipt),
+                    ..Default::default()
+                })
+            }
+            Some(MockResponse::Record(record)) => {
+                response::Response::TransactionGetRecord(services::TransactionGetRecordResponse {
+                    header: Some(header(Status::Ok, 0)),
+                    transaction_record: Some(record),
+                    ..Default::default()
+                })
+            }
+            Some(MockResponse::Query(response)) => response,
+            Some(MockResponse::Error(code)) => return Err(tonic::Status::new(code, "mock error")),
+            Some(MockResponse::Delay(..)) => unreachable!("delays are resolved first"),
+            None => {
+                return Err(tonic::Status::failed_precondition(
+                    "no mock response queued",
+                ))
+            }
+        };
+        Ok(Response::new(services::Response {
+            response: Some(response),
+        }))
+    }
+
+    // waits out any delays wrapped around the response
+    async fn delayed(&self, mut next: Option<MockResponse>) -> Option<MockResponse> {
+        while let Some(MockResponse::Delay(delay, response)) = next {
+            tokio::time::sleep(delay).await;
+            next = Some(*response);
+        }
+        next
+    }
+}
+
+fn header(status: Status, cost: u64) -> services::ResponseHeader {
+    services::ResponseHeader {
+        node_transaction_precheck_code: status as i32,
+        cost,
+        ..Default::default()
+    }
+}
+
+// answers `query` with the matching response type, carrying only `header`
+macro_rules! response_for_query {
+    (
+        $query:expr,
+        $header:expr,
+        [$($query_enum:ident => $response_enum:ident($response:ident)),* $(,)?]
+    ) => {
+        match $query {
+            $(
+                query::Query::$query_enum(_) => response::Response::$response_enum(services::$response {
+                    header: Some($header),
+                    ..Default::default()
+                }),
+            )*
+        }
+    };
+}
+
+fn response_for_query(
+    query: &query::Query,
+    header: services::ResponseHeader,
+) -> response::Response {
+    response_for_query!(query, header, [
+        GetByKey => GetByKey(GetByKeyResponse),
+        GetBySolidityId => GetBySolidityId(GetBySolidityIdResponse),
+        ContractCallLocal => ContractCallLocal(ContractCallLocalResponse),
+        ContractGetInfo => ContractGetInfo(ContractGetInfoResponse),
+        ContractGetBytecode => ContractGetBytecodeResponse(ContractGetBytecodeResponse),
+        ContractGetRecords => ContractGetRecordsResponse(ContractGetRecordsResponse),
+        CryptogetAccountBalance => CryptogetAccountBalance(CryptoGetAccountBalanceResponse),
+        CryptoGetAccountRecords => CryptoGetAccountRecords(CryptoGetAccountRecordsResponse),
+        CryptoGetInfo => CryptoGetInfo(CryptoGetInfoResponse),
+        CryptoGetLiveHash => CryptoGetLiveHash(CryptoGetLiveHashResponse),
+        CryptoGetProxyStakers => CryptoGetProxyStakers(CryptoGetStakersResponse),
+        FileGetContents => FileGetContents(FileGetContentsResponse),
+        FileGetInfo => FileGetInfo(FileGetInfoResponse),
+        TransactionGetReceipt => TransactionGetReceipt(TransactionGetReceiptResponse),
+        TransactionGetRecord => TransactionGetRecord(TransactionGetRecordResponse),
+        TransactionGetFastRecord => TransactionGetFastRecord(TransactionGetFastRecordResponse),
+        ConsensusGetTopicInfo => ConsensusGetTopicInfo(ConsensusGetTopicInfoResponse),
+        NetworkGetVersionInfo => NetworkGetVersionInfo(NetworkGetVersionInfoResponse),
+        TokenGetInfo => TokenGetInfo(TokenGetInfoResponse),
+        ScheduleGetInfo => ScheduleGetInfo(ScheduleGetInfoResponse),
+        TokenGetAccountNftInfos => TokenGetAccountNftInfos(TokenGetAccountNftInfosResponse),
+        TokenGetNftInfo => TokenGetNftInfo(TokenGetNftInfoResponse),
+        TokenGetNftInfos => TokenGetNftInfos(TokenGetNftInfosResponse),
+        NetworkGetExecutionTime => NetworkGetExecutionTime(NetworkGetExecutionTimeResponse),
+        AccountDetails => AccountDetails(GetAccountDetailsResponse),
+    ])
+}
+
+// implements a generated service trait, forwarding every method to the
+// `transaction` and `query` methods of `$handler`
+macro_rules! impl_node_service {
+    (
+        $handler:ty,
+        $service:ident,
+        transactions: [$($tx_fn:ident),* $(,)?],
+        queries: [$($query_fn:ident),* $(,)?]
+    ) => {
+        #[tonic::async_trait]
+        impl $service for $handler {
+            $(
+                async fn $tx_fn(
+                    &self,
+                    request: Request<services::Transaction>,
+                ) -> Result<Response<services::TransactionResponse>, tonic::Status> {
+                    self.transaction(request).await
+                }
+            )*
+            $(
+                async fn $query_fn(
+                    &self,
+                    request: Request<services::Query>,
+                ) -> Result<Response<services::Response>, tonic::Status> {
+                    self.query(request).await
+                }
+            )*
+        }
+    };
+}
+
+// implements every consensus node service for `$handler`
+macro_rules! impl_node_services {
+    ($handler:ty) => {
+        impl_node_service!(
+            $handler,
+            CryptoService,
+            transactions: [
+                create_account,
+                update_account,
+                crypto_transfer,
+                crypto_delete,
+                approve_allowances,
+                delete_allowances,
+                add_live_hash,
+                delete_live_hash,
+            ],
+            queries: [
+                get_live_hash,
+                get_account_records,
+                crypto_get_balance,
+                get_account_info,
+                get_transaction_receipts,
+                get_fast_transaction_record,
+                get_tx_record_by_tx_id,
+                get_stakers_by_account_id,
+            ]
+        );
+
+        impl_node_service!(
+            $handler,
+            TokenService,
+            transactions: [
+                create_token,
+                update_token,
+                mint_token,
+                burn_token,
+                delete_token,
+                wipe_token_account,
+                freeze_token_account,
+                unfreeze_token_account,
+                grant_kyc_to_token_account,
+                revoke_kyc_from_token_account,
+                associate_tokens,
+                dissociate_tokens,
+                update_token_fee_schedule,
+                pause_token,
+                unpause_token,
+            ],
+            queries: [
+                get_token_info,
+                get_account_nft_infos,
+                get_token_nft_info,
+                get_token_nft_infos,
+            ]
+        );
+
+        impl_node_service!(
+            $handler,
+            ConsensusService,
+            transactions: [create_topic, update_topic, delete_topic, submit_message],
+            queries: [get_topic_info]
+        );
+
+        impl_node_service!(
+            $handler,
+            FileService,
+            transactions: [
+                create_file,
+                update_file,
+                delete_file,
+                append_content,
+                system_delete,
+                system_undelete,
+            ],
+            queries: [get_file_content, get_file_info]
+        );
+
+        impl_node_service!(
+            $handler,
+            SmartContractService,
+            transactions: [
+                create_contract,
+                update_contract,
+                contract_call_method,
+                delete_contract,
+                system_delete,
+                system_undelete,
+                call_ethereum,
+            ],
+            queries: [
+                get_contract_info,
+                contract_call_local_method,
+                contract_get_bytecode,
+                get_by_solidity_id,
+                get_tx_record_by_contract_id,
+            ]
+        );
+
+        impl_node_service!(
+            $handler,
+            ScheduleService,
+            transactions: [create_schedule, sign_schedule, delete_schedule],
+            queries: [get_schedule_info]
+        );
+
+        impl_node_service!($handler, FreezeService, transactions: [freeze], queries: []);
+
+        impl_node_service!(
+            $handler,
+            NetworkService,
+            transactions: [unchecked_submit],
+            queries: [get_version_info, get_execution_time, get_account_details]
+        );
+
+        impl_node_service!($handler, UtilService, transactions: [prng], queries: []);
+    };
+}
+
+impl_node_services!(MockService);
+
+mod emulator;
+mod ledger;
+mod mirror_node;
+
+pub use self::emulator::LedgerEmulator;
+pub use self::mirror_node::{MockMirrorNode, MockTopicResponse};
+pub use crate::remote_signer::RemoteSignerServer;
+
+// serves `service` on a free local port until `shutdown` fires or is dropped
+async fn serve<S>(service: S) -> Result<(SocketAddr, oneshot::Sender<()>), HederaError>
+where
+    S: CryptoService
+        + TokenService
+        + ConsensusService
+        + FileService
+        + SmartContractService
+        + ScheduleService
+        + FreezeService
+        + NetworkService
+        + UtilService
+        + Clone,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await?;
+    let address = listener.local_addr()?;
+    let (shutdown, shutdown_rx) = oneshot::channel::<()>();
+
+    let router = Server::builder()
+        .add_service(CryptoServiceServer::new(service.clone()))
+        .add_service(TokenServiceServer::new(service.clone()))
+        .add_service(ConsensusServiceServer::new(service.clone()))
+        .add_service(FileServiceServer::new(service.clone()))
+        .add_service(SmartContractServiceServer::new(service.clone()))
+        .add_service(ScheduleServiceServer::new(service.clone()))
+        .add_service(FreezeServiceServer::new(service.clone()))
+        .add_service(NetworkServiceServer::new(service.clone()))
+        .add_service(UtilServiceServer::new(service));
+    tokio::spawn(
+        router.serve_with_incoming_shutdown(TcpListenerStream::new(listener), async {
+            let _ = shutdown_rx.await;
+        }),
+    );
+    Ok((address, shutdown))
+}
+
+/// A consensus node served in-process on `127.0.0.1`, shut down on drop.
+#[derive(Debug)]
+pub struct MockNode {
+    account_id: AccountId,
+    address: SocketAddr,
+    service: MockService,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockNode {
+    /// Starts serving on a free local port.
+    pub async fn start(account_id: AccountId) -> Result<MockNode, HederaError> {
+        let service = MockService::default();
+        let (address, shutdown) = serve(service.clone()).await?;
+        Ok(MockNode {
+            account_id,
+            address,
+            service,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    pub fn account_id(&self) -> AccountId {
+        self.account_id
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// The node as a network map, for `Network::from_network` or
+    /// `Client::set_network`.
+    pub fn network(&self) -> HashMap<String, AccountId> {
+        let mut network = HashMap::new();
+        network.insert(self.address.to_string(), self.account_id);
+        network
+    }
+
+    /// Queues the answer to a future request. Requests are answered in the
+    /// order responses were queued, whatever service they target.
+    pub fn push_response(&self, response: MockResponse) {
+        self.service
+            .state
+            .lock()
+            .unwrap()
+            .responses
+            .push_back(response);
+    }
+
+    pub fn push_responses<I: IntoIterator<Item = MockResponse>>(&self, responses: I) {
+        self.service
+            .state
+            .lock()
+            .unwrap()
+            .responses
+            .extend(responses);
+    }
+
+    /// Number of queued responses not yet used.
+    pub fn pending_responses(&self) -> usize {
+        self.service.state.lock().unwrap().responses.len()
+    }
+
+    /// Every request received so far, oldest first.
+    pub fn requests(&self) -> Vec<MockRequest> {
+        self.service.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl Drop for MockNode {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
@@ -0,0 +1,2079 @@
+use bytes::Bytes;
+use chrono::{DateTime, Duration, Utc};
+use prost::Message;
+use sha2::{Digest, Sha384};
+use sha3::{Digest as _, Sha3_384};
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+
+use super::{header, response_for_query};
+use crate::crypto::{PublicKey, Signature};
+use crate::proto::services::{
+    self, crypto_get_account_balance_query, file_get_contents_response, file_get_info_response,
+    key, query, response, schedulable_transaction_body, signature_pair, transaction_body,
+};
+use crate::proto::ToProto;
+use crate::response_type::ResponseType;
+use crate::status::Status;
+use crate::AccountId;
+use crate::FileId;
+use crate::ScheduleId;
+use crate::TokenId;
+use crate::TopicId;
+use crate::TransactionId;
+
+const TOPIC_RUNNING_HASH_VERSION: u64 = 3;
+const FIRST_ENTITY_NUM: i64 = 1001;
+/// Seconds until a token without an expiry or auto renew period expires.
+const DEFAULT_TOKEN_LIFETIME: i64 = 7_890_000;
+
+type LedgerResult<T> = Result<T, Status>;
+
+#[derive(Debug, Clone, Default)]
+struct TokenRelation {
+    balance: u64,
+    frozen: bool,
+    kyc_granted: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Account {
+    key: services::Key,
+    balance: u64,
+    deleted: bool,
+    memo: String,
+    receiver_sig_required: bool,
+    tokens: HashMap<TokenId, TokenRelation>,
+    /// tinybars each spender may still transfer out of the account
+    hbar_allowances: HashMap<AccountId, u64>,
+    /// token units each spender may still transfer out of the account
+    token_allowances: HashMap<(TokenId, AccountId), u64>,
+}
+
+#[derive(Debug, Clone)]
+struct Nft {
+    owner: AccountId,
+    metadata: Vec<u8>,
+    creation_time: services::Timestamp,
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    info: services::TokenInfo,
+    next_serial: i64,
+    nfts: HashMap<i64, Nft>,
+}
+
+impl Token {
+    fn is_nft(&self) -> bool {
+        self.info.token_type == services::TokenType::NonFungibleUnique as i32
+    }
+
+    fn is_paused(&self) -> bool {
+        self.info.pause_status == services::TokenPauseStatus::Paused as i32
+    }
+
+    fn nft_owner(&self, serial: i64) -> Option<AccountId> {
+        self.nfts.get(&serial).map(|nft| nft.owner)
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Topic {
+    info: services::ConsensusTopicInfo,
+    deleted: bool,
+}
+
+#[derive(Debug, Clone)]
+struct File {
+    info: file_get_info_response::FileInfo,
+    contents: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+struct Schedule {
+    info: services::ScheduleInfo,
+    signatories: HashSet<Vec<u8>>,
+    executed: bool,
+    deleted: bool,
+}
+
+// what a handled transaction adds to its receipt and record
+#[derive(Debug, Default)]
+struct Outcome {
+    receipt: services::TransactionReceipt,
+    transfers: Vec<(AccountId, i64)>,
+    token_transfers: Vec<services::TokenTransferList>,
+    entropy: Option<services::transaction_record::Entropy>,
+}
+
+impl Outcome {
+    fn transfer(&mut self, account_id: AccountId, amount: i64) {
+        match self.transfers.iter_mut().find(|(id, _)| *id == account_id) {
+            Some((_, total)) => *total += amount,
+            None => self.transfers.push((account_id, amount)),
+        }
+    }
+}
+
+/// The state behind a `LedgerEmulator`, shared by all its nodes.
+#[derive(Debug)]
+pub(super) struct Ledger {
+    accounts: HashMap<AccountId, Account>,
+    tokens: HashMap<TokenId, Token>,
+    topics: HashMap<TopicId, Topic>,
+    files: HashMap<FileId, File>,
+    schedules: HashMap<ScheduleId, Schedule>,
+    records: HashMap<TransactionId, services::TransactionRecord>,
+    next_entity_num: i64,
+    last_consensus: DateTime<Utc>,
+    pub(super) transaction_fee: u64,
+}
+
+fn account_id(id: Option<services::AccountId>) -> LedgerResult<AccountId> {
+    id.and_then(|id| AccountId::try_from(id).ok())
+        .ok_or(Status::InvalidAccountId)
+}
+
+fn timestamp(time: DateTime<Utc>) -> services::Timestamp {
+    services::Timestamp {
+        seconds: time.timestamp(),
+        nanos: time.timestamp_subsec_nanos() as i32,
+    }
+}
+
+fn to_proto<T, P>(value: &T) -> P
+where
+    T: ToProto<P>,
+{
+    // ids and transaction ids of the ledger always convert
+    value.to_proto().unwrap()
+}
+
+fn key_list_key(keys: &services::KeyList) -> services::Key {
+    services::Key {
+        key: Some(key::Key::KeyList(keys.clone())),
+    }
+}
+
+/// Whether the keys in `signers` satisfy `key`, honouring key lists and
+/// threshold keys. Contract keys are never satisfied.
+pub(super) fn is_satisfied(key: &services::Key, signers: &HashSet<Vec<u8>>) -> bool {
+    match &key.key {
+        Some(key::Key::Ed25519(bytes)) | Some(key::Key::EcdsaSecp256k1(bytes)) => {
+            match PublicKey::from_bytes(bytes) {
+                Ok(public_key) => signers.contains(public_key.as_bytes()),
+                Err(_) => false,
+            }
+        }
+        Some(key::Key::KeyList(list)) => {
+            !list.keys.is_empty() && list.keys.iter().all(|key| is_satisfied(key, signers))
+        }
+        Some(key::Key::ThresholdKey(threshold_key)) => match &threshold_key.keys {
+            Some(list) => {
+                list.keys
+                    .iter()
+                    .filter(|key| is_satisfied(key, signers))
+                    .count()
+                    >= threshold_key.threshold as usize
+            }
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+// the keys that validly signed `body_bytes`, any invalid signature fails
+fn verified_signers(
+    body_bytes: &[u8],
+    sig_map: Option<&services::SignatureMap>,
+) -> LedgerResult<HashSet<Vec<u8>>> {
+    let mut signers = HashSet::new();
+    let pairs = match sig_map {
+        Some(sig_map) => &sig_map.sig_pair[..],
+        None => &[],
+    };
+    for pair in pairs {
+        // the emulator only accepts full public keys as prefixes
+        let public_key =
+            PublicKey::from_bytes(&pair.pub_key_prefix).map_err(|_| Status::InvalidSignature)?;
+        let signature = match &pair.signature {
+            Some(signature_pair::Signature::Ed25519(bytes)) => Signature::from_bytes(bytes),
+            Some(signature_pair::Signature::EcdsaSecp256k1(bytes)) => {
+                Signature::from_bytes_ecdsa(bytes)
+            }
+            _ => return Err(Status::InvalidSignatureTypeMismatchingKey),
+        }
+        .map_err(|_| Status::InvalidSignature)?;
+        if !public_key.verify(body_bytes, &signature).unwrap_or(false) {
+            return Err(Status::InvalidSignature);
+        }
+        signers.insert(public_key.as_bytes_vec());
+    }
+    Ok(signers)
+}
+
+// the transaction body a schedule executes
+fn scheduled_data(
+    data: schedulable_transaction_body::Data,
+) -> LedgerResult<transaction_body::Data> {
+    use schedulable_transaction_body::Data as S;
+    use transaction_body::Data as T;
+    Ok(match data {
+        S::CryptoCreateAccount(body) => T::CryptoCreateAccount(body),
+        S::CryptoTransfer(body) => T::CryptoTransfer(body),
+        S::CryptoUpdateAccount(body) => T::CryptoUpdateAccount(body),
+        S::CryptoDelete(body) => T::CryptoDelete(body),
+        S::TokenCreation(body) => T::TokenCreation(body),
+        S::TokenAssociate(body) => T::TokenAssociate(body),
+        S::TokenDissociate(body) => T::TokenDissociate(body),
+        S::TokenMint(body) => T::TokenMint(body),
+        S::TokenBurn(body) => T::TokenBurn(body),
+        S::TokenFreeze(body) => T::TokenFreeze(body),
+        S::TokenUnfreeze(body) => T::TokenUnfreeze(body),
+        S::TokenGrantKyc(body) => T::TokenGrantKyc(body),
+        S::TokenRevokeKyc(body) => T::TokenRevokeKyc(body),
+        S::ConsensusCreateTopic(body) => T::ConsensusCreateTopic(body),
+        S::ConsensusSubmitMessage(body) => T::ConsensusSubmitMessage(body),
+        S::ConsensusDeleteTopic(body) => T::ConsensusDeleteTopic(body),
+        S::FileCreate(body) => T::FileCreate(body),
+        S::FileAppend(body) => T::FileAppend(body),
+        S::FileUpdate(body) => T::FileUpdate(body),
+        S::FileDelete(body) => T::FileDelete(body),
+        S::ScheduleDelete(body) => T::ScheduleDelete(body),
+        _ => return Err(Status::ScheduledTransactionNotInWhitelist),
+    })
+}
+
+impl Ledger {
+    pub(super) fn new() -> Ledger {
+        Ledger {
+            accounts: HashMap::new(),
+            tokens: HashMap::new(),
+            topics: HashMap::new(),
+            files: HashMap::new(),
+            schedules: HashMap::new(),
+            records: HashMap::new(),
+            next_entity_num: FIRST_ENTITY_NUM,
+            last_consensus: Utc::now(),
+            transaction_fee: 0,
+        }
+    }
+
+    fn next_num(&mut self) -> i64 {
+        let num = self.next_entity_num;
+        self.next_entity_num += 1;
+        num
+    }
+
+    fn next_consensus_time(&mut self) -> DateTime<Utc> {
+        let now = Utc::now();
+        self.last_consensus = if now > self.last_consensus {
+            now
+        } else {
+            self.last_consensus + Duration::nanoseconds(1)
+        };
+        self.last_consensus
+    }
+
+    /// Adds an account outside of any transaction, e.g. for genesis and nodes.
+    pub(super) fn insert_account(
+        &mut self,
+        account_id: AccountId,
+        key: services::Key,
+        balance: u64,
+    ) {
+        self.accounts.insert(
+            account_id,
+            Account {
+                key,
+                balance,
+                deleted: false,
+                memo: String::new(),
+                receiver_sig_required: false,
+                tokens: HashMap::new(),
+                hbar_allowances: HashMap::new(),
+                token_allowances: HashMap::new(),
+            },
+        );
+    }
+
+    /// Creates an account with the next free entity number.
+    pub(super) fn create_account(&mut self, key: services::Key, balance: u64) -> AccountId {
+        let account_id = AccountId::simple(self.next_num());
+        self.insert_account(account_id, key, balance);
+        account_id
+    }
+
+    pub(super) fn balance(&self, account_id: &AccountId) -> Option<u64> {
+        self.accounts.get(account_id).map(|account| account.balance)
+    }
+
+    pub(super) fn token_balance(&self, account_id: &AccountId, token_id: &TokenId) -> Option<u64> {
+        self.accounts
+            .get(account_id)?
+            .tokens
+            .get(token_id)
+            .map(|relation| relation.balance)
+    }
+
+    fn account(&self, account_id: &AccountId) -> LedgerResult<&Account> {
+        match self.accounts.get(account_id) {
+            Some(account) if account.deleted => Err(Status::AccountDeleted),
+            Some(account) => Ok(account),
+            None => Err(Status::InvalidAccountId),
+        }
+    }
+
+    fn account_mut(&mut self, account_id: &AccountId) -> LedgerResult<&mut Account> {
+        match self.accounts.get_mut(account_id) {
+            Some(account) if account.deleted => Err(Status::AccountDeleted),
+            Some(account) => Ok(account),
+            None => Err(Status::InvalidAccountId),
+        }
+    }
+
+    fn token(&self, token: Option<services::TokenId>) -> LedgerResult<(TokenId, &Token)> {
+        let token_id = token.map(TokenId::from).ok_or(Status::InvalidTokenId)?;
+        match self.tokens.get(&token_id) {
+            Some(token) if token.info.deleted => Err(Status::TokenWasDeleted),
+            Some(token) => Ok((token_id, token)),
+            None => Err(Status::InvalidTokenId),
+        }
+    }
+
+    fn topic(&self, topic: Option<services::TopicId>) -> LedgerResult<(TopicId, &Topic)> {
+        let topic_id = topic.map(TopicId::from).ok_or(Status::InvalidTopicId)?;
+        match self.topics.get(&topic_id) {
+            Some(topic) if !topic.deleted => Ok((topic_id, topic)),
+            _ => Err(Status::InvalidTopicId),
+        }
+    }
+
+    fn file(&self, file: Option<services::FileId>) -> LedgerResult<(FileId, &File)> {
+        let file_id = file.map(FileId::from).ok_or(Status::InvalidFileId)?;
+        match self.files.get(&file_id) {
+            Some(file) if file.info.deleted => Err(Status::FileDeleted),
+            Some(file) => Ok((file_id, file)),
+            None => Err(Status::InvalidFileId),
+        }
+    }
+
+    fn schedule(
+        &self,
+        schedule: Option<services::ScheduleId>,
+    ) -> LedgerResult<(ScheduleId, &Schedule)> {
+        let schedule_id = schedule
+            .map(ScheduleId::from)
+            .ok_or(Status::InvalidScheduleId)?;
+        match self.schedules.get(&schedule_id) {
+            Some(schedule) if schedule.executed => Err(Status::ScheduleAlreadyExecuted),
+            Some(schedule) if schedule.deleted => Err(Status::ScheduleAlreadyDeleted),
+            Some(schedule) => Ok((schedule_id, schedule)),
+            None => Err(Status::InvalidScheduleId),
+        }
+    }
+
+    // the token relationship of an account that may receive or send the token
+    fn usable_relation(
+        &self,
+        account_id: &AccountId,
+        token_id: &TokenId,
+    ) -> LedgerResult<&TokenRelation> {
+        let relation = self
+            .account(account_id)?
+            .tokens
+            .get(token_id)
+            .ok_or(Status::TokenNotAssociatedToAccount)?;
+        if relation.frozen {
+            return Err(Status::AccountFrozenForToken);
+        }
+        if !relation.kyc_granted {
+            return Err(Status::AccountKycNotGrantedForToken);
+        }
+        Ok(relation)
+    }
+
+    /// Handles a transaction submitted to `node_account_id` and returns its
+    /// precheck status. Accepted transactions reach consensus right away.
+    pub(super) fn submit(
+        &mut self,
+        node_account_id: AccountId,
+        transaction: &services::Transaction,
+    ) -> Status {
+        match self.try_submit(node_account_id, transaction) {
+            Ok(()) => Status::Ok,
+            Err(status) => status,
+        }
+    }
+
+    fn try_submit(
+        &mut self,
+        node_account_id: AccountId,
+        transaction: &services::Transaction,
+    ) -> LedgerResult<()> {
+        let signed = services::SignedTransaction::decode(Bytes::copy_from_slice(
+            &transaction.signed_transaction_bytes,
+        ))
+        .map_err(|_| Status::InvalidTransaction)?;
+        let body_bytes = signed.body_bytes;
+        let sig_map = signed.sig_map;
+        let hash = Sha3_384::digest(&transaction.signed_transaction_bytes).to_vec();
+        let body = services::TransactionBody::decode(Bytes::from(body_bytes.clone()))
+            .map_err(|_| Status::InvalidTransactionBody)?;
+
+        let proto_transaction_id = body
+            .transaction_id
+            .clone()
+            .ok_or(Status::InvalidTransactionId)?;
+        let transaction_id = TransactionId::try_from(proto_transaction_id)
+            .map_err(|_| Status::InvalidTransactionId)?;
+        let payer_id = transaction_id
+            .account_id
+            .ok_or(Status::InvalidPayerAccountId)?;
+        if account_id(body.node_account_id.clone()).ok() != Some(node_account_id) {
+            return Err(Status::InvalidNodeAccount);
+        }
+        if self.records.contains_key(&transaction_id) {
+            return Err(Status::DuplicateTransaction);
+        }
+        let data = body.data.clone().ok_or(Status::InvalidTransactionBody)?;
+        if !self.is_supported(&data) {
+            return Err(Status::NotSupported);
+        }
+        if let transaction_body::Data::CryptoCreateAccount(create) = &data {
+            if create.key.is_none() {
+                return Err(Status::KeyRequired);
+            }
+        }
+
+        let signers = verified_signers(&body_bytes, sig_map.as_ref())?;
+        let payer = match self.accounts.get(&payer_id) {
+            Some(payer) if payer.deleted => return Err(Status::AccountDeleted),
+            Some(payer) => payer,
+            None => return Err(Status::PayerAccountNotFound),
+        };
+        if !is_satisfied(&payer.key, &signers) {
+            return Err(Status::InvalidSignature);
+        }
+        let fee = self.transaction_fee;
+        if body.transaction_fee < fee {
+            return Err(Status::InsufficientTxFee);
+        }
+        if payer.balance < fee {
+            return Err(Status::InsufficientPayerBalance);
+        }
+
+        // consensus
+        let consensus_time = self.next_consensus_time();
+        let mut outcome = Outcome::default();
+        self.charge_fee(payer_id, node_account_id, fee, &mut outcome);
+        let status = match self.handle(&data, payer_id, &signers, &transaction_id, &mut outcome) {
+            Ok(()) => Status::Success,
+            Err(status) => status,
+        };
+        outcome.receipt.status = status as i32;
+        let record = services::TransactionRecord {
+            receipt: Some(outcome.receipt),
+            transaction_hash: hash,
+            consensus_timestamp: Some(timestamp(consensus_time)),
+            transaction_id: body.transaction_id,
+            memo: body.memo,
+            transaction_fee: fee,
+            transfer_list: Some(services::TransferList {
+                account_amounts: outcome
+                    .transfers
+                    .into_iter()
+                    .filter(|(_, amount)| *amount != 0)
+                    .map(|(id, amount)| services::AccountAmount {
+                        account_id: Some(to_proto(&id)),
+                        amount,
+                        is_approval: false,
+                    })
+                    .collect(),
+            }),
+            token_transfer_lists: outcome.token_transfers,
+            entropy: outcome.entropy,
+            ..Default::default()
+        };
+        self.records.insert(transaction_id.clone(), record);
+
+        // a schedule whose signatures are complete runs in the same round
+        if status == Status::Success {
+            if let transaction_body::Data::ScheduleCreate(_)
+            | transaction_body::Data::ScheduleSign(_) = data
+            {
+                let schedule_id = self.records[&transaction_id]
+                    .receipt
+                    .as_ref()
+                    .and_then(|receipt| receipt.schedule_id.clone());
+                if let Some(schedule_id) = schedule_id {
+                    self.execute_schedule(ScheduleId::from(schedule_id));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn charge_fee(
+        &mut self,
+        payer_id: AccountId,
+        node_account_id: AccountId,
+        fee: u64,
+        outcome: &mut Outcome,
+    ) {
+        if fee == 0 {
+            return;
+        }
+        if let Some(payer) = self.accounts.get_mut(&payer_id) {
+            payer.balance -= fee;
+        }
+        if let Some(node) = self.accounts.get_mut(&node_account_id) {
+            node.balance += fee;
+        }
+        outcome.transfer(payer_id, -(fee as i64));
+        outcome.transfer(node_account_id, fee as i64);
+    }
+
+    fn is_supported(&self, data: &transaction_body::Data) -> bool {
+        use transaction_body::Data as T;
+        matches!(
+            data,
+            T::CryptoCreateAccount(_)
+                | T::CryptoTransfer(_)
+                | T::CryptoUpdateAccount(_)
+                | T::CryptoDelete(_)
+                | T::CryptoApproveAllowance(_)
+                | T::TokenCreation(_)
+                | T::TokenDeletion(_)
+                | T::TokenAssociate(_)
+                | T::TokenDissociate(_)
+                | T::TokenMint(_)
+                | T::TokenBurn(_)
+                | T::TokenWipe(_)
+                | T::TokenFreeze(_)
+                | T::TokenUnfreeze(_)
+                | T::TokenGrantKyc(_)
+                | T::TokenRevokeKyc(_)
+                | T::TokenPause(_)
+                | T::TokenUnpause(_)
+                | T::ConsensusCreateTopic(_)
+                | T::ConsensusSubmitMessage(_)
+                | T::ConsensusDeleteTopic(_)
+                | T::FileCreate(_)
+                | T::FileAppend(_)
+                | T::FileUpdate(_)
+                | T::FileDelete(_)
+                | T::ScheduleCreate(_)
+                | T::ScheduleSign(_)
+                | T::ScheduleDelete(_)
+                | T::UtilPrng(_)
+        )
+    }
+
+    // checks the required signatures, then applies `data`
+    fn handle(
+        &mut self,
+        data: &transaction_body::Data,
+        payer_id: AccountId,
+        signers: &HashSet<Vec<u8>>,
+        transaction_id: &TransactionId,
+        outcome: &mut Outcome,
+    ) -> LedgerResult<()> {
+        for key in self.required_keys(data, payer_id)? {
+            if !is_satisfied(&key, signers) {
+                return Err(Status::InvalidSignature);
+            }
+        }
+        self.apply(data, payer_id, signers, transaction_id, outcome)
+    }
+
+    /// The keys that must sign `data`, besides the payer's.
+    fn required_keys(
+        &self,
+        data: &transaction_body::Data,
+        payer_id: AccountId,
+    ) -> LedgerResult<Vec<services::Key>> {
+        use transaction_body::Data as T;
+        let mut keys = Vec::new();
+        match data {
+            T::CryptoCreateAccount(_) => {}
+            // approved debits are signed by the spender, the payer
+            T::CryptoTransfer(body) => {
+                if let Some(transfers) = &body.transfers {
+                    for transfer in &transfers.account_amounts {
+                        let account = self.account(&account_id(transfer.account_id.clone())?)?;
+                        if (transfer.amount < 0 && !transfer.is_approval)
+                            || (transfer.amount > 0 && account.receiver_sig_required)
+                        {
+                            keys.push(account.key.clone());
+                        }
+                    }
+                }
+                for token_transfers in &body.token_transfers {
+                    for transfer in &token_transfers.transfers {
+                        if transfer.amount < 0 && !transfer.is_approval {
+                            let sender = account_id(transfer.account_id.clone())?;
+                            keys.push(self.account(&sender)?.key.clone());
+                        }
+                    }
+                    for transfer in &token_transfers.nft_transfers {
+                        let sender = account_id(transfer.sender_account_id.clone())?;
+                        keys.push(self.account(&sender)?.key.clone());
+                    }
+                }
+            }
+            T::CryptoUpdateAccount(body) => {
+                let account_id = account_id(body.account_id_to_update.clone())?;
+                keys.push(self.account(&account_id)?.key.clone());
+                keys.extend(body.key.clone());
+            }
+            T::CryptoDelete(body) => {
+                let account_id = account_id(body.delete_account_id.clone())?;
+                keys.push(self.account(&account_id)?.key.clone());
+            }
+            T::CryptoApproveAllowance(body) => {
+                let owners = body
+                    .crypto_allowances
+                    .iter()
+                    .map(|allowance| &allowance.owner)
+                    .chain(
+                        body.token_allowances
+                            .iter()
+                            .map(|allowance| &allowance.owner),
+                    );
+                for owner in owners {
+                    // the payer owns allowances without an owner
+                    let owner = match owner {
+                        Some(owner) => account_id(Some(owner.clone()))?,
+                        None => payer_id,
+                    };
+                    keys.push(self.account(&owner)?.key.clone());
+                }
+            }
+            T::TokenCreation(body) => {
+                let treasury = account_id(body.treasury.clone())?;
+                keys.push(self.account(&treasury)?.key.clone());
+                keys.extend(body.admin_key.clone());
+            }
+            T::TokenDeletion(body) => {
+                let (_, token) = self.token(body.token.clone())?;
+                keys.push(
+                    token
+                        .info
+                        .admin_key
+                        .clone()
+                        .ok_or(Status::TokenIsImmutable)?,
+                );
+            }
+            T::TokenWipe(body) => {
+                let (_, token) = self.token(body.token.clone())?;
+                keys.push(
+                    token
+                        .info
+                        .wipe_key
+                        .clone()
+                        .ok_or(Status::TokenHasNoWipeKey)?,
+                );
+            }
+            T::TokenPause(body) => {
+                let (_, token) = self.token(body.token.clone())?;
+                keys.push(
+                    token
+                        .info
+                        .pause_key
+                        .clone()
+                        .ok_or(Status::TokenHasNoPauseKey)?,
+                );
+            }
+            T::TokenUnpause(body) => {
+                let (_, token) = self.token(body.token.clone())?;
+                keys.push(
+                    token
+                        .info
+                        .pause_key
+                        .clone()
+                        .ok_or(Status::TokenHasNoPauseKey)?,
+                );
+            }
+            T::TokenAssociate(body) => {
+                let account_id = account_id(body.account.clone())?;
+                keys.push(self.account(&account_id)?.key.clone());
+            }
+            T::TokenDissociate(body) => {
+                let account_id = account_id(body.account.clone())?;
+                keys.push(self.account(&account_id)?.key.clone());
+            }
+            T::TokenMint(body) => {
+                let (_, token) = self.token(body.token.clone())?;
+                keys.push(
+                    token
+                        .info
+                        .supply_key
+                        .clone()
+                        .ok_or(Status::TokenHasNoSupplyKey)?,
+                );
+            }
+            T::TokenBurn(body) => {
+                let (_, token) = self.token(body.token.clone())?;
+                keys.push(
+                    token
+                        .info
+                        .supply_key
+                        .clone()
+                        .ok_or(Status::TokenHasNoSupplyKey)?,
+                );
+            }
+            T::TokenFreeze(body) => {
+                let (_, token) = self.token(body.token.clone())?;
+                keys.push(
+                    token
+                        .info
+                        .freeze_key
+                        .clone()
+                        .ok_or(Status::TokenHasNoFreezeKey)?,
+                );
+            }
+            T::TokenUnfreeze(body) => {
+                let (_, token) = self.token(body.token.clone())?;
+                keys.push(
+                    token
+                        .info
+                        .freeze_key
+                        .clone()
+                        .ok_or(Status::TokenHasNoFreezeKey)?,
+                );
+            }
+            T::TokenGrantKyc(body) => {
+                let (_, token) = self.token(body.token.clone())?;
+                keys.push(token.info.kyc_key.clone().ok_or(Status::TokenHasNoKycKey)?);
+            }
+            T::TokenRevokeKyc(body) => {
+                let (_, token) = self.token(body.token.clone())?;
+                keys.push(token.info.kyc_key.clone().ok_or(Status::TokenHasNoKycKey)?);
+            }
+            T::ConsensusCreateTopic(body) => {
+                keys.extend(body.admin_key.clone());
+                if let Some(auto_renew_account) = &body.auto_renew_account {
+                    let account_id = account_id(Some(auto_renew_account.clone()))?;
+                    keys.push(self.account(&account_id)?.key.clone());
+                }
+            }
+            T::ConsensusSubmitMessage(body) => {
+                let (_, topic) = self.topic(body.topic_id.clone())?;
+                keys.extend(topic.info.submit_key.clone());
+            }
+            T::ConsensusDeleteTopic(body) => {
+                let (_, topic) = self.topic(body.topic_id.clone())?;
+                keys.push(topic.info.admin_key.clone().ok_or(Status::Unauthorized)?);
+            }
+            T::FileCreate(body) => {
+                if let Some(file_keys) = &body.keys {
+                    keys.push(key_list_key(file_keys));
+                }
+            }
+            T::FileAppend(body) => keys.push(self.file_key(body.file_id.clone())?),
+            T::FileUpdate(body) => {
+                keys.push(self.file_key(body.file_id.clone())?);
+                if let Some(file_keys) = &body.keys {
+                    keys.push(key_list_key(file_keys));
+                }
+            }
+            T::FileDelete(body) => keys.push(self.file_key(body.file_id.clone())?),
+            T::ScheduleCreate(body) => keys.extend(body.admin_key.clone()),
+            T::ScheduleSign(_) => {}
+            T::UtilPrng(_) => {}
+            T::ScheduleDelete(body) => {
+                let (_, schedule) = self.schedule(body.schedule_id.clone())?;
+                keys.push(
+                    schedule
+                        .info
+                        .admin_key
+                        .clone()
+                        .ok_or(Status::ScheduleIsImmutable)?,
+                );
+            }
+            _ => return Err(Status::NotSupported),
+        }
+        Ok(keys)
+    }
+
+    // files without keys can't be changed
+    fn file_key(&self, file_id: Option<services::FileId>) -> LedgerResult<services::Key> {
+        let (_, file) = self.file(file_id)?;
+        match &file.info.keys {
+            Some(keys) if !keys.keys.is_empty() => Ok(key_list_key(keys)),
+            _ => Err(Status::Unauthorized),
+        }
+    }
+
+    fn apply(
+        &mut self,
+        data: &transaction_body::Data,
+        payer_id: AccountId,
+        signers: &HashSet<Vec<u8>>,
+        transaction_id: &TransactionId,
+        outcome: &mut Outcome,
+    ) -> LedgerResult<()> {
+        use transaction_body::Data as T;
+        match data {
+            T::CryptoCreateAccount(body) => {
+                let key = body.key.clone().ok_or(Status::KeyRequired)?;
+                if self.account(&payer_id)?.balance < body.initial_balance {
+                    return Err(Status::InsufficientPayerBalance);
+                }
+                self.account_mut(&payer_id)?.balance -= body.initial_balance;
+                let account_id = self.create_account(key, body.initial_balance);
+                let account = self.account_mut(&account_id)?;
+                account.memo = body.memo.clone();
+                account.receiver_sig_required = body.receiver_sig_required;
+                outcome.transfer(payer_id, -(body.initial_balance as i64));
+                outcome.transfer(account_id, body.initial_balance as i64);
+                outcome.receipt.account_id = Some(to_proto(&account_id));
+            }
+            T::CryptoTransfer(body) => self.crypto_transfer(body, payer_id, outcome)?,
+            T::CryptoUpdateAccount(body) => {
+                let account_id = account_id(body.account_id_to_update.clone())?;
+                let account = self.account_mut(&account_id)?;
+                if let Some(key) = &body.key {
+                    account.key = key.clone();
+                }
+                if let Some(memo) = &body.memo {
+                    account.memo = memo.clone();
+                }
+            }
+            T::CryptoDelete(body) => {
+                let delete_id = account_id(body.delete_account_id.clone())?;
+                let transfer_id = account_id(body.transfer_account_id.clone())
+                    .map_err(|_| Status::InvalidTransferAccountId)?;
+                if delete_id == transfer_id {
+                    return Err(Status::TransferAccountSameAsDeleteAccount);
+                }
+                self.account(&transfer_id)?;
+                let account = self.account(&delete_id)?;
+                // balances of deleted tokens don't count
+                if account.tokens.iter().any(|(token_id, relation)| {
+                    relation.balance > 0 && !self.tokens[token_id].info.deleted
+                }) {
+                    return Err(Status::TransactionRequiresZeroTokenBalances);
+                }
+                let balance = account.balance;
+                let account = self.account_mut(&delete_id)?;
+                account.balance = 0;
+                account.deleted = true;
+                self.account_mut(&transfer_id)?.balance += balance;
+                outcome.transfer(delete_id, -(balance as i64));
+                outcome.transfer(transfer_id, balance as i64);
+            }
+            T::CryptoApproveAllowance(body) => self.approve_allowance(body, payer_id)?,
+            T::TokenCreation(body) => self.token_create(body, outcome)?,
+            T::TokenDeletion(body) => {
+                let (token_id, _) = self.token(body.token.clone())?;
+                self.tokens.get_mut(&token_id).unwrap().info.deleted = true;
+            }
+            T::TokenAssociate(body) => {
+                let account_id = account_id(body.account.clone())?;
+                let mut relations = Vec::new();
+                for token in &body.tokens {
+                    let (token_id, token) = self.token(Some(token.clone()))?;
+                    if self.account(&account_id)?.tokens.contains_key(&token_id) {
+                        return Err(Status::TokenAlreadyAssociatedToAccount);
+                    }
+                    let relation = TokenRelation {
+                        balance: 0,
+                        frozen: token.info.freeze_key.is_some()
+                            && token.info.default_freeze_status
+                                == services::TokenFreezeStatus::Frozen as i32,
+                        kyc_granted: token.info.kyc_key.is_none(),
+                    };
+                    relations.push((token_id, relation));
+                }
+                self.account_mut(&account_id)?.tokens.extend(relations);
+            }
+            T::TokenDissociate(body) => {
+                let account_id = account_id(body.account.clone())?;
+                let mut token_ids = Vec::new();
+                for token in &body.tokens {
+                    let (token_id, token) = self.token(Some(token.clone()))?;
+                    if token.info.treasury == Some(to_proto(&account_id)) {
+                        return Err(Status::AccountIsTreasury);
+                    }
+                    match self.account(&account_id)?.tokens.get(&token_id) {
+                        Some(relation) if relation.balance > 0 => {
+                            return Err(Status::TransactionRequiresZeroTokenBalances)
+                        }
+                        Some(_) => token_ids.push(token_id),
+                        None => return Err(Status::TokenNotAssociatedToAccount),
+                    }
+                }
+                let account = self.account_mut(&account_id)?;
+                for token_id in token_ids {
+                    account.tokens.remove(&token_id);
+                }
+            }
+            T::TokenMint(body) => self.token_mint(body, outcome)?,
+            T::TokenBurn(body) => self.token_burn(body, outcome)?,
+            T::TokenWipe(body) => self.token_wipe(body, outcome)?,
+            T::TokenPause(body) => {
+                let (token_id, _) = self.token(body.token.clone())?;
+                self.tokens.get_mut(&token_id).unwrap().info.pause_status =
+                    services::TokenPauseStatus::Paused as i32;
+            }
+            T::TokenUnpause(body) => {
+                let (token_id, _) = self.token(body.token.clone())?;
+                self.tokens.get_mut(&token_id).unwrap().info.pause_status =
+                    services::TokenPauseStatus::Unpaused as i32;
+            }
+            T::TokenFreeze(body) => {
+                self.update_relation(body.token.clone(), body.account.clone(), |relation| {
+                    relation.frozen = true
+                })?
+            }
+            T::TokenUnfreeze(body) => {
+                self.update_relation(body.token.clone(), body.account.clone(), |relation| {
+                    relation.frozen = false
+                })?
+            }
+            T::TokenGrantKyc(body) => {
+                self.update_relation(body.token.clone(), body.account.clone(), |relation| {
+                    relation.kyc_granted = true
+                })?
+            }
+            T::TokenRevokeKyc(body) => {
+                self.update_relation(body.token.clone(), body.account.clone(), |relation| {
+                    relation.kyc_granted = false
+                })?
+            }
+            T::ConsensusCreateTopic(body) => {
+                let topic_id = TopicId::simple(self.next_num());
+                self.topics.insert(
+                    topic_id,
+                    Topic {
+                        info: services::ConsensusTopicInfo {
+                            memo: body.memo.clone(),
+                            running_hash: vec![0; 48],
+                            sequence_number: 0,
+                            admin_key: body.admin_key.clone(),
+                            submit_key: body.submit_key.clone(),
+                            auto_renew_period: body.auto_renew_period.clone(),
+                            auto_renew_account: body.auto_renew_account.clone(),
+                            ..Default::default()
+                        },
+                        deleted: false,
+                    },
+                );
+                outcome.receipt.topic_id = Some(to_proto(&topic_id));
+            }
+            T::ConsensusSubmitMessage(body) => {
+                if body.message.is_empty() {
+                    return Err(Status::InvalidTopicMessage);
+                }
+                let (topic_id, _) = self.topic(body.topic_id.clone())?;
+                let consensus_time = self.last_consensus;
+                let topic = self.topics.get_mut(&topic_id).unwrap();
+                topic.info.sequence_number += 1;
+                topic.info.running_hash = running_hash(
+                    &topic.info.running_hash,
+                    &payer_id,
+                    &topic_id,
+                    consensus_time,
+                    topic.info.sequence_number,
+                    &body.message,
+                );
+                outcome.receipt.topic_sequence_number = topic.info.sequence_number;
+                outcome.receipt.topic_running_hash = topic.info.running_hash.clone();
+                outcome.receipt.topic_running_hash_version = TOPIC_RUNNING_HASH_VERSION;
+            }
+            T::ConsensusDeleteTopic(body) => {
+                let (topic_id, _) = self.topic(body.topic_id.clone())?;
+                self.topics.get_mut(&topic_id).unwrap().deleted = true;
+            }
+            T::FileCreate(body) => {
+                let file_id = FileId::simple(self.next_num());
+                self.files.insert(
+                    file_id,
+                    File {
+                        info: file_get_info_response::FileInfo {
+                            file_id: Some(to_proto(&file_id)),
+                            size: body.contents.len() as i64,
+                            expiration_time: body.expiration_time.clone(),
+                            deleted: false,
+                            keys: body.keys.clone(),
+                            memo: body.memo.clone(),
+                            ..Default::default()
+                        },
+                        contents: body.contents.clone(),
+                    },
+                );
+                outcome.receipt.file_id = Some(to_proto(&file_id));
+            }
+            T::FileAppend(body) => {
+                let (file_id, _) = self.file(body.file_id.clone())?;
+                let file = self.files.get_mut(&file_id).unwrap();
+                file.contents.extend_from_slice(&body.contents);
+                file.info.size = file.contents.len() as i64;
+            }
+            T::FileUpdate(body) => {
+                let (file_id, _) = self.file(body.file_id.clone())?;
+                let file = self.files.get_mut(&file_id).unwrap();
+                if !body.contents.is_empty() {
+                    file.contents = body.contents.clone();
+                    file.info.size = file.contents.len() as i64;
+                }
+                if body.keys.is_some() {
+                    file.info.keys = body.keys.clone();
+                }
+                if let Some(memo) = &body.memo {
+                    file.info.memo = memo.clone();
+                }
+                if body.expiration_time.is_some() {
+                    file.info.expiration_time = body.expiration_time.clone();
+                }
+            }
+            T::FileDelete(body) => {
+                let (file_id, _) = self.file(body.file_id.clone())?;
+                let file = self.files.get_mut(&file_id).unwrap();
+                file.info.deleted = true;
+                file.contents.clear();
+                file.info.size = 0;
+            }
+            T::ScheduleCreate(body) => {
+                let scheduled = body
+                    .scheduled_transaction_body
+                    .clone()
+                    .ok_or(Status::InvalidTransaction)?;
+                scheduled_data(scheduled.data.clone().ok_or(Status::InvalidTransaction)?)?;
+                let payer_account_id = match &body.payer_account_id {
+                    Some(id) => account_id(Some(id.clone()))?,
+                    None => payer_id,
+                };
+                self.account(&payer_account_id)?;
+                let schedule_id = ScheduleId::simple(self.next_num());
+                let mut scheduled_transaction_id = transaction_id.clone();
+                scheduled_transaction_id.scheduled = true;
+                self.schedules.insert(
+                    schedule_id,
+                    Schedule {
+                        info: services::ScheduleInfo {
+                            schedule_id: Some(to_proto(&schedule_id)),
+                            expiration_time: body.expiration_time.clone(),
+                            scheduled_transaction_body: Some(scheduled),
+                            memo: body.memo.clone(),
+                            admin_key: body.admin_key.clone(),
+                            creator_account_id: Some(to_proto(&payer_id)),
+                            payer_account_id: Some(to_proto(&payer_account_id)),
+                            scheduled_transaction_id: Some(to_proto(&scheduled_transaction_id)),
+                            wait_for_expiry: body.wait_for_expiry,
+                            ..Default::default()
+                        },
+                        signatories: signers.clone(),
+                        executed: false,
+                        deleted: false,
+                    },
+                );
+                outcome.receipt.schedule_id = Some(to_proto(&schedule_id));
+                outcome.receipt.scheduled_transaction_id =
+                    Some(to_proto(&scheduled_transaction_id));
+            }
+            T::ScheduleSign(body) => {
+                let (schedule_id, schedule) = self.schedule(body.schedule_id.clone())?;
+                if signers.is_subset(&schedule.signatories) {
+                    return Err(Status::NoNewValidSignatures);
+                }
+                let schedule = self.schedules.get_mut(&schedule_id).unwrap();
+                schedule.signatories.extend(signers.iter().cloned());
+                outcome.receipt.schedule_id = Some(to_proto(&schedule_id));
+                outcome.receipt.scheduled_transaction_id =
+                    schedule.info.scheduled_transaction_id.clone();
+            }
+            T::ScheduleDelete(body) => {
+                let (schedule_id, _) = self.schedule(body.schedule_id.clone())?;
+                let consensus_time = self.last_consensus;
+                let schedule = self.schedules.get_mut(&schedule_id).unwrap();
+                schedule.deleted = true;
+                schedule.info.data = Some(services::schedule_info::Data::DeletionTime(timestamp(
+                    consensus_time,
+                )));
+            }
+            T::UtilPrng(body) => {
+                if body.range < 0 {
+                    return Err(Status::InvalidPrngRange);
+                }
+                // not a secure source, only as unpredictable as the clock
+                let seed = timestamp(self.last_consensus);
+                let bytes = Sha384::digest(
+                    [
+                        &seed.seconds.to_be_bytes()[..],
+                        &seed.nanos.to_be_bytes()[..],
+                        &transaction_id.to_string().into_bytes()[..],
+                    ]
+                    .concat(),
+                )
+                .to_vec();
+                outcome.entropy = Some(if body.range == 0 {
+                    services::transaction_record::Entropy::PrngBytes(bytes)
+                } else {
+                    let number = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                    services::transaction_record::Entropy::PrngNumber(
+                        (number % body.range as u32) as i32,
+                    )
+                });
+            }
+            _ => return Err(Status::NotSupported),
+        }
+        Ok(())
+    }
+
+    fn crypto_transfer(
+        &mut self,
+        body: &services::CryptoTransferTransactionBody,
+        payer_id: AccountId,
+        outcome: &mut Outcome,
+    ) -> LedgerResult<()> {
+        // validate everything before moving anything
+        let mut balances: HashMap<AccountId, i128> = HashMap::new();
+        // approved debits, spent from the payer's allowances
+        let mut hbar_spent: HashMap<AccountId, u64> = HashMap::new();
+        let mut token_spent: HashMap<(AccountId, TokenId), u64> = HashMap::new();
+        let account_amounts = match &body.transfers {
+            Some(transfers) => &transfers.account_amounts[..],
+            None => &[],
+        };
+        if account_amounts
+            .iter()
+            .map(|t| t.amount as i128)
+            .sum::<i128>()
+            != 0
+        {
+            return Err(Status::InvalidAccountAmounts);
+        }
+        for transfer in account_amounts {
+            let id = account_id(transfer.account_id.clone())?;
+            let balance = match balances.get(&id) {
+                Some(balance) => *balance,
+                None => self.account(&id)?.balance as i128,
+            };
+            balances.insert(id, balance + transfer.amount as i128);
+            if transfer.is_approval && transfer.amount < 0 {
+                *hbar_spent.entry(id).or_default() += transfer.amount.unsigned_abs();
+            }
+        }
+        if balances.values().any(|balance| *balance < 0) {
+            return Err(Status::InsufficientAccountBalance);
+        }
+        for (owner, spent) in &hbar_spent {
+            let allowance = self
+                .account(owner)?
+                .hbar_allowances
+                .get(&payer_id)
+                .ok_or(Status::SpenderDoesNotHaveAllowance)?;
+            if spent > allowance {
+                return Err(Status::AmountExceedsAllowance);
+            }
+        }
+
+        let mut token_balances: HashMap<(AccountId, TokenId), i128> = HashMap::new();
+        let mut nft_moves: Vec<(TokenId, i64, AccountId)> = Vec::new();
+        for token_transfers in &body.token_transfers {
+            let (token_id, token) = self.token(token_transfers.token.clone())?;
+            if token.is_paused() {
+                return Err(Status::TokenIsPaused);
+            }
+            if let Some(decimals) = token_transfers.expected_decimals {
+                if decimals != token.info.decimals {
+                    return Err(Status::UnexpectedTokenDecimals);
+                }
+            }
+            if !token_transfers.transfers.is_empty() && token.is_nft() {
+                return Err(Status::AccountAmountTransfersOnlyAllowedForFungibleCommon);
+            }
+            let sum: i128 = token_transfers
+                .transfers
+                .iter()
+                .map(|t| t.amount as i128)
+                .sum();
+            if sum != 0 {
+                return Err(Status::TransfersNotZeroSumForToken);
+            }
+            for transfer in &token_transfers.transfers {
+                let id = account_id(transfer.account_id.clone())?;
+                let balance = match token_balances.get(&(id, token_id)) {
+                    Some(balance) => *balance,
+                    None => self.usable_relation(&id, &token_id)?.balance as i128,
+                };
+                token_balances.insert((id, token_id), balance + transfer.amount as i128);
+                if transfer.is_approval && transfer.amount < 0 {
+                    *token_spent.entry((id, token_id)).or_default() +=
+                        transfer.amount.unsigned_abs();
+                }
+            }
+            for transfer in &token_transfers.nft_transfers {
+                if !token.is_nft() {
+                    return Err(Status::InvalidNftId);
+                }
+                let sender = account_id(transfer.sender_account_id.clone())?;
+                let receiver = account_id(transfer.receiver_account_id.clone())?;
+                match token.nft_owner(transfer.serial_number) {
+                    Some(owner) if owner == sender => {}
+                    Some(_) => return Err(Status::SenderDoesNotOwnNftSerialNo),
+                    None => return Err(Status::InvalidNftId),
+                }
+                self.usable_relation(&sender, &token_id)?;
+                self.usable_relation(&receiver, &token_id)?;
+                nft_moves.push((token_id, transfer.serial_number, receiver));
+                for (id, amount) in [(sender, -1), (receiver, 1)] {
+                    let balance = match token_balances.get(&(id, token_id)) {
+                        Some(balance) => *balance,
+                        None => self.usable_relation(&id, &token_id)?.balance as i128,
+                    };
+                    token_balances.insert((id, token_id), balance + amount);
+                }
+            }
+        }
+        if token_balances.values().any(|balance| *balance < 0) {
+            return Err(Status::InsufficientTokenBalance);
+        }
+        for ((owner, token_id), spent) in &token_spent {
+            let allowance = self
+                .account(owner)?
+                .token_allowances
+                .get(&(*token_id, payer_id))
+                .ok_or(Status::SpenderDoesNotHaveAllowance)?;
+            if spent > allowance {
+                return Err(Status::AmountExceedsAllowance);
+            }
+        }
+
+        for (id, balance) in balances {
+            self.accounts.get_mut(&id).unwrap().balance = balance as u64;
+        }
+        for ((id, token_id), balance) in token_balances {
+            let account = self.accounts.get_mut(&id).unwrap();
+            account.tokens.get_mut(&token_id).unwrap().balance = balance as u64;
+        }
+        for (token_id, serial, receiver) in nft_moves {
+            let token = self.tokens.get_mut(&token_id).unwrap();
+            token.nfts.get_mut(&serial).unwrap().owner = receiver;
+        }
+        for (owner, spent) in hbar_spent {
+            let allowances = &mut self.accounts.get_mut(&owner).unwrap().hbar_allowances;
+            *allowances.get_mut(&payer_id).unwrap() -= spent;
+        }
+        for ((owner, token_id), spent) in token_spent {
+            let allowances = &mut self.accounts.get_mut(&owner).unwrap().token_allowances;
+            *allowances.get_mut(&(token_id, payer_id)).unwrap() -= spent;
+        }
+        for transfer in account_amounts {
+            outcome.transfer(account_id(transfer.account_id.clone())?, transfer.amount);
+        }
+        outcome
+            .token_transfers
+            .extend(body.token_transfers.iter().cloned());
+        Ok(())
+    }
+
+    // sets allowances, replacing any earlier ones of the same spender
+    fn approve_allowance(
+        &mut self,
+        body: &services::CryptoApproveAllowanceTransactionBody,
+        payer_id: AccountId,
+    ) -> LedgerResult<()> {
+        if !body.nft_allowances.is_empty() {
+            return Err(Status::NotSupported);
+        }
+        let owner_id = |owner: &Option<services::AccountId>| match owner {
+            Some(owner) => account_id(Some(owner.clone())),
+            None => Ok(payer_id),
+        };
+        let mut hbar_allowances = Vec::new();
+        for allowance in &body.crypto_allowances {
+            let owner = owner_id(&allowance.owner)?;
+            let spender = account_id(allowance.spender.clone())
+                .map_err(|_| Status::InvalidAllowanceSpenderId)?;
+            if allowance.amount < 0 {
+                return Err(Status::NegativeAllowanceAmount);
+            }
+            self.account(&owner)?;
+            self.account(&spender)?;
+            hbar_allowances.push((owner, spender, allowance.amount as u64));
+        }
+        let mut token_allowances = Vec::new();
+        for allowance in &body.token_allowances {
+            let (token_id, token) = self.token(allowance.token_id.clone())?;
+            if token.is_nft() {
+                return Err(Status::NftInFungibleTokenAllowances);
+            }
+            let owner = owner_id(&allowance.owner)?;
+            let spender = account_id(allowance.spender.clone())
+                .map_err(|_| Status::InvalidAllowanceSpenderId)?;
+            if allowance.amount < 0 {
+                return Err(Status::NegativeAllowanceAmount);
+            }
+            if !self.account(&owner)?.tokens.contains_key(&token_id) {
+                return Err(Status::TokenNotAssociatedToAccount);
+            }
+            self.account(&spender)?;
+            token_allowances.push((owner, token_id, spender, allowance.amount as u64));
+        }
+
+        for (owner, spender, amount) in hbar_allowances {
+            self.account_mut(&owner)?
+                .hbar_allowances
+                .insert(spender, amount);
+        }
+        for (owner, token_id, spender, amount) in token_allowances {
+            self.account_mut(&owner)?
+                .token_allowances
+                .insert((token_id, spender), amount);
+        }
+        Ok(())
+    }
+
+    fn token_create(
+        &mut self,
+        body: &services::TokenCreateTransactionBody,
+        outcome: &mut Outcome,
+    ) -> LedgerResult<()> {
+        let treasury = account_id(body.treasury.clone())
+            .map_err(|_| Status::InvalidTreasuryAccountForToken)?;
+        self.account(&treasury)?;
+        let is_nft = body.token_type == services::TokenType::NonFungibleUnique as i32;
+        if is_nft && body.initial_supply != 0 {
+            return Err(Status::InvalidTokenInitialSupply);
+        }
+        let token_id = TokenId::simple(self.next_num());
+        let default_freeze_status = match (&body.freeze_key, body.freeze_default) {
+            (None, _) => services::TokenFreezeStatus::FreezeNotApplicable,
+            (Some(_), true) => services::TokenFreezeStatus::Frozen,
+            (Some(_), false) => services::TokenFreezeStatus::Unfrozen,
+        };
+        let default_kyc_status = match &body.kyc_key {
+            None => services::TokenKycStatus::KycNotApplicable,
+            Some(_) => services::TokenKycStatus::Revoked,
+        };
+        let pause_status = match &body.pause_key {
+            None => services::TokenPauseStatus::PauseNotApplicable,
+            Some(_) => services::TokenPauseStatus::Unpaused,
+        };
+        let expiry = match &body.expiry {
+            Some(expiry) if expiry.seconds > 0 => expiry.clone(),
+            _ => {
+                let lifetime = body
+                    .auto_renew_period
+                    .as_ref()
+                    .map(|period| period.seconds)
+                    .filter(|seconds| *seconds > 0)
+                    .unwrap_or(DEFAULT_TOKEN_LIFETIME);
+                timestamp(self.last_consensus + Duration::seconds(lifetime))
+            }
+        };
+        self.tokens.insert(
+            token_id,
+            Token {
+                info: services::TokenInfo {
+                    token_id: Some(to_proto(&token_id)),
+                    name: body.name.clone(),
+                    symbol: body.symbol.clone(),
+                    decimals: body.decimals,
+                    total_supply: body.initial_supply,
+                    treasury: body.treasury.clone(),
+                    admin_key: body.admin_key.clone(),
+                    kyc_key: body.kyc_key.clone(),
+                    freeze_key: body.freeze_key.clone(),
+                    wipe_key: body.wipe_key.clone(),
+                    supply_key: body.supply_key.clone(),
+                    default_freeze_status: default_freeze_status as i32,
+                    default_kyc_status: default_kyc_status as i32,
+                    auto_renew_account: body.auto_renew_account.clone(),
+                    auto_renew_period: body.auto_renew_period.clone(),
+                    expiry: Some(expiry),
+                    memo: body.memo.clone(),
+                    token_type: body.token_type,
+                    supply_type: body.supply_type,
+                    max_supply: body.max_supply,
+                    fee_schedule_key: body.fee_schedule_key.clone(),
+                    custom_fees: body.custom_fees.clone(),
+                    pause_key: body.pause_key.clone(),
+                    pause_status: pause_status as i32,
+                    ..Default::default()
+                },
+                next_serial: 1,
+                nfts: HashMap::new(),
+            },
+        );
+        // the treasury is associated, unfrozen and KYCed
+        self.account_mut(&treasury)?.tokens.insert(
+            token_id,
+            TokenRelation {
+                balance: body.initial_supply,
+                frozen: false,
+                kyc_granted: true,
+            },
+        );
+        if body.initial_supply > 0 {
+            outcome.token_transfers.push(services::TokenTransferList {
+                token: Some(to_proto(&token_id)),
+                transfers: vec![services::AccountAmount {
+                    account_id: body.treasury.clone(),
+                    amount: body.initial_supply as i64,
+                    is_approval: false,
+                }],
+                ..Default::default()
+            });
+        }
+        outcome.receipt.token_id = Some(to_proto(&token_id));
+        outcome.receipt.new_total_supply = body.initial_supply;
+        Ok(())
+    }
+
+    fn token_mint(
+        &mut self,
+        body: &services::TokenMintTransactionBody,
+        outcome: &mut Outcome,
+    ) -> LedgerResult<()> {
+        let (token_id, token) = self.token(body.token.clone())?;
+        if token.is_paused() {
+            return Err(Status::TokenIsPaused);
+        }
+        let treasury = account_id(token.info.treasury.clone())?;
+        let minted = if token.is_nft() {
+            if body.metadata.is_empty() || body.amount != 0 {
+                return Err(Status::InvalidTokenMintMetadata);
+            }
+            body.metadata.len() as u64
+        } else {
+            if body.amount == 0 || !body.metadata.is_empty() {
+                return Err(Status::InvalidTokenMintAmount);
+            }
+            body.amount
+        };
+        let total_supply = token.info.total_supply + minted;
+        if token.info.supply_type == services::TokenSupplyType::Finite as i32
+            && total_supply > token.info.max_supply as u64
+        {
+            return Err(Status::TokenMaxSupplyReached);
+        }
+
+        let creation_time = timestamp(self.last_consensus);
+        let token = self.tokens.get_mut(&token_id).unwrap();
+        token.info.total_supply = total_supply;
+        let mut serial_numbers = Vec::new();
+        if token.is_nft() {
+            for metadata in &body.metadata {
+                let serial = token.next_serial;
+                token.next_serial += 1;
+                token.nfts.insert(
+                    serial,
+                    Nft {
+                        owner: treasury,
+                        metadata: metadata.clone(),
+                        creation_time: creation_time.clone(),
+                    },
+                );
+                serial_numbers.push(serial);
+            }
+        }
+        let relation = self
+            .account_mut(&treasury)?
+            .tokens
+            .entry(token_id)
+            .or_default();
+        relation.balance += minted;
+
+        let mut transfers = services::TokenTransferList {
+            token: Some(to_proto(&token_id)),
+            ..Default::default()
+        };
+        if serial_numbers.is_empty() {
+            transfers.transfers.push(services::AccountAmount {
+                account_id: Some(to_proto(&treasury)),
+                amount: minted as i64,
+                is_approval: false,
+            });
+        } else {
+            transfers.nft_transfers = serial_numbers
+                .iter()
+                .map(|serial| services::NftTransfer {
+                    receiver_account_id: Some(to_proto(&treasury)),
+                    serial_number: *serial,
+                    ..Default::default()
+                })
+                .collect();
+        }
+        outcome.token_transfers.push(transfers);
+        outcome.receipt.new_total_supply = total_supply;
+        outcome.receipt.serial_numbers = serial_numbers;
+        Ok(())
+    }
+
+    fn token_burn(
+        &mut self,
+        body: &services::TokenBurnTransactionBody,
+        outcome: &mut Outcome,
+    ) -> LedgerResult<()> {
+        let (token_id, token) = self.token(body.token.clone())?;
+        if token.is_paused() {
+            return Err(Status::TokenIsPaused);
+        }
+        let treasury = account_id(token.info.treasury.clone())?;
+        let treasury_balance = self.account(&treasury)?.tokens[&token_id].balance;
+        let burned = if token.is_nft() {
+            if body.serial_numbers.is_empty() || body.amount != 0 {
+                return Err(Status::InvalidTokenBurnMetadata);
+            }
+            for serial in &body.serial_numbers {
+                match token.nft_owner(*serial) {
+                    Some(owner) if owner == treasury => {}
+                    Some(_) => return Err(Status::TreasuryMustOwnBurnedNft),
+                    None => return Err(Status::InvalidNftId),
+                }
+            }
+            body.serial_numbers.len() as u64
+        } else {
+            if body.amount == 0 || !body.serial_numbers.is_empty() {
+                return Err(Status::InvalidTokenBurnAmount);
+            }
+            body.amount
+        };
+        if treasury_balance < burned {
+            return Err(Status::InsufficientTokenBalance);
+        }
+
+        let token = self.tokens.get_mut(&token_id).unwrap();
+        token.info.total_supply -= burned;
+        for serial in &body.serial_numbers {
+            token.nfts.remove(serial);
+        }
+        let total_supply = token.info.total_supply;
+        let is_nft = token.is_nft();
+        self.account_mut(&treasury)?
+            .tokens
+            .get_mut(&token_id)
+            .unwrap()
+            .balance -= burned;
+
+        let mut transfers = services::TokenTransferList {
+            token: Some(to_proto(&token_id)),
+            ..Default::default()
+        };
+        if is_nft {
+            transfers.nft_transfers = body
+                .serial_numbers
+                .iter()
+                .map(|serial| services::NftTransfer {
+                    sender_account_id: Some(to_proto(&treasury)),
+                    serial_number: *serial,
+                    ..Default::default()
+                })
+                .collect();
+        } else {
+            transfers.transfers.push(services::AccountAmount {
+                account_id: Some(to_proto(&treasury)),
+                amount: -(burned as i64),
+                is_approval: false,
+            });
+        }
+        outcome.token_transfers.push(transfers);
+        outcome.receipt.new_total_supply = total_supply;
+        Ok(())
+    }
+
+    fn token_wipe(
+        &mut self,
+        body: &services::TokenWipeAccountTransactionBody,
+        outcome: &mut Outcome,
+    ) -> LedgerResult<()> {
+        let (token_id, token) = self.token(body.token.clone())?;
+        if token.is_paused() {
+            return Err(Status::TokenIsPaused);
+        }
+        let account_id = account_id(body.account.clone())?;
+        if token.info.treasury == Some(to_proto(&account_id)) {
+            return Err(Status::CannotWipeTokenTreasuryAccount);
+        }
+        let balance = self
+            .account(&account_id)?
+            .tokens
+            .get(&token_id)
+            .ok_or(Status::TokenNotAssociatedToAccount)?
+            .balance;
+        let wiped = if token.is_nft() {
+            if body.serial_numbers.is_empty() || body.amount != 0 {
+                return Err(Status::InvalidWipingAmount);
+            }
+            for serial in &body.serial_numbers {
+                match token.nft_owner(*serial) {
+                    Some(owner) if owner == account_id => {}
+                    Some(_) => return Err(Status::AccountDoesNotOwnWipedNft),
+                    None => return Err(Status::InvalidNftId),
+                }
+            }
+            body.serial_numbers.len() as u64
+        } else {
+            if body.amount == 0 || !body.serial_numbers.is_empty() {
+                return Err(Status::InvalidWipingAmount);
+            }
+            body.amount
+        };
+        if balance < wiped {
+            return Err(Status::InvalidWipingAmount);
+        }
+
+        let token = self.tokens.get_mut(&token_id).unwrap();
+        token.info.total_supply -= wiped;
+        for serial in &body.serial_numbers {
+            token.nfts.remove(serial);
+        }
+        let total_supply = token.info.total_supply;
+        let is_nft = token.is_nft();
+        self.account_mut(&account_id)?
+            .tokens
+            .get_mut(&token_id)
+            .unwrap()
+            .balance -= wiped;
+
+        let mut transfers = services::TokenTransferList {
+            token: Some(to_proto(&token_id)),
+            ..Default::default()
+        };
+        if is_nft {
+            transfers.nft_transfers = body
+                .serial_numbers
+                .iter()
+                .map(|serial| services::NftTransfer {
+                    sender_account_id: Some(to_proto(&account_id)),
+                    serial_number: *serial,
+                    ..Default::default()
+                })
+                .collect();
+        } else {
+            transfers.transfers.push(services::AccountAmount {
+                account_id: Some(to_proto(&account_id)),
+                amount: -(wiped as i64),
+                is_approval: false,
+            });
+        }
+        outcome.token_transfers.push(transfers);
+        outcome.receipt.new_total_supply = total_supply;
+        Ok(())
+    }
+
+    fn update_relation<F: FnOnce(&mut TokenRelation)>(
+        &mut self,
+        token: Option<services::TokenId>,
+        account: Option<services::AccountId>,
+        update: F,
+    ) -> LedgerResult<()> {
+        let (token_id, _) = self.token(token)?;
+        let account_id = account_id(account)?;
+        let relation = self
+            .account_mut(&account_id)?
+            .tokens
+            .get_mut(&token_id)
+            .ok_or(Status::TokenNotAssociatedToAccount)?;
+        update(relation);
+        Ok(())
+    }
+
+    // runs a schedule once its signatories satisfy every required key
+    fn execute_schedule(&mut self, schedule_id: ScheduleId) {
+        let schedule = match self.schedules.get(&schedule_id) {
+            Some(schedule) if !schedule.executed && !schedule.deleted => schedule,
+            _ => return,
+        };
+        let info = schedule.info.clone();
+        let signatories = schedule.signatories.clone();
+        let scheduled = match info
+            .scheduled_transaction_body
+            .clone()
+            .and_then(|body| body.data)
+            .map(scheduled_data)
+        {
+            Some(Ok(data)) => data,
+            _ => return,
+        };
+        let payer_id = match account_id(info.payer_account_id.clone()) {
+            Ok(payer_id) => payer_id,
+            Err(_) => return,
+        };
+        let payer_key = match self.accounts.get(&payer_id) {
+            Some(payer) => payer.key.clone(),
+            None => return,
+        };
+        // an invalid transaction runs anyway, to report why in its receipt
+        let required = self.required_keys(&scheduled, payer_id).unwrap_or_default();
+        if !is_satisfied(&payer_key, &signatories)
+            || !required.iter().all(|key| is_satisfied(key, &signatories))
+        {
+            return;
+        }
+        let transaction_id = match info
+            .scheduled_transaction_id
+            .clone()
+            .map(TransactionId::try_from)
+        {
+            Some(Ok(transaction_id)) => transaction_id,
+            _ => return,
+        };
+
+        let consensus_time = self.next_consensus_time();
+        let mut outcome = Outcome::default();
+        let status = match self.apply(
+            &scheduled,
+            payer_id,
+            &signatories,
+            &transaction_id,
+            &mut outcome,
+        ) {
+            Ok(()) => Status::Success,
+            Err(status) => status,
+        };
+        outcome.receipt.status = status as i32;
+        let schedule = self.schedules.get_mut(&schedule_id).unwrap();
+        schedule.executed = true;
+        schedule.info.data = Some(services::schedule_info::Data::ExecutionTime(timestamp(
+            consensus_time,
+        )));
+        let memo = info
+            .scheduled_transaction_body
+            .map(|body| body.memo)
+            .unwrap_or_default();
+        let record = services::TransactionRecord {
+            receipt: Some(outcome.receipt),
+            consensus_timestamp: Some(timestamp(consensus_time)),
+            transaction_id: info.scheduled_transaction_id,
+            memo,
+            transfer_list: Some(services::TransferList {
+                account_amounts: outcome
+                    .transfers
+                    .into_iter()
+                    .filter(|(_, amount)| *amount != 0)
+                    .map(|(id, amount)| services::AccountAmount {
+                        account_id: Some(to_proto(&id)),
+                        amount,
+                        is_approval: false,
+                    })
+                    .collect(),
+            }),
+            token_transfer_lists: outcome.token_transfers,
+            entropy: outcome.entropy,
+            schedule_ref: Some(to_proto(&schedule_id)),
+            ..Default::default()
+        };
+        self.records.insert(transaction_id, record);
+    }
+
+    /// Answers a query the way a consensus node would. Every query is free.
+    pub(super) fn query(&self, query: &services::Query) -> LedgerResult<response::Response> {
+        let query = query.query.as_ref().ok_or(Status::InvalidTransactionBody)?;
+        if query_header(query)
+            .map(|header| header.response_type == ResponseType::CostAnswer as i32)
+            .unwrap_or(false)
+        {
+            return Ok(response_for_query(query, header(Status::Ok, 0)));
+        }
+        let answer = match self.answer(query) {
+            Ok(answer) => answer,
+            Err(status) => response_for_query(query, header(status, 0)),
+        };
+        Ok(answer)
+    }
+
+    fn answer(&self, query: &query::Query) -> LedgerResult<response::Response> {
+        let header = Some(header(Status::Ok, 0));
+        let response = match query {
+            query::Query::TransactionGetReceipt(query) => {
+                let record = self.record(query.transaction_id.clone(), Status::ReceiptNotFound)?;
+                response::Response::TransactionGetReceipt(services::TransactionGetReceiptResponse {
+                    header,
+                    receipt: record.receipt.clone(),
+                    ..Default::default()
+                })
+            }
+            query::Query::TransactionGetRecord(query) => {
+                let record = self.record(query.transaction_id.clone(), Status::RecordNotFound)?;
+                response::Response::TransactionGetRecord(services::TransactionGetRecordResponse {
+                    header,
+                    transaction_record: Some(record.clone()),
+                    ..Default::default()
+                })
+            }
+            query::Query::CryptogetAccountBalance(query) => {
+                let account_id = match &query.balance_source {
+                    Some(crypto_get_account_balance_query::BalanceSource::AccountId(id)) => {
+                        account_id(Some(id.clone()))?
+                    }
+                    _ => return Err(Status::InvalidAccountId),
+                };
+                let account = self.account(&account_id)?;
+                response::Response::CryptogetAccountBalance(
+                    services::CryptoGetAccountBalanceResponse {
+                        header,
+                        account_id: Some(to_proto(&account_id)),
+                        balance: account.balance,
+                        token_balances: account
+                            .tokens
+                            .iter()
+                            .map(|(token_id, relation)| services::TokenBalance {
+                                token_id: Some(to_proto(token_id)),
+                                balance: relation.balance,
+                                decimals: self.tokens[token_id].info.decimals,
+                            })
+                            .collect(),
+                    },
+                )
+            }
+            query::Query::CryptoGetInfo(query) => {
+                let account_id = account_id(query.account_id.clone())?;
+                let account = self.account(&account_id)?;
+                response::Response::CryptoGetInfo(services::CryptoGetInfoResponse {
+                    header,
+                    account_info: Some(services::crypto_get_info_response::AccountInfo {
+                        account_id: Some(to_proto(&account_id)),
+                        key: Some(account.key.clone()),
+                        balance: account.balance,
+                        receiver_sig_required: account.receiver_sig_required,
+                        memo: account.memo.clone(),
+                        token_relationships: account
+                            .tokens
+                            .iter()
+                            .map(|(token_id, relation)| self.token_relationship(token_id, relation))
+                            .collect(),
+                        ..Default::default()
+                    }),
+                })
+            }
+            query::Query::TokenGetInfo(query) => {
+                let token_id = query
+                    .token
+                    .clone()
+                    .map(TokenId::from)
+                    .ok_or(Status::InvalidTokenId)?;
+                let token = self.tokens.get(&token_id).ok_or(Status::InvalidTokenId)?;
+                response::Response::TokenGetInfo(services::TokenGetInfoResponse {
+                    header,
+                    token_info: Some(token.info.clone()),
+                })
+            }
+            query::Query::TokenGetNftInfo(query) => {
+                let nft_id = query.nft_id.clone().ok_or(Status::InvalidNftId)?;
+                let token_id = nft_id
+                    .token_id
+                    .clone()
+                    .map(TokenId::from)
+                    .ok_or(Status::InvalidTokenId)?;
+                let token = self.tokens.get(&token_id).ok_or(Status::InvalidTokenId)?;
+                let nft = token
+                    .nfts
+                    .get(&nft_id.serial_number)
+                    .ok_or(Status::InvalidNftId)?;
+                response::Response::TokenGetNftInfo(services::TokenGetNftInfoResponse {
+                    header,
+                    nft: Some(services::TokenNftInfo {
+                        nft_id: Some(nft_id),
+                        account_id: Some(to_proto(&nft.owner)),
+                        creation_time: Some(nft.creation_time.clone()),
+                        metadata: nft.metadata.clone(),
+                        ..Default::default()
+                    }),
+                })
+            }
+            query::Query::FileGetContents(query) => {
+                let (file_id, file) = self.file(query.file_id.clone())?;
+                response::Response::FileGetContents(services::FileGetContentsResponse {
+                    header,
+                    file_contents: Some(file_get_contents_response::FileContents {
+                        file_id: Some(to_proto(&file_id)),
+                        contents: file.contents.clone(),
+                    }),
+                })
+            }
+            query::Query::FileGetInfo(query) => {
+                let file_id = query
+                    .file_id
+                    .clone()
+                    .map(FileId::from)
+                    .ok_or(Status::InvalidFileId)?;
+                let file = self.files.get(&file_id).ok_or(Status::InvalidFileId)?;
+                response::Response::FileGetInfo(services::FileGetInfoResponse {
+                    header,
+                    file_info: Some(file.info.clone()),
+                })
+            }
+            query::Query::ConsensusGetTopicInfo(query) => {
+                let (topic_id, topic) = self.topic(query.topic_id.clone())?;
+                response::Response::ConsensusGetTopicInfo(services::ConsensusGetTopicInfoResponse {
+                    header,
+                    topic_id: Some(to_proto(&topic_id)),
+                    topic_info: Some(topic.info.clone()),
+                })
+            }
+            query::Query::ScheduleGetInfo(query) => {
+                let schedule_id = query
+                    .schedule_id
+                    .clone()
+                    .map(ScheduleId::from)
+                    .ok_or(Status::InvalidScheduleId)?;
+                let schedule = self
+                    .schedules
+                    .get(&schedule_id)
+                    .ok_or(Status::InvalidScheduleId)?;
+                let mut info = schedule.info.clone();
+                let mut signatories: Vec<&Vec<u8>> = schedule.signatories.iter().collect();
+                signatories.sort();
+                info.signers = Some(services::KeyList {
+                    keys: signatories
+                        .into_iter()
+                        .filter_map(|bytes| PublicKey::from_bytes(bytes).ok())
+                        .filter_map(|key| key.to_proto().ok())
+                        .map(|key| services::Key { key: Some(key) })
+                        .collect(),
+                });
+                response::Response::ScheduleGetInfo(services::ScheduleGetInfoResponse {
+                    header,
+                    schedule_info: Some(info),
+                })
+            }
+            _ => return Err(Status::NotSupported),
+        };
+        Ok(response)
+    }
+
+    fn record(
+        &self,
+        transaction_id: Option<services::TransactionId>,
+        not_found: Status,
+    ) -> LedgerResult<&services::TransactionRecord> {
+        let transaction_id = transaction_id
+            .map(TransactionId::try_from)
+            .ok_or(Status::InvalidTransactionId)?
+            .map_err(|_| Status::InvalidTransactionId)?;
+        self.records.get(&transaction_id).ok_or(not_found)
+    }
+
+    fn token_relationship(
+        &self,
+        token_id: &TokenId,
+        relation: &TokenRelation,
+    ) -> services::TokenRelationship {
+        let info = &self.tokens[token_id].info;
+        let freeze_status = match (&info.freeze_key, relation.frozen) {
+            (None, _) => services::TokenFreezeStatus::FreezeNotApplicable,
+            (Some(_), true) => services::TokenFreezeStatus::Frozen,
+            (Some(_), false) => services::TokenFreezeStatus::Unfrozen,
+        };
+        let kyc_status = match (&info.kyc_key, relation.kyc_granted) {
+            (None, _) => services::TokenKycStatus::KycNotApplicable,
+            (Some(_), true) => services::TokenKycStatus::Granted,
+            (Some(_), false) => services::TokenKycStatus::Revoked,
+        };
+        services::TokenRelationship {
+            token_id: Some(to_proto(token_id)),
+            symbol: info.symbol.clone(),
+            balance: relation.balance,
+            kyc_status: kyc_status as i32,
+            freeze_status: freeze_status as i32,
+            decimals: info.decimals,
+            automatic_association: false,
+        }
+    }
+}
+
+fn query_header(query: &query::Query) -> Option<&services::QueryHeader> {
+    match query {
+        query::Query::TransactionGetReceipt(q) => q.header.as_ref(),
+        query::Query::TransactionGetRecord(q) => q.header.as_ref(),
+        query::Query::CryptogetAccountBalance(q) => q.header.as_ref(),
+        query::Query::CryptoGetInfo(q) => q.header.as_ref(),
+        query::Query::TokenGetInfo(q) => q.header.as_ref(),
+        query::Query::TokenGetNftInfo(q) => q.header.as_ref(),
+        query::Query::FileGetContents(q) => q.header.as_ref(),
+        query::Query::FileGetInfo(q) => q.header.as_ref(),
+        query::Query::ConsensusGetTopicInfo(q) => q.header.as_ref(),
+        query::Query::ScheduleGetInfo(q) => q.header.as_ref(),
+        _ => None,
+    }
+}
+
+// the version 3 running hash of a topic after `message`
+fn running_hash(
+    previous: &[u8],
+    payer_id: &AccountId,
+    topic_id: &TopicId,
+    consensus_time: DateTime<Utc>,
+    sequence_number: u64,
+    message: &[u8],
+) -> Vec<u8> {
+    let payer: services::AccountId = to_proto(payer_id);
+    let topic: services::TopicId = to_proto(topic_id);
+    let payer_num = match payer.account {
+        Some(services::account_id::Account::AccountNum(num)) => num,
+        _ => 0,
+    };
+    let mut hasher = Sha384::new();
+    hasher.update(previous);
+    hasher.update(TOPIC_RUNNING_HASH_VERSION.to_be_bytes());
+    hasher.update(payer.shard_num.to_be_bytes());
+    hasher.update(payer.realm_num.to_be_bytes());
+    hasher.update(payer_num.to_be_bytes());
+    hasher.update(topic.shard_num.to_be_bytes());
+    hasher.update(topic.realm_num.to_be_bytes());
+    hasher.update(topic.topic_num.to_be_bytes());
+    hasher.update(consensus_time.timestamp().to_be_bytes());
+    hasher.update((consensus_time.timestamp_subsec_nanos() as i32).to_be_bytes());
+    hasher.update(sequence_number.to_be_bytes());
+    hasher.update(Sha384::digest(message));
+    hasher.finalize().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Key, KeyList, PrivateKey};
+
+    fn signers(keys: &[&PrivateKey]) -> HashSet<Vec<u8>> {
+        keys.iter().map(|key| key.public().as_bytes_vec()).collect()
+    }
+
+    #[test]
+    fn test_is_satisfied_threshold_key() {
+        let keys: Vec<PrivateKey> = (0..3).map(|_| PrivateKey::new()).collect();
+        let mut list = KeyList::with_threshold(2);
+        list.add_all_public_keys(keys.iter().map(|key| key.public()).collect());
+        let threshold_key = Key::ThresholdKey(list.clone()).to_proto().unwrap();
+        let key_list = Key::KeyList(list).to_proto().unwrap();
+
+        assert!(!is_satisfied(&threshold_key, &signers(&[&keys[0]])));
+        assert!(is_satisfied(
+            &threshold_key,
+            &signers(&[&keys[0], &keys[2]])
+        ));
+        assert!(!is_satisfied(&key_list, &signers(&[&keys[0], &keys[2]])));
+        assert!(is_satisfied(
+            &key_list,
+            &signers(&[&keys[0], &keys[1], &keys[2]])
+        ));
+    }
+
+    #[test]
+    fn test_verified_signers() {
+        let key = PrivateKey::new();
+        let body_bytes = b"body".to_vec();
+        let sig_map = |signature: &[u8]| services::SignatureMap {
+            sig_pair: vec![services::SignaturePair {
+                pub_key_prefix: key.public().as_bytes_vec(),
+                signature: Some(signature_pair::Signature::Ed25519(signature.to_vec())),
+            }],
+        };
+
+        let signature = key.sign(&body_bytes).as_bytes_vec();
+        let verified = verified_signers(&body_bytes, Some(&sig_map(&signature))).unwrap();
+        assert_eq!(verified, signers(&[&key]));
+
+        let signature = key.sign(b"other").as_bytes_vec();
+        assert_eq!(
+            verified_signers(&body_bytes, Some(&sig_map(&signature))),
+            Err(Status::InvalidSignature)
+        );
+    }
+}
@@ -0,0 +1,163 @@
+use chrono::{DateTime, Utc};
+use std::collections::VecDeque;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::{mpsc, oneshot};
+use tokio_stream::wrappers::{ReceiverStream, TcpListenerStream};
+use tonic::transport::Server;
+use tonic::{Request, Response};
+
+use crate::error::HederaError;
+use crate::proto::mirror::{
+    self,
+    consensus_service_server::{ConsensusService, ConsensusServiceServer},
+};
+use crate::proto::services;
+
+/// One step of the answer to a topic subscription.
+#[derive(Debug, Clone)]
+pub enum MockTopicResponse {
+    Message(mirror::ConsensusTopicResponse),
+    /// Fails the stream with this code, ending it.
+    Error(tonic::Code),
+    /// Waits before the next step.
+    Delay(Duration),
+}
+
+impl MockTopicResponse {
+    /// A single chunk message.
+    pub fn message(
+        sequence_number: u64,
+        consensus_timestamp: DateTime<Utc>,
+        contents: &[u8],
+    ) -> MockTopicResponse {
+        MockTopicResponse::Message(mirror::ConsensusTopicResponse {
+            consensus_timestamp: Some(services::Timestamp {
+                seconds: consensus_timestamp.timestamp(),
+                nanos: consensus_timestamp.timestamp_subsec_nanos() as i32,
+            }),
+            message: contents.to_vec(),
+            running_hash: sequence_number.to_be_bytes().to_vec(),
+            running_hash_version: 3,
+            sequence_number,
+            chunk_info: None,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+struct MockMirrorState {
+    subscriptions: VecDeque<Vec<MockTopicResponse>>,
+    requests: Vec<mirror::ConsensusTopicQuery>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct MockMirrorService {
+    state: Arc<Mutex<MockMirrorState>>,
+}
+
+#[tonic::async_trait]
+impl ConsensusService for MockMirrorService {
+    type subscribeTopicStream =
+        ReceiverStream<Result<mirror::ConsensusTopicResponse, tonic::Status>>;
+
+    async fn subscribe_topic(
+        &self,
+        request: Request<mirror::ConsensusTopicQuery>,
+    ) -> Result<Response<Self::subscribeTopicStream>, tonic::Status> {
+        let responses = {
+            let mut state = self.state.lock().unwrap();
+            state.requests.push(request.into_inner());
+            state.subscriptions.pop_front().unwrap_or_default()
+        };
+        let (sender, receiver) = mpsc::channel(1);
+        tokio::spawn(async move {
+            for response in responses {
+                let sent = match response {
+                    MockTopicResponse::Message(message) => sender.send(Ok(message)).await,
+                    MockTopicResponse::Error(code) => {
+                        let _ = sender.send(Err(tonic::Status::new(code, "mock"))).await;
+                        return;
+                    }
+                    MockTopicResponse::Delay(delay) => {
+                        tokio::time::sleep(delay).await;
+                        Ok(())
+                    }
+                };
+                if sent.is_err() {
+                    return;
+                }
+            }
+        });
+        Ok(Response::new(ReceiverStream::new(receiver)))
+    }
+}
+
+/// A mirror node serving topic subscriptions in-process on `127.0.0.1`,
+/// shut down on drop.
+///
+/// Each `subscribe_topic` call is answered with the next queued subscription,
+/// the stream closing after its last step. Calls with none queued get an
+/// empty stream.
+#[derive(Debug)]
+pub struct MockMirrorNode {
+    address: SocketAddr,
+    service: MockMirrorService,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockMirrorNode {
+    /// Starts serving on a free local port.
+    pub async fn start() -> Result<MockMirrorNode, HederaError> {
+        let service = MockMirrorService::default();
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        let (shutdown, shutdown_rx) = oneshot::channel::<()>();
+        let router = Server::builder().add_service(ConsensusServiceServer::new(service.clone()));
+        tokio::spawn(router.serve_with_incoming_shutdown(
+            TcpListenerStream::new(listener),
+            async {
+                let _ = shutdown_rx.await;
+            },
+        ));
+        Ok(MockMirrorNode {
+            address,
+            service,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// The node as a mirror network, for `MirrorNetwork::from_network`.
+    pub fn network(&self) -> Vec<String> {
+        vec![self.address.to_string()]
+    }
+
+    /// Queues the answer to a future subscription.
+    pub fn push_subscription(&self, responses: Vec<MockTopicResponse>) {
+        self.service
+            .state
+            .lock()
+            .unwrap()
+            .subscriptions
+            .push_back(responses);
+    }
+
+    /// Every subscription query received so far, oldest first.
+    pub fn requests(&self) -> Vec<mirror::ConsensusTopicQuery> {
+        self.service.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl Drop for MockMirrorNode {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
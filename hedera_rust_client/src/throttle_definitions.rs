@@ -0,0 +1,141 @@
+use bytes::Bytes;
+use num_traits::{FromPrimitive, ToPrimitive};
+use prost::Message;
+use std::convert::{TryFrom, TryInto};
+
+use crate::error::HederaError;
+use crate::proto::services::{
+    ThrottleBucket as ProtoThrottleBucket, ThrottleDefinitions as ProtoThrottleDefinitions,
+    ThrottleGroup as ProtoThrottleGroup,
+};
+use crate::proto::ToProto;
+use crate::transaction_fee_schedule::HederaFunctionality;
+
+/// The network's throttling policy, as kept in file `0.0.123`. An operation
+/// must have room in every bucket it appears in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThrottleDefinitions {
+    pub throttle_buckets: Vec<ThrottleBucket>,
+}
+
+impl ThrottleDefinitions {
+    pub fn from_proto_bytes(bytes: Vec<u8>) -> Result<ThrottleDefinitions, HederaError> {
+        match ProtoThrottleDefinitions::decode(Bytes::from(bytes)) {
+            Ok(proto) => proto.try_into(),
+            Err(e) => Err(HederaError::ProtoDeserialize("ThrottleDefinitions", e)),
+        }
+    }
+
+    /// The buckets throttling `operation`.
+    pub fn buckets_for(&self, operation: HederaFunctionality) -> Vec<&ThrottleBucket> {
+        self.throttle_buckets
+            .iter()
+            .filter(|bucket| {
+                bucket
+                    .throttle_groups
+                    .iter()
+                    .any(|group| group.operations.contains(&operation))
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<ProtoThrottleDefinitions> for ThrottleDefinitions {
+    type Error = HederaError;
+    fn try_from(services: ProtoThrottleDefinitions) -> Result<ThrottleDefinitions, Self::Error> {
+        let throttle_buckets = services
+            .throttle_buckets
+            .into_iter()
+            .map(|x| x.try_into())
+            .collect::<Result<Vec<ThrottleBucket>, HederaError>>()?;
+        Ok(ThrottleDefinitions { throttle_buckets })
+    }
+}
+
+impl ToProto<ProtoThrottleDefinitions> for ThrottleDefinitions {
+    fn to_proto(&self) -> Result<ProtoThrottleDefinitions, HederaError> {
+        let throttle_buckets = self
+            .throttle_buckets
+            .iter()
+            .map(|x| x.to_proto())
+            .collect::<Result<Vec<ProtoThrottleBucket>, HederaError>>()?;
+        Ok(ProtoThrottleDefinitions { throttle_buckets })
+    }
+}
+
+/// Throttle groups competing for the same bucket.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThrottleBucket {
+    pub name: String,
+    /// Milliseconds for the bucket to drain when full.
+    pub burst_period_ms: u64,
+    pub throttle_groups: Vec<ThrottleGroup>,
+}
+
+impl TryFrom<ProtoThrottleBucket> for ThrottleBucket {
+    type Error = HederaError;
+    fn try_from(services: ProtoThrottleBucket) -> Result<ThrottleBucket, Self::Error> {
+        let throttle_groups = services
+            .throttle_groups
+            .into_iter()
+            .map(|x| x.try_into())
+            .collect::<Result<Vec<ThrottleGroup>, HederaError>>()?;
+        Ok(ThrottleBucket {
+            name: services.name,
+            burst_period_ms: services.burst_period_ms,
+            throttle_groups,
+        })
+    }
+}
+
+impl ToProto<ProtoThrottleBucket> for ThrottleBucket {
+    fn to_proto(&self) -> Result<ProtoThrottleBucket, HederaError> {
+        let throttle_groups = self
+            .throttle_groups
+            .iter()
+            .map(|x| x.to_proto())
+            .collect::<Result<Vec<ProtoThrottleGroup>, HederaError>>()?;
+        Ok(ProtoThrottleBucket {
+            name: self.name.clone(),
+            burst_period_ms: self.burst_period_ms,
+            throttle_groups,
+        })
+    }
+}
+
+/// Operations throttled together at a network-wide rate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ThrottleGroup {
+    pub operations: Vec<HederaFunctionality>,
+    /// Operations per second across the network, times 1000.
+    pub milli_ops_per_sec: u64,
+}
+
+impl TryFrom<ProtoThrottleGroup> for ThrottleGroup {
+    type Error = HederaError;
+    fn try_from(services: ProtoThrottleGroup) -> Result<ThrottleGroup, Self::Error> {
+        let operations = services
+            .operations
+            .into_iter()
+            .map(|x| HederaFunctionality::from_i32(x).ok_or(HederaError::UnexpectedProtoType))
+            .collect::<Result<Vec<HederaFunctionality>, HederaError>>()?;
+        Ok(ThrottleGroup {
+            operations,
+            milli_ops_per_sec: services.milli_ops_per_sec,
+        })
+    }
+}
+
+impl ToProto<ProtoThrottleGroup> for ThrottleGroup {
+    fn to_proto(&self) -> Result<ProtoThrottleGroup, HederaError> {
+        let operations = self
+            .operations
+            .iter()
+            .map(|x| x.to_i32().ok_or(HederaError::UnexpectedProtoType))
+            .collect::<Result<Vec<i32>, HederaError>>()?;
+        Ok(ProtoThrottleGroup {
+            operations,
+            milli_ops_per_sec: self.milli_ops_per_sec,
+        })
+    }
+}
@@ -0,0 +1,63 @@
+use serde::Serialize;
+use std::convert::TryFrom;
+
+use crate::error::HederaError;
+use crate::proto::{services, ToProto};
+use crate::utils;
+use crate::AccountId;
+use crate::TokenId;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct TokenAllowance {
+    pub token_id: Option<TokenId>,
+    pub owner_account_id: Option<AccountId>,
+    pub spender_account_id: Option<AccountId>,
+    pub amount: i64,
+}
+
+impl TokenAllowance {
+    pub fn new(
+        token_id: Option<TokenId>,
+        owner_account_id: Option<AccountId>,
+        spender_account_id: Option<AccountId>,
+        amount: i64,
+    ) -> Self {
+        TokenAllowance {
+            token_id,
+            owner_account_id,
+            spender_account_id,
+            amount,
+        }
+    }
+}
+
+impl TryFrom<services::TokenAllowance> for TokenAllowance {
+    type Error = HederaError;
+    fn try_from(services: services::TokenAllowance) -> Result<TokenAllowance, Self::Error> {
+        Ok(TokenAllowance {
+            token_id: services.token_id.map(TokenId::from),
+            owner_account_id: utils::optional_account_id(services.owner)?,
+            spender_account_id: utils::optional_account_id(services.spender)?,
+            amount: services.amount,
+        })
+    }
+}
+
+impl ToProto<services::TokenAllowance> for TokenAllowance {
+    fn to_proto(&self) -> Result<services::TokenAllowance, HederaError> {
+        Ok(services::TokenAllowance {
+            token_id: self.token_id.as_ref().map(|x| x.to_proto()).transpose()?,
+            owner: self
+                .owner_account_id
+                .as_ref()
+                .map(|x| x.to_proto())
+                .transpose()?,
+            spender: self
+                .spender_account_id
+                .as_ref()
+                .map(|x| x.to_proto())
+                .transpose()?,
+            amount: self.amount,
+        })
+    }
+}
@@ -1,3 +1,4 @@
+use serde::Serialize;
 use std::convert::TryFrom;
 
 use crate::error::HederaError;
@@ -5,7 +6,7 @@ use crate::proto::services::{self};
 use crate::AccountId;
 use crate::TokenId;
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Serialize)]
 pub struct TokenAssociation {
     pub token_id: TokenId,
     pub account_id: AccountId,
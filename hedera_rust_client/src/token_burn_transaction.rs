@@ -6,6 +6,7 @@ use crate::Hbar;
 use crate::HederaError;
 use crate::TokenId;
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
 
 #[derive(TransactionSchedule, TransactionExecute, Debug, Clone, PartialEq)]
 #[hedera_rust_client_derive(service(
@@ -42,7 +43,7 @@ impl TokenBurnTransaction {
     gen_transaction_serial_numbers_fns!();
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "TokenBurn",
     proto_type = "TokenBurnTransactionBody"
@@ -1,5 +1,6 @@
 use chrono::{DateTime, Duration, Utc};
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
 
 use crate::entity_id::{validate_option_id_checksum, ValidateChecksum};
 use crate::transaction::Transaction;
@@ -104,7 +105,7 @@ impl TokenCreateTransaction {
     gen_transaction_pause_key_fns!();
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "TokenCreation",
     proto_type = "TokenCreateTransactionBody"
@@ -132,11 +133,12 @@ struct Proto {
     #[hedera_rust_client_derive(to_option_proto)]
     pub auto_renew_account: Option<AccountId>,
     #[hedera_rust_client_derive(to_option_proto)]
+    #[serde(serialize_with = "crate::json::option_duration")]
     pub auto_renew_period: Option<Duration>,
     pub memo: String,
-    #[hedera_rust_client_derive(to_proto)]
+    #[hedera_rust_client_derive(to_proto, from_proto_with_fn = "TokenType::from_proto")]
     pub token_type: TokenType,
-    #[hedera_rust_client_derive(to_proto)]
+    #[hedera_rust_client_derive(to_proto, from_proto_with_fn = "TokenSupplyType::from_proto")]
     pub supply_type: TokenSupplyType,
     pub max_supply: i64,
     #[hedera_rust_client_derive(to_option_proto)]
@@ -7,6 +7,7 @@ use crate::Hbar;
 use crate::HederaError;
 use crate::TokenId;
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
 
 #[derive(TransactionSchedule, TransactionExecute, Debug, Clone, PartialEq)]
 #[hedera_rust_client_derive(service(
@@ -43,7 +44,7 @@ impl TokenDissociateTransaction {
     gen_transaction_tokens_fns!();
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "TokenDissociate",
     proto_type = "TokenDissociateTransactionBody"
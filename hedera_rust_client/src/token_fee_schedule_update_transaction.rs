@@ -6,6 +6,7 @@ use crate::Hbar;
 use crate::HederaError;
 use crate::TokenId;
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
 
 #[derive(TransactionSchedule, TransactionExecute, Debug, Clone, PartialEq)]
 #[hedera_rust_client_derive(service(
@@ -42,7 +43,7 @@ impl TokenFeeScheduleUpdateTransaction {
     gen_transaction_custom_fees_fns!();
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "TokenFeeScheduleUpdate",
     proto_type = "TokenFeeScheduleUpdateTransactionBody"
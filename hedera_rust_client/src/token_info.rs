@@ -1,4 +1,5 @@
 use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
 use std::convert::TryFrom;
 
 use crate::error::HederaError;
@@ -9,8 +10,9 @@ use crate::AccountId;
 use crate::TokenFreezeStatus;
 use crate::TokenId;
 use crate::TokenKycStatus;
+use crate::TokenPauseStatus;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct TokenInfo {
     /// ID of the token instance
     pub token_id: TokenId,
@@ -43,11 +45,16 @@ pub struct TokenInfo {
     /// An account which will be automatically charged to renew the token's expiration, at autoRenewPeriod interval
     pub auto_renew_account: Option<AccountId>,
     /// The interval at which the auto-renew account will be charged to extend the token's expiry
+    #[serde(serialize_with = "crate::json::option_duration")]
     pub auto_renew_period: Option<Duration>,
     /// The epoch second at which the token will expire
     pub expiry: Option<DateTime<Utc>>,
     /// The memo associated with the token
     pub token_memo: String,
+    /// The key which can pause and unpause the token. If empty, the token cannot be paused
+    pub pause_key: Option<Key>,
+    /// Whether the token is paused or not. PauseNotApplicable is returned if the pause key is not set
+    pub pause_status: TokenPauseStatus,
 }
 
 impl TryFrom<services::TokenInfo> for TokenInfo {
@@ -57,6 +64,8 @@ impl TryFrom<services::TokenInfo> for TokenInfo {
             .ok_or(HederaError::UnexpectedProtoType)?;
         let default_freeze_status = TokenFreezeStatus::from_i32(services.default_kyc_status)
             .ok_or(HederaError::UnexpectedProtoType)?;
+        let pause_status = TokenPauseStatus::from_i32(services.pause_status)
+            .ok_or(HederaError::UnexpectedProtoType)?;
         Ok(TokenInfo {
             token_id: utils::non_optional_token_id(services.token_id)?,
             name: services.name,
@@ -76,6 +85,8 @@ impl TryFrom<services::TokenInfo> for TokenInfo {
             auto_renew_period: utils::optional_duration(services.auto_renew_period)?,
             expiry: utils::optional_timestamp(services.expiry)?,
             token_memo: services.memo,
+            pause_key: utils::optional_key(services.pause_key)?,
+            pause_status,
         })
     }
 }
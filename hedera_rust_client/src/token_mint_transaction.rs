@@ -6,6 +6,7 @@ use crate::Hbar;
 use crate::HederaError;
 use crate::TokenId;
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
 
 #[derive(TransactionSchedule, TransactionExecute, Debug, Clone, PartialEq)]
 #[hedera_rust_client_derive(service(
@@ -42,7 +43,7 @@ impl TokenMintTransaction {
     gen_transaction_metadatas_fns!();
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "TokenMint",
     proto_type = "TokenMintTransactionBody"
@@ -51,6 +52,7 @@ struct Proto {
     #[hedera_rust_client_derive(to_option_proto)]
     pub token: Option<TokenId>,
     pub amount: u64,
+    #[serde(serialize_with = "crate::json::hex_vec")]
     pub metadata: Vec<Vec<u8>>,
 }
 
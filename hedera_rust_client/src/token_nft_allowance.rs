@@ -0,0 +1,135 @@
+use serde::Serialize;
+use std::convert::TryFrom;
+
+use crate::error::HederaError;
+use crate::proto::{services, ToProto};
+use crate::utils;
+use crate::AccountId;
+use crate::TokenId;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TokenNftAllowance {
+    pub token_id: Option<TokenId>,
+    pub owner_account_id: Option<AccountId>,
+    pub spender_account_id: Option<AccountId>,
+    pub serial_numbers: Vec<i64>,
+    // `Some(true)` grants the spender every serial of the token, current and future
+    pub all_serials: Option<bool>,
+    pub delegating_spender: Option<AccountId>,
+}
+
+impl TokenNftAllowance {
+    pub fn new(
+        token_id: Option<TokenId>,
+        owner_account_id: Option<AccountId>,
+        spender_account_id: Option<AccountId>,
+        serial_numbers: Vec<i64>,
+        all_serials: Option<bool>,
+        delegating_spender: Option<AccountId>,
+    ) -> Self {
+        TokenNftAllowance {
+            token_id,
+            owner_account_id,
+            spender_account_id,
+            serial_numbers,
+            all_serials,
+            delegating_spender,
+        }
+    }
+}
+
+impl TryFrom<services::NftAllowance> for TokenNftAllowance {
+    type Error = HederaError;
+    fn try_from(services: services::NftAllowance) -> Result<TokenNftAllowance, Self::Error> {
+        Ok(TokenNftAllowance {
+            token_id: services.token_id.map(TokenId::from),
+            owner_account_id: utils::optional_account_id(services.owner)?,
+            spender_account_id: utils::optional_account_id(services.spender)?,
+            serial_numbers: services.serial_numbers,
+            all_serials: services.approved_for_all,
+            delegating_spender: utils::optional_account_id(services.delegating_spender)?,
+        })
+    }
+}
+
+impl ToProto<services::NftAllowance> for TokenNftAllowance {
+    fn to_proto(&self) -> Result<services::NftAllowance, HederaError> {
+        Ok(services::NftAllowance {
+            token_id: self.token_id.as_ref().map(|x| x.to_proto()).transpose()?,
+            owner: self
+                .owner_account_id
+                .as_ref()
+                .map(|x| x.to_proto())
+                .transpose()?,
+            spender: self
+                .spender_account_id
+                .as_ref()
+                .map(|x| x.to_proto())
+                .transpose()?,
+            serial_numbers: self.serial_numbers.clone(),
+            approved_for_all: self.all_serials,
+            delegating_spender: self
+                .delegating_spender
+                .as_ref()
+                .map(|x| x.to_proto())
+                .transpose()?,
+        })
+    }
+}
+
+impl TryFrom<services::NftRemoveAllowance> for TokenNftAllowance {
+    type Error = HederaError;
+    fn try_from(services: services::NftRemoveAllowance) -> Result<TokenNftAllowance, Self::Error> {
+        Ok(TokenNftAllowance {
+            token_id: services.token_id.map(TokenId::from),
+            owner_account_id: utils::optional_account_id(services.owner)?,
+            spender_account_id: None,
+            serial_numbers: services.serial_numbers,
+            all_serials: None,
+            delegating_spender: None,
+        })
+    }
+}
+
+impl ToProto<services::NftRemoveAllowance> for TokenNftAllowance {
+    fn to_proto(&self) -> Result<services::NftRemoveAllowance, HederaError> {
+        Ok(services::NftRemoveAllowance {
+            token_id: self.token_id.as_ref().map(|x| x.to_proto()).transpose()?,
+            owner: self
+                .owner_account_id
+                .as_ref()
+                .map(|x| x.to_proto())
+                .transpose()?,
+            serial_numbers: self.serial_numbers.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_nft_allowance_proto() -> Result<(), HederaError> {
+        let allowance = TokenNftAllowance::new(
+            Some(TokenId::simple(5)),
+            Some(AccountId::simple(1001)),
+            Some(AccountId::simple(1002)),
+            vec![1, 2],
+            None,
+            Some(AccountId::simple(1003)),
+        );
+        let approval: services::NftAllowance = allowance.to_proto()?;
+        assert_eq!(TokenNftAllowance::try_from(approval)?, allowance);
+
+        // removals name no spender
+        let removal: services::NftRemoveAllowance = allowance.to_proto()?;
+        assert_eq!(removal.serial_numbers, vec![1, 2]);
+        let removed = TokenNftAllowance::try_from(removal)?;
+        assert_eq!(removed.token_id, allowance.token_id);
+        assert_eq!(removed.owner_account_id, allowance.owner_account_id);
+        assert_eq!(removed.spender_account_id, None);
+        assert_eq!(removed.delegating_spender, None);
+        Ok(())
+    }
+}
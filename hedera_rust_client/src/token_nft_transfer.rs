@@ -1,14 +1,16 @@
+use serde::Serialize;
 use std::convert::TryFrom;
 
 use crate::account_id::AccountId;
 use crate::error::HederaError;
 use crate::proto::{services::NftTransfer, ToProto};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub struct TokenNftTransfer {
     pub sender_account_id: AccountId,
     pub receiver_account_id: AccountId,
     pub serial_number: i64,
+    pub is_approval: bool,
 }
 
 impl TryFrom<NftTransfer> for TokenNftTransfer {
@@ -28,6 +30,7 @@ impl TryFrom<NftTransfer> for TokenNftTransfer {
                 }
             },
             serial_number: services.serial_number,
+            is_approval: services.is_approval,
         })
     }
 }
@@ -38,7 +41,7 @@ impl ToProto<NftTransfer> for TokenNftTransfer {
             sender_account_id: Some(self.sender_account_id.to_proto()?),
             receiver_account_id: Some(self.receiver_account_id.to_proto()?),
             serial_number: self.serial_number,
-            is_approval: false,
+            is_approval: self.is_approval,
         })
     }
 }
@@ -0,0 +1,2 @@
+// re export TokenPauseStatus in proto
+pub use crate::proto::services::TokenPauseStatus;
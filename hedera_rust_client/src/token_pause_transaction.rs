@@ -0,0 +1,54 @@
+use crate::entity_id::validate_option_id_checksum;
+
+use crate::transaction::Transaction;
+use crate::Client;
+use crate::Hbar;
+use crate::HederaError;
+use crate::TokenId;
+use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
+
+#[derive(TransactionSchedule, TransactionExecute, Debug, Clone, PartialEq)]
+#[hedera_rust_client_derive(service(
+    method_service_name = "token",
+    method_service_fn = "pause_token"
+))]
+pub struct TokenPauseTransaction {
+    transaction: Transaction,
+    services: Proto,
+}
+
+impl TokenPauseTransaction {
+    pub fn new() -> TokenPauseTransaction {
+        let transaction = Transaction::with_max_transaction_fee(Hbar::new(30.0));
+        let services = Proto::new();
+        TokenPauseTransaction {
+            transaction,
+            services,
+        }
+    }
+
+    fn validate_network_on_ids(&self, client: &Client) -> Result<(), HederaError> {
+        validate_option_id_checksum(&self.services.token, client)?;
+        Ok(())
+    }
+
+    // token
+    gen_transaction_token_fns!();
+}
+
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
+#[hedera_rust_client_derive(proto(
+    proto_enum = "TokenPause",
+    proto_type = "TokenPauseTransactionBody"
+))]
+struct Proto {
+    #[hedera_rust_client_derive(to_option_proto)]
+    pub token: Option<TokenId>,
+}
+
+impl Proto {
+    pub fn new() -> Self {
+        Proto { token: None }
+    }
+}
@@ -1,3 +1,4 @@
+use serde::Serialize;
 use std::convert::TryFrom;
 
 use crate::error::HederaError;
@@ -6,7 +7,7 @@ use crate::TokenFreezeStatus;
 use crate::TokenId;
 use crate::TokenKycStatus;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct TokenRelationship {
     pub token_id: TokenId,
     pub symbol: String,
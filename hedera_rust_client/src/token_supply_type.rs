@@ -1,9 +1,13 @@
 use num_traits::{FromPrimitive, ToPrimitive};
+use serde::Serialize;
 
 use crate::error::HederaError;
 use crate::proto::ToProto;
 
-#[derive(FromPrimitive, ToPrimitive, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(
+    FromPrimitive, ToPrimitive, Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize,
+)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum TokenSupplyType {
     Infinite = 0,
     Finite = 1,
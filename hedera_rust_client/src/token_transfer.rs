@@ -1,3 +1,4 @@
+use serde::Serialize;
 use std::convert::TryFrom;
 
 use crate::error::HederaError;
@@ -5,10 +6,11 @@ use crate::proto::services::AccountAmount as ProtoAccountAmount;
 use crate::proto::ToProto;
 use crate::AccountId;
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
 pub struct TokenTransfer {
     pub account_id: AccountId,
     pub amount: i64,
+    pub is_approval: bool,
 }
 
 impl TryFrom<ProtoAccountAmount> for TokenTransfer {
@@ -20,6 +22,7 @@ impl TryFrom<ProtoAccountAmount> for TokenTransfer {
                 None => return Err(HederaError::MissingInProto("account_id".to_string())),
             },
             amount: services.amount,
+            is_approval: services.is_approval,
         })
     }
 }
@@ -29,7 +32,7 @@ impl ToProto<ProtoAccountAmount> for TokenTransfer {
         Ok(ProtoAccountAmount {
             account_id: Some(self.account_id.to_proto()?),
             amount: self.amount,
-            is_approval: false,
+            is_approval: self.is_approval,
         })
     }
 }
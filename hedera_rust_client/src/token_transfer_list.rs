@@ -1,3 +1,4 @@
+use serde::Serialize;
 use std::collections::HashMap;
 use std::convert::TryFrom;
 
@@ -8,11 +9,13 @@ use crate::AccountId;
 use crate::HederaError;
 use crate::TokenId;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct TokenTransferList {
     pub token_id: TokenId,
+    #[serde(serialize_with = "crate::json::sorted_values")]
     pub transfers: AccountIdTokenTransferHashMap,
     pub expected_decimals: Option<u32>,
+    #[serde(serialize_with = "crate::json::sorted_values")]
     pub nft_transfers: SerialNumberTokenNftTransferHashMap,
 }
 
@@ -9,6 +9,7 @@ use crate::Key;
 use crate::TokenId;
 use chrono::{DateTime, Duration, Utc};
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
 
 #[derive(TransactionSchedule, TransactionExecute, Debug, Clone, PartialEq)]
 #[hedera_rust_client_derive(service(
@@ -80,7 +81,7 @@ impl TokenUpdateTransaction {
     gen_transaction_pause_key_fns!();
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "TokenUpdate",
     proto_type = "TokenUpdateTransactionBody"
@@ -107,6 +108,7 @@ struct Proto {
     #[hedera_rust_client_derive(to_option_proto)]
     pub auto_renew_account: Option<AccountId>,
     #[hedera_rust_client_derive(to_option_proto)]
+    #[serde(serialize_with = "crate::json::option_duration")]
     pub auto_renew_period: Option<Duration>,
     pub memo: Option<String>,
     #[hedera_rust_client_derive(to_option_proto)]
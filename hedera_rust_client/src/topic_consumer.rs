@@ -0,0 +1,326 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use tokio::io::AsyncWriteExt;
+
+use crate::client::Client;
+use crate::error::HederaError;
+use crate::topic_message::TopicMessage;
+use crate::topic_message_query::{TopicMessageQuery, TopicMessageStream};
+use crate::TopicId;
+
+/// Where a consumer resumes a topic: after the last message processed, or
+/// just before the first chunk of a message that was still incomplete.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub sequence_number: u64,
+    pub consensus_timestamp: DateTime<Utc>,
+}
+
+impl From<&TopicMessage> for Checkpoint {
+    fn from(message: &TopicMessage) -> Checkpoint {
+        Checkpoint {
+            sequence_number: message.sequence_number,
+            consensus_timestamp: message.consensus_timestamp,
+        }
+    }
+}
+
+/// Keeps the checkpoint of each topic a `TopicConsumer` reads, e.g. in a
+/// file or in the database its handler writes to.
+#[async_trait]
+pub trait CheckpointStore: Send + Sync + fmt::Debug {
+    async fn load(&self, topic_id: TopicId) -> Result<Option<Checkpoint>, HederaError>;
+
+    async fn save(&self, topic_id: TopicId, checkpoint: Checkpoint) -> Result<(), HederaError>;
+}
+
+/// A `CheckpointStore` lost with the process, e.g. for tests.
+#[derive(Debug, Default)]
+pub struct InMemoryCheckpointStore {
+    checkpoints: Mutex<HashMap<TopicId, Checkpoint>>,
+}
+
+impl InMemoryCheckpointStore {
+    pub fn new() -> InMemoryCheckpointStore {
+        InMemoryCheckpointStore::default()
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for InMemoryCheckpointStore {
+    async fn load(&self, topic_id: TopicId) -> Result<Option<Checkpoint>, HederaError> {
+        Ok(self.checkpoints.lock().unwrap().get(&topic_id).copied())
+    }
+
+    async fn save(&self, topic_id: TopicId, checkpoint: Checkpoint) -> Result<(), HederaError> {
+        self.checkpoints
+            .lock()
+            .unwrap()
+            .insert(topic_id, checkpoint);
+        Ok(())
+    }
+}
+
+/// A `CheckpointStore` keeping the checkpoints of all topics in a JSON file,
+/// by topic id. Each save replaces the file through a temporary one, so a
+/// crash leaves either the previous checkpoints or the new ones.
+#[derive(Debug)]
+pub struct FileCheckpointStore {
+    path: PathBuf,
+    // saves read, change and replace the whole file
+    lock: tokio::sync::Mutex<()>,
+}
+
+impl FileCheckpointStore {
+    /// A store in the file at `path`, created on the first save.
+    pub fn new<P: Into<PathBuf>>(path: P) -> FileCheckpointStore {
+        FileCheckpointStore {
+            path: path.into(),
+            lock: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    async fn read(&self) -> Result<BTreeMap<TopicId, Checkpoint>, HederaError> {
+        match tokio::fs::read(&self.path).await {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|e| {
+                HederaError::CheckpointStore(format!("{}: {}", self.path.display(), e))
+            }),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(BTreeMap::new()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for FileCheckpointStore {
+    async fn load(&self, topic_id: TopicId) -> Result<Option<Checkpoint>, HederaError> {
+        Ok(self.read().await?.get(&topic_id).copied())
+    }
+
+    async fn save(&self, topic_id: TopicId, checkpoint: Checkpoint) -> Result<(), HederaError> {
+        let _guard = self.lock.lock().await;
+        let mut checkpoints = self.read().await?;
+        checkpoints.insert(topic_id, checkpoint);
+        let bytes = serde_json::to_vec_pretty(&checkpoints)
+            .map_err(|e| HederaError::CheckpointStore(e.to_string()))?;
+
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+        let temporary = PathBuf::from(temporary);
+        let mut file = tokio::fs::File::create(&temporary).await?;
+        file.write_all(&bytes).await?;
+        file.sync_all().await?;
+        drop(file);
+        tokio::fs::rename(&temporary, &self.path).await?;
+        Ok(())
+    }
+}
+
+/// When a `TopicConsumer` saves the checkpoint of a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProcessingMode {
+    /// The consumer saves it once the handler returns, so a message being
+    /// handled when the process stops is handled again after a restart.
+    AtLeastOnce,
+    /// The handler saves it itself, together with the effects of the
+    /// message, e.g. in the same database transaction, so they are applied
+    /// once. The consumer only loads it.
+    ExactlyOnceEffect,
+}
+
+impl Default for ProcessingMode {
+    fn default() -> Self {
+        ProcessingMode::AtLeastOnce
+    }
+}
+
+/// Processes the messages of a `TopicConsumer`, in order.
+#[async_trait]
+pub trait TopicMessageHandler: Send + Sync {
+    /// Processes `message`, whose checkpoint is `checkpoint`. An error stops
+    /// the consumer before the checkpoint is saved.
+    async fn handle(
+        &self,
+        message: TopicMessage,
+        checkpoint: Checkpoint,
+    ) -> Result<(), HederaError>;
+
+    /// Called with the messages the consumer skipped over: a
+    /// `TopicMessageSequenceGap` or a `TopicMessageChunksExpired`. Returning
+    /// the error, as by default, stops the consumer.
+    async fn on_gap(&self, error: HederaError) -> Result<(), HederaError> {
+        Err(error)
+    }
+}
+
+/// Reads a topic as an ordered log from where it was left off, tracking the
+/// last message processed in a `CheckpointStore`.
+#[derive(Debug, Clone)]
+pub struct TopicConsumer {
+    topic_id: TopicId,
+    store: Arc<dyn CheckpointStore>,
+    processing_mode: ProcessingMode,
+    query: TopicMessageQuery,
+}
+
+impl TopicConsumer {
+    pub fn new(topic_id: TopicId, store: Arc<dyn CheckpointStore>) -> TopicConsumer {
+        TopicConsumer {
+            topic_id,
+            store,
+            processing_mode: ProcessingMode::default(),
+            query: TopicMessageQuery::new(),
+        }
+    }
+
+    pub fn topic_id(&self) -> TopicId {
+        self.topic_id
+    }
+
+    pub fn processing_mode(&self) -> ProcessingMode {
+        self.processing_mode
+    }
+
+    pub fn set_processing_mode(&mut self, processing_mode: ProcessingMode) -> &mut Self {
+        self.processing_mode = processing_mode;
+        self
+    }
+
+    pub fn query(&self) -> &TopicMessageQuery {
+        &self.query
+    }
+
+    /// The query subscribed with, for its end time, limit, retries and chunk
+    /// timeout. Its topic is replaced with the consumer's, and its start
+    /// time is only used while there is no checkpoint.
+    pub fn set_query(&mut self, query: TopicMessageQuery) -> &mut Self {
+        self.query = query;
+        self
+    }
+
+    pub async fn checkpoint(&self) -> Result<Option<Checkpoint>, HederaError> {
+        self.store.load(self.topic_id).await
+    }
+
+    /// Passes the messages after the checkpoint to `handler` until the
+    /// subscription ends, verifying their sequence numbers follow on from
+    /// it.
+    ///
+    /// Returns the first error of the handler, the store or the
+    /// subscription; running again resumes from the checkpoint.
+    ///
+    /// With `AtLeastOnce`, while a chunked message is incomplete the saved
+    /// checkpoint stays before its first chunk, so after a restart it is
+    /// completed and the messages handled since that chunk are handled
+    /// again. With `ExactlyOnceEffect` the handler saves the checkpoint of
+    /// each message, and a chunked message whose first chunks came before
+    /// it can't be completed after a restart, so it is reported to `on_gap`
+    /// as expired.
+    pub async fn run<H>(&self, client: &Client, handler: &H) -> Result<(), HederaError>
+    where
+        H: TopicMessageHandler + ?Sized,
+    {
+        let mut query = self.query.clone();
+        query.set_topic_id(self.topic_id)?;
+        if let Some(checkpoint) = self.checkpoint().await? {
+            query.resume_after(checkpoint.sequence_number, checkpoint.consensus_timestamp)?;
+        }
+        let mut stream = query.subscribe_stream(client).await?;
+        while let Some(message) = stream.next().await {
+            match message {
+                Ok(message) => {
+                    let checkpoint = Checkpoint::from(&message);
+                    handler.handle(message, checkpoint).await?;
+                    if self.processing_mode == ProcessingMode::AtLeastOnce {
+                        self.save_resume_point(&stream, checkpoint).await?;
+                    }
+                }
+                Err(
+                    e @ (HederaError::TopicMessageSequenceGap(..)
+                    | HederaError::TopicMessageChunksExpired(..)),
+                ) => handler.on_gap(e).await?,
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    // the stream's resume point, which is `checkpoint` unless a chunked
+    // message is incomplete
+    async fn save_resume_point(
+        &self,
+        stream: &TopicMessageStream,
+        checkpoint: Checkpoint,
+    ) -> Result<(), HederaError> {
+        let checkpoint = match stream.resume_point()? {
+            Some((sequence_number, consensus_timestamp)) => Checkpoint {
+                sequence_number,
+                consensus_timestamp,
+            },
+            None => checkpoint,
+        };
+        self.store.save(self.topic_id, checkpoint).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn checkpoint(sequence_number: u64) -> Checkpoint {
+        Checkpoint {
+            sequence_number,
+            consensus_timestamp: Utc
+                .timestamp_opt(1_650_000_000 + sequence_number as i64, 5)
+                .unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_file_checkpoint_store() -> Result<(), HederaError> {
+        let path = std::env::temp_dir().join(format!(
+            "hedera_checkpoints_{}_{}.json",
+            std::process::id(),
+            Utc::now().timestamp_nanos_opt().unwrap()
+        ));
+        let store = FileCheckpointStore::new(&path);
+        assert_eq!(store.load(TopicId::simple(5)).await?, None);
+
+        store.save(TopicId::simple(5), checkpoint(1)).await?;
+        store.save(TopicId::simple(6), checkpoint(7)).await?;
+        store.save(TopicId::simple(5), checkpoint(2)).await?;
+
+        let reopened = FileCheckpointStore::new(&path);
+        assert_eq!(
+            reopened.load(TopicId::simple(5)).await?,
+            Some(checkpoint(2))
+        );
+        assert_eq!(
+            reopened.load(TopicId::simple(6)).await?,
+            Some(checkpoint(7))
+        );
+        let value: serde_json::Value = serde_json::from_slice(&std::fs::read(&path)?).unwrap();
+        assert_eq!(value["0.0.5"]["sequence_number"], serde_json::json!(2));
+        assert!(value["0.0.6"]["consensus_timestamp"].is_string());
+
+        std::fs::write(&path, b"{")?;
+        assert!(matches!(
+            reopened.load(TopicId::simple(5)).await,
+            Err(HederaError::CheckpointStore(_))
+        ));
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+}
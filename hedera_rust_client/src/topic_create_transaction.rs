@@ -9,6 +9,7 @@ use crate::HederaError;
 use crate::Key;
 use chrono::Duration;
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
 
 #[derive(TransactionSchedule, TransactionExecute, Debug, Clone, PartialEq)]
 #[hedera_rust_client_derive(service(
@@ -52,7 +53,7 @@ impl TopicCreateTransaction {
     gen_transaction_auto_renew_period_fns!();
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "ConsensusCreateTopic",
     proto_type = "ConsensusCreateTopicTransactionBody"
@@ -64,6 +65,7 @@ struct Proto {
     #[hedera_rust_client_derive(to_option_proto)]
     pub submit_key: Option<Key>,
     #[hedera_rust_client_derive(to_option_proto)]
+    #[serde(serialize_with = "crate::json::option_duration")]
     pub auto_renew_period: Option<Duration>,
     #[hedera_rust_client_derive(to_option_proto)]
     pub auto_renew_account: Option<AccountId>,
@@ -6,6 +6,7 @@ use crate::Hbar;
 use crate::HederaError;
 use crate::TopicId;
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto, TransactionSchedule};
+use serde::Serialize;
 
 #[derive(TransactionSchedule, TransactionExecute, Debug, Clone, PartialEq)]
 #[hedera_rust_client_derive(service(
@@ -36,7 +37,7 @@ impl TopicDeleteTransaction {
     gen_transaction_topic_id_fns!();
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "ConsensusDeleteTopic",
     proto_type = "ConsensusDeleteTopicTransactionBody"
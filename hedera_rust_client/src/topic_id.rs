@@ -1,11 +1,8 @@
 use hedera_rust_client_derive::{Id, IdPartialEq, IdProto, IdValidateChecksum};
-use serde::{Deserialize, Serialize};
 
 use crate::id::IdChecksum;
 
-#[derive(
-    Id, IdProto, IdPartialEq, IdValidateChecksum, Debug, Clone, Copy, Hash, Serialize, Deserialize,
-)]
+#[derive(Id, IdProto, IdPartialEq, IdValidateChecksum, Debug, Clone, Copy, Hash)]
 #[hedera_rust_client_derive(field_name = "topic_num")]
 pub struct TopicId {
     shard_num: i64,
@@ -32,14 +32,15 @@ impl TopicMessage {
     }
 
     pub fn of_many(
-        responses: Vec<mirror::ConsensusTopicResponse>,
+        mut responses: Vec<mirror::ConsensusTopicResponse>,
     ) -> Result<TopicMessage, HederaError> {
+        // chunks may arrive out of order
+        responses.sort_by_key(|r| r.chunk_info.as_ref().map(|info| info.number));
         let length = responses.len();
         let mut chunks = Vec::with_capacity(length);
         let mut transaction_id: Option<TransactionId> = None;
         let mut messages = Vec::with_capacity(length);
         let mut size = 0usize;
-        let mut index;
 
         let consensus_timestamp =
             utils::non_optional_timestamp(responses[length - 1].consensus_timestamp.clone())?;
@@ -47,19 +48,18 @@ impl TopicMessage {
         let sequence_number = responses[length - 1].sequence_number;
 
         for r in responses.into_iter() {
-            if let Some(info) = &r.chunk_info {
-                if transaction_id.is_none() {
-                    if let Some(id) = &info.initial_transaction_id {
-                        transaction_id = Some(TransactionId::try_from(id.clone())?);
-                    }
+            if transaction_id.is_none() {
+                if let Some(id) = r
+                    .chunk_info
+                    .as_ref()
+                    .and_then(|info| info.initial_transaction_id.as_ref())
+                {
+                    transaction_id = Some(TransactionId::try_from(id.clone())?);
                 }
-
-                index = usize::try_from(info.number - 1)?;
-
-                chunks[index] = TopicMessageChunk::try_from(r.clone())?;
-                messages[index] = r.message.clone();
-                size += r.message.len();
             }
+            size += r.message.len();
+            messages.push(r.message.clone());
+            chunks.push(TopicMessageChunk::try_from(r)?);
         }
 
         let mut final_message = Vec::with_capacity(size);
@@ -1,17 +1,36 @@
 use crate::client::Client;
 use crate::error::HederaError;
-use crate::proto::{mirror, ToProto};
+use crate::managed_network::ArcNetworkNode;
+use crate::proto::{mirror, services, ToProto};
 use crate::topic_message::TopicMessage;
 use crate::TransactionId;
+use chrono::{DateTime, Utc};
+use futures::{Stream, StreamExt};
 use num::pow;
 use std::cmp::min;
 use std::collections::HashMap;
 use std::convert::TryFrom;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
 use std::time::Duration;
+use tokio::sync::{mpsc, Notify};
+use tokio::time::Instant;
+use tonic::Streaming;
+use tracing::warn;
+
+// messages received but not yet taken from a `TopicMessageStream`; once full,
+// the mirror stream is no longer read until some are
+const STREAM_BUFFER: usize = 16;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct TopicMessageQuery {
     services: mirror::ConsensusTopicQuery,
+    max_attempts: u8,
+    chunk_timeout: Duration,
+    // sequence number and consensus timestamp of a message already processed
+    resume_after: Option<(u64, services::Timestamp)>,
 }
 
 impl Default for TopicMessageQuery {
@@ -29,6 +48,9 @@ impl TopicMessageQuery {
                 consensus_end_time: None,
                 limit: 0,
             },
+            max_attempts: 10,
+            chunk_timeout: Duration::from_secs(60),
+            resume_after: None,
         }
     }
 
@@ -44,69 +66,478 @@ impl TopicMessageQuery {
     // limit
     gen_query_non_optional!(limit, u64, limit, set_limit);
 
-    pub async fn subscribe<F: Fn(TopicMessage) -> Result<(), HederaError>>(
+    pub fn max_attempts(&self) -> u8 {
+        self.max_attempts
+    }
+
+    /// How many times in a row connecting to a mirror node may fail before
+    /// the subscription gives up with `MaxAttempsExceeded`.
+    pub fn set_max_attempts(&mut self, max_attempts: u8) -> &mut Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    pub fn chunk_timeout(&self) -> Duration {
+        self.chunk_timeout
+    }
+
+    /// How long the chunks of a message are kept waiting for the rest, from
+    /// the first received, before it is reported as
+    /// `TopicMessageChunksExpired`.
+    pub fn set_chunk_timeout(&mut self, chunk_timeout: Duration) -> &mut Self {
+        self.chunk_timeout = chunk_timeout;
+        self
+    }
+
+    /// Starts the subscription after a message already processed, in place of
+    /// `consensus_start_time`, expecting the next sequence number first.
+    pub(crate) fn resume_after(
         &mut self,
+        sequence_number: u64,
+        consensus_timestamp: DateTime<Utc>,
+    ) -> Result<&mut Self, HederaError> {
+        self.resume_after = Some((sequence_number, consensus_timestamp.to_proto()?));
+        Ok(self)
+    }
+
+    /// Subscribes to the topic, its messages read from the returned stream.
+    ///
+    /// A subscription failing with `Unavailable`, `NotFound` or
+    /// `ResourceExhausted` is resumed on the next mirror node after the last
+    /// message received, without repeating any. The stream ends once the
+    /// mirror node closes the subscription, e.g. after `consensus_end_time`
+    /// or `limit` is reached, on `unsubscribe`, or after yielding an error.
+    /// Expired chunked messages and gaps in the sequence numbers are reported
+    /// without ending it. Dropping it unsubscribes.
+    pub async fn subscribe_stream(
+        &self,
+        client: &Client,
+    ) -> Result<TopicMessageStream, HederaError> {
+        let (sender, receiver) = mpsc::channel(STREAM_BUFFER);
+        let handle = SubscriptionHandle::default();
+        let mut subscription = Subscription {
+            query: self.clone(),
+            client: client.clone(),
+            sender,
+            handle: handle.clone(),
+            node: None,
+            chunks: HashMap::new(),
+            last_received: self.resume_after.clone(),
+            received: 0,
+            attempt: 0,
+        };
+        let stream = subscription.connect().await?;
+        tokio::spawn(subscription.run(stream));
+        Ok(TopicMessageStream {
+            receiver,
+            handle,
+            resume_point: self.resume_after.clone(),
+        })
+    }
+
+    /// Subscribes to the topic, calling `on_next` with each message from a
+    /// spawned task until the subscription ends.
+    ///
+    /// An error from the subscription or from `on_next` is passed to
+    /// `on_error`. Those other than expired chunked messages and sequence
+    /// gaps end the subscription.
+    pub async fn subscribe<F, E>(
+        &self,
         client: &Client,
         on_next: F,
-    ) -> Result<(), HederaError> {
-        let mut attempt = 0u8;
-        let max_attempts = 10u8;
-        let node = client.next_mirror_node().await?;
-        let mut n_w = node.write().await;
-        let sub_client = n_w.mirror_channel();
-        drop(n_w);
+        on_error: E,
+    ) -> Result<SubscriptionHandle, HederaError>
+    where
+        F: Fn(TopicMessage) -> Result<(), HederaError> + Send + 'static,
+        E: Fn(HederaError) + Send + 'static,
+    {
+        let mut stream = self.subscribe_stream(client).await?;
+        let handle = stream.handle();
+        tokio::spawn(async move {
+            while let Some(message) = stream.next().await {
+                match message.and_then(&on_next) {
+                    Ok(()) => {}
+                    Err(
+                        e @ (HederaError::TopicMessageChunksExpired(..)
+                        | HederaError::TopicMessageSequenceGap(..)),
+                    ) => on_error(e),
+                    Err(e) => {
+                        stream.handle().unsubscribe();
+                        on_error(e);
+                        return;
+                    }
+                }
+            }
+        });
+        Ok(handle)
+    }
+}
 
-        let request = sub_client?.subscribe_topic(self.services.clone()).await;
+fn should_retry(code: tonic::Code) -> bool {
+    matches!(
+        code,
+        tonic::Code::Unavailable | tonic::Code::NotFound | tonic::Code::ResourceExhausted
+    )
+}
 
-        let mut stream = request
-            .map_err(|status| HederaError::ProtoClientFailed(status.code()))?
-            .into_inner();
+// the chunks of a message received so far, in the order received
+#[derive(Debug)]
+struct PendingMessage {
+    first_received: Instant,
+    total: usize,
+    responses: Vec<mirror::ConsensusTopicResponse>,
+}
 
-        let mut messages: HashMap<TransactionId, Vec<mirror::ConsensusTopicResponse>> =
-            HashMap::new();
+// what a subscription hands to its stream: the result, and where it can be
+// resumed from once the result is processed
+#[derive(Debug)]
+struct Received {
+    result: Result<TopicMessage, HederaError>,
+    resume_point: Option<(u64, services::Timestamp)>,
+}
 
-        loop {
-            let response = match stream.message().await {
-                Ok(v) => v,
-                Err(status) => {
-                    if attempt >= max_attempts {
-                        return Err(HederaError::MaxAttempsExceeded(max_attempts));
-                    }
-                    let code = status.code();
-                    if code != tonic::Code::Ok
-                        && (code == tonic::Code::Unavailable || code == tonic::Code::NotFound)
-                    {
-                        let delay = min(250 * pow(u64::from(attempt), 2), 8000);
-                        tokio::time::sleep(Duration::from_millis(delay)).await;
-                        attempt += 1;
-                        continue;
+// reads a subscription into `sender`, reconnecting where it left off
+struct Subscription {
+    query: TopicMessageQuery,
+    client: Client,
+    sender: mpsc::Sender<Received>,
+    handle: SubscriptionHandle,
+    // the mirror node of the current stream
+    node: Option<ArcNetworkNode>,
+    chunks: HashMap<TransactionId, PendingMessage>,
+    // sequence number and consensus timestamp of the last response read
+    last_received: Option<(u64, services::Timestamp)>,
+    received: u64,
+    attempt: u8,
+}
+
+impl Subscription {
+    async fn run(mut self, stream: Option<Streaming<mirror::ConsensusTopicResponse>>) {
+        let mut stream = match stream {
+            Some(stream) => stream,
+            None => return,
+        };
+        while !self.handle.is_unsubscribed() {
+            let expiry = self.next_expiry();
+            let response = tokio::select! {
+                response = stream.message() => response,
+                _ = self.handle.cancel.notified() => return,
+                _ = self.sender.closed() => return,
+                _ = tokio::time::sleep_until(expiry.unwrap_or_else(Instant::now)), if expiry.is_some() => {
+                    if !self.expire_chunks().await {
+                        return;
                     }
-                    return Err(HederaError::ProtoClientFailed(code));
+                    continue;
                 }
             };
+            match response {
+                Ok(Some(response)) => {
+                    self.attempt = 0;
+                    if !self.receive(response).await {
+                        return;
+                    }
+                }
+                Ok(None) => return,
+                Err(status) if should_retry(status.code()) => {
+                    warn!(
+                        "topic subscription failed with {:?}, resuming",
+                        status.code()
+                    );
+                    self.fail_node().await;
+                    let reconnected = match self.backoff().await {
+                        Ok(true) => self.connect().await,
+                        Ok(false) => return,
+                        Err(e) => Err(e),
+                    };
+                    match reconnected {
+                        Ok(Some(reconnected)) => stream = reconnected,
+                        Ok(None) => return,
+                        Err(e) => {
+                            self.send(Err(e)).await;
+                            return;
+                        }
+                    }
+                }
+                Err(status) => {
+                    self.send(Err(HederaError::ProtoClientFailed(status.code())))
+                        .await;
+                    return;
+                }
+            }
+        }
+    }
 
-            if let Some(message) = response {
-                if let Some(ref chunk_info) = message.chunk_info {
-                    if let Some(ref proto_tx_id) = chunk_info.initial_transaction_id {
-                        let tx_id = TransactionId::try_from(proto_tx_id.clone())?;
-                        let chunk_total = chunk_info.total as usize;
+    // the query left to run: after the last response read, for what is left
+    // of `limit`
+    fn remaining_query(&self) -> Option<mirror::ConsensusTopicQuery> {
+        let mut services = self.query.services.clone();
+        if let Some((_, timestamp)) = &self.last_received {
+            let mut start = timestamp.clone();
+            start.nanos += 1;
+            if start.nanos >= 1_000_000_000 {
+                start.seconds += 1;
+                start.nanos -= 1_000_000_000;
+            }
+            services.consensus_start_time = Some(start);
+        }
+        if services.limit > 0 {
+            if self.received >= services.limit {
+                return None;
+            }
+            services.limit -= self.received;
+        }
+        Some(services)
+    }
 
-                        if !messages.contains_key(&tx_id) {
-                            messages.insert(tx_id.clone(), Vec::with_capacity(chunk_total));
-                        }
+    // opens a stream on the next mirror node, `None` once there is nothing
+    // left to read or the subscription was cancelled
+    async fn connect(
+        &mut self,
+    ) -> Result<Option<Streaming<mirror::ConsensusTopicResponse>>, HederaError> {
+        loop {
+            let query = match self.remaining_query() {
+                Some(query) => query,
+                None => return Ok(None),
+            };
+            let node = self.client.next_mirror_node().await?;
+            let mut n_w = node.write().await;
+            n_w.in_use();
+            let sub_client = n_w.mirror_channel();
+            drop(n_w);
+            self.node = Some(node);
 
-                        if let Some(chunks) = messages.get_mut(&tx_id) {
-                            chunks.push(message);
-                            if chunks.len() == chunk_total {
-                                on_next(TopicMessage::of_many(chunks.clone())?)?;
-                                messages.remove(&tx_id);
-                            }
-                        }
+            match sub_client?.subscribe_topic(query).await {
+                Ok(response) => return Ok(Some(response.into_inner())),
+                Err(status) if should_retry(status.code()) => {
+                    self.fail_node().await;
+                    if !self.backoff().await? {
+                        return Ok(None);
                     }
-                } else {
-                    on_next(TopicMessage::of_single(message)?)?;
                 }
+                Err(status) => return Err(HederaError::ProtoClientFailed(status.code())),
+            }
+        }
+    }
+
+    async fn fail_node(&mut self) {
+        if let Some(node) = &self.node {
+            node.write().await.increase_delay();
+        }
+    }
+
+    // waits before the next attempt, `false` if cancelled meanwhile
+    async fn backoff(&mut self) -> Result<bool, HederaError> {
+        if self.attempt >= self.query.max_attempts {
+            return Err(HederaError::MaxAttempsExceeded(self.query.max_attempts));
+        }
+        let delay = min(250 * pow(u64::from(self.attempt), 2), 8000);
+        self.attempt += 1;
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_millis(delay)) => Ok(true),
+            _ = self.handle.cancel.notified() => Ok(false),
+        }
+    }
+
+    // hands `result` to the stream, `false` if it is no longer read
+    async fn send(&self, result: Result<TopicMessage, HederaError>) -> bool {
+        let received = Received {
+            result,
+            resume_point: self.resume_point(),
+        };
+        tokio::select! {
+            sent = self.sender.send(received) => sent.is_ok(),
+            _ = self.handle.cancel.notified() => false,
+        }
+    }
+
+    async fn receive(&mut self, response: mirror::ConsensusTopicResponse) -> bool {
+        // a mirror node may resume from before the last response read
+        if let Some((sequence_number, _)) = &self.last_received {
+            if response.sequence_number <= *sequence_number {
+                return true;
+            }
+            let expected = sequence_number + 1;
+            if response.sequence_number != expected {
+                let gap = HederaError::TopicMessageSequenceGap(expected, response.sequence_number);
+                if !self.send(Err(gap)).await {
+                    return false;
+                }
+            }
+        }
+        if let Some(timestamp) = &response.consensus_timestamp {
+            self.last_received = Some((response.sequence_number, timestamp.clone()));
+        }
+        self.received += 1;
+        match self.add_response(response) {
+            Ok(Some(message)) => self.send(Ok(message)).await,
+            Ok(None) => true,
+            Err(e) => {
+                self.send(Err(e)).await;
+                false
+            }
+        }
+    }
+
+    // the message `response` completes, if any, keeping the chunks of those
+    // still incomplete
+    fn add_response(
+        &mut self,
+        response: mirror::ConsensusTopicResponse,
+    ) -> Result<Option<TopicMessage>, HederaError> {
+        let chunk_info = match &response.chunk_info {
+            Some(chunk_info) if chunk_info.total > 1 => chunk_info,
+            _ => return TopicMessage::of_single(response).map(Some),
+        };
+        let tx_id = match &chunk_info.initial_transaction_id {
+            Some(tx_id) => TransactionId::try_from(tx_id.clone())?,
+            None => return TopicMessage::of_single(response).map(Some),
+        };
+        let total = chunk_info.total as usize;
+        let pending = self
+            .chunks
+            .entry(tx_id.clone())
+            .or_insert_with(|| PendingMessage {
+                first_received: Instant::now(),
+                total,
+                responses: Vec::with_capacity(total),
+            });
+        pending.responses.push(response);
+        if pending.responses.len() < total {
+            return Ok(None);
+        }
+        let responses = self
+            .chunks
+            .remove(&tx_id)
+            .map(|pending| pending.responses)
+            .unwrap_or_default();
+        TopicMessage::of_many(responses).map(Some)
+    }
+
+    // the last response read, or just before the first chunk of the oldest
+    // message still incomplete, whose chunks would be lost resuming later
+    fn resume_point(&self) -> Option<(u64, services::Timestamp)> {
+        let first_pending = self
+            .chunks
+            .values()
+            .filter_map(|pending| pending.responses.first())
+            .min_by_key(|response| response.sequence_number);
+        let response = match first_pending {
+            Some(response) => response,
+            None => return self.last_received.clone(),
+        };
+        let mut timestamp = response.consensus_timestamp.clone()?;
+        timestamp.nanos -= 1;
+        if timestamp.nanos < 0 {
+            timestamp.seconds -= 1;
+            timestamp.nanos += 1_000_000_000;
+        }
+        Some((response.sequence_number.saturating_sub(1), timestamp))
+    }
+
+    fn next_expiry(&self) -> Option<Instant> {
+        self.chunks
+            .values()
+            .map(|pending| pending.first_received + self.query.chunk_timeout)
+            .min()
+    }
+
+    // reports and drops the messages whose chunks timed out, `false` if the
+    // stream is no longer read
+    async fn expire_chunks(&mut self) -> bool {
+        let now = Instant::now();
+        let chunk_timeout = self.query.chunk_timeout;
+        let mut expired: Vec<TransactionId> = self
+            .chunks
+            .iter()
+            .filter(|(_, pending)| pending.first_received + chunk_timeout <= now)
+            .map(|(tx_id, _)| tx_id.clone())
+            .collect();
+        expired.sort_by_key(|tx_id| self.chunks[tx_id].first_received);
+        for tx_id in expired {
+            let pending = match self.chunks.remove(&tx_id) {
+                Some(pending) => pending,
+                None => continue,
+            };
+            let error = HederaError::TopicMessageChunksExpired(
+                tx_id,
+                pending.responses.len(),
+                pending.total,
+            );
+            if !self.send(Err(error)).await {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Ends a subscription from anywhere, e.g. another task.
+#[derive(Debug, Clone, Default)]
+pub struct SubscriptionHandle {
+    cancelled: Arc<AtomicBool>,
+    // wakes the task reading the mirror stream
+    cancel: Arc<Notify>,
+}
+
+impl SubscriptionHandle {
+    pub fn unsubscribe(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.cancel.notify_one();
+    }
+
+    pub fn is_unsubscribed(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+}
+
+/// The messages of a topic subscription, see
+/// `TopicMessageQuery::subscribe_stream`.
+#[derive(Debug)]
+pub struct TopicMessageStream {
+    receiver: mpsc::Receiver<Received>,
+    handle: SubscriptionHandle,
+    resume_point: Option<(u64, services::Timestamp)>,
+}
+
+impl TopicMessageStream {
+    /// The sequence number and consensus timestamp to resume after once the
+    /// items taken so far are processed. While a chunked message is
+    /// incomplete it is one before its first chunk, so that resuming doesn't
+    /// lose it, and messages completed after that chunk are read again.
+    pub fn resume_point(&self) -> Result<Option<(u64, DateTime<Utc>)>, HederaError> {
+        match &self.resume_point {
+            Some((sequence_number, timestamp)) => Ok(Some((
+                *sequence_number,
+                DateTime::<Utc>::try_from(timestamp.clone())?,
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    pub fn handle(&self) -> SubscriptionHandle {
+        self.handle.clone()
+    }
+
+    pub fn unsubscribe(&self) {
+        self.handle.unsubscribe()
+    }
+}
+
+impl Stream for TopicMessageStream {
+    type Item = Result<TopicMessage, HederaError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        // messages already buffered are dropped once unsubscribed
+        if self.handle.is_unsubscribed() {
+            return Poll::Ready(None);
+        }
+        match self.receiver.poll_recv(cx) {
+            Poll::Ready(Some(received)) => {
+                self.resume_point = received.resume_point;
+                Poll::Ready(Some(received.result))
             }
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
         }
     }
 }
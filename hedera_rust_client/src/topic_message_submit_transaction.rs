@@ -6,6 +6,7 @@ use crate::Hbar;
 use crate::HederaError;
 use crate::TopicId;
 use hedera_rust_client_derive::{TransactionChunked, TransactionChunkedSchedule, TransactionProto};
+use serde::Serialize;
 
 const CHUNK_SIZE: usize = 1024;
 
@@ -65,9 +66,14 @@ impl TopicMessageSubmitTransaction {
         }
         Ok(())
     }
+
+    fn on_decode_chunk(&mut self, services: Proto) {
+        self.data.extend_from_slice(&services.message);
+        self.services = services;
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "ConsensusSubmitMessage",
     proto_type = "ConsensusSubmitMessageTransactionBody"
@@ -75,7 +81,9 @@ impl TopicMessageSubmitTransaction {
 struct Proto {
     #[hedera_rust_client_derive(to_option_proto)]
     pub topic_id: Option<TopicId>,
+    #[serde(serialize_with = "crate::json::hex")]
     pub message: Vec<u8>,
+    #[serde(skip)]
     pub chunk_info: Option<services::ConsensusMessageChunkInfo>,
 }
 
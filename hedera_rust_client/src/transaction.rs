@@ -2,20 +2,24 @@ use bytes::Bytes;
 use chrono::Duration;
 use itertools::enumerate;
 use prost::Message;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
 use sha3::{Digest, Sha3_384};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{self, Debug, Display};
 
 use crate::client::Client;
 use crate::error::HederaError;
+use crate::execute_middleware::ExecuteHooks;
 use crate::executor::{IntermediateResponse, ProtoRequest, Request, Response};
 use crate::memo::check_memo_length;
 use crate::proto::sdk::TransactionList as ProtoTransactionList;
 use crate::proto::services::{
     signature_pair::Signature as ProtoSignature, transaction_body::Data as ProtoData,
-    SchedulableTransactionBody as ProtoSchedulableTransactionBody, Transaction as ProtoTransaction,
+    SchedulableTransactionBody as ProtoSchedulableTransactionBody,
+    SignaturePair as ProtoSignaturePair, Transaction as ProtoTransaction,
 };
 use crate::signed_transaction::SignedTransaction;
+use crate::signer::Signer;
 use crate::status::Status;
 use crate::transaction_body::TransactionBody;
 use crate::transaction_id::TransactionId;
@@ -26,6 +30,7 @@ use crate::PrivateKey;
 use crate::PublicKey;
 use crate::ScheduleCreateTransaction;
 use crate::Signature;
+use crate::{Key, KeyRequirement};
 
 pub fn transaction_get_node_account_id(request: &Request) -> Result<AccountId, HederaError> {
     let transaction = request.get_transaction()?;
@@ -94,6 +99,9 @@ pub struct Transaction {
     pub max_retry: u8,
     pub min_backoff: Option<u64>,
     pub max_backoff: Option<u64>,
+    pub grpc_deadline: Option<std::time::Duration>,
+    pub request_timeout: Option<std::time::Duration>,
+    pub(crate) hooks: ExecuteHooks,
     transaction_ids: Vec<TransactionId>,
     transactions: Vec<ProtoTransaction>,
     pub signed_transactions: Vec<SignedTransaction>,
@@ -109,6 +117,9 @@ impl Transaction {
             max_retry: 5,
             min_backoff: None,
             max_backoff: None,
+            grpc_deadline: None,
+            request_timeout: None,
+            hooks: ExecuteHooks::default(),
             transaction_ids: Vec::new(),
             transactions: Vec::new(),
             signed_transactions: Vec::new(),
@@ -130,6 +141,11 @@ impl Transaction {
         self.signed_transactions.clear();
     }
 
+    // selects the chunk the next execution sends
+    pub(crate) fn set_next_transaction_index(&mut self, index: usize) {
+        self.next_transaction_index = index;
+    }
+
     pub fn to_bytes(&mut self) -> Result<Vec<u8>, HederaError> {
         if !self.is_frozen() {
             return Err(HederaError::TransactionHashMustBeFrozen);
@@ -146,26 +162,58 @@ impl Transaction {
         }
     }
 
+    /// Decodes a `TransactionList`, as written by `to_bytes` and the other
+    /// SDKs. A single `Transaction` message is accepted too.
     pub fn from_bytes(bytes: Vec<u8>) -> Result<Transaction, HederaError> {
-        let list = match ProtoTransactionList::decode(Bytes::from(bytes)) {
+        let bytes = Bytes::from(bytes);
+        let list = match ProtoTransactionList::decode(bytes.clone()) {
             Ok(list) => list,
             Err(e) => return Err(HederaError::TransactionDeserializationError(e)),
         };
+        if list.transaction_list.is_empty() {
+            // a lone `Transaction` decodes as an empty list
+            #[allow(deprecated)]
+            match ProtoTransaction::decode(bytes) {
+                Ok(transaction)
+                    if !transaction.signed_transaction_bytes.is_empty()
+                        || !transaction.body_bytes.is_empty() =>
+                {
+                    return Self::from_proto_transactions(vec![transaction]);
+                }
+                _ => (),
+            }
+        }
+        Self::from_proto_transactions(list.transaction_list)
+    }
 
+    pub(crate) fn from_proto_transactions(
+        transaction_list: Vec<ProtoTransaction>,
+    ) -> Result<Transaction, HederaError> {
         let mut tx = Transaction::new();
-        tx.transactions = list.transaction_list.clone();
+        tx.transactions = transaction_list.clone();
 
-        for transaction in list.transaction_list.iter() {
-            let signed_transaction = SignedTransaction::try_from_proto_bytes(
-                transaction.signed_transaction_bytes.clone(),
-            )?;
+        for transaction in transaction_list.iter() {
+            #[allow(deprecated)]
+            let signed_transaction = if transaction.signed_transaction_bytes.is_empty()
+                && !transaction.body_bytes.is_empty()
+            {
+                // written before `signed_transaction_bytes` existed
+                SignedTransaction {
+                    body_bytes: transaction.body_bytes.clone(),
+                    sig_map: transaction.sig_map.clone(),
+                }
+            } else {
+                SignedTransaction::try_from_proto_bytes(
+                    transaction.signed_transaction_bytes.clone(),
+                )?
+            };
             tx.signed_transactions.push(signed_transaction.clone());
 
             let transaction_body =
                 TransactionBody::try_from_proto_bytes(signed_transaction.body_bytes)?;
 
-            if let Some(transaction_id) = transaction_body.transaction_id {
-                if !tx.transaction_ids.contains(&transaction_id) {
+            if let Some(transaction_id) = &transaction_body.transaction_id {
+                if !tx.transaction_ids.contains(transaction_id) {
                     tx.transaction_ids.push(transaction_id.clone());
                 }
             }
@@ -175,10 +223,42 @@ impl Transaction {
                     tx.node_account_ids.push(node_id.clone());
                 }
             }
+
+            // the first body stands in for the unfrozen body, for the getters
+            if tx.signed_transactions.len() == 1 {
+                tx.transaction_body = TransactionBody {
+                    node_account_id: None,
+                    ..transaction_body
+                };
+            }
         }
         Ok(tx)
     }
 
+    /// The body data of each chunk, in order, with the index of its first
+    /// signed transaction. Fails with `TransactionBodyMismatch` if the nodes
+    /// weren't sent the same chunks.
+    pub(crate) fn chunk_data(&self) -> Result<Vec<(usize, ProtoData)>, HederaError> {
+        let mut chunks: Vec<(usize, Option<TransactionId>, Option<ProtoData>)> = Vec::new();
+        for (i, st) in self.signed_transactions.iter().enumerate() {
+            let body = TransactionBody::try_from_proto_bytes(st.body_bytes.clone())?;
+            match chunks.iter().find(|(_, id, _)| *id == body.transaction_id) {
+                Some((_, _, data)) if *data != body.data => {
+                    return Err(HederaError::TransactionBodyMismatch(i))
+                }
+                Some(_) => (),
+                None => chunks.push((i, body.transaction_id, body.data)),
+            }
+        }
+        chunks
+            .into_iter()
+            .map(|(i, _, data)| match data {
+                Some(data) => Ok((i, data)),
+                None => Err(HederaError::UnsupportedTransactionBodyType),
+            })
+            .collect()
+    }
+
     pub fn signatures(
         &self,
     ) -> Result<HashMap<AccountId, HashMap<PublicKey, Vec<u8>>>, HederaError> {
@@ -192,22 +272,22 @@ impl Transaction {
             if let Some(ref sig_ref) = &self.signed_transactions[i].sig_map {
                 let mut inner = HashMap::new();
                 for sig_pair in &sig_ref.sig_pair {
-                    match PublicKey::from_bytes(&sig_pair.pub_key_prefix) {
-                        Ok(key) => {
-                            let bytes = match &sig_pair.signature {
-                                Some(v) => match v {
-                                    ProtoSignature::Contract(bytes) => bytes.clone(),
-                                    ProtoSignature::Ed25519(bytes) => bytes.clone(),
-                                    ProtoSignature::Rsa3072(bytes) => bytes.clone(),
-                                    ProtoSignature::Ecdsa384(bytes) => bytes.clone(),
-                                    ProtoSignature::EcdsaSecp256k1(bytes) => bytes.clone(),
-                                },
-                                None => Vec::new(),
-                            };
-                            inner.insert(key, bytes);
-                        }
-                        Err(_) => return Err(HederaError::UnableToSerializeKey),
-                    }
+                    // a prefix shorter than the key doesn't say whose it is
+                    let key = match PublicKey::from_bytes(&sig_pair.pub_key_prefix) {
+                        Ok(key) => key,
+                        Err(_) => continue,
+                    };
+                    let bytes = match &sig_pair.signature {
+                        Some(v) => match v {
+                            ProtoSignature::Contract(bytes) => bytes.clone(),
+                            ProtoSignature::Ed25519(bytes) => bytes.clone(),
+                            ProtoSignature::Rsa3072(bytes) => bytes.clone(),
+                            ProtoSignature::Ecdsa384(bytes) => bytes.clone(),
+                            ProtoSignature::EcdsaSecp256k1(bytes) => bytes.clone(),
+                        },
+                        None => Vec::new(),
+                    };
+                    inner.insert(key, bytes);
                 }
                 map.insert(*node_account_id, inner);
             }
@@ -397,6 +477,10 @@ impl Transaction {
         Ok(())
     }
 
+    /// Signs every body with `signer`, a closure that can't fail or wait.
+    /// It stays apart from `sign_with_signer` so that keys in memory can be
+    /// used without an async runtime; keys held elsewhere go through the
+    /// `Signer` trait, whose signing is async and fallible.
     pub fn sign_with<F: Fn(&Vec<u8>) -> Signature>(
         &mut self,
         public_key: &PublicKey,
@@ -430,7 +514,248 @@ impl Transaction {
         }
 
         let tx_signer = client.operator_transaction_signer();
-        self.sign_with(public_key, |b| tx_signer(b))
+        self.sign_with_signer(tx_signer.as_ref()).await
+    }
+
+    /// Signs every body with `signer`. The bodies are all signed before any
+    /// signature is added, so a failing signer leaves the transaction as is.
+    pub async fn sign_with_signer(&mut self, signer: &dyn Signer) -> Result<(), HederaError> {
+        if !self.is_frozen() {
+            return Err(HederaError::TransactionSignWithFailed);
+        }
+        self.transactions.clear();
+
+        let public_key = signer.public_key();
+        if self.key_already_signed(&public_key) {
+            return Ok(());
+        }
+
+        let mut signatures = Vec::with_capacity(self.signed_transactions.len());
+        for signed_transaction in self.signed_transactions.iter() {
+            signatures.push(signer.sign(&signed_transaction.body_bytes).await?);
+        }
+        for (signed_transaction, signature) in
+            self.signed_transactions.iter_mut().zip(signatures.iter())
+        {
+            signed_transaction
+                .add_signature_pair(public_key.to_signature_pair_protobuf(signature))?;
+        }
+        Ok(())
+    }
+
+    /// Signs the bodies sent to `node_account_id` only.
+    pub fn sign_for_node(
+        &mut self,
+        node_account_id: AccountId,
+        private_key: &PrivateKey,
+    ) -> Result<(), HederaError> {
+        let public_key = private_key.public();
+        let nodes = self.signed_transaction_node_ids()?;
+        let mut signed = false;
+        for (st, node) in self.signed_transactions.iter_mut().zip(nodes) {
+            if node == Some(node_account_id) {
+                signed = true;
+                if !has_signature(st, &public_key) {
+                    let signature = private_key.sign(&st.body_bytes);
+                    st.add_signature_pair(public_key.to_signature_pair_protobuf(&signature))?;
+                }
+            }
+        }
+        if !signed {
+            return Err(HederaError::TransactionNodeNotFound(node_account_id));
+        }
+        self.transactions.clear();
+        Ok(())
+    }
+
+    /// Adds a signature made over one of the bodies sent to `node_account_id`,
+    /// e.g. by an offline custodian. The signature is verified first.
+    pub fn add_signature_for_node(
+        &mut self,
+        node_account_id: AccountId,
+        public_key: PublicKey,
+        signature: Signature,
+    ) -> Result<(), HederaError> {
+        let nodes = self.signed_transaction_node_ids()?;
+        let mut found = false;
+        let mut added = false;
+        for (st, node) in self.signed_transactions.iter_mut().zip(nodes) {
+            if node != Some(node_account_id) {
+                continue;
+            }
+            found = true;
+            // chunked transactions send several bodies to each node; a key of
+            // the wrong kind doesn't verify either
+            if matches!(public_key.verify(&st.body_bytes, &signature), Ok(true)) {
+                added = true;
+                if !has_signature(st, &public_key) {
+                    st.add_signature_pair(public_key.to_signature_pair_protobuf(&signature))?;
+                }
+            }
+        }
+        if !found {
+            return Err(HederaError::TransactionNodeNotFound(node_account_id));
+        }
+        if !added {
+            return Err(HederaError::InvalidTransactionSignature(
+                public_key.to_string(),
+            ));
+        }
+        self.transactions.clear();
+        Ok(())
+    }
+
+    /// Copies the signatures of `other` that this transaction lacks. Both
+    /// must hold the same bodies in the same order, as two `to_bytes()` of
+    /// one frozen transaction do. Only signatures that verify against their
+    /// body are copied, and a valid one replaces an invalid one of its key.
+    pub fn merge_signatures(&mut self, other: &Transaction) -> Result<(), HederaError> {
+        if self.signed_transactions.len() != other.signed_transactions.len() {
+            return Err(HederaError::TransactionBodyMismatch(
+                self.signed_transactions
+                    .len()
+                    .min(other.signed_transactions.len()),
+            ));
+        }
+        for (i, (st, other_st)) in self
+            .signed_transactions
+            .iter()
+            .zip(other.signed_transactions.iter())
+            .enumerate()
+        {
+            if st.body_bytes != other_st.body_bytes {
+                return Err(HederaError::TransactionBodyMismatch(i));
+            }
+        }
+
+        for (st, other_st) in self
+            .signed_transactions
+            .iter_mut()
+            .zip(other.signed_transactions.iter())
+        {
+            let pairs = match &other_st.sig_map {
+                Some(sig_map) => &sig_map.sig_pair,
+                None => continue,
+            };
+            for pair in pairs {
+                if verified_key(&st.body_bytes, pair).is_none() {
+                    continue;
+                }
+                let body_bytes = &st.body_bytes;
+                let existing = st.sig_map.as_mut().and_then(|sig_map| {
+                    sig_map
+                        .sig_pair
+                        .iter_mut()
+                        .find(|p| p.pub_key_prefix == pair.pub_key_prefix)
+                });
+                match existing {
+                    Some(existing) => {
+                        if verified_key(body_bytes, existing).is_none() {
+                            *existing = pair.clone();
+                        }
+                    }
+                    None => st.add_signature_pair(pair.clone())?,
+                }
+            }
+        }
+        self.transactions.clear();
+        Ok(())
+    }
+
+    /// The keys that signed the bodies sent to each node. When a node gets
+    /// several chunks, only keys that signed all of them are listed.
+    /// Signatures whose prefix isn't a full public key are left out.
+    pub fn signers(&self) -> Result<HashMap<AccountId, Vec<PublicKey>>, HederaError> {
+        let nodes = self.signed_transaction_node_ids()?;
+        let mut signers: HashMap<AccountId, Vec<PublicKey>> = HashMap::new();
+        for (st, node) in self.signed_transactions.iter().zip(nodes) {
+            let node = match node {
+                Some(v) => v,
+                None => continue,
+            };
+            let keys: Vec<PublicKey> = match &st.sig_map {
+                Some(sig_map) => sig_map
+                    .sig_pair
+                    .iter()
+                    .filter_map(|pair| PublicKey::from_bytes(&pair.pub_key_prefix).ok())
+                    .collect(),
+                None => Vec::new(),
+            };
+            match signers.get_mut(&node) {
+                Some(signed) => signed.retain(|key| keys.contains(key)),
+                None => {
+                    signers.insert(node, keys);
+                }
+            }
+        }
+        Ok(signers)
+    }
+
+    /// The keys with a valid signature on every body sent to each node.
+    /// Signatures that don't verify against their body are left out.
+    pub fn verified_signers(&self) -> Result<HashMap<AccountId, HashSet<PublicKey>>, HederaError> {
+        let nodes = self.signed_transaction_node_ids()?;
+        let mut signers: HashMap<AccountId, HashSet<PublicKey>> = HashMap::new();
+        for (st, node) in self.signed_transactions.iter().zip(nodes) {
+            let node = match node {
+                Some(v) => v,
+                None => continue,
+            };
+            let keys = verified_keys(st);
+            match signers.get_mut(&node) {
+                Some(signed) => signed.retain(|key| keys.contains(key)),
+                None => {
+                    signers.insert(node, keys);
+                }
+            }
+        }
+        Ok(signers)
+    }
+
+    /// Whether the verified signatures on the bodies of each node satisfy
+    /// `key`, and if not, which signers are missing.
+    pub fn key_requirement(
+        &self,
+        key: &Key,
+    ) -> Result<HashMap<AccountId, KeyRequirement>, HederaError> {
+        Ok(self
+            .verified_signers()?
+            .into_iter()
+            .map(|(node, signers)| (node, key.requirement(&signers)))
+            .collect())
+    }
+
+    /// The body bytes sent to `node_account_id`, one per chunk, for signing
+    /// elsewhere.
+    pub fn body_bytes_for_node(
+        &self,
+        node_account_id: AccountId,
+    ) -> Result<Vec<Vec<u8>>, HederaError> {
+        let nodes = self.signed_transaction_node_ids()?;
+        let bodies = self
+            .signed_transactions
+            .iter()
+            .zip(nodes)
+            .filter(|(_, node)| *node == Some(node_account_id))
+            .map(|(st, _)| st.body_bytes.clone())
+            .collect::<Vec<Vec<u8>>>();
+        if bodies.is_empty() {
+            return Err(HederaError::TransactionNodeNotFound(node_account_id));
+        }
+        Ok(bodies)
+    }
+
+    // the node each signed transaction is sent to
+    fn signed_transaction_node_ids(&self) -> Result<Vec<Option<AccountId>>, HederaError> {
+        if !self.is_frozen() {
+            return Err(HederaError::TransactionSignWithFailed);
+        }
+        self.signed_transactions
+            .iter()
+            .map(|st| {
+                Ok(TransactionBody::try_from_proto_bytes(st.body_bytes.clone())?.node_account_id)
+            })
+            .collect()
     }
 
     fn key_already_signed(&self, pk: &PublicKey) -> bool {
@@ -450,6 +775,33 @@ impl Transaction {
         false
     }
 
+    /// Writes the transaction with `data`, the fields of its type `transaction_type`.
+    pub(crate) fn serialize_with_data<S: Serializer, D: Serialize>(
+        &self,
+        transaction_type: &'static str,
+        data: &D,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Transaction", 7)?;
+        state.serialize_field("type", transaction_type)?;
+        state.serialize_field("transaction_id", &self.transaction_ids.first())?;
+        state.serialize_field("node_account_ids", &self.node_account_ids)?;
+        state.serialize_field(
+            "max_transaction_fee",
+            &self.transaction_body.transaction_fee,
+        )?;
+        state.serialize_field(
+            "transaction_valid_duration",
+            &self
+                .transaction_body
+                .transaction_valid_duration
+                .map(|d| d.num_seconds()),
+        )?;
+        state.serialize_field("memo", &self.transaction_body.memo)?;
+        state.serialize_field("data", data)?;
+        state.end()
+    }
+
     pub fn max_transaction_fee(&self) -> Hbar {
         self.transaction_body.transaction_fee
     }
@@ -528,6 +880,47 @@ impl Transaction {
     }
 }
 
+// the keys whose signature verifies against the body
+fn verified_keys(signed_transaction: &SignedTransaction) -> HashSet<PublicKey> {
+    let pairs = match &signed_transaction.sig_map {
+        Some(sig_map) => &sig_map.sig_pair[..],
+        None => &[],
+    };
+    pairs
+        .iter()
+        .filter_map(|pair| verified_key(&signed_transaction.body_bytes, pair))
+        .collect()
+}
+
+// the key of `pair`, if its signature verifies against `body_bytes`
+fn verified_key(body_bytes: &[u8], pair: &ProtoSignaturePair) -> Option<PublicKey> {
+    // a prefix shorter than the key can't be verified
+    let public_key = PublicKey::from_bytes(&pair.pub_key_prefix).ok()?;
+    let signature = match &pair.signature {
+        Some(ProtoSignature::Ed25519(bytes)) => Signature::from_bytes(bytes),
+        Some(ProtoSignature::EcdsaSecp256k1(bytes)) => Signature::from_bytes_ecdsa(bytes),
+        _ => return None,
+    }
+    .ok()?;
+    match public_key.verify(body_bytes, &signature) {
+        Ok(true) => Some(public_key),
+        _ => None,
+    }
+}
+
+fn has_signature(signed_transaction: &SignedTransaction, public_key: &PublicKey) -> bool {
+    let pk_bytes = public_key.as_bytes_vec();
+    signed_transaction
+        .sig_map
+        .as_ref()
+        .map_or(false, |sig_map| {
+            sig_map
+                .sig_pair
+                .iter()
+                .any(|pair| pair.pub_key_prefix == pk_bytes)
+        })
+}
+
 impl Display for Transaction {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
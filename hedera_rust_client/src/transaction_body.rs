@@ -1,8 +1,10 @@
 use bytes::Bytes;
 use chrono::Duration;
 use prost::Message;
+use serde::ser::{Serialize, SerializeStruct, Serializer};
 use std::convert::{TryFrom, TryInto};
 
+use crate::any_transaction::{transaction_type_name, AnyTransactionData};
 use crate::error::HederaError;
 use crate::proto::services::{
     self, schedulable_transaction_body::Data as SchTxData, transaction_body::Data as TxData,
@@ -104,6 +106,24 @@ impl TryFrom<services::TransactionBody> for TransactionBody {
     }
 }
 
+// the data is written as the fields of its transaction type, named in `type`
+impl Serialize for TransactionBody {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("TransactionBody", 7)?;
+        state.serialize_field("transaction_id", &self.transaction_id)?;
+        state.serialize_field("node_account_id", &self.node_account_id)?;
+        state.serialize_field("transaction_fee", &self.transaction_fee)?;
+        state.serialize_field(
+            "transaction_valid_duration",
+            &self.transaction_valid_duration.map(|d| d.num_seconds()),
+        )?;
+        state.serialize_field("memo", &self.memo)?;
+        state.serialize_field("type", &self.data.as_ref().and_then(transaction_type_name))?;
+        state.serialize_field("data", &self.data.as_ref().map(AnyTransactionData))?;
+        state.end()
+    }
+}
+
 // Note: following tx body types are not supported b/c no scheduled version
 // TxData::CryptoAddLiveHash(super::CryptoAddLiveHashTransactionBody),
 // TxData::CryptoDeleteLiveHash(super::CryptoDeleteLiveHashTransactionBody),
@@ -126,6 +146,8 @@ impl TryFrom<TransactionBody> for services::SchedulableTransactionBody {
                     TxData::CryptoDelete(data) => SchTxData::CryptoDelete(data),
                     TxData::CryptoTransfer(data) => SchTxData::CryptoTransfer(data),
                     TxData::CryptoUpdateAccount(data) => SchTxData::CryptoUpdateAccount(data),
+                    TxData::CryptoApproveAllowance(data) => SchTxData::CryptoApproveAllowance(data),
+                    TxData::CryptoDeleteAllowance(data) => SchTxData::CryptoDeleteAllowance(data),
                     TxData::FileAppend(data) => SchTxData::FileAppend(data),
                     TxData::FileCreate(data) => SchTxData::FileCreate(data),
                     TxData::FileDelete(data) => SchTxData::FileDelete(data),
@@ -149,7 +171,10 @@ impl TryFrom<TransactionBody> for services::SchedulableTransactionBody {
                     TxData::TokenWipe(data) => SchTxData::TokenWipe(data),
                     TxData::TokenAssociate(data) => SchTxData::TokenAssociate(data),
                     TxData::TokenDissociate(data) => SchTxData::TokenDissociate(data),
+                    TxData::TokenPause(data) => SchTxData::TokenPause(data),
+                    TxData::TokenUnpause(data) => SchTxData::TokenUnpause(data),
                     TxData::ScheduleDelete(data) => SchTxData::ScheduleDelete(data),
+                    TxData::UtilPrng(data) => SchTxData::UtilPrng(data),
                     _ => return Err(HederaError::UnsupportedTransactionBodyType),
                 };
                 Some(sch_data)
@@ -163,3 +188,61 @@ impl TryFrom<TransactionBody> for services::SchedulableTransactionBody {
         })
     }
 }
+
+// the body a schedule runs, without a transaction or node id
+impl TryFrom<services::SchedulableTransactionBody> for TransactionBody {
+    type Error = HederaError;
+    fn try_from(
+        services: services::SchedulableTransactionBody,
+    ) -> Result<TransactionBody, Self::Error> {
+        let data = services.data.map(|data| match data {
+            SchTxData::ContractCall(data) => TxData::ContractCall(data),
+            SchTxData::ContractCreateInstance(data) => TxData::ContractCreateInstance(data),
+            SchTxData::ContractUpdateInstance(data) => TxData::ContractUpdateInstance(data),
+            SchTxData::ContractDeleteInstance(data) => TxData::ContractDeleteInstance(data),
+            SchTxData::CryptoCreateAccount(data) => TxData::CryptoCreateAccount(data),
+            SchTxData::CryptoDelete(data) => TxData::CryptoDelete(data),
+            SchTxData::CryptoTransfer(data) => TxData::CryptoTransfer(data),
+            SchTxData::CryptoUpdateAccount(data) => TxData::CryptoUpdateAccount(data),
+            SchTxData::CryptoApproveAllowance(data) => TxData::CryptoApproveAllowance(data),
+            SchTxData::CryptoDeleteAllowance(data) => TxData::CryptoDeleteAllowance(data),
+            SchTxData::FileAppend(data) => TxData::FileAppend(data),
+            SchTxData::FileCreate(data) => TxData::FileCreate(data),
+            SchTxData::FileDelete(data) => TxData::FileDelete(data),
+            SchTxData::FileUpdate(data) => TxData::FileUpdate(data),
+            SchTxData::SystemDelete(data) => TxData::SystemDelete(data),
+            SchTxData::SystemUndelete(data) => TxData::SystemUndelete(data),
+            SchTxData::Freeze(data) => TxData::Freeze(data),
+            SchTxData::ConsensusCreateTopic(data) => TxData::ConsensusCreateTopic(data),
+            SchTxData::ConsensusUpdateTopic(data) => TxData::ConsensusUpdateTopic(data),
+            SchTxData::ConsensusDeleteTopic(data) => TxData::ConsensusDeleteTopic(data),
+            SchTxData::ConsensusSubmitMessage(data) => TxData::ConsensusSubmitMessage(data),
+            SchTxData::TokenCreation(data) => TxData::TokenCreation(data),
+            SchTxData::TokenFreeze(data) => TxData::TokenFreeze(data),
+            SchTxData::TokenUnfreeze(data) => TxData::TokenUnfreeze(data),
+            SchTxData::TokenGrantKyc(data) => TxData::TokenGrantKyc(data),
+            SchTxData::TokenRevokeKyc(data) => TxData::TokenRevokeKyc(data),
+            SchTxData::TokenDeletion(data) => TxData::TokenDeletion(data),
+            SchTxData::TokenUpdate(data) => TxData::TokenUpdate(data),
+            SchTxData::TokenMint(data) => TxData::TokenMint(data),
+            SchTxData::TokenBurn(data) => TxData::TokenBurn(data),
+            SchTxData::TokenWipe(data) => TxData::TokenWipe(data),
+            SchTxData::TokenAssociate(data) => TxData::TokenAssociate(data),
+            SchTxData::TokenDissociate(data) => TxData::TokenDissociate(data),
+            SchTxData::TokenPause(data) => TxData::TokenPause(data),
+            SchTxData::TokenUnpause(data) => TxData::TokenUnpause(data),
+            SchTxData::TokenFeeScheduleUpdate(data) => TxData::TokenFeeScheduleUpdate(data),
+            SchTxData::ScheduleDelete(data) => TxData::ScheduleDelete(data),
+            SchTxData::UtilPrng(data) => TxData::UtilPrng(data),
+        });
+        Ok(TransactionBody {
+            transaction_id: None,
+            node_account_id: None,
+            transaction_fee: services.transaction_fee.try_into()?,
+            transaction_valid_duration: None,
+            generate_record: false,
+            memo: services.memo,
+            data,
+        })
+    }
+}
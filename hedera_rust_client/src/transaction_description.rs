@@ -0,0 +1,363 @@
+use chrono::Duration;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use crate::error::HederaError;
+use crate::key_list::KeyList;
+use crate::proto::services::{self, transaction_body::Data as ProtoData};
+use crate::token_transfer_list::{
+    proto_token_transfer_list_vec_to_token_transfer_list_hashmap,
+    proto_transfer_list_to_account_token_transfer_hashmap, AccountIdTokenTransferHashMap,
+    TokenIdTokenTransferListHashMap,
+};
+use crate::transaction::Transaction;
+use crate::AccountId;
+use crate::Hbar;
+use crate::Key;
+use crate::TransactionId;
+
+/// A key a transaction needs a signature from, either directly or as the key
+/// of an account.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RequiredKey {
+    Account(AccountId),
+    Key(Key),
+}
+
+/// What the signers of a transaction approve, for showing it before it is
+/// signed.
+///
+/// `required_keys` holds the payer, the accounts a transfer or an allowance
+/// debits, the other accounts whose own key the network always asks for
+/// and the keys the body sets. Keys only the ledger knows, e.g. the admin
+/// key of the token a `TokenUpdateTransaction` changes or the key of a
+/// receiver that requires signatures, are not listed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TransactionDescription {
+    pub transaction_type: &'static str,
+    pub transaction_id: Option<TransactionId>,
+    pub payer: Option<AccountId>,
+    pub node_account_ids: Vec<AccountId>,
+    pub max_transaction_fee: Hbar,
+    #[serde(serialize_with = "crate::json::option_duration")]
+    pub transaction_valid_duration: Option<Duration>,
+    pub memo: String,
+    #[serde(serialize_with = "crate::json::hbar_transfers")]
+    pub hbar_transfers: AccountIdTokenTransferHashMap,
+    #[serde(serialize_with = "crate::json::sorted_values")]
+    pub token_transfers: TokenIdTokenTransferListHashMap,
+    pub required_keys: Vec<RequiredKey>,
+}
+
+impl TransactionDescription {
+    pub(crate) fn new(
+        transaction_type: &'static str,
+        transaction: &Transaction,
+        data: ProtoData,
+    ) -> Result<TransactionDescription, HederaError> {
+        let transaction_id = transaction.transaction_id().ok();
+        let payer = transaction_id.as_ref().and_then(|id| id.account_id);
+        let mut description = TransactionDescription {
+            transaction_type,
+            transaction_id,
+            payer,
+            node_account_ids: transaction.node_account_ids(),
+            max_transaction_fee: transaction.max_transaction_fee(),
+            transaction_valid_duration: transaction.transaction_valid_duration(),
+            memo: transaction.transaction_memo(),
+            hbar_transfers: HashMap::new(),
+            token_transfers: HashMap::new(),
+            required_keys: Vec::new(),
+        };
+        if let Some(payer) = payer {
+            description.require(RequiredKey::Account(payer));
+        }
+        description.read_data(data)?;
+        Ok(description)
+    }
+
+    fn require(&mut self, required_key: RequiredKey) {
+        if !self.required_keys.contains(&required_key) {
+            self.required_keys.push(required_key);
+        }
+    }
+
+    fn require_account(
+        &mut self,
+        account_id: Option<services::AccountId>,
+    ) -> Result<(), HederaError> {
+        if let Some(account_id) = account_id {
+            self.require(RequiredKey::Account(AccountId::try_from(account_id)?));
+        }
+        Ok(())
+    }
+
+    fn require_key(&mut self, key: Option<services::Key>) -> Result<(), HederaError> {
+        if let Some(key) = key {
+            self.require(RequiredKey::Key(Key::try_from(key)?));
+        }
+        Ok(())
+    }
+
+    fn read_data(&mut self, data: ProtoData) -> Result<(), HederaError> {
+        match data {
+            ProtoData::CryptoTransfer(body) => {
+                if let Some(transfers) = body.transfers {
+                    self.hbar_transfers =
+                        proto_transfer_list_to_account_token_transfer_hashmap(transfers)?;
+                }
+                self.token_transfers =
+                    proto_token_transfer_list_vec_to_token_transfer_list_hashmap(
+                        body.token_transfers,
+                    )?;
+                self.require_senders();
+            }
+            ProtoData::CryptoCreateAccount(body) if body.receiver_sig_required => {
+                self.require_key(body.key)?
+            }
+            ProtoData::CryptoUpdateAccount(body) => {
+                self.require_account(body.account_id_to_update)?;
+                self.require_key(body.key)?;
+            }
+            ProtoData::CryptoDelete(body) => self.require_account(body.delete_account_id)?,
+            ProtoData::CryptoApproveAllowance(body) => {
+                let owners = body
+                    .crypto_allowances
+                    .into_iter()
+                    .map(|allowance| allowance.owner)
+                    .chain(
+                        body.token_allowances
+                            .into_iter()
+                            .map(|allowance| allowance.owner),
+                    )
+                    .chain(
+                        body.nft_allowances
+                            .into_iter()
+                            .map(|allowance| allowance.owner),
+                    );
+                for owner in owners {
+                    self.require_account(owner)?;
+                }
+            }
+            ProtoData::CryptoDeleteAllowance(body) => {
+                for allowance in body.nft_allowances {
+                    self.require_account(allowance.owner)?;
+                }
+            }
+            ProtoData::TokenCreation(body) => {
+                self.require_account(body.treasury)?;
+                self.require_account(body.auto_renew_account)?;
+                self.require_key(body.admin_key)?;
+            }
+            ProtoData::TokenUpdate(body) => {
+                self.require_account(body.treasury)?;
+                self.require_account(body.auto_renew_account)?;
+                self.require_key(body.admin_key)?;
+            }
+            ProtoData::TokenAssociate(body) => self.require_account(body.account)?,
+            ProtoData::TokenDissociate(body) => self.require_account(body.account)?,
+            ProtoData::ConsensusCreateTopic(body) => {
+                self.require_account(body.auto_renew_account)?;
+                self.require_key(body.admin_key)?;
+            }
+            ProtoData::ConsensusUpdateTopic(body) => {
+                self.require_account(body.auto_renew_account)?;
+                self.require_key(body.admin_key)?;
+            }
+            ProtoData::FileCreate(body) => {
+                if let Some(keys) = body.keys {
+                    self.require(RequiredKey::Key(Key::KeyList(KeyList::try_from(keys)?)));
+                }
+            }
+            ProtoData::FileUpdate(body) => {
+                if let Some(keys) = body.keys {
+                    self.require(RequiredKey::Key(Key::KeyList(KeyList::try_from(keys)?)));
+                }
+            }
+            ProtoData::ContractCreateInstance(body) => {
+                self.require_account(body.auto_renew_account_id)?;
+                self.require_key(body.admin_key)?;
+            }
+            ProtoData::ContractUpdateInstance(body) => {
+                self.require_account(body.auto_renew_account_id)?;
+                self.require_key(body.admin_key)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    // an approved transfer is signed by its spender, the payer
+    fn require_senders(&mut self) {
+        let mut senders: Vec<AccountId> = self
+            .hbar_transfers
+            .values()
+            .chain(
+                self.token_transfers
+                    .values()
+                    .flat_map(|list| list.transfers.values()),
+            )
+            .filter(|transfer| transfer.amount < 0 && !transfer.is_approval)
+            .map(|transfer| transfer.account_id)
+            .chain(
+                self.token_transfers
+                    .values()
+                    .flat_map(|list| list.nft_transfers.values())
+                    .filter(|transfer| !transfer.is_approval)
+                    .map(|transfer| transfer.sender_account_id),
+            )
+            .collect();
+        senders.sort();
+        for sender in senders {
+            self.require(RequiredKey::Account(sender));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::test_utils::*;
+    use crate::{
+        AccountDeleteTransaction, AnyTransaction, PrivateKey, TokenAssociateTransaction,
+        TokenFreezeTransaction, TokenId, TopicCreateTransaction, TransferTransaction,
+    };
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_describe_transfer() -> Result<(), HederaError> {
+        let mut tx = TransferTransaction::new();
+        tx.add_hbar_transfer(AccountId::simple(2), Hbar::from_tinybar(-150_000_000))?
+            .add_hbar_transfer(AccountId::simple(3), Hbar::from_tinybar(150_000_000))?
+            .add_token_transfer(TokenId::simple(7), AccountId::simple(5), -10, None)?
+            .add_token_transfer(TokenId::simple(7), AccountId::simple(3), 10, None)?
+            .set_transaction_memo("memo".to_string())?
+            .set_transaction_id(mock_transaction_id())?
+            .set_node_account_ids(vec![AccountId::simple(3)])?
+            .freeze()
+            .await?;
+        let transaction_id = tx.transaction_id()?;
+        let payer = transaction_id.account_id.unwrap();
+
+        let description = AnyTransaction::from_bytes(&tx.to_bytes()?)?.describe()?;
+        assert_eq!(description, tx.describe()?);
+        assert_eq!(description.transaction_type, "TransferTransaction");
+        assert_eq!(description.payer, Some(payer));
+        assert_eq!(description.memo, "memo");
+        assert_eq!(
+            description.required_keys,
+            vec![
+                RequiredKey::Account(payer),
+                RequiredKey::Account(AccountId::simple(2)),
+                RequiredKey::Account(AccountId::simple(5)),
+            ]
+        );
+
+        let value = serde_json::to_value(&description).unwrap();
+        assert_eq!(value["transaction_id"], json!(transaction_id.to_string()));
+        assert_eq!(value["node_account_ids"], json!(["0.0.3"]));
+        assert_eq!(value["max_transaction_fee"], json!("1 ℏ"));
+        assert_eq!(
+            value["hbar_transfers"],
+            json!([
+                {"account_id": "0.0.2", "amount": "-1.5 ℏ", "is_approval": false},
+                {"account_id": "0.0.3", "amount": "1.5 ℏ", "is_approval": false},
+            ])
+        );
+        assert_eq!(value["token_transfers"][0]["token_id"], json!("0.0.7"));
+        assert_eq!(value["required_keys"][1], json!({"account": "0.0.2"}));
+
+        let value = serde_json::to_value(&tx).unwrap();
+        assert_eq!(value["type"], json!("TransferTransaction"));
+        assert_eq!(value["memo"], json!("memo"));
+        assert_eq!(value["data"]["transfers"][1]["amount"], json!("1.5 ℏ"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_describe_required_key() -> Result<(), HederaError> {
+        let admin_key = PrivateKey::new().public();
+        let mut tx = TopicCreateTransaction::new();
+        tx.set_admin_key(admin_key.into())?
+            .set_transaction_id(mock_transaction_id())?
+            .set_node_account_ids(vec![AccountId::simple(3)])?
+            .freeze()
+            .await?;
+
+        let description = tx.describe()?;
+        assert_eq!(
+            description.required_keys[1],
+            RequiredKey::Key(admin_key.into())
+        );
+        let value = serde_json::to_value(&description).unwrap();
+        assert_eq!(
+            value["required_keys"][1],
+            json!({"key": {"ed25519": admin_key.to_string()}})
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_describe_token_associate() -> Result<(), HederaError> {
+        let mut tx = TokenAssociateTransaction::new();
+        tx.set_account_id(AccountId::simple(1001))?
+            .set_tokens(vec![TokenId::simple(7)])?
+            .set_transaction_id(mock_transaction_id())?
+            .set_node_account_ids(vec![AccountId::simple(3)])?
+            .freeze()
+            .await?;
+
+        let description = AnyTransaction::from_bytes(&tx.to_bytes()?)?.describe()?;
+        assert_eq!(
+            description.required_keys,
+            vec![
+                RequiredKey::Account(tx.transaction_id()?.account_id.unwrap()),
+                RequiredKey::Account(AccountId::simple(1001)),
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_describe_unsigned_accounts() -> Result<(), HederaError> {
+        // the transfer account of a delete only signs if it asks to
+        let mut delete = AccountDeleteTransaction::new();
+        delete
+            .set_delete_account_id(AccountId::simple(1001))?
+            .set_transfer_account_id(AccountId::simple(1002))?
+            .set_transaction_id(mock_transaction_id())?
+            .set_node_account_ids(vec![AccountId::simple(3)])?
+            .freeze()
+            .await?;
+        let payer = RequiredKey::Account(delete.transaction_id()?.account_id.unwrap());
+        assert_eq!(
+            delete.describe()?.required_keys,
+            vec![payer.clone(), RequiredKey::Account(AccountId::simple(1001))]
+        );
+
+        // and a frozen account's key isn't asked for, the token's freeze key is
+        let mut freeze = TokenFreezeTransaction::new();
+        freeze
+            .set_token_id(TokenId::simple(7))?
+            .set_account_id(AccountId::simple(1001))?
+            .set_transaction_id(mock_transaction_id())?
+            .set_node_account_ids(vec![AccountId::simple(3)])?
+            .freeze()
+            .await?;
+        assert_eq!(freeze.describe()?.required_keys, vec![payer]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_id_json() {
+        let account_id = AccountId::simple(98);
+        let value = serde_json::to_value(&account_id).unwrap();
+        assert_eq!(value, json!("0.0.98"));
+        assert_eq!(
+            serde_json::from_value::<AccountId>(value).unwrap(),
+            account_id
+        );
+    }
+}
@@ -264,4 +264,23 @@ pub enum HederaFunctionality {
     ///*
     /// Unpause the Token
     TokenUnpause = 80,
+    ///*
+    /// Approve allowance for a spender relative to the owner account
+    CryptoApproveAllowance = 81,
+    ///*
+    /// Deletes granted allowances on owner account
+    CryptoDeleteAllowance = 82,
+    ///*
+    /// Gets all the information about an account, including balance and allowances. This does not get the list of
+    /// account records.
+    GetAccountDetails = 83,
+    ///*
+    /// Ethereum Transaction
+    EthereumTransaction = 84,
+    ///*
+    /// Updates the staking info at the end of staking period to indicate new staking period has started.
+    NodeStakeUpdate = 85,
+    ///*
+    /// Generates a pseudorandom number.
+    UtilPrng = 86,
 }
@@ -111,13 +111,15 @@ impl FromStr for TransactionId {
     }
 }
 
+serde_from_str!(TransactionId);
+
 impl TryFrom<services::TransactionId> for TransactionId {
     type Error = HederaError;
 
     fn try_from(services: services::TransactionId) -> Result<TransactionId, Self::Error> {
         if services.account_id.is_some() && services.transaction_valid_start.is_some() {
             let mut nonce: Option<i32> = None;
-            if services.nonce == 0 {
+            if services.nonce != 0 {
                 nonce = Some(services.nonce);
             }
             Ok(TransactionId::new(
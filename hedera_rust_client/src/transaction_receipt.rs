@@ -1,3 +1,4 @@
+use serde::Serialize;
 use std::convert::TryFrom;
 
 use crate::error::HederaError;
@@ -13,7 +14,7 @@ use crate::TokenId;
 use crate::TopicId;
 use crate::TransactionId;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct TransactionReceipt {
     pub status: Status,
     pub exchange_rate: Option<ExchangeRate>,
@@ -23,6 +24,7 @@ pub struct TransactionReceipt {
     pub account_id: Option<AccountId>,
     pub token_id: Option<TokenId>,
     pub topic_sequence_num: u64,
+    #[serde(serialize_with = "crate::json::hex")]
     pub topic_running_hash: Vec<u8>,
     pub topic_running_hash_version: u64,
     pub total_supply: u64,
@@ -1,4 +1,5 @@
 use chrono::{DateTime, Utc};
+use serde::Serialize;
 use std::collections::HashMap;
 use std::convert::TryFrom;
 
@@ -21,15 +22,18 @@ use crate::Hbar;
 use crate::PublicKey;
 use crate::TokenAssociation;
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 pub struct TransactionRecord {
     pub receipt: TransactionReceipt,
+    #[serde(serialize_with = "crate::json::hex")]
     pub transaction_hash: Vec<u8>,
     pub consensus_timestamp: Option<DateTime<Utc>>,
     pub transaction_id: TransactionId,
     pub transaction_memo: String,
     pub transaction_fee: Hbar,
+    #[serde(serialize_with = "crate::json::hbar_transfers")]
     pub transfers: AccountIdTokenTransferHashMap,
+    #[serde(serialize_with = "crate::json::sorted_values")]
     pub token_transfers: TokenIdTokenTransferListHashMap,
     pub call_result: Option<ContractFunctionResult>,
     pub call_result_is_create: bool,
@@ -37,11 +41,31 @@ pub struct TransactionRecord {
     pub automatic_token_associations: Vec<TokenAssociation>,
     pub parent_consensus_timestamp: Option<DateTime<Utc>>,
     pub alias_key: Option<PublicKey>,
+    #[serde(serialize_with = "crate::json::hex")]
     pub ethereum_hash: Vec<u8>,
+    #[serde(serialize_with = "crate::json::sorted_map")]
     pub paid_staking_rewards: HashMap<AccountId, Hbar>,
     pub entropy: Option<Entropy>,
 }
 
+impl TransactionRecord {
+    /// The 384-bit pseudorandom string of a `PrngTransaction` submitted without a range
+    pub fn prng_bytes(&self) -> Option<&[u8]> {
+        match &self.entropy {
+            Some(Entropy::PrngBytes(bytes)) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// The pseudorandom number of a `PrngTransaction` submitted with a range
+    pub fn prng_number(&self) -> Option<i32> {
+        match &self.entropy {
+            Some(Entropy::PrngNumber(num)) => Some(*num),
+            _ => None,
+        }
+    }
+}
+
 impl TryFrom<services::TransactionRecord> for TransactionRecord {
     type Error = HederaError;
     fn try_from(services: services::TransactionRecord) -> Result<TransactionRecord, Self::Error> {
@@ -0,0 +1,222 @@
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use num_traits::FromPrimitive;
+use prost::Message;
+use std::convert::TryFrom;
+
+use crate::error::HederaError;
+use crate::proto::streams::{self, contract_action, transaction_sidecar_record};
+use crate::utils;
+use crate::AccountId;
+use crate::ContractId;
+
+#[derive(FromPrimitive, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ContractActionType {
+    NoAction = 0,
+    Call = 1,
+    Create = 2,
+    Precompile = 3,
+    System = 4,
+}
+
+impl ContractActionType {
+    pub fn from_proto(streams: i32) -> Result<ContractActionType, HederaError> {
+        ContractActionType::from_i32(streams).ok_or(HederaError::UnexpectedProtoType)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContractActionCaller {
+    Account(AccountId),
+    Contract(ContractId),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContractActionRecipient {
+    Account(AccountId),
+    Contract(ContractId),
+    InvalidSolidityAddress(Vec<u8>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContractActionResult {
+    Output(Vec<u8>),
+    RevertReason(Vec<u8>),
+    Error(Vec<u8>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractAction {
+    pub call_type: ContractActionType,
+    pub caller: Option<ContractActionCaller>,
+    pub gas: i64,
+    pub input: Vec<u8>,
+    pub recipient: Option<ContractActionRecipient>,
+    pub value: i64,
+    pub gas_used: i64,
+    pub result: Option<ContractActionResult>,
+    pub call_depth: i32,
+}
+
+impl TryFrom<streams::ContractAction> for ContractAction {
+    type Error = HederaError;
+    fn try_from(streams: streams::ContractAction) -> Result<ContractAction, Self::Error> {
+        let caller = match streams.caller {
+            Some(contract_action::Caller::CallingAccount(id)) => {
+                Some(ContractActionCaller::Account(AccountId::try_from(id)?))
+            }
+            Some(contract_action::Caller::CallingContract(id)) => {
+                Some(ContractActionCaller::Contract(ContractId::from(id)))
+            }
+            None => None,
+        };
+        let recipient = match streams.recipient {
+            Some(contract_action::Recipient::RecipientAccount(id)) => {
+                Some(ContractActionRecipient::Account(AccountId::try_from(id)?))
+            }
+            Some(contract_action::Recipient::RecipientContract(id)) => {
+                Some(ContractActionRecipient::Contract(ContractId::from(id)))
+            }
+            Some(contract_action::Recipient::InvalidSolidityAddress(address)) => {
+                Some(ContractActionRecipient::InvalidSolidityAddress(address))
+            }
+            None => None,
+        };
+        let result = streams.result_data.map(|result| match result {
+            contract_action::ResultData::Output(v) => ContractActionResult::Output(v),
+            contract_action::ResultData::RevertReason(v) => ContractActionResult::RevertReason(v),
+            contract_action::ResultData::Error(v) => ContractActionResult::Error(v),
+        });
+        Ok(ContractAction {
+            call_type: ContractActionType::from_proto(streams.call_type)?,
+            caller,
+            gas: streams.gas,
+            input: streams.input,
+            recipient,
+            value: streams.value,
+            gas_used: streams.gas_used,
+            result,
+            call_depth: streams.call_depth,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageChange {
+    pub slot: Vec<u8>,
+    pub value_read: Vec<u8>,
+    /// `None` when the slot was only read
+    pub value_written: Option<Vec<u8>>,
+}
+
+impl From<streams::StorageChange> for StorageChange {
+    fn from(streams: streams::StorageChange) -> StorageChange {
+        StorageChange {
+            slot: streams.slot,
+            value_read: streams.value_read,
+            value_written: streams.value_written,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractStateChange {
+    pub contract_id: ContractId,
+    pub storage_changes: Vec<StorageChange>,
+}
+
+impl TryFrom<streams::ContractStateChange> for ContractStateChange {
+    type Error = HederaError;
+    fn try_from(streams: streams::ContractStateChange) -> Result<ContractStateChange, Self::Error> {
+        Ok(ContractStateChange {
+            contract_id: utils::non_optional_contract_id(streams.contract_id)?,
+            storage_changes: streams
+                .storage_changes
+                .into_iter()
+                .map(StorageChange::from)
+                .collect(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContractBytecode {
+    pub contract_id: Option<ContractId>,
+    pub initcode: Vec<u8>,
+    pub runtime_bytecode: Vec<u8>,
+}
+
+impl From<streams::ContractBytecode> for ContractBytecode {
+    fn from(streams: streams::ContractBytecode) -> ContractBytecode {
+        ContractBytecode {
+            contract_id: streams.contract_id.map(ContractId::from),
+            initcode: streams.initcode,
+            runtime_bytecode: streams.runtime_bytecode,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SidecarRecordData {
+    StateChanges(Vec<ContractStateChange>),
+    Actions(Vec<ContractAction>),
+    Bytecode(ContractBytecode),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionSidecarRecord {
+    /// matches the consensus timestamp of the record this sidecar complements
+    pub consensus_timestamp: DateTime<Utc>,
+    pub migration: bool,
+    pub data: Option<SidecarRecordData>,
+}
+
+impl TryFrom<streams::TransactionSidecarRecord> for TransactionSidecarRecord {
+    type Error = HederaError;
+    fn try_from(
+        streams: streams::TransactionSidecarRecord,
+    ) -> Result<TransactionSidecarRecord, Self::Error> {
+        let data = match streams.sidecar_records {
+            Some(transaction_sidecar_record::SidecarRecords::StateChanges(changes)) => {
+                Some(SidecarRecordData::StateChanges(
+                    changes
+                        .contract_state_changes
+                        .into_iter()
+                        .map(ContractStateChange::try_from)
+                        .collect::<Result<Vec<ContractStateChange>, HederaError>>()?,
+                ))
+            }
+            Some(transaction_sidecar_record::SidecarRecords::Actions(actions)) => {
+                Some(SidecarRecordData::Actions(
+                    actions
+                        .contract_actions
+                        .into_iter()
+                        .map(ContractAction::try_from)
+                        .collect::<Result<Vec<ContractAction>, HederaError>>()?,
+                ))
+            }
+            Some(transaction_sidecar_record::SidecarRecords::Bytecode(bytecode)) => {
+                Some(SidecarRecordData::Bytecode(bytecode.into()))
+            }
+            None => None,
+        };
+        Ok(TransactionSidecarRecord {
+            consensus_timestamp: utils::non_optional_timestamp(streams.consensus_timestamp)?,
+            migration: streams.migration,
+            data,
+        })
+    }
+}
+
+/// Decodes the (already decompressed) contents of a sidecar file.
+pub(crate) fn sidecar_records_from_bytes(
+    bytes: &[u8],
+) -> Result<Vec<TransactionSidecarRecord>, HederaError> {
+    let sidecar_file = streams::SidecarFile::decode(Bytes::copy_from_slice(bytes))
+        .map_err(|e| HederaError::InvalidRecordStreamFile(e.to_string()))?;
+    sidecar_file
+        .sidecar_records
+        .into_iter()
+        .map(TransactionSidecarRecord::try_from)
+        .collect()
+}
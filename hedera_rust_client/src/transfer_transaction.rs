@@ -1,4 +1,5 @@
 use hedera_rust_client_derive::{TransactionExecute, TransactionProto};
+use serde::Serialize;
 use std::collections::HashMap;
 
 use crate::entity_id::validate_id_checksum;
@@ -6,6 +7,8 @@ use crate::proto::{services, ToProto};
 use crate::token_nft_transfer::TokenNftTransfer;
 use crate::token_transfer::TokenTransfer;
 use crate::token_transfer_list::{
+    proto_token_transfer_list_vec_to_token_transfer_list_hashmap,
+    proto_transfer_list_to_account_token_transfer_hashmap,
     token_transfer_list_hashmap_to_proto_vec, AccountIdTokenTransferHashMap,
     TokenIdTokenTransferListHashMap, TokenTransferList,
 };
@@ -96,11 +99,67 @@ impl TransferTransaction {
         &mut self,
         account_id: AccountId,
         amount: Hbar,
+    ) -> Result<&mut Self, HederaError> {
+        self.add_hbar_transfer_with_approval(account_id, amount, false)
+    }
+
+    pub fn add_approved_hbar_transfer(
+        &mut self,
+        account_id: AccountId,
+        amount: Hbar,
+    ) -> Result<&mut Self, HederaError> {
+        self.add_hbar_transfer_with_approval(account_id, amount, true)
+    }
+
+    pub fn add_token_transfer(
+        &mut self,
+        token_id: TokenId,
+        account_id: AccountId,
+        value: i64,
+        decimals: Option<u32>,
+    ) -> Result<&mut Self, HederaError> {
+        self.add_token_transfer_with_approval(token_id, account_id, value, decimals, false)
+    }
+
+    pub fn add_approved_token_transfer(
+        &mut self,
+        token_id: TokenId,
+        account_id: AccountId,
+        value: i64,
+        decimals: Option<u32>,
+    ) -> Result<&mut Self, HederaError> {
+        self.add_token_transfer_with_approval(token_id, account_id, value, decimals, true)
+    }
+
+    pub fn add_nft_transfer(
+        &mut self,
+        nft_id: NftId,
+        sender_account_id: AccountId,
+        receiver_account_id: AccountId,
+    ) -> Result<&mut Self, HederaError> {
+        self.add_nft_transfer_with_approval(nft_id, sender_account_id, receiver_account_id, false)
+    }
+
+    pub fn add_approved_nft_transfer(
+        &mut self,
+        nft_id: NftId,
+        sender_account_id: AccountId,
+        receiver_account_id: AccountId,
+    ) -> Result<&mut Self, HederaError> {
+        self.add_nft_transfer_with_approval(nft_id, sender_account_id, receiver_account_id, true)
+    }
+
+    fn add_hbar_transfer_with_approval(
+        &mut self,
+        account_id: AccountId,
+        amount: Hbar,
+        is_approval: bool,
     ) -> Result<&mut Self, HederaError> {
         self.transaction.require_not_frozen()?;
         let token_transfer = TokenTransfer {
             account_id,
             amount: amount.as_tinybar(),
+            is_approval,
         };
         match &mut self.services.transfers {
             Some(v) => {
@@ -115,17 +174,19 @@ impl TransferTransaction {
         Ok(self)
     }
 
-    pub fn add_token_transfer(
+    fn add_token_transfer_with_approval(
         &mut self,
         token_id: TokenId,
         account_id: AccountId,
         value: i64,
         decimals: Option<u32>,
+        is_approval: bool,
     ) -> Result<&mut Self, HederaError> {
         self.transaction.require_not_frozen()?;
         let amount_transfer = TokenTransfer {
             account_id,
             amount: value,
+            is_approval,
         };
 
         match self.services.token_transfers.get_mut(&token_id) {
@@ -147,17 +208,19 @@ impl TransferTransaction {
         Ok(self)
     }
 
-    pub fn add_nft_transfer(
+    fn add_nft_transfer_with_approval(
         &mut self,
         nft_id: NftId,
         sender_account_id: AccountId,
         receiver_account_id: AccountId,
+        is_approval: bool,
     ) -> Result<&mut Self, HederaError> {
         self.transaction.require_not_frozen()?;
         let nft_transfer = TokenNftTransfer {
             sender_account_id,
             receiver_account_id,
             serial_number: nft_id.serial_number,
+            is_approval,
         };
         match self.services.token_transfers.get_mut(&nft_id.token_id) {
             Some(token_transfer) => {
@@ -182,15 +245,23 @@ impl TransferTransaction {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, TransactionProto)]
+#[derive(Debug, Clone, PartialEq, TransactionProto, Serialize)]
 #[hedera_rust_client_derive(proto(
     proto_enum = "CryptoTransfer",
     proto_type = "CryptoTransferTransactionBody"
 ))]
 struct Proto {
-    #[hedera_rust_client_derive(to_proto_with_fn = "token_transfer_account_hashmap_to_proto")]
+    #[hedera_rust_client_derive(
+        to_proto_with_fn = "token_transfer_account_hashmap_to_proto",
+        from_proto_with_fn = "token_transfer_account_hashmap_from_proto"
+    )]
+    #[serde(serialize_with = "crate::json::option_hbar_transfers")]
     pub transfers: Option<AccountIdTokenTransferHashMap>,
-    #[hedera_rust_client_derive(to_proto_with_fn = "token_transfer_list_hashmap_to_proto_vec")]
+    #[hedera_rust_client_derive(
+        to_proto_with_fn = "token_transfer_list_hashmap_to_proto_vec",
+        from_proto_with_fn = "proto_token_transfer_list_vec_to_token_transfer_list_hashmap"
+    )]
+    #[serde(serialize_with = "crate::json::sorted_values")]
     pub token_transfers: TokenIdTokenTransferListHashMap,
 }
 
@@ -216,3 +287,11 @@ pub fn token_transfer_account_hashmap_to_proto(
         None => Ok(None),
     }
 }
+
+pub fn token_transfer_account_hashmap_from_proto(
+    services: Option<services::TransferList>,
+) -> Result<Option<AccountIdTokenTransferHashMap>, HederaError> {
+    services
+        .map(proto_transfer_list_to_account_token_transfer_hashmap)
+        .transpose()
+}
@@ -0,0 +1,65 @@
+mod utils;
+use hedera_rust_client::{
+    AccountAllowanceApproveTransaction, AccountBalanceQuery, Hbar, TransactionId,
+    TransferTransaction,
+};
+
+#[test_log::test(tokio::test)]
+#[ignore]
+async fn test_approved_hbar_transfer() {
+    approved_hbar_transfer(utils::IntegrationTestEnv::open().await.unwrap()).await;
+}
+
+#[cfg(feature = "testing")]
+#[test_log::test(tokio::test)]
+async fn test_approved_hbar_transfer_emulator() {
+    approved_hbar_transfer(utils::IntegrationTestEnv::open_emulator().await.unwrap()).await;
+}
+
+async fn approved_hbar_transfer(env: utils::IntegrationTestEnv) {
+    // create spender and receiver
+    let (spender_account_id, spender_key) = env.new_test_account(Hbar::new(2.0)).await.unwrap();
+    let (to_account_id, _) = env.new_test_account(Hbar::new(0.0)).await.unwrap();
+
+    // approve spender
+    let _tx = AccountAllowanceApproveTransaction::new()
+        .approve_hbar_allowance(env.operator_id, spender_account_id, Hbar::new(2.0))
+        .unwrap()
+        .execute(&env.client)
+        .await
+        .unwrap()
+        .get_receipt(&env.client)
+        .await
+        .unwrap();
+
+    // spender pays for and signs the transfer out of the owner's account
+    let amount = Hbar::new(1.0);
+    let _tx = TransferTransaction::new()
+        .add_approved_hbar_transfer(env.operator_id, Hbar::from_tinybar(-amount.as_tinybar()))
+        .unwrap()
+        .add_hbar_transfer(to_account_id, amount)
+        .unwrap()
+        .set_transaction_id(TransactionId::generate(spender_account_id))
+        .unwrap()
+        .freeze_with(Some(&env.client))
+        .await
+        .unwrap()
+        .sign(&spender_key)
+        .unwrap()
+        .execute(&env.client)
+        .await
+        .unwrap()
+        .get_receipt(&env.client)
+        .await
+        .unwrap();
+
+    let balance = AccountBalanceQuery::new()
+        .set_account_id(to_account_id)
+        .unwrap()
+        .execute(&env.client)
+        .await
+        .unwrap();
+    assert_eq!(balance.hbars, amount);
+
+    env.close().await.unwrap();
+}
@@ -1,14 +1,22 @@
 mod utils;
 use hedera_rust_client::{
-    AccountCreateTransaction, AccountDeleteTransaction, AccountId, Hbar, NetworkName, PrivateKey,
-    TransactionId,
+    AccountCreateTransaction, AccountDeleteTransaction, AccountId, Client, Hbar, NetworkName,
+    PrivateKey, TransactionId,
 };
 
 #[test_log::test(tokio::test)]
 #[ignore]
 async fn test_account_create_delete() {
-    let env = utils::IntegrationTestEnv::open().await.unwrap();
+    account_create_delete(utils::IntegrationTestEnv::open().await.unwrap()).await;
+}
 
+#[cfg(feature = "testing")]
+#[test_log::test(tokio::test)]
+async fn test_account_create_delete_emulator() {
+    account_create_delete(utils::IntegrationTestEnv::open_emulator().await.unwrap()).await;
+}
+
+async fn account_create_delete(env: utils::IntegrationTestEnv) {
     let new_key = PrivateKey::new();
     let initial_balance = Hbar::new(2.0);
     let resp = AccountCreateTransaction::new()
@@ -54,9 +62,21 @@ async fn test_account_create_delete() {
 }
 
 #[test_log::test(tokio::test)]
+#[ignore]
 async fn test_account_create_no_key() {
     let client = utils::IntegrationTestEnv::testnet_client().unwrap();
+    account_create_no_key(&client).await;
+}
+
+#[cfg(feature = "testing")]
+#[test_log::test(tokio::test)]
+async fn test_account_create_no_key_emulator() {
+    let env = utils::IntegrationTestEnv::open_emulator().await.unwrap();
+    account_create_no_key(&env.client).await;
+    env.close().await.unwrap();
+}
 
+async fn account_create_no_key(client: &Client) {
     let initial_balance = Hbar::new(2.0);
     let mut resp = AccountCreateTransaction::new();
     let res = resp
@@ -64,7 +84,7 @@ async fn test_account_create_no_key() {
         .unwrap()
         .set_max_automatic_token_associations(100)
         .unwrap()
-        .execute(&client)
+        .execute(client)
         .await;
     assert!(res.is_err());
 }
@@ -4,7 +4,16 @@ use hedera_rust_client::AccountInfoQuery;
 #[test_log::test(tokio::test)]
 #[ignore]
 async fn test_account_get_info() {
-    let env = utils::IntegrationTestEnv::open().await.unwrap();
+    account_get_info(utils::IntegrationTestEnv::open().await.unwrap()).await;
+}
+
+#[cfg(feature = "testing")]
+#[test_log::test(tokio::test)]
+async fn test_account_get_info_emulator() {
+    account_get_info(utils::IntegrationTestEnv::open_emulator().await.unwrap()).await;
+}
+
+async fn account_get_info(env: utils::IntegrationTestEnv) {
     let info = AccountInfoQuery::new()
         .set_account_id(env.operator_id)
         .unwrap()
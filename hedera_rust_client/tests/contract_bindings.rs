@@ -0,0 +1,132 @@
+use hedera_rust_client::{
+    abi_encode, hedera_contract, AbiType, AbiValue, ContractAbi, ContractFunctionResult,
+    ContractId, ContractLogInfo, HederaError,
+};
+
+hedera_contract!("tests/abi/Pool.json");
+
+const OWNER: [u8; 20] = [7; 20];
+
+fn order() -> Order {
+    Order {
+        amount: 5,
+        flags: vec![true, false],
+        memo: "first".to_string(),
+    }
+}
+
+fn result(contract_call_result: Vec<u8>) -> ContractFunctionResult {
+    ContractFunctionResult {
+        contract_id: ContractId::simple(1001),
+        contract_call_result,
+        error_message: String::new(),
+        bloom: Vec::new(),
+        gas_used: 0,
+        log_info: Vec::new(),
+        created_contract_ids: Vec::new(),
+    }
+}
+
+#[test]
+fn test_contract_bindings_encode() -> Result<(), HederaError> {
+    let abi = ContractAbi::from_json(Pool::ABI)?;
+    let pool = Pool::new(ContractId::simple(1001));
+
+    let tx = pool.place(vec![order()], -2)?;
+    assert_eq!(tx.contract_id()?, ContractId::simple(1001));
+    let expected = abi
+        .function_by_signature("place((uint128,bool[],string)[],int16)")?
+        .encode_input(&[
+            AbiValue::Array(vec![AbiValue::Tuple(vec![
+                5u8.into(),
+                vec![true, false].into(),
+                "first".into(),
+            ])]),
+            (-2i16).into(),
+        ])?;
+    assert_eq!(tx.function_parameters()?, expected);
+
+    // the overload takes a numeric suffix
+    let tx = pool.place_1(9)?;
+    let expected = abi
+        .function_by_signature("place(uint128)")?
+        .encode_input(&[9u8.into()])?;
+    assert_eq!(tx.function_parameters()?, expected);
+
+    // view functions are queries
+    let query = pool.balance_of(OWNER)?;
+    let expected = abi
+        .function("balanceOf")?
+        .encode_input(&[AbiValue::Address(OWNER)])?;
+    assert_eq!(query.function_parameters(), expected);
+
+    let create = Pool::deploy(OWNER)?;
+    assert_eq!(
+        create.constructor_parameters()?,
+        abi.encode_constructor(&[AbiValue::Address(OWNER)])?
+    );
+    Ok(())
+}
+
+#[test]
+fn test_contract_bindings_decode() -> Result<(), HederaError> {
+    let balance = abi_encode(&[AbiType::Uint(256)], &[1_000u64.into()])?;
+    assert_eq!(
+        Pool::decode_balance_of(&result(balance))?,
+        hedera_rust_client::BigUint::from(1_000u64)
+    );
+
+    let abi = ContractAbi::from_json(Pool::ABI)?;
+    let place = abi.function_by_signature("place((uint128,bool[],string)[],int16)")?;
+    let output = abi_encode(
+        &place
+            .outputs
+            .iter()
+            .map(|output| output.kind.clone())
+            .collect::<Vec<_>>(),
+        &[
+            3u64.into(),
+            AbiValue::Tuple(vec![5u8.into(), vec![true, false].into(), "first".into()]),
+        ],
+    )?;
+    assert_eq!(Pool::decode_place(&result(output))?, (3, order()));
+    assert!(Pool::decode_place(&result(vec![0; 64])).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_contract_bindings_events() -> Result<(), HederaError> {
+    let log = ContractLogInfo {
+        contract_id: ContractId::simple(1001),
+        bloom: Vec::new(),
+        topic: vec![
+            PlacedEvent::TOPIC.to_vec(),
+            abi_encode(&[AbiType::Uint(64)], &[3u64.into()])?,
+            vec![0xaa; 32],
+        ],
+        data: abi_encode(
+            &[
+                AbiType::Address,
+                AbiType::Array(Box::new(AbiType::Uint(128))),
+            ],
+            &[AbiValue::Address(OWNER), vec![5u64, 6].into()],
+        )?,
+    };
+
+    let expected = PlacedEvent {
+        id: 3,
+        memo: [0xaa; 32],
+        owner: OWNER,
+        amounts: vec![5, 6],
+    };
+    assert_eq!(PlacedEvent::decode(&log)?, expected);
+    assert_eq!(Pool::decode_log(&log)?, PoolEvent::Placed(expected));
+
+    let mut unknown = log.clone();
+    unknown.topic[0] = vec![0; 32];
+    assert!(matches!(
+        Pool::decode_log(&unknown),
+        Err(HederaError::AbiEventNotFound(_))
+    ));
+    Ok(())
+}
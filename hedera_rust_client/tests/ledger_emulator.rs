@@ -0,0 +1,437 @@
+use hedera_rust_client::testing::LedgerEmulator;
+use hedera_rust_client::{
+    AccountBalanceQuery, AccountCreateTransaction, AccountId, Client, FileAppendTransaction,
+    FileContentsQuery, FileCreateTransaction, Hbar, HederaError, Key, KeyList, PrivateKey,
+    ScheduleSignTransaction, Status, TokenAssociateTransaction, TokenCreateTransaction,
+    TokenFreezeTransaction, TokenGrantKycTransaction, TokenMintTransaction, TopicCreateTransaction,
+    TopicMessageSubmitTransaction, TransactionReceiptQuery, TransferTransaction,
+};
+
+async fn create_account(client: &Client, key: &PrivateKey, balance: Hbar) -> AccountId {
+    AccountCreateTransaction::new()
+        .set_key(key.public().into())
+        .unwrap()
+        .set_initial_balance(balance)
+        .unwrap()
+        .execute(client)
+        .await
+        .unwrap()
+        .get_receipt(client)
+        .await
+        .unwrap()
+        .account_id
+        .unwrap()
+}
+
+// `Hbar::new` drops the sign
+fn hbar(bars: i64) -> Hbar {
+    Hbar::from_tinybar(bars * 100_000_000)
+}
+
+fn assert_receipt_status<T: std::fmt::Debug>(result: Result<T, HederaError>, expected: Status) {
+    match result {
+        Err(HederaError::ReceiptStatusError { status, .. }) => assert_eq!(status, expected),
+        other => panic!("expected a {:?} receipt, got {:?}", expected, other),
+    }
+}
+
+#[test_log::test(tokio::test)]
+async fn test_ledger_emulator_transfer() {
+    let emulator = LedgerEmulator::start(2).await.unwrap();
+    let client = emulator.client().unwrap();
+    let key = PrivateKey::new();
+    let account_id = create_account(&client, &key, Hbar::new(10.0)).await;
+    assert_eq!(emulator.balance(&account_id), Some(Hbar::new(10.0)));
+
+    // spending from the new account needs its signature
+    let mut transfer = TransferTransaction::new();
+    transfer
+        .add_hbar_transfer(account_id, hbar(-4))
+        .unwrap()
+        .add_hbar_transfer(emulator.operator_id(), hbar(4))
+        .unwrap();
+    let response = transfer.clone().execute(&client).await.unwrap();
+    assert_receipt_status(
+        response.get_receipt(&client).await,
+        Status::InvalidSignature,
+    );
+
+    let response = transfer
+        .freeze_with(Some(&client))
+        .await
+        .unwrap()
+        .sign(&key)
+        .unwrap()
+        .execute(&client)
+        .await
+        .unwrap();
+    response.get_receipt(&client).await.unwrap();
+    let record = response
+        .get_record(&client)
+        .await
+        .unwrap()
+        .transaction_record
+        .unwrap();
+    assert_eq!(record.transfers[&account_id].amount, hbar(-4).as_tinybar());
+
+    let balance = AccountBalanceQuery::new()
+        .set_account_id(account_id)
+        .unwrap()
+        .execute(&client)
+        .await
+        .unwrap();
+    assert_eq!(balance.hbars, Hbar::new(6.0));
+
+    // the ledger's error comes back as the precheck status
+    let result = AccountBalanceQuery::new()
+        .set_account_id(AccountId::simple(9999))
+        .unwrap()
+        .execute(&client)
+        .await;
+    match result {
+        Err(HederaError::FailedPreCheck(status)) => assert_eq!(status, Status::InvalidAccountId),
+        other => panic!("expected a failed precheck, got {:?}", other),
+    }
+
+    let mut overdraft = TransferTransaction::new();
+    overdraft
+        .add_hbar_transfer(account_id, hbar(-7))
+        .unwrap()
+        .add_hbar_transfer(emulator.operator_id(), hbar(7))
+        .unwrap();
+    let response = overdraft
+        .freeze_with(Some(&client))
+        .await
+        .unwrap()
+        .sign(&key)
+        .unwrap()
+        .execute(&client)
+        .await
+        .unwrap();
+    assert_receipt_status(
+        response.get_receipt(&client).await,
+        Status::InsufficientAccountBalance,
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn test_ledger_emulator_transaction_fee() {
+    let emulator = LedgerEmulator::start(1).await.unwrap();
+    emulator.set_transaction_fee(Hbar::new(1.0)).unwrap();
+    let client = emulator.client().unwrap();
+    let node_id = AccountId::simple(3);
+    let operator_balance = emulator.balance(&emulator.operator_id()).unwrap();
+
+    let key = PrivateKey::new();
+    create_account(&client, &key, Hbar::new(10.0)).await;
+    assert_eq!(
+        emulator.balance(&emulator.operator_id()),
+        Some(Hbar::from_tinybar(
+            operator_balance.as_tinybar() - Hbar::new(11.0).as_tinybar()
+        ))
+    );
+    assert_eq!(emulator.balance(&node_id), Some(Hbar::new(1.0)));
+
+    let result = AccountCreateTransaction::new()
+        .set_key(key.public().into())
+        .unwrap()
+        .set_max_transaction_fee(Hbar::new(0.5))
+        .unwrap()
+        .execute(&client)
+        .await;
+    match result {
+        Err(HederaError::FailedPreCheck(status)) => assert_eq!(status, Status::InsufficientTxFee),
+        other => panic!("expected a failed precheck, got {:?}", other),
+    }
+}
+
+#[test_log::test(tokio::test)]
+async fn test_ledger_emulator_token() {
+    let emulator = LedgerEmulator::start(1).await.unwrap();
+    let client = emulator.client().unwrap();
+    let operator_key: Key = emulator.operator_key().public().into();
+    let receipt = TokenCreateTransaction::new()
+        .set_name("emulated".to_string())
+        .unwrap()
+        .set_symbol("EMU".to_string())
+        .unwrap()
+        .set_decimals(2)
+        .unwrap()
+        .set_initial_supply(1000)
+        .unwrap()
+        .set_treasury(emulator.operator_id())
+        .unwrap()
+        .set_freeze_key(operator_key.clone())
+        .unwrap()
+        .set_kyc_key(operator_key.clone())
+        .unwrap()
+        .set_supply_key(operator_key)
+        .unwrap()
+        .set_freeze_default(false)
+        .unwrap()
+        .execute(&client)
+        .await
+        .unwrap()
+        .get_receipt(&client)
+        .await
+        .unwrap();
+    let token_id = receipt.token_id.unwrap();
+    assert_eq!(
+        emulator.token_balance(&emulator.operator_id(), &token_id),
+        Some(1000)
+    );
+
+    let key = PrivateKey::new();
+    let account_id = create_account(&client, &key, Hbar::new(1.0)).await;
+    TokenAssociateTransaction::new()
+        .set_account_id(account_id)
+        .unwrap()
+        .set_tokens(vec![token_id])
+        .unwrap()
+        .freeze_with(Some(&client))
+        .await
+        .unwrap()
+        .sign(&key)
+        .unwrap()
+        .execute(&client)
+        .await
+        .unwrap()
+        .get_receipt(&client)
+        .await
+        .unwrap();
+
+    let transfer = || {
+        let mut transfer = TransferTransaction::new();
+        transfer
+            .add_token_transfer(token_id, emulator.operator_id(), -100, Some(2))
+            .unwrap()
+            .add_token_transfer(token_id, account_id, 100, Some(2))
+            .unwrap();
+        transfer
+    };
+    let response = transfer().execute(&client).await.unwrap();
+    assert_receipt_status(
+        response.get_receipt(&client).await,
+        Status::AccountKycNotGrantedForToken,
+    );
+
+    TokenGrantKycTransaction::new()
+        .set_token_id(token_id)
+        .unwrap()
+        .set_account_id(account_id)
+        .unwrap()
+        .execute(&client)
+        .await
+        .unwrap()
+        .get_receipt(&client)
+        .await
+        .unwrap();
+    let response = transfer().execute(&client).await.unwrap();
+    response.get_receipt(&client).await.unwrap();
+    assert_eq!(emulator.token_balance(&account_id, &token_id), Some(100));
+
+    TokenFreezeTransaction::new()
+        .set_token_id(token_id)
+        .unwrap()
+        .set_account_id(account_id)
+        .unwrap()
+        .execute(&client)
+        .await
+        .unwrap()
+        .get_receipt(&client)
+        .await
+        .unwrap();
+    let response = transfer().execute(&client).await.unwrap();
+    assert_receipt_status(
+        response.get_receipt(&client).await,
+        Status::AccountFrozenForToken,
+    );
+
+    let receipt = TokenMintTransaction::new()
+        .set_token_id(token_id)
+        .unwrap()
+        .set_amount(500)
+        .unwrap()
+        .execute(&client)
+        .await
+        .unwrap()
+        .get_receipt(&client)
+        .await
+        .unwrap();
+    assert_eq!(receipt.total_supply, 1500);
+    let balance = AccountBalanceQuery::new()
+        .set_account_id(emulator.operator_id())
+        .unwrap()
+        .execute(&client)
+        .await
+        .unwrap();
+    assert_eq!(balance.token.get(&token_id), Some(&1400));
+}
+
+#[test_log::test(tokio::test)]
+async fn test_ledger_emulator_topic() {
+    let emulator = LedgerEmulator::start(1).await.unwrap();
+    let client = emulator.client().unwrap();
+    let submit_key = PrivateKey::new();
+    let topic_id = TopicCreateTransaction::new()
+        .set_submit_key(submit_key.public().into())
+        .unwrap()
+        .execute(&client)
+        .await
+        .unwrap()
+        .get_receipt(&client)
+        .await
+        .unwrap()
+        .topic_id
+        .unwrap();
+
+    let response = TopicMessageSubmitTransaction::new()
+        .set_topic_id(topic_id)
+        .unwrap()
+        .set_message(b"unsigned".to_vec())
+        .unwrap()
+        .execute_async(&client)
+        .await
+        .unwrap();
+    assert_receipt_status(
+        response.get_receipt(&client).await,
+        Status::InvalidSignature,
+    );
+
+    let mut running_hash = Vec::new();
+    for (message, sequence_number) in [(b"first", 1), (b"again", 2)] {
+        let receipt = TopicMessageSubmitTransaction::new()
+            .set_topic_id(topic_id)
+            .unwrap()
+            .set_message(message.to_vec())
+            .unwrap()
+            .freeze_with(Some(&client))
+            .await
+            .unwrap()
+            .sign(&submit_key)
+            .unwrap()
+            .execute_async(&client)
+            .await
+            .unwrap()
+            .get_receipt(&client)
+            .await
+            .unwrap();
+        assert_eq!(receipt.topic_sequence_num, sequence_number);
+        assert_eq!(receipt.topic_running_hash_version, 3);
+        assert_eq!(receipt.topic_running_hash.len(), 48);
+        assert_ne!(receipt.topic_running_hash, running_hash);
+        running_hash = receipt.topic_running_hash;
+    }
+}
+
+#[test_log::test(tokio::test)]
+async fn test_ledger_emulator_file() {
+    let emulator = LedgerEmulator::start(1).await.unwrap();
+    let client = emulator.client().unwrap();
+    let mut keys = KeyList::new(None);
+    keys.add(emulator.operator_key().public().into());
+    let file_id = FileCreateTransaction::new()
+        .set_keys(keys)
+        .unwrap()
+        .set_contents(b"hello".to_vec())
+        .unwrap()
+        .execute(&client)
+        .await
+        .unwrap()
+        .get_receipt(&client)
+        .await
+        .unwrap()
+        .file_id
+        .unwrap();
+
+    FileAppendTransaction::new()
+        .set_file_id(file_id)
+        .unwrap()
+        .set_contents(b", world".to_vec())
+        .unwrap()
+        .execute_async(&client)
+        .await
+        .unwrap()
+        .get_receipt(&client)
+        .await
+        .unwrap();
+
+    let contents = FileContentsQuery::new()
+        .set_file_id(file_id)
+        .unwrap()
+        .execute(&client)
+        .await
+        .unwrap();
+    assert_eq!(contents, Some(b"hello, world".to_vec()));
+}
+
+#[test_log::test(tokio::test)]
+async fn test_ledger_emulator_schedule() {
+    let emulator = LedgerEmulator::start(1).await.unwrap();
+    let client = emulator.client().unwrap();
+    let (first, second) = (PrivateKey::new(), PrivateKey::new());
+    let mut keys = KeyList::with_threshold(2);
+    keys.add(first.public().into());
+    keys.add(second.public().into());
+    let account_id = emulator
+        .create_account(Key::ThresholdKey(keys), Hbar::new(10.0))
+        .unwrap();
+
+    // the account pays for the scheduled account creation
+    let receipt = AccountCreateTransaction::new()
+        .set_key(first.public().into())
+        .unwrap()
+        .set_initial_balance(Hbar::new(3.0))
+        .unwrap()
+        .schedule()
+        .unwrap()
+        .set_payer_account_id(account_id)
+        .unwrap()
+        .freeze_with(Some(&client))
+        .await
+        .unwrap()
+        .sign(&first)
+        .unwrap()
+        .execute(&client)
+        .await
+        .unwrap()
+        .get_receipt(&client)
+        .await
+        .unwrap();
+    let schedule_id = receipt.scheduled_id.unwrap();
+    let scheduled_transaction_id = receipt.scheduled_transaction_id.unwrap();
+    assert_eq!(emulator.balance(&account_id), Some(Hbar::new(10.0)));
+
+    // one of two signatures isn't enough, the second executes the transfer
+    let result = TransactionReceiptQuery::new()
+        .set_transaction_id(scheduled_transaction_id.clone())
+        .unwrap()
+        .execute(&client)
+        .await;
+    assert!(result.is_err());
+
+    ScheduleSignTransaction::new()
+        .set_schedule_id(schedule_id)
+        .unwrap()
+        .freeze_with(Some(&client))
+        .await
+        .unwrap()
+        .sign(&second)
+        .unwrap()
+        .execute(&client)
+        .await
+        .unwrap()
+        .get_receipt(&client)
+        .await
+        .unwrap();
+    let receipt = TransactionReceiptQuery::new()
+        .set_transaction_id(scheduled_transaction_id)
+        .unwrap()
+        .execute(&client)
+        .await
+        .unwrap();
+    assert_eq!(receipt.status, Status::Success);
+    let created_id = receipt.account_id.unwrap();
+    assert_eq!(emulator.balance(&created_id), Some(Hbar::new(3.0)));
+    assert_eq!(emulator.balance(&account_id), Some(Hbar::new(7.0)));
+}
@@ -0,0 +1,424 @@
+use chrono::Utc;
+use hedera_rust_client::testing::{services, MockNode, MockRequest, MockResponse};
+use hedera_rust_client::{
+    AccountBalanceQuery, AccountId, Client, ClientBuilder, ExecuteContext, ExecuteMiddleware, Hbar,
+    HederaError, MirrorNetwork, Network, Operator, PrivateKey, ProtoRequest, ProtoResponse,
+    RetryContext, RetryPolicy, Status, TransferTransaction,
+};
+use prost::Message;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+fn mock_client(nodes: &[&MockNode]) -> Client {
+    let mut network = HashMap::new();
+    for node in nodes {
+        network.extend(node.network());
+    }
+    let operator = Operator::new(AccountId::simple(2), PrivateKey::new());
+    let mut client = ClientBuilder::default()
+        .operator(operator)
+        .network(Network::from_network(network).unwrap())
+        .mirror_network(MirrorNetwork::from_network(Vec::new()).unwrap())
+        .build()
+        .unwrap();
+    client.set_min_backoff(1);
+    client.set_max_backoff(10);
+    client
+}
+
+fn transfer() -> TransferTransaction {
+    let mut tx = TransferTransaction::new();
+    tx.add_hbar_transfer(AccountId::simple(2), Hbar::new(-1.0))
+        .unwrap()
+        .add_hbar_transfer(AccountId::simple(1001), Hbar::new(1.0))
+        .unwrap();
+    tx
+}
+
+#[test_log::test(tokio::test)]
+async fn test_mock_node_transaction_receipt() {
+    let node = MockNode::start(AccountId::simple(3)).await.unwrap();
+    node.push_responses(vec![
+        MockResponse::Precheck(Status::Ok),
+        MockResponse::receipt(Status::Success),
+    ]);
+    let client = mock_client(&[&node]);
+
+    let response = transfer().execute(&client).await.unwrap();
+    assert_eq!(response.node_id, node.account_id());
+    let receipt = response.get_receipt(&client).await.unwrap();
+    assert_eq!(receipt.status, Status::Success);
+
+    let requests = node.requests();
+    assert_eq!(requests.len(), 2);
+    assert!(matches!(requests[0], MockRequest::Transaction(_)));
+    assert!(matches!(requests[1], MockRequest::Query(_)));
+    assert_eq!(node.pending_responses(), 0);
+}
+
+#[test_log::test(tokio::test)]
+async fn test_mock_node_precheck() {
+    let node = MockNode::start(AccountId::simple(3)).await.unwrap();
+    node.push_responses(vec![
+        MockResponse::Precheck(Status::Busy),
+        MockResponse::Precheck(Status::Ok),
+    ]);
+    let client = mock_client(&[&node]);
+    transfer().execute(&client).await.unwrap();
+    assert_eq!(node.requests().len(), 2);
+
+    node.push_response(MockResponse::Precheck(Status::InsufficientPayerBalance));
+    match transfer().execute(&client).await {
+        Err(HederaError::FailedPreCheck(status)) => {
+            assert_eq!(status, Status::InsufficientPayerBalance)
+        }
+        other => panic!("expected a failed precheck, got {:?}", other),
+    }
+}
+
+#[test_log::test(tokio::test)]
+async fn test_mock_node_failover() {
+    let unavailable = MockNode::start(AccountId::simple(3)).await.unwrap();
+    let available = MockNode::start(AccountId::simple(4)).await.unwrap();
+    unavailable.push_response(MockResponse::Error(tonic::Code::Unavailable));
+    available.push_response(MockResponse::Precheck(Status::Ok));
+    let client = mock_client(&[&unavailable, &available]);
+
+    let response = transfer()
+        .set_node_account_ids(vec![unavailable.account_id(), available.account_id()])
+        .unwrap()
+        .execute(&client)
+        .await
+        .unwrap();
+    assert_eq!(response.node_id, available.account_id());
+    assert_eq!(unavailable.requests().len(), 1);
+    assert_eq!(available.requests().len(), 1);
+}
+
+#[test_log::test(tokio::test)]
+async fn test_mock_node_timeouts() {
+    let slow = MockNode::start(AccountId::simple(3)).await.unwrap();
+    let fast = MockNode::start(AccountId::simple(4)).await.unwrap();
+    slow.push_response(MockResponse::delay(
+        Duration::from_secs(5),
+        MockResponse::Precheck(Status::Ok),
+    ));
+    fast.push_response(MockResponse::Precheck(Status::Ok));
+    let mut client = mock_client(&[&slow, &fast]);
+    client.set_grpc_deadline(Duration::from_millis(200));
+    let node_account_ids = vec![slow.account_id(), fast.account_id()];
+
+    // the attempt against the hanging node times out and the next node answers
+    let response = transfer()
+        .set_node_account_ids(node_account_ids.clone())
+        .unwrap()
+        .execute(&client)
+        .await
+        .unwrap();
+    assert_eq!(response.node_id, fast.account_id());
+
+    client.set_request_timeout(Duration::from_millis(100));
+    for node in [&slow, &fast] {
+        node.push_response(MockResponse::delay(
+            Duration::from_secs(5),
+            MockResponse::Precheck(Status::Ok),
+        ));
+    }
+    let result = transfer()
+        .set_node_account_ids(node_account_ids)
+        .unwrap()
+        .set_grpc_deadline(Duration::from_secs(10))
+        .unwrap()
+        .execute(&client)
+        .await;
+    assert!(matches!(result, Err(HederaError::ExecuteTimeout(_))));
+}
+
+#[test_log::test(tokio::test)]
+async fn test_mock_node_chunked_execute() {
+    use hedera_rust_client::testing::services;
+    use hedera_rust_client::{FileAppendTransaction, FileId};
+    use prost::Message;
+
+    let node = MockNode::start(AccountId::simple(3)).await.unwrap();
+    node.push_response(MockResponse::Precheck(Status::Ok));
+    let client = mock_client(&[&node]);
+
+    // the node account ids are left for the client to pick
+    let contents = vec![7u8; 500];
+    let response = FileAppendTransaction::new()
+        .set_file_id(FileId::simple(1001))
+        .unwrap()
+        .set_contents(contents.clone())
+        .unwrap()
+        .execute_async(&client)
+        .await
+        .unwrap();
+    assert_eq!(response.node_id, node.account_id());
+
+    let chunks: Vec<Vec<u8>> = node
+        .requests()
+        .into_iter()
+        .map(|request| match request {
+            MockRequest::Transaction(tx) => {
+                let signed =
+                    services::SignedTransaction::decode(&tx.signed_transaction_bytes[..]).unwrap();
+                let body = services::TransactionBody::decode(&signed.body_bytes[..]).unwrap();
+                match body.data {
+                    Some(services::transaction_body::Data::FileAppend(body)) => body.contents,
+                    other => panic!("expected a file append, got {:?}", other),
+                }
+            }
+            other => panic!("expected a transaction, got {:?}", other),
+        })
+        .collect();
+    assert_eq!(chunks, vec![contents]);
+}
+
+#[test_log::test(tokio::test)]
+async fn test_mock_node_query() {
+    let node = MockNode::start(AccountId::simple(3)).await.unwrap();
+    node.push_response(MockResponse::Precheck(Status::InvalidAccountId));
+    let client = mock_client(&[&node]);
+
+    let result = AccountBalanceQuery::new()
+        .set_account_id(AccountId::simple(1001))
+        .unwrap()
+        .execute(&client)
+        .await;
+    match result {
+        Err(HederaError::FailedPreCheck(status)) => assert_eq!(status, Status::InvalidAccountId),
+        other => panic!("expected a failed precheck, got {:?}", other),
+    }
+}
+
+fn file_contents(contents: Vec<u8>) -> MockResponse {
+    MockResponse::Query(services::response::Response::FileGetContents(
+        services::FileGetContentsResponse {
+            header: Some(services::ResponseHeader::default()),
+            file_contents: Some(services::file_get_contents_response::FileContents {
+                file_id: None,
+                contents,
+            }),
+        },
+    ))
+}
+
+fn exchange_rate(cents: i32, expiration: i64) -> services::ExchangeRate {
+    services::ExchangeRate {
+        hbar_equiv: 1,
+        cent_equiv: cents,
+        expiration_time: Some(services::TimestampSeconds {
+            seconds: expiration,
+        }),
+    }
+}
+
+#[test_log::test(tokio::test)]
+async fn test_mock_node_system_files() {
+    let node = MockNode::start(AccountId::simple(3)).await.unwrap();
+    let now = Utc::now().timestamp();
+    let expired = services::ExchangeRateSet {
+        current_rate: Some(exchange_rate(12, now - 1)),
+        next_rate: Some(exchange_rate(13, now + 3600)),
+    };
+    let fresh = services::ExchangeRateSet {
+        current_rate: Some(exchange_rate(14, now + 3600)),
+        next_rate: None,
+    };
+    node.push_responses(vec![
+        MockResponse::Cost(0),
+        file_contents(expired.encode_to_vec()),
+        MockResponse::Cost(0),
+        file_contents(fresh.encode_to_vec()),
+    ]);
+    let client = mock_client(&[&node]);
+
+    // an expired current rate isn't cached
+    let rates = client.exchange_rates().await.unwrap();
+    assert_eq!(rates.rate_at(Utc::now()).unwrap().cents, 13);
+    let rates = client.exchange_rates().await.unwrap();
+    assert_eq!(rates.current_rate.unwrap().cents, 14);
+    assert_eq!(node.pending_responses(), 0);
+
+    // clones share the cache until the current rate expires
+    let requests = node.requests().len();
+    let rates = client.clone().exchange_rates().await.unwrap();
+    assert_eq!(rates.current_rate.unwrap().cents, 14);
+    assert_eq!(node.requests().len(), requests);
+
+    client.clear_system_file_cache();
+    assert!(client.exchange_rates().await.is_err());
+}
+
+// records its hook calls, and points balance queries at account 1002
+#[derive(Debug)]
+struct RecordingMiddleware {
+    name: &'static str,
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+impl ExecuteMiddleware for RecordingMiddleware {
+    fn before_request(
+        &self,
+        context: &ExecuteContext,
+        request: &mut ProtoRequest,
+    ) -> Result<(), HederaError> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("{} before {}", self.name, context.attempt));
+        if let ProtoRequest::Query(query) = request {
+            if let Some(services::query::Query::CryptogetAccountBalance(balance)) = &mut query.query
+            {
+                balance.balance_source = Some(
+                    services::crypto_get_account_balance_query::BalanceSource::AccountId(
+                        services::AccountId {
+                            shard_num: 0,
+                            realm_num: 0,
+                            account: Some(services::account_id::Account::AccountNum(1002)),
+                        },
+                    ),
+                );
+            }
+        }
+        Ok(())
+    }
+
+    fn after_response(
+        &self,
+        context: &ExecuteContext,
+        response: Result<&ProtoResponse, &tonic::Status>,
+    ) {
+        self.calls.lock().unwrap().push(format!(
+            "{} after {} {}",
+            self.name,
+            context.attempt,
+            response.is_ok()
+        ));
+    }
+}
+
+#[derive(Debug)]
+struct FixedRetryPolicy {
+    name: &'static str,
+    retry: bool,
+    calls: Arc<Mutex<Vec<String>>>,
+}
+
+impl RetryPolicy for FixedRetryPolicy {
+    fn retry_delay(&self, context: &RetryContext) -> Option<Duration> {
+        self.calls
+            .lock()
+            .unwrap()
+            .push(format!("{} policy {}", self.name, context.attempt));
+        if self.retry {
+            Some(Duration::ZERO)
+        } else {
+            None
+        }
+    }
+}
+
+fn balance(tinybars: u64) -> MockResponse {
+    MockResponse::Query(services::response::Response::CryptogetAccountBalance(
+        services::CryptoGetAccountBalanceResponse {
+            header: Some(services::ResponseHeader::default()),
+            account_id: None,
+            balance: tinybars,
+            ..Default::default()
+        },
+    ))
+}
+
+#[test_log::test(tokio::test)]
+async fn test_mock_node_middleware_and_retry_policy() {
+    let node = MockNode::start(AccountId::simple(3)).await.unwrap();
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let mut client = mock_client(&[&node]);
+    client.add_middleware(Arc::new(RecordingMiddleware {
+        name: "client",
+        calls: calls.clone(),
+    }));
+    client.set_retry_policy(Arc::new(FixedRetryPolicy {
+        name: "client",
+        retry: false,
+        calls: calls.clone(),
+    }));
+
+    // the request's policy overrides the client's, middleware of both runs
+    node.push_responses(vec![MockResponse::Precheck(Status::Busy), balance(5)]);
+    let result = AccountBalanceQuery::new()
+        .set_account_id(AccountId::simple(1001))
+        .unwrap()
+        .set_retry_policy(Arc::new(FixedRetryPolicy {
+            name: "request",
+            retry: true,
+            calls: calls.clone(),
+        }))
+        .unwrap()
+        .add_middleware(Arc::new(RecordingMiddleware {
+            name: "request",
+            calls: calls.clone(),
+        }))
+        .unwrap()
+        .execute(&client)
+        .await
+        .unwrap();
+    assert_eq!(result.hbars, Hbar::from_tinybar(5));
+    assert_eq!(
+        *calls.lock().unwrap(),
+        vec![
+            "client before 1",
+            "request before 1",
+            "client after 1 true",
+            "request after 1 true",
+            "request policy 1",
+            "client before 2",
+            "request before 2",
+            "client after 2 true",
+            "request after 2 true",
+        ]
+    );
+
+    // before_request changed what the node received
+    for request in node.requests() {
+        let query = match request {
+            MockRequest::Query(query) => query,
+            other => panic!("expected a query, got {:?}", other),
+        };
+        match query.query {
+            Some(services::query::Query::CryptogetAccountBalance(balance)) => assert_eq!(
+                balance.balance_source,
+                Some(
+                    services::crypto_get_account_balance_query::BalanceSource::AccountId(
+                        services::AccountId {
+                            shard_num: 0,
+                            realm_num: 0,
+                            account: Some(services::account_id::Account::AccountNum(1002)),
+                        }
+                    )
+                )
+            ),
+            other => panic!("expected a balance query, got {:?}", other),
+        }
+    }
+
+    // without a request policy the client's gives up
+    calls.lock().unwrap().clear();
+    node.push_response(MockResponse::Precheck(Status::Busy));
+    let result = AccountBalanceQuery::new()
+        .set_account_id(AccountId::simple(1001))
+        .unwrap()
+        .execute(&client)
+        .await;
+    assert!(matches!(
+        result,
+        Err(HederaError::FailedPreCheck(Status::Busy))
+    ));
+    assert_eq!(
+        *calls.lock().unwrap(),
+        vec!["client before 1", "client after 1 true", "client policy 1"]
+    );
+}
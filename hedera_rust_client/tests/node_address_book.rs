@@ -0,0 +1,24 @@
+mod utils;
+use hedera_rust_client::{FileId, NodeAddressBookQuery};
+
+#[test_log::test(tokio::test)]
+#[ignore]
+async fn test_node_address_book_query() {
+    let env = utils::IntegrationTestEnv::open().await.unwrap();
+
+    let address_book = NodeAddressBookQuery::new()
+        .set_file_id(FileId::simple(102))
+        .execute(&env.client)
+        .await
+        .unwrap();
+    assert!(!address_book.node_addresses.is_empty());
+    for node_address in address_book.node_addresses.iter() {
+        assert!(node_address.account_id.is_some());
+    }
+
+    env.client.update_network().await.unwrap();
+    let network = env.client.network().await.unwrap();
+    assert!(!network.is_empty());
+
+    env.close().await.unwrap();
+}
@@ -0,0 +1,50 @@
+mod utils;
+use hedera_rust_client::PrngTransaction;
+
+#[test_log::test(tokio::test)]
+#[ignore]
+async fn test_prng() {
+    prng(utils::IntegrationTestEnv::open().await.unwrap()).await;
+}
+
+#[cfg(feature = "testing")]
+#[test_log::test(tokio::test)]
+async fn test_prng_emulator() {
+    prng(utils::IntegrationTestEnv::open_emulator().await.unwrap()).await;
+}
+
+async fn prng(env: utils::IntegrationTestEnv) {
+    // without range
+    let record = PrngTransaction::new()
+        .execute(&env.client)
+        .await
+        .unwrap()
+        .get_record(&env.client)
+        .await
+        .unwrap()
+        .transaction_record
+        .unwrap();
+    let bytes = record
+        .prng_bytes()
+        .unwrap_or_else(|| panic!("no prng_bytes in record: {:?}", record));
+    assert_eq!(bytes.len(), 48);
+
+    // with range
+    let record = PrngTransaction::new()
+        .set_range(100)
+        .unwrap()
+        .execute(&env.client)
+        .await
+        .unwrap()
+        .get_record(&env.client)
+        .await
+        .unwrap()
+        .transaction_record
+        .unwrap();
+    let number = record
+        .prng_number()
+        .unwrap_or_else(|| panic!("no prng_number in record: {:?}", record));
+    assert!((0..100).contains(&number));
+
+    env.close().await.unwrap();
+}
@@ -8,8 +8,16 @@ use hedera_rust_client::{
 #[test_log::test(tokio::test)]
 #[ignore]
 async fn test_token_create() {
-    let env = utils::IntegrationTestEnv::open().await.unwrap();
+    token_create(utils::IntegrationTestEnv::open().await.unwrap()).await;
+}
+
+#[cfg(feature = "testing")]
+#[test_log::test(tokio::test)]
+async fn test_token_create_emulator() {
+    token_create(utils::IntegrationTestEnv::open_emulator().await.unwrap()).await;
+}
 
+async fn token_create(env: utils::IntegrationTestEnv) {
     let amount = 1000000u64;
     let key: Key = env.client.operator_public_key().into();
     let tx = TokenCreateTransaction::new()
@@ -94,8 +102,16 @@ async fn test_token_create() {
 #[test_log::test(tokio::test)]
 #[ignore]
 async fn test_token_create_multiple_keys() {
-    let env = utils::IntegrationTestEnv::open().await.unwrap();
+    token_create_multiple_keys(utils::IntegrationTestEnv::open().await.unwrap()).await;
+}
+
+#[cfg(feature = "testing")]
+#[test_log::test(tokio::test)]
+async fn test_token_create_multiple_keys_emulator() {
+    token_create_multiple_keys(utils::IntegrationTestEnv::open_emulator().await.unwrap()).await;
+}
 
+async fn token_create_multiple_keys(env: utils::IntegrationTestEnv) {
     let amount = 1000000u64;
 
     let op_key: Key = env.client.operator_public_key().into();
@@ -191,8 +207,16 @@ async fn test_token_create_multiple_keys() {
 #[test_log::test(tokio::test)]
 #[ignore]
 async fn test_token_no_admin_sign() {
-    let env = utils::IntegrationTestEnv::open().await.unwrap();
+    token_no_admin_sign(utils::IntegrationTestEnv::open().await.unwrap()).await;
+}
+
+#[cfg(feature = "testing")]
+#[test_log::test(tokio::test)]
+async fn test_token_no_admin_sign_emulator() {
+    token_no_admin_sign(utils::IntegrationTestEnv::open_emulator().await.unwrap()).await;
+}
 
+async fn token_no_admin_sign(env: utils::IntegrationTestEnv) {
     let resp = TokenCreateTransaction::new()
         .set_node_account_ids(env.node_account_ids.clone())
         .unwrap()
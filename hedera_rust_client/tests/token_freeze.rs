@@ -7,8 +7,16 @@ use hedera_rust_client::{
 #[test_log::test(tokio::test)]
 #[ignore]
 async fn test_token_freeze() {
-    let env = utils::IntegrationTestEnv::open().await.unwrap();
+    token_freeze(utils::IntegrationTestEnv::open().await.unwrap()).await;
+}
+
+#[cfg(feature = "testing")]
+#[test_log::test(tokio::test)]
+async fn test_token_freeze_emulator() {
+    token_freeze(utils::IntegrationTestEnv::open_emulator().await.unwrap()).await;
+}
 
+async fn token_freeze(env: utils::IntegrationTestEnv) {
     // create token
     let amount = 1000000u64;
     let key: Key = env.client.operator_public_key().into();
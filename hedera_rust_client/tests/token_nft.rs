@@ -8,8 +8,16 @@ use hedera_rust_client::{
 #[test_log::test(tokio::test)]
 #[ignore]
 async fn test_token_nft_create() {
-    let env = utils::IntegrationTestEnv::open().await.unwrap();
+    token_nft_create(utils::IntegrationTestEnv::open().await.unwrap()).await;
+}
+
+#[cfg(feature = "testing")]
+#[test_log::test(tokio::test)]
+async fn test_token_nft_create_emulator() {
+    token_nft_create(utils::IntegrationTestEnv::open_emulator().await.unwrap()).await;
+}
 
+async fn token_nft_create(env: utils::IntegrationTestEnv) {
     let key: Key = env.client.operator_public_key().into();
     let tx = TokenCreateTransaction::new()
         .set_node_account_ids(env.node_account_ids.clone())
@@ -0,0 +1,89 @@
+mod utils;
+use hedera_rust_client::{
+    Key, TokenCreateTransaction, TokenInfoQuery, TokenPauseStatus, TokenPauseTransaction,
+    TokenUnpauseTransaction,
+};
+
+#[test_log::test(tokio::test)]
+#[ignore]
+async fn test_token_pause_unpause() {
+    token_pause_unpause(utils::IntegrationTestEnv::open().await.unwrap()).await;
+}
+
+#[cfg(feature = "testing")]
+#[test_log::test(tokio::test)]
+async fn test_token_pause_unpause_emulator() {
+    token_pause_unpause(utils::IntegrationTestEnv::open_emulator().await.unwrap()).await;
+}
+
+async fn token_pause_unpause(env: utils::IntegrationTestEnv) {
+    // create token
+    let key: Key = env.client.operator_public_key().into();
+    let tx = TokenCreateTransaction::new()
+        .set_node_account_ids(env.node_account_ids.clone())
+        .unwrap()
+        .set_name("ffff".to_string())
+        .unwrap()
+        .set_symbol("F".to_string())
+        .unwrap()
+        .set_decimals(3)
+        .unwrap()
+        .set_initial_supply(1000000)
+        .unwrap()
+        .set_treasury(env.operator_id)
+        .unwrap()
+        .set_auto_renew_account(env.operator_id)
+        .unwrap()
+        .set_admin_key(key.clone())
+        .unwrap()
+        .set_pause_key(key)
+        .unwrap()
+        .execute(&env.client)
+        .await
+        .unwrap()
+        .get_receipt(&env.client)
+        .await
+        .unwrap();
+
+    let token_id = tx
+        .token_id
+        .unwrap_or_else(|| panic!("no token_id in receipt: {:?}", tx));
+
+    let _tx = TokenPauseTransaction::new()
+        .set_token_id(token_id)
+        .unwrap()
+        .execute(&env.client)
+        .await
+        .unwrap()
+        .get_receipt(&env.client)
+        .await
+        .unwrap();
+
+    let info = TokenInfoQuery::new()
+        .set_token_id(token_id)
+        .unwrap()
+        .execute(&env.client)
+        .await
+        .unwrap();
+    assert_eq!(info.pause_status, TokenPauseStatus::Paused);
+
+    let _tx = TokenUnpauseTransaction::new()
+        .set_token_id(token_id)
+        .unwrap()
+        .execute(&env.client)
+        .await
+        .unwrap()
+        .get_receipt(&env.client)
+        .await
+        .unwrap();
+
+    let info = TokenInfoQuery::new()
+        .set_token_id(token_id)
+        .unwrap()
+        .execute(&env.client)
+        .await
+        .unwrap();
+    assert_eq!(info.pause_status, TokenPauseStatus::Unpaused);
+
+    env.close_with_token(token_id).await.unwrap();
+}
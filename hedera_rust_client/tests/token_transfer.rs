@@ -8,8 +8,16 @@ use hedera_rust_client::{
 #[test_log::test(tokio::test)]
 #[ignore]
 async fn test_transfer_fungible() {
-    let env = utils::IntegrationTestEnv::open().await.unwrap();
+    transfer_fungible(utils::IntegrationTestEnv::open().await.unwrap()).await;
+}
+
+#[cfg(feature = "testing")]
+#[test_log::test(tokio::test)]
+async fn test_transfer_fungible_emulator() {
+    transfer_fungible(utils::IntegrationTestEnv::open_emulator().await.unwrap()).await;
+}
 
+async fn transfer_fungible(env: utils::IntegrationTestEnv) {
     // create token
     let init_supply = 1000000u64;
     let key: Key = env.client.operator_public_key().into();
@@ -132,8 +140,16 @@ async fn test_transfer_fungible() {
 #[tokio::test]
 #[ignore]
 async fn test_transfer_nonfungible() {
-    let env = utils::IntegrationTestEnv::open().await.unwrap();
+    transfer_nonfungible(utils::IntegrationTestEnv::open().await.unwrap()).await;
+}
+
+#[cfg(feature = "testing")]
+#[tokio::test]
+async fn test_transfer_nonfungible_emulator() {
+    transfer_nonfungible(utils::IntegrationTestEnv::open_emulator().await.unwrap()).await;
+}
 
+async fn transfer_nonfungible(env: utils::IntegrationTestEnv) {
     // create token
     let key: Key = env.client.operator_public_key().into();
     let tx = TokenCreateTransaction::new()
@@ -0,0 +1,268 @@
+use chrono::{Duration as ChronoDuration, TimeZone, Utc};
+use hedera_rust_client::testing::{services, MockMirrorNode, MockTopicResponse};
+use hedera_rust_client::{
+    async_trait, AccountId, Checkpoint, CheckpointStore, Client, ClientBuilder, HederaError,
+    InMemoryCheckpointStore, MirrorNetwork, Network, Operator, PrivateKey, ProcessingMode,
+    TopicConsumer, TopicId, TopicMessage, TopicMessageHandler,
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+fn mirror_client(node: &MockMirrorNode) -> Client {
+    let operator = Operator::new(AccountId::simple(2), PrivateKey::new());
+    ClientBuilder::default()
+        .operator(operator)
+        .network(Network::from_network(HashMap::new()).unwrap())
+        .mirror_network(MirrorNetwork::from_network(node.network()).unwrap())
+        .build()
+        .unwrap()
+}
+
+fn message(sequence_number: u64) -> MockTopicResponse {
+    MockTopicResponse::message(
+        sequence_number,
+        timestamp(sequence_number),
+        &[sequence_number as u8],
+    )
+}
+
+// a chunk of the message sent by the transaction valid from `valid_start`
+fn chunk(sequence_number: u64, valid_start: i64, number: i32, total: i32) -> MockTopicResponse {
+    let mut response = match message(sequence_number) {
+        MockTopicResponse::Message(response) => response,
+        _ => unreachable!(),
+    };
+    response.chunk_info = Some(services::ConsensusMessageChunkInfo {
+        initial_transaction_id: Some(services::TransactionId {
+            transaction_valid_start: Some(services::Timestamp {
+                seconds: valid_start,
+                nanos: 0,
+            }),
+            account_id: Some(services::AccountId {
+                shard_num: 0,
+                realm_num: 0,
+                account: Some(services::account_id::Account::AccountNum(2)),
+            }),
+            scheduled: false,
+            nonce: 0,
+        }),
+        total,
+        number,
+    });
+    MockTopicResponse::Message(response)
+}
+
+fn timestamp(sequence_number: u64) -> chrono::DateTime<Utc> {
+    Utc.timestamp(1_650_000_000, 0) + ChronoDuration::seconds(sequence_number as i64)
+}
+
+fn checkpoint(sequence_number: u64) -> Checkpoint {
+    Checkpoint {
+        sequence_number,
+        consensus_timestamp: timestamp(sequence_number),
+    }
+}
+
+// records the messages handled, failing on `fail_on`
+#[derive(Default)]
+struct Recorder {
+    handled: Mutex<Vec<u64>>,
+    gaps: Mutex<Vec<HederaError>>,
+    fail_on: Option<u64>,
+    skip_gaps: bool,
+    // saves checkpoints as `ProcessingMode::ExactlyOnceEffect` has it
+    store: Option<Arc<InMemoryCheckpointStore>>,
+}
+
+impl Recorder {
+    fn handled(&self) -> Vec<u64> {
+        self.handled.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl TopicMessageHandler for Recorder {
+    async fn handle(
+        &self,
+        message: TopicMessage,
+        checkpoint: Checkpoint,
+    ) -> Result<(), HederaError> {
+        if self.fail_on == Some(message.sequence_number) {
+            return Err(HederaError::ValueNotSet("contents".to_string()));
+        }
+        if let Some(store) = &self.store {
+            store.save(TopicId::simple(5), checkpoint).await?;
+        }
+        self.handled.lock().unwrap().push(message.sequence_number);
+        Ok(())
+    }
+
+    async fn on_gap(&self, error: HederaError) -> Result<(), HederaError> {
+        if !self.skip_gaps {
+            return Err(error);
+        }
+        self.gaps.lock().unwrap().push(error);
+        Ok(())
+    }
+}
+
+#[test_log::test(tokio::test)]
+async fn test_consumer_resumes_from_checkpoint() {
+    let node = MockMirrorNode::start().await.unwrap();
+    node.push_subscription(vec![message(1), message(2), message(3)]);
+    // a mirror node may send messages already processed again
+    node.push_subscription(vec![message(2), message(3), message(4)]);
+    let client = mirror_client(&node);
+    let store = Arc::new(InMemoryCheckpointStore::new());
+    let consumer = TopicConsumer::new(TopicId::simple(5), store.clone());
+
+    let failing = Recorder {
+        fail_on: Some(3),
+        ..Recorder::default()
+    };
+    assert!(matches!(
+        consumer.run(&client, &failing).await,
+        Err(HederaError::ValueNotSet(_))
+    ));
+    assert_eq!(failing.handled(), vec![1, 2]);
+    assert_eq!(consumer.checkpoint().await.unwrap(), Some(checkpoint(2)));
+
+    let recorder = Recorder::default();
+    consumer.run(&client, &recorder).await.unwrap();
+    assert_eq!(recorder.handled(), vec![3, 4]);
+    assert_eq!(
+        store.load(TopicId::simple(5)).await.unwrap(),
+        Some(checkpoint(4))
+    );
+
+    let requests = node.requests();
+    assert_eq!(requests[0].consensus_start_time, None);
+    assert_eq!(
+        requests[1].consensus_start_time,
+        Some(services::Timestamp {
+            seconds: 1_650_000_002,
+            nanos: 1,
+        })
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn test_consumer_gap() {
+    let node = MockMirrorNode::start().await.unwrap();
+    node.push_subscription(vec![message(1), message(2), message(4)]);
+    node.push_subscription(vec![message(4), message(5)]);
+    let client = mirror_client(&node);
+    let consumer = TopicConsumer::new(TopicId::simple(5), Arc::new(InMemoryCheckpointStore::new()));
+
+    let recorder = Recorder::default();
+    assert!(matches!(
+        consumer.run(&client, &recorder).await,
+        Err(HederaError::TopicMessageSequenceGap(3, 4))
+    ));
+    assert_eq!(recorder.handled(), vec![1, 2]);
+
+    // the gap is against the checkpoint after a restart
+    let recorder = Recorder {
+        skip_gaps: true,
+        ..Recorder::default()
+    };
+    consumer.run(&client, &recorder).await.unwrap();
+    assert_eq!(recorder.handled(), vec![4, 5]);
+    assert!(matches!(
+        recorder.gaps.lock().unwrap()[..],
+        [HederaError::TopicMessageSequenceGap(3, 4)]
+    ));
+    assert_eq!(consumer.checkpoint().await.unwrap(), Some(checkpoint(5)));
+}
+
+#[test_log::test(tokio::test)]
+async fn test_consumer_exactly_once_effect() {
+    let node = MockMirrorNode::start().await.unwrap();
+    node.push_subscription(vec![message(1), message(2), message(3)]);
+    node.push_subscription(vec![message(3), message(4)]);
+    let client = mirror_client(&node);
+    let store = Arc::new(InMemoryCheckpointStore::new());
+    let mut consumer = TopicConsumer::new(TopicId::simple(5), store.clone());
+    consumer.set_processing_mode(ProcessingMode::ExactlyOnceEffect);
+
+    // the handler failed on 3 without saving its checkpoint
+    let recorder = Recorder {
+        fail_on: Some(3),
+        store: Some(store.clone()),
+        ..Recorder::default()
+    };
+    assert!(consumer.run(&client, &recorder).await.is_err());
+    assert_eq!(consumer.checkpoint().await.unwrap(), Some(checkpoint(2)));
+
+    let recorder = Recorder {
+        store: Some(store.clone()),
+        ..Recorder::default()
+    };
+    consumer.run(&client, &recorder).await.unwrap();
+    assert_eq!(recorder.handled(), vec![3, 4]);
+    assert_eq!(consumer.checkpoint().await.unwrap(), Some(checkpoint(4)));
+
+    // without the handler saving it, the checkpoint stays where it was
+    node.push_subscription(vec![message(5)]);
+    let recorder = Recorder::default();
+    consumer.run(&client, &recorder).await.unwrap();
+    assert_eq!(recorder.handled(), vec![5]);
+    assert_eq!(consumer.checkpoint().await.unwrap(), Some(checkpoint(4)));
+}
+
+#[test_log::test(tokio::test)]
+async fn test_consumer_interleaved_chunks() {
+    let node = MockMirrorNode::start().await.unwrap();
+    // a and b are chunked, and b is incomplete when 5 completes a
+    let (a, b) = (1_650_000_100, 1_650_000_200);
+    node.push_subscription(vec![
+        message(1),
+        chunk(2, a, 1, 2),
+        message(3),
+        chunk(4, b, 1, 2),
+        chunk(5, a, 2, 2),
+        message(6),
+    ]);
+    // resumed from before b's first chunk
+    node.push_subscription(vec![
+        chunk(4, b, 1, 2),
+        chunk(5, a, 2, 2),
+        message(6),
+        chunk(7, b, 2, 2),
+    ]);
+    let client = mirror_client(&node);
+    let store = Arc::new(InMemoryCheckpointStore::new());
+    let consumer = TopicConsumer::new(TopicId::simple(5), store.clone());
+
+    let failing = Recorder {
+        fail_on: Some(6),
+        ..Recorder::default()
+    };
+    assert!(consumer.run(&client, &failing).await.is_err());
+    assert_eq!(failing.handled(), vec![1, 3, 5]);
+    let before_b = Checkpoint {
+        sequence_number: 3,
+        consensus_timestamp: timestamp(4) - ChronoDuration::nanoseconds(1),
+    };
+    assert_eq!(consumer.checkpoint().await.unwrap(), Some(before_b));
+
+    // a isn't handled again, its second chunk waits for a first one that
+    // came before the checkpoint
+    let recorder = Recorder::default();
+    consumer.run(&client, &recorder).await.unwrap();
+    assert_eq!(recorder.handled(), vec![6, 7]);
+    assert_eq!(
+        node.requests()[1].consensus_start_time,
+        Some(services::Timestamp {
+            seconds: 1_650_000_004,
+            nanos: 0,
+        })
+    );
+    assert_eq!(
+        consumer.checkpoint().await.unwrap(),
+        Some(Checkpoint {
+            sequence_number: 4,
+            consensus_timestamp: timestamp(5) - ChronoDuration::nanoseconds(1),
+        })
+    );
+}
@@ -0,0 +1,286 @@
+use chrono::{Duration as ChronoDuration, TimeZone, Utc};
+use futures::StreamExt;
+use hedera_rust_client::testing::{services, MockMirrorNode, MockTopicResponse};
+use hedera_rust_client::{
+    AccountId, Client, ClientBuilder, HederaError, MirrorNetwork, Network, Operator, PrivateKey,
+    TopicId, TopicMessageQuery,
+};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+fn mirror_client(node: &MockMirrorNode) -> Client {
+    let operator = Operator::new(AccountId::simple(2), PrivateKey::new());
+    ClientBuilder::default()
+        .operator(operator)
+        .network(Network::from_network(HashMap::new()).unwrap())
+        .mirror_network(MirrorNetwork::from_network(node.network()).unwrap())
+        .build()
+        .unwrap()
+}
+
+fn timestamp(sequence_number: u64) -> chrono::DateTime<Utc> {
+    Utc.timestamp(1_650_000_000, 0) + ChronoDuration::seconds(sequence_number as i64)
+}
+
+fn message(sequence_number: u64) -> MockTopicResponse {
+    MockTopicResponse::message(
+        sequence_number,
+        timestamp(sequence_number),
+        &[sequence_number as u8],
+    )
+}
+
+fn chunk(sequence_number: u64, number: i32, total: i32, contents: &[u8]) -> MockTopicResponse {
+    let mut response = match message(sequence_number) {
+        MockTopicResponse::Message(response) => response,
+        _ => unreachable!(),
+    };
+    response.message = contents.to_vec();
+    response.chunk_info = Some(services::ConsensusMessageChunkInfo {
+        initial_transaction_id: Some(services::TransactionId {
+            transaction_valid_start: Some(services::Timestamp {
+                seconds: 1_650_000_000,
+                nanos: 0,
+            }),
+            account_id: Some(services::AccountId {
+                shard_num: 0,
+                realm_num: 0,
+                account: Some(services::account_id::Account::AccountNum(2)),
+            }),
+            scheduled: false,
+            nonce: 0,
+        }),
+        total,
+        number,
+    });
+    MockTopicResponse::Message(response)
+}
+
+fn query() -> TopicMessageQuery {
+    let mut query = TopicMessageQuery::new();
+    query.set_topic_id(TopicId::simple(5)).unwrap();
+    query
+}
+
+#[test_log::test(tokio::test)]
+async fn test_subscribe_stream_ends_with_subscription() {
+    let node = MockMirrorNode::start().await.unwrap();
+    node.push_subscription(vec![message(1), message(2), message(3)]);
+    let client = mirror_client(&node);
+
+    let stream = query().subscribe_stream(&client).await.unwrap();
+    let messages: Vec<_> = stream.map(Result::unwrap).collect().await;
+    assert_eq!(
+        messages
+            .iter()
+            .map(|message| (message.sequence_number, message.contents.clone()))
+            .collect::<Vec<_>>(),
+        vec![(1, vec![1]), (2, vec![2]), (3, vec![3])]
+    );
+
+    let requests = node.requests();
+    assert_eq!(requests.len(), 1);
+    assert_eq!(
+        requests[0].topic_id,
+        Some(services::TopicId {
+            shard_num: 0,
+            realm_num: 0,
+            topic_num: 5,
+        })
+    );
+}
+
+#[test_log::test(tokio::test)]
+async fn test_subscribe_stream_chunks() {
+    let node = MockMirrorNode::start().await.unwrap();
+    node.push_subscription(vec![
+        chunk(1, 2, 3, b"lo "),
+        message(2),
+        chunk(3, 1, 3, b"hel"),
+        chunk(4, 3, 3, b"world"),
+    ]);
+    let client = mirror_client(&node);
+
+    let mut stream = query().subscribe_stream(&client).await.unwrap();
+    assert_eq!(stream.resume_point().unwrap(), None);
+    assert_eq!(stream.next().await.unwrap().unwrap().sequence_number, 2);
+    // resuming after 2 would lose the first chunk read
+    assert_eq!(
+        stream.resume_point().unwrap(),
+        Some((0, timestamp(1) - ChronoDuration::nanoseconds(1)))
+    );
+    let message = stream.next().await.unwrap().unwrap();
+    assert_eq!(stream.resume_point().unwrap(), Some((4, timestamp(4))));
+    assert_eq!(message.contents, b"hello world".to_vec());
+    assert_eq!(message.sequence_number, 4);
+    assert_eq!(message.chunks.unwrap().len(), 3);
+    assert!(message.transaction_id.is_some());
+    assert!(stream.next().await.is_none());
+}
+
+#[test_log::test(tokio::test)]
+async fn test_subscribe_stream_unsubscribe() {
+    let node = MockMirrorNode::start().await.unwrap();
+    node.push_subscription(vec![
+        message(1),
+        MockTopicResponse::Delay(Duration::from_secs(60)),
+        message(2),
+    ]);
+    let client = mirror_client(&node);
+
+    let mut stream = query().subscribe_stream(&client).await.unwrap();
+    let handle = stream.handle();
+    assert_eq!(stream.next().await.unwrap().unwrap().sequence_number, 1);
+    tokio::spawn(async move { handle.unsubscribe() });
+    let next = tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .unwrap();
+    assert!(next.is_none());
+    assert!(stream.handle().is_unsubscribed());
+}
+
+#[test_log::test(tokio::test)]
+async fn test_subscribe_stream_error() {
+    let node = MockMirrorNode::start().await.unwrap();
+    node.push_subscription(vec![
+        message(1),
+        MockTopicResponse::Error(tonic::Code::Internal),
+    ]);
+    let client = mirror_client(&node);
+
+    let mut stream = query().subscribe_stream(&client).await.unwrap();
+    assert!(stream.next().await.unwrap().is_ok());
+    assert!(matches!(
+        stream.next().await,
+        Some(Err(HederaError::ProtoClientFailed(tonic::Code::Internal)))
+    ));
+    assert!(stream.next().await.is_none());
+}
+
+#[test_log::test(tokio::test)]
+async fn test_subscribe_on_error() {
+    let node = MockMirrorNode::start().await.unwrap();
+    node.push_subscription(vec![message(1), message(2), message(3)]);
+    let client = mirror_client(&node);
+
+    let (messages, mut received) = mpsc::unbounded_channel();
+    let (errors, mut failed) = mpsc::unbounded_channel();
+    let handle = query()
+        .subscribe(
+            &client,
+            move |message| {
+                if message.sequence_number == 2 {
+                    return Err(HederaError::ValueNotSet("contents".to_string()));
+                }
+                messages.send(message.sequence_number).unwrap();
+                Ok(())
+            },
+            move |e| errors.send(e).unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        failed.recv().await,
+        Some(HederaError::ValueNotSet(_))
+    ));
+    assert!(handle.is_unsubscribed());
+    assert_eq!(received.recv().await, Some(1));
+    // the subscription ended before the third message
+    assert_eq!(received.recv().await, None);
+}
+
+#[test_log::test(tokio::test)]
+async fn test_subscribe_stream_resumes() {
+    let nodes = vec![
+        MockMirrorNode::start().await.unwrap(),
+        MockMirrorNode::start().await.unwrap(),
+    ];
+    for node in &nodes {
+        node.push_subscription(vec![
+            message(1),
+            message(2),
+            MockTopicResponse::Error(tonic::Code::Unavailable),
+        ]);
+        node.push_subscription(vec![message(3)]);
+    }
+    let operator = Operator::new(AccountId::simple(2), PrivateKey::new());
+    let client = ClientBuilder::default()
+        .operator(operator)
+        .network(Network::from_network(HashMap::new()).unwrap())
+        .mirror_network(
+            MirrorNetwork::from_network(nodes.iter().flat_map(MockMirrorNode::network).collect())
+                .unwrap(),
+        )
+        .build()
+        .unwrap();
+
+    let mut query = query();
+    query.set_limit(3).unwrap();
+    let stream = query.subscribe_stream(&client).await.unwrap();
+    let messages: Vec<_> = stream
+        .map(|message| message.unwrap().sequence_number)
+        .collect()
+        .await;
+    assert_eq!(messages, vec![1, 2, 3]);
+
+    // each node was subscribed to, every resubscription after message 2
+    assert!(nodes.iter().all(|node| !node.requests().is_empty()));
+    let mut requests: Vec<_> = nodes.iter().flat_map(MockMirrorNode::requests).collect();
+    assert_eq!(requests.len(), 3);
+    requests.sort_by_key(|request| request.consensus_start_time.is_some());
+    assert_eq!(requests[0].limit, 3);
+    for request in &requests[1..] {
+        assert_eq!(
+            request.consensus_start_time,
+            Some(services::Timestamp {
+                seconds: 1_650_000_002,
+                nanos: 1,
+            })
+        );
+        assert_eq!(request.limit, 1);
+    }
+}
+
+#[test_log::test(tokio::test)]
+async fn test_subscribe_stream_max_attempts() {
+    let node = MockMirrorNode::start().await.unwrap();
+    for _ in 0..3 {
+        node.push_subscription(vec![MockTopicResponse::Error(tonic::Code::Unavailable)]);
+    }
+    let client = mirror_client(&node);
+
+    let mut query = query();
+    query.set_max_attempts(2);
+    let mut stream = query.subscribe_stream(&client).await.unwrap();
+    assert!(matches!(
+        stream.next().await,
+        Some(Err(HederaError::MaxAttempsExceeded(2)))
+    ));
+    assert!(stream.next().await.is_none());
+    assert_eq!(node.requests().len(), 3);
+}
+
+#[test_log::test(tokio::test)]
+async fn test_subscribe_stream_chunk_timeout() {
+    let node = MockMirrorNode::start().await.unwrap();
+    node.push_subscription(vec![
+        chunk(1, 1, 2, b"hel"),
+        message(2),
+        MockTopicResponse::Delay(Duration::from_millis(500)),
+        message(3),
+    ]);
+    let client = mirror_client(&node);
+
+    let mut query = query();
+    query.set_chunk_timeout(Duration::from_millis(100));
+    let mut stream = query.subscribe_stream(&client).await.unwrap();
+    assert_eq!(stream.next().await.unwrap().unwrap().sequence_number, 2);
+    assert!(matches!(
+        stream.next().await,
+        Some(Err(HederaError::TopicMessageChunksExpired(_, 1, 2)))
+    ));
+    assert_eq!(stream.next().await.unwrap().unwrap().sequence_number, 3);
+    assert!(stream.next().await.is_none());
+}
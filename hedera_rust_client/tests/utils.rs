@@ -1,6 +1,8 @@
 #![allow(dead_code)]
 
 use dotenv::dotenv;
+#[cfg(feature = "testing")]
+use hedera_rust_client::testing::LedgerEmulator;
 use hedera_rust_client::{
     AccountCreateTransaction, AccountDeleteTransaction, AccountId, Client, ClientBuilder, Hbar,
     HederaError, NetworkName, Operator, PrivateKey, PublicKey, TokenDeleteTransaction, TokenId,
@@ -16,6 +18,9 @@ pub struct IntegrationTestEnv {
     pub origional_operator_key: PublicKey,
     pub origional_operator_id: AccountId,
     pub node_account_ids: Vec<AccountId>,
+    // keeps the emulator's nodes serving while the env is in use
+    #[cfg(feature = "testing")]
+    emulator: Option<LedgerEmulator>,
 }
 
 impl IntegrationTestEnv {
@@ -61,7 +66,22 @@ impl IntegrationTestEnv {
             return Err(HederaError::NoNetworkNodes);
         }
         client.set_network(network).await.unwrap();
+        Self::with_client(client).await
+    }
+
+    /// Opens an env on a fresh `LedgerEmulator`, its genesis account acting
+    /// as the original operator.
+    #[cfg(feature = "testing")]
+    pub async fn open_emulator() -> Result<Self, HederaError> {
+        let emulator = LedgerEmulator::start(1).await?;
+        let client = emulator.client()?;
+        let mut env = Self::with_client(client).await?;
+        env.emulator = Some(emulator);
+        Ok(env)
+    }
 
+    // funds a new account on `client` and makes it the operator
+    async fn with_client(mut client: Client) -> Result<Self, HederaError> {
         let new_key = PrivateKey::new();
         let initial_balance = Hbar::new(50.0);
         let resp = AccountCreateTransaction::new()
@@ -93,6 +113,8 @@ impl IntegrationTestEnv {
             origional_operator_key,
             origional_operator_id,
             node_account_ids,
+            #[cfg(feature = "testing")]
+            emulator: None,
         })
     }
 
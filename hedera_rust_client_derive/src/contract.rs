@@ -0,0 +1,885 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use serde_json::Value;
+use sha3::{Digest, Keccak256};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, Index, LitStr, Token};
+
+// `hedera_contract!("Token.abi.json")` or `hedera_contract!(Token, "Token.abi.json")`
+pub struct ContractInput {
+    name: Option<Ident>,
+    path: LitStr,
+}
+
+impl Parse for ContractInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = if input.peek(Ident) {
+            let name = input.parse()?;
+            input.parse::<Token![,]>()?;
+            Some(name)
+        } else {
+            None
+        };
+        let path = input.parse()?;
+        if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+        }
+        Ok(ContractInput { name, path })
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum",
+    "extern", "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move",
+    "mut", "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true", "try",
+    "type", "unsafe", "use", "where", "while", "yield",
+];
+
+// methods of the generated struct that functions can't shadow
+const RESERVED: &[&str] = &["new", "contract_id", "deploy", "decode_log"];
+
+fn snake_case(name: &str) -> String {
+    let mut snake = String::new();
+    let chars = name.trim_start_matches('_').chars().collect::<Vec<_>>();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            let after_lower = i > 0 && (chars[i - 1].is_lowercase() || chars[i - 1].is_numeric());
+            let before_lower = i > 0 && matches!(chars.get(i + 1), Some(n) if n.is_lowercase());
+            if after_lower || (before_lower && chars[i - 1].is_uppercase()) {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else if c.is_alphanumeric() || *c == '_' {
+            snake.push(*c);
+        }
+    }
+    snake
+}
+
+fn upper_camel_case(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            let first = chars.next().unwrap().to_uppercase();
+            first.chain(chars).collect::<String>()
+        })
+        .collect()
+}
+
+// an identifier from an ABI name, `fallback` if the name is empty
+fn ident(name: &str, fallback: String) -> Ident {
+    let mut name = snake_case(name);
+    if name.is_empty() {
+        name = fallback;
+    }
+    if name.starts_with(|c: char| c.is_numeric()) || KEYWORDS.contains(&name.as_str()) {
+        name.push('_');
+    }
+    Ident::new(&name, Span::call_site())
+}
+
+// the identifiers of `params`, numbered apart where their names coincide
+// once snake cased, e.g. `_to` and `to`
+fn param_idents(params: &[Param], fallback: &str) -> Vec<Ident> {
+    let mut used = Vec::new();
+    params
+        .iter()
+        .enumerate()
+        .map(|(i, param)| {
+            let base = ident(&param.name, format!("{}{}", fallback, i));
+            let mut name = base.clone();
+            let mut n = 0usize;
+            while used.contains(&name.to_string()) {
+                n += 1;
+                name = format_ident!("{}_{}", base, n);
+            }
+            used.push(name.to_string());
+            name
+        })
+        .collect()
+}
+
+// the rust struct of a qualified solidity struct name, `Pool.Order` is `Order`
+fn struct_ident(name: &str) -> Ident {
+    format_ident!(
+        "{}",
+        upper_camel_case(name.rsplit('.').next().unwrap_or(name))
+    )
+}
+
+#[derive(Debug, Clone)]
+enum Ty {
+    Address,
+    Bool,
+    Int(usize),
+    Uint(usize),
+    FixedBytes(usize),
+    Bytes,
+    String,
+    Function,
+    Array(Box<Ty>),
+    FixedArray(Box<Ty>, usize),
+    // the qualified struct name, from the `internalType`, and the fields
+    Tuple(Option<String>, Vec<Param>),
+}
+
+#[derive(Debug, Clone)]
+struct Param {
+    name: String,
+    ty: Ty,
+    indexed: bool,
+}
+
+fn parse_elementary(ty: &str) -> Result<Ty, String> {
+    let bits = |size: &str| -> Result<usize, String> {
+        if size.is_empty() {
+            return Ok(256);
+        }
+        match size.parse::<usize>() {
+            Ok(bits) if bits > 0 && bits <= 256 && bits % 8 == 0 => Ok(bits),
+            _ => Err(format!("unknown type `{}`", ty)),
+        }
+    };
+    let parsed = match ty {
+        "address" => Ty::Address,
+        "bool" => Ty::Bool,
+        "string" => Ty::String,
+        "bytes" => Ty::Bytes,
+        "function" => Ty::Function,
+        "byte" => Ty::FixedBytes(1),
+        _ => {
+            if let Some(size) = ty.strip_prefix("uint") {
+                Ty::Uint(bits(size)?)
+            } else if let Some(size) = ty.strip_prefix("int") {
+                Ty::Int(bits(size)?)
+            } else if let Some(size) = ty.strip_prefix("bytes") {
+                match size.parse::<usize>() {
+                    Ok(size) if size > 0 && size <= 32 => Ty::FixedBytes(size),
+                    _ => return Err(format!("unknown type `{}`", ty)),
+                }
+            } else {
+                return Err(format!("unknown type `{}`", ty));
+            }
+        }
+    };
+    Ok(parsed)
+}
+
+fn parse_param(value: &Value) -> Result<Param, String> {
+    let kind = value["type"]
+        .as_str()
+        .ok_or_else(|| "parameter without a type".to_string())?;
+    let (base, mut suffixes) = match kind.find('[') {
+        Some(start) => kind.split_at(start),
+        None => (kind, ""),
+    };
+    let mut ty = if base == "tuple" {
+        let components = value["components"]
+            .as_array()
+            .ok_or_else(|| format!("tuple `{}` without components", kind))?
+            .iter()
+            .map(parse_param)
+            .collect::<Result<Vec<_>, _>>()?;
+        // e.g. `struct Pool.Order[]`
+        let name = value["internalType"]
+            .as_str()
+            .and_then(|internal| internal.strip_prefix("struct "))
+            .map(|internal| internal.split('[').next().unwrap_or(internal).to_string());
+        Ty::Tuple(name, components)
+    } else {
+        parse_elementary(base)?
+    };
+    while !suffixes.is_empty() {
+        let end = suffixes
+            .find(']')
+            .ok_or_else(|| format!("unknown type `{}`", kind))?;
+        ty = match &suffixes[1..end] {
+            "" => Ty::Array(Box::new(ty)),
+            size => Ty::FixedArray(
+                Box::new(ty),
+                size.parse()
+                    .map_err(|_| format!("unknown type `{}`", kind))?,
+            ),
+        };
+        suffixes = &suffixes[end + 1..];
+    }
+    Ok(Param {
+        name: value["name"].as_str().unwrap_or_default().to_string(),
+        ty,
+        indexed: value["indexed"].as_bool().unwrap_or(false),
+    })
+}
+
+fn parse_params(value: &Value) -> Result<Vec<Param>, String> {
+    match value.as_array() {
+        Some(params) => params.iter().map(parse_param).collect(),
+        None => Ok(Vec::new()),
+    }
+}
+
+impl Ty {
+    fn canonical(&self) -> String {
+        match self {
+            Ty::Address => "address".to_string(),
+            Ty::Bool => "bool".to_string(),
+            Ty::Int(bits) => format!("int{}", bits),
+            Ty::Uint(bits) => format!("uint{}", bits),
+            Ty::FixedBytes(size) => format!("bytes{}", size),
+            Ty::Bytes => "bytes".to_string(),
+            Ty::String => "string".to_string(),
+            Ty::Function => "function".to_string(),
+            Ty::Array(ty) => format!("{}[]", ty.canonical()),
+            Ty::FixedArray(ty, size) => format!("{}[{}]", ty.canonical(), size),
+            Ty::Tuple(_, fields) => format!("({})", canonical_list(fields)),
+        }
+    }
+
+    // types an indexed event parameter is logged as is, rather than hashed
+    fn is_value_type(&self) -> bool {
+        !matches!(
+            self,
+            Ty::Bytes | Ty::String | Ty::Array(_) | Ty::FixedArray(..) | Ty::Tuple(..)
+        )
+    }
+
+    fn abi_type(&self) -> TokenStream {
+        match self {
+            Ty::Address => quote!(::hedera_rust_client::AbiType::Address),
+            Ty::Bool => quote!(::hedera_rust_client::AbiType::Bool),
+            Ty::Int(bits) => quote!(::hedera_rust_client::AbiType::Int(#bits)),
+            Ty::Uint(bits) => quote!(::hedera_rust_client::AbiType::Uint(#bits)),
+            Ty::FixedBytes(size) => quote!(::hedera_rust_client::AbiType::FixedBytes(#size)),
+            Ty::Bytes => quote!(::hedera_rust_client::AbiType::Bytes),
+            Ty::String => quote!(::hedera_rust_client::AbiType::String),
+            Ty::Function => quote!(::hedera_rust_client::AbiType::Function),
+            Ty::Array(ty) => {
+                let ty = ty.abi_type();
+                quote!(::hedera_rust_client::AbiType::Array(Box::new(#ty)))
+            }
+            Ty::FixedArray(ty, size) => {
+                let ty = ty.abi_type();
+                quote!(::hedera_rust_client::AbiType::FixedArray(Box::new(#ty), #size))
+            }
+            Ty::Tuple(_, fields) => {
+                let types = fields.iter().map(|field| field.ty.abi_type());
+                quote!(::hedera_rust_client::AbiType::Tuple(vec![#(#types),*]))
+            }
+        }
+    }
+
+    fn rust_type(&self) -> TokenStream {
+        match self {
+            Ty::Address => quote!([u8; 20]),
+            Ty::Bool => quote!(bool),
+            Ty::Int(bits) => match bits {
+                0..=8 => quote!(i8),
+                9..=16 => quote!(i16),
+                17..=32 => quote!(i32),
+                33..=64 => quote!(i64),
+                65..=128 => quote!(i128),
+                _ => quote!(::hedera_rust_client::BigInt),
+            },
+            Ty::Uint(bits) => match bits {
+                0..=8 => quote!(u8),
+                9..=16 => quote!(u16),
+                17..=32 => quote!(u32),
+                33..=64 => quote!(u64),
+                65..=128 => quote!(u128),
+                _ => quote!(::hedera_rust_client::BigUint),
+            },
+            Ty::FixedBytes(size) => quote!([u8; #size]),
+            Ty::Bytes => quote!(Vec<u8>),
+            Ty::String => quote!(String),
+            Ty::Function => quote!([u8; 24]),
+            Ty::Array(ty) | Ty::FixedArray(ty, _) => {
+                let ty = ty.rust_type();
+                quote!(Vec<#ty>)
+            }
+            Ty::Tuple(Some(name), _) => {
+                let name = struct_ident(name);
+                quote!(#name)
+            }
+            Ty::Tuple(None, fields) => {
+                let types = fields.iter().map(|field| field.ty.rust_type());
+                quote!((#(#types,)*))
+            }
+        }
+    }
+
+    // an expression turning `value`, of the rust type, into an `AbiValue`
+    fn encode(&self, value: TokenStream) -> TokenStream {
+        match self {
+            Ty::Address => quote!(::hedera_rust_client::AbiValue::Address(#value)),
+            Ty::Bool => quote!(::hedera_rust_client::AbiValue::Bool(#value)),
+            Ty::Int(_) => quote!(::hedera_rust_client::AbiValue::Int(
+                ::hedera_rust_client::BigInt::from(#value)
+            )),
+            Ty::Uint(_) => quote!(::hedera_rust_client::AbiValue::Uint(
+                ::hedera_rust_client::BigUint::from(#value)
+            )),
+            Ty::FixedBytes(_) => {
+                quote!(::hedera_rust_client::AbiValue::FixedBytes(#value.to_vec()))
+            }
+            Ty::Bytes => quote!(::hedera_rust_client::AbiValue::Bytes(#value)),
+            Ty::String => quote!(::hedera_rust_client::AbiValue::String(#value)),
+            Ty::Function => quote!(::hedera_rust_client::AbiValue::Function(#value)),
+            Ty::Array(ty) | Ty::FixedArray(ty, _) => {
+                let element = ty.encode(quote!(v));
+                quote!(::hedera_rust_client::AbiValue::Array(
+                    IntoIterator::into_iter(#value).map(|v| #element).collect()
+                ))
+            }
+            Ty::Tuple(name, fields) => {
+                let field_names = param_idents(fields, "field");
+                let values = fields.iter().enumerate().map(|(i, field)| {
+                    let member = match name {
+                        Some(_) => {
+                            let field_name = &field_names[i];
+                            quote!(v.#field_name)
+                        }
+                        None => {
+                            let index = Index::from(i);
+                            quote!(v.#index)
+                        }
+                    };
+                    field.ty.encode(member)
+                });
+                quote!({
+                    let v = #value;
+                    ::hedera_rust_client::AbiValue::Tuple(vec![#(#values),*])
+                })
+            }
+        }
+    }
+
+    // an expression turning the `AbiValue` `value` into the rust type, with
+    // `?` on mismatches
+    fn decode(&self, value: TokenStream) -> TokenStream {
+        match self {
+            Ty::Address => quote!(#value.into_address()?),
+            Ty::Bool => quote!(#value.into_bool()?),
+            Ty::Int(_) | Ty::Uint(_) => {
+                let ty = self.rust_type();
+                match self {
+                    Ty::Int(_) => quote!(#value.into_int::<#ty>()?),
+                    _ => quote!(#value.into_uint::<#ty>()?),
+                }
+            }
+            Ty::FixedBytes(size) => quote!(#value.into_fixed_bytes::<#size>()?),
+            Ty::Bytes => quote!(#value.into_bytes()?),
+            Ty::String => quote!(#value.into_string()?),
+            Ty::Function => quote!(#value.into_function()?),
+            Ty::Array(ty) | Ty::FixedArray(ty, _) => {
+                let element = ty.decode(quote!(v));
+                quote!(#value
+                    .into_array()?
+                    .into_iter()
+                    .map(|v| Ok(#element))
+                    .collect::<Result<Vec<_>, ::hedera_rust_client::HederaError>>()?)
+            }
+            Ty::Tuple(name, fields) => {
+                let len = fields.len();
+                let values = fields
+                    .iter()
+                    .map(|field| field.ty.decode(quote!(fields.next().unwrap())));
+                let built = match name {
+                    Some(name) => {
+                        let name = struct_ident(name);
+                        let names = param_idents(fields, "field");
+                        quote!(#name { #(#names: #values),* })
+                    }
+                    None => quote!((#(#values,)*)),
+                };
+                quote!({
+                    let mut fields = #value.into_tuple(#len)?.into_iter();
+                    #built
+                })
+            }
+        }
+    }
+
+    // the structs of this type and the types it contains, keyed by their
+    // rust name along with the qualified name they were first seen under;
+    // different structs sharing a rust name are an error
+    fn collect_structs(
+        &self,
+        structs: &mut BTreeMap<String, (String, Vec<Param>)>,
+    ) -> Result<(), String> {
+        match self {
+            Ty::Array(ty) | Ty::FixedArray(ty, _) => ty.collect_structs(structs)?,
+            Ty::Tuple(name, fields) => {
+                if let Some(name) = name {
+                    let rust_name = struct_ident(name).to_string();
+                    match structs.get(&rust_name) {
+                        Some((seen, seen_fields)) => {
+                            if field_signature(seen_fields) != field_signature(fields) {
+                                return Err(format!(
+                                    "structs `{}` and `{}` differ but would both be generated as `{}`",
+                                    seen, name, rust_name
+                                ));
+                            }
+                        }
+                        None => {
+                            structs.insert(rust_name, (name.clone(), fields.clone()));
+                        }
+                    }
+                }
+                for field in fields {
+                    field.ty.collect_structs(structs)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+// the names and rust types of the fields of a struct
+fn field_signature(fields: &[Param]) -> Vec<(String, String)> {
+    fields
+        .iter()
+        .map(|field| (field.name.clone(), field.ty.rust_type().to_string()))
+        .collect()
+}
+
+fn canonical_list(params: &[Param]) -> String {
+    params
+        .iter()
+        .map(|param| param.ty.canonical())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn keccak(signature: &str) -> Vec<u8> {
+    Keccak256::digest(signature.as_bytes()).to_vec()
+}
+
+fn gen_struct(name: &str, fields: &[Param]) -> TokenStream {
+    let name = format_ident!("{}", name);
+    let names = param_idents(fields, "field");
+    let types = fields.iter().map(|field| field.ty.rust_type());
+    quote! {
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct #name {
+            #(pub #names: #types,)*
+        }
+    }
+}
+
+// the arguments of a generated method and the `AbiType`s and `AbiValue`s
+// to encode them with
+fn gen_args(inputs: &[Param]) -> (Vec<TokenStream>, Vec<TokenStream>, Vec<TokenStream>) {
+    let mut args = Vec::new();
+    let mut types = Vec::new();
+    let mut values = Vec::new();
+    for (input, name) in inputs.iter().zip(param_idents(inputs, "arg")) {
+        let ty = input.ty.rust_type();
+        args.push(quote!(#name: #ty));
+        types.push(input.ty.abi_type());
+        values.push(input.ty.encode(quote!(#name)));
+    }
+    (args, types, values)
+}
+
+fn gen_function(
+    function: &Value,
+    method: &Ident,
+    decode_method: &Ident,
+) -> Result<TokenStream, String> {
+    let name = function["name"].as_str().unwrap_or_default();
+    let inputs = parse_params(&function["inputs"])?;
+    let outputs = parse_params(&function["outputs"])?;
+    let signature = format!("{}({})", name, canonical_list(&inputs));
+    let selector = &keccak(&signature)[..4];
+    let read_only = match function["stateMutability"].as_str() {
+        Some(mutability) => mutability == "view" || mutability == "pure",
+        None => function["constant"].as_bool().unwrap_or(false),
+    };
+    let builder = if read_only {
+        quote!(::hedera_rust_client::ContractCallQuery)
+    } else {
+        quote!(::hedera_rust_client::ContractExecuteTransaction)
+    };
+    let (args, types, values) = gen_args(&inputs);
+    let doc = format!(" A call of `{}`.", signature);
+
+    let mut tokens = quote! {
+        #[doc = #doc]
+        pub fn #method(&self, #(#args),*) -> Result<#builder, ::hedera_rust_client::HederaError> {
+            // encoded before any local could shadow an argument
+            let encoded = ::hedera_rust_client::abi_encode(&[#(#types),*], &[#(#values),*])?;
+            let mut data = vec![#(#selector),*];
+            data.extend(encoded);
+            let mut call = #builder::new();
+            call.set_contract_id(self.contract_id)?;
+            call.set_function_parameters(data)?;
+            Ok(call)
+        }
+    };
+
+    if !outputs.is_empty() {
+        let doc = format!(" Decodes the return values of `{}`.", signature);
+        let types = outputs.iter().map(|output| output.ty.abi_type());
+        let values = outputs
+            .iter()
+            .map(|output| output.ty.decode(quote!(outputs.next().unwrap())))
+            .collect::<Vec<_>>();
+        let rust_types = outputs
+            .iter()
+            .map(|output| output.ty.rust_type())
+            .collect::<Vec<_>>();
+        let (returns, value) = match outputs.len() {
+            1 => (quote!(#(#rust_types)*), quote!(#(#values)*)),
+            _ => (quote!((#(#rust_types,)*)), quote!((#(#values,)*))),
+        };
+        tokens.extend(quote! {
+            #[doc = #doc]
+            pub fn #decode_method(
+                result: &::hedera_rust_client::ContractFunctionResult,
+            ) -> Result<#returns, ::hedera_rust_client::HederaError> {
+                let mut outputs = result.decode(&[#(#types),*])?.into_iter();
+                Ok(#value)
+            }
+        });
+    }
+    Ok(tokens)
+}
+
+fn gen_constructor(constructor: Option<&Value>) -> Result<TokenStream, String> {
+    let inputs = match constructor {
+        Some(constructor) => parse_params(&constructor["inputs"])?,
+        None => Vec::new(),
+    };
+    let (args, types, values) = gen_args(&inputs);
+    Ok(quote! {
+        /// A contract creation with the constructor arguments set; the
+        /// bytecode and gas are left to the caller.
+        pub fn deploy(
+            #(#args),*
+        ) -> Result<::hedera_rust_client::ContractCreateTransaction, ::hedera_rust_client::HederaError> {
+            let mut create = ::hedera_rust_client::ContractCreateTransaction::new();
+            create.set_constructor_parameters_raw(
+                ::hedera_rust_client::abi_encode(&[#(#types),*], &[#(#values),*])?,
+            )?;
+            Ok(create)
+        }
+    })
+}
+
+// the event struct and its decoding
+fn gen_event(event: &Value, struct_name: &Ident) -> Result<TokenStream, String> {
+    let name = event["name"].as_str().unwrap_or_default();
+    let anonymous = event["anonymous"].as_bool().unwrap_or(false);
+    let inputs = parse_params(&event["inputs"])?;
+    let signature = format!("{}({})", name, canonical_list(&inputs));
+    let topic = keccak(&signature);
+
+    let mut fields = Vec::new();
+    let mut params = Vec::new();
+    let mut values = Vec::new();
+    for (input, field) in inputs.iter().zip(param_idents(&inputs, "field")) {
+        let param_name = &input.name;
+        let kind = input.ty.abi_type();
+        let indexed = input.indexed;
+        params.push(quote! {
+            ::hedera_rust_client::AbiParam {
+                name: #param_name.to_string(),
+                kind: #kind,
+                internal_type: None,
+                indexed: #indexed,
+            }
+        });
+        // indexed reference types are logged as their hash
+        let (ty, value) = if input.indexed && !input.ty.is_value_type() {
+            (
+                quote!([u8; 32]),
+                quote!(params.next().unwrap().into_fixed_bytes::<32>()?),
+            )
+        } else {
+            (
+                input.ty.rust_type(),
+                input.ty.decode(quote!(params.next().unwrap())),
+            )
+        };
+        fields.push(quote!(pub #field: #ty));
+        values.push(quote!(#field: #value));
+    }
+
+    let doc = format!(" The `{}` event.", signature);
+    Ok(quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct #struct_name {
+            #(#fields,)*
+        }
+
+        impl #struct_name {
+            /// The first topic of the event's logs.
+            pub const TOPIC: [u8; 32] = [#(#topic),*];
+
+            pub fn decode(
+                log: &::hedera_rust_client::ContractLogInfo,
+            ) -> Result<#struct_name, ::hedera_rust_client::HederaError> {
+                let event = ::hedera_rust_client::AbiEvent {
+                    name: #name.to_string(),
+                    inputs: vec![#(#params),*],
+                    anonymous: #anonymous,
+                };
+                let mut params = event
+                    .decode_log(log)?
+                    .params
+                    .into_iter()
+                    .map(|(_, value)| value);
+                Ok(#struct_name { #(#values,)* })
+            }
+        }
+    })
+}
+
+fn gen_contract(name: &Ident, path: &str, abi: &Value) -> Result<TokenStream, String> {
+    let entries = match abi {
+        Value::Array(entries) => entries,
+        Value::Object(artifact) => artifact
+            .get("abi")
+            .and_then(Value::as_array)
+            .ok_or_else(|| "expected an abi array or an artifact with an `abi`".to_string())?,
+        _ => return Err("expected an abi array or an artifact with an `abi`".to_string()),
+    };
+    let kind = |entry: &Value| entry["type"].as_str().unwrap_or("function").to_string();
+
+    let mut structs = BTreeMap::new();
+    for entry in entries {
+        for key in ["inputs", "outputs"] {
+            for param in parse_params(&entry[key])? {
+                param.ty.collect_structs(&mut structs)?;
+            }
+        }
+    }
+    let structs = structs
+        .iter()
+        .map(|(name, (_, fields))| gen_struct(name, fields));
+
+    let mut methods = Vec::new();
+    let mut used = RESERVED
+        .iter()
+        .map(|name| name.to_string())
+        .collect::<Vec<_>>();
+    for function in entries.iter().filter(|entry| kind(entry) == "function") {
+        let base = ident(
+            function["name"].as_str().unwrap_or_default(),
+            "call".to_string(),
+        );
+        let has_outputs = !parse_params(&function["outputs"])?.is_empty();
+        let mut method = base.clone();
+        let mut decode_method = format_ident!("decode_{}", method);
+        let mut n = 0usize;
+        // overloads, and functions named like another's decoder, get a
+        // numeric suffix
+        while used.contains(&method.to_string())
+            || (has_outputs && used.contains(&decode_method.to_string()))
+        {
+            n += 1;
+            method = format_ident!("{}_{}", base, n);
+            decode_method = format_ident!("decode_{}", method);
+        }
+        used.push(method.to_string());
+        if has_outputs {
+            used.push(decode_method.to_string());
+        }
+        methods.push(gen_function(function, &method, &decode_method)?);
+    }
+    let constructor = gen_constructor(entries.iter().find(|entry| kind(entry) == "constructor"))?;
+
+    let event_enum = format_ident!("{}Event", name);
+    let mut events = Vec::new();
+    let mut variants = Vec::new();
+    let mut used = Vec::new();
+    for event in entries.iter().filter(|entry| kind(entry) == "event") {
+        let base = upper_camel_case(event["name"].as_str().unwrap_or_default());
+        let mut variant = base.clone();
+        let mut n = 0;
+        while used.contains(&variant) {
+            n += 1;
+            variant = format!("{}{}", base, n);
+        }
+        used.push(variant.clone());
+        let variant = format_ident!("{}", variant);
+        let struct_name = format_ident!("{}Event", variant);
+        events.push(gen_event(event, &struct_name)?);
+        if !event["anonymous"].as_bool().unwrap_or(false) {
+            variants.push((variant, struct_name));
+        }
+    }
+    let decode_log = if variants.is_empty() {
+        quote!()
+    } else {
+        let names = variants.iter().map(|(variant, _)| variant);
+        let structs = variants.iter().map(|(_, name)| name);
+        let enum_doc = format!(" The events of `{}`.", name);
+        let arms = variants.iter().map(|(variant, struct_name)| {
+            quote! {
+                Some(topic) if topic[..] == #struct_name::TOPIC[..] => {
+                    Ok(#event_enum::#variant(#struct_name::decode(log)?))
+                }
+            }
+        });
+        quote! {
+            #[doc = #enum_doc]
+            #[derive(Debug, Clone, PartialEq, Eq)]
+            pub enum #event_enum {
+                #(#names(#structs),)*
+            }
+
+            impl #name {
+                /// Decodes a log of one of the contract's events.
+                pub fn decode_log(
+                    log: &::hedera_rust_client::ContractLogInfo,
+                ) -> Result<#event_enum, ::hedera_rust_client::HederaError> {
+                    match log.topic.first() {
+                        #(#arms)*
+                        topic => Err(::hedera_rust_client::HederaError::AbiEventNotFound(
+                            topic
+                                .map(|topic| topic.iter().map(|b| format!("{:02x}", b)).collect())
+                                .unwrap_or_default(),
+                        )),
+                    }
+                }
+            }
+        }
+    };
+
+    let doc = format!(" Bindings of the contract ABI `{}`.", path);
+    Ok(quote! {
+        #(#structs)*
+
+        #[doc = #doc]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        pub struct #name {
+            contract_id: ::hedera_rust_client::ContractId,
+        }
+
+        impl #name {
+            /// The ABI the bindings were generated from.
+            pub const ABI: &'static str = include_str!(#path);
+
+            pub fn new(contract_id: ::hedera_rust_client::ContractId) -> #name {
+                #name { contract_id }
+            }
+
+            pub fn contract_id(&self) -> ::hedera_rust_client::ContractId {
+                self.contract_id
+            }
+
+            #constructor
+
+            #(#methods)*
+        }
+
+        #(#events)*
+
+        #decode_log
+    })
+}
+
+pub fn gen_hedera_contract(input: ContractInput) -> TokenStream {
+    let relative = input.path.value();
+    let mut path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default());
+    path.push(&relative);
+
+    let name = match input.name {
+        Some(name) => name,
+        None => {
+            let file_name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .unwrap_or_default();
+            let stem = file_name.split('.').next().unwrap_or_default();
+            Ident::new(&upper_camel_case(stem), input.path.span())
+        }
+    };
+
+    let abi = std::fs::read_to_string(&path)
+        .map_err(|e| format!("unable to read `{}`: {}", path.display(), e))
+        .and_then(|json| {
+            serde_json::from_str::<Value>(&json).map_err(|e| format!("invalid abi json: {}", e))
+        });
+    match abi.and_then(|abi| gen_contract(&name, &path.to_string_lossy(), &abi)) {
+        Ok(tokens) => tokens,
+        Err(err) => syn::Error::new(input.path.span(), err).to_compile_error(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snake_case() {
+        assert_eq!(snake_case("balanceOf"), "balance_of");
+        assert_eq!(snake_case("ERC20Name"), "erc20_name");
+        assert_eq!(snake_case("_to"), "to");
+        assert_eq!(snake_case("getHTTPResponse"), "get_http_response");
+        assert_eq!(ident("type", String::new()).to_string(), "type_");
+        assert_eq!(ident("", "arg0".to_string()).to_string(), "arg0");
+    }
+
+    #[test]
+    fn test_parse_param() {
+        let param: Value = serde_json::from_str(
+            r#"{"name": "orders", "type": "tuple[2][]", "internalType": "struct Pool.Order[2][]",
+                "components": [{"name": "amount", "type": "uint128"}, {"name": "to", "type": "address"}]}"#,
+        )
+        .unwrap();
+        let param = parse_param(&param).unwrap();
+        assert_eq!(param.ty.canonical(), "(uint128,address)[2][]");
+        assert_eq!(param.ty.rust_type().to_string(), "Vec < Vec < Order > >");
+    }
+
+    fn contract(abi: &str) -> Result<String, String> {
+        let abi = serde_json::from_str(abi).unwrap();
+        gen_contract(&format_ident!("Pool"), "Pool.abi.json", &abi).map(|tokens| tokens.to_string())
+    }
+
+    #[test]
+    fn test_struct_name_collision() {
+        let order = |internal: &str, field: &str| {
+            format!(
+                r#"{{"name": "order", "type": "tuple", "internalType": "struct {}",
+                    "components": [{{"name": "{}", "type": "uint64"}}]}}"#,
+                internal, field
+            )
+        };
+        let abi = |a: String, b: String| {
+            format!(
+                r#"[{{"type": "function", "name": "a", "inputs": [{}]}},
+                    {{"type": "function", "name": "b", "inputs": [{}]}}]"#,
+                a, b
+            )
+        };
+
+        let err =
+            contract(&abi(order("A.Order", "amount"), order("B.Order", "price"))).unwrap_err();
+        assert!(err.contains("`A.Order` and `B.Order`"), "{}", err);
+        let tokens =
+            contract(&abi(order("A.Order", "amount"), order("B.Order", "amount"))).unwrap();
+        assert_eq!(tokens.matches("pub struct Order").count(), 1);
+    }
+
+    #[test]
+    fn test_name_collisions() {
+        let tokens = contract(
+            r#"[{"type": "function", "name": "price", "inputs": [],
+                 "outputs": [{"name": "", "type": "uint64"}]},
+                {"type": "function", "name": "decodePrice", "inputs": [
+                    {"name": "_to", "type": "address"}, {"name": "to", "type": "address"}]}]"#,
+        )
+        .unwrap();
+        assert!(tokens.contains("pub fn decode_price ("));
+        assert!(
+            tokens.contains("pub fn decode_price_1 (& self , to : [u8 ; 20] , to_1 : [u8 ; 20])")
+        );
+    }
+}
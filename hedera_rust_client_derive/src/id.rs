@@ -71,6 +71,8 @@ impl ToTokens for Id {
                     Ok(#ident::new(shard_num, realm_num, num, checksum))
                 }
             }
+
+            serde_from_str!(#ident);
         });
     }
 }
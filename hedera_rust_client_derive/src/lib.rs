@@ -3,6 +3,7 @@ use proc_macro::TokenStream;
 use quote::{quote, ToTokens};
 use syn::{parse_macro_input, DeriveInput};
 
+mod contract;
 mod id;
 mod query_base;
 mod query_executable;
@@ -242,3 +243,24 @@ pub fn transaction_chunked_schedule(input: TokenStream) -> TokenStream {
     let expanded = transaction_chunked_schedule::gen_transaction_chunked_schedule(&ast);
     TokenStream::from(expanded)
 }
+
+////////////////////////////////////////////////////////////////////////////////
+// Contract bindings
+////////////////////////////////////////////////////////////////////////////////
+/// Generates typed bindings from a Solidity ABI JSON file, relative to the
+/// crate root: `hedera_contract!("abi/Token.json")` or, to pick the struct
+/// name, `hedera_contract!(Token, "abi/Token.json")`.
+///
+/// The contract struct has a method per function returning a
+/// `ContractCallQuery` for `view` and `pure` functions and a
+/// `ContractExecuteTransaction` otherwise, with the contract id and encoded
+/// arguments set, and a `decode_<function>` to decode the return values of a
+/// `ContractFunctionResult`. `deploy` sets the constructor arguments of a
+/// `ContractCreateTransaction`. Solidity structs become structs named after
+/// them, each event a `<Event>Event` struct, and `decode_log` decodes any of
+/// them into the `<Contract>Event` enum.
+#[proc_macro]
+pub fn hedera_contract(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as contract::ContractInput);
+    TokenStream::from(contract::gen_hedera_contract(input))
+}
@@ -32,6 +32,22 @@ pub fn gen_query_base(ast: &DeriveInput) -> TokenStream {
                 self.query.max_backoff = Some(backoff);
                 Ok(self)
             }
+            pub fn set_grpc_deadline(&mut self, deadline: std::time::Duration) -> std::result::Result<&mut Self, crate::error::HederaError> {
+                self.query.grpc_deadline = Some(deadline);
+                Ok(self)
+            }
+            pub fn set_request_timeout(&mut self, timeout: std::time::Duration) -> std::result::Result<&mut Self, crate::error::HederaError> {
+                self.query.request_timeout = Some(timeout);
+                Ok(self)
+            }
+            pub fn set_retry_policy(&mut self, retry_policy: std::sync::Arc<dyn crate::retry_policy::RetryPolicy>) -> std::result::Result<&mut Self, crate::error::HederaError> {
+                self.query.hooks.retry_policy = Some(retry_policy);
+                Ok(self)
+            }
+            pub fn add_middleware(&mut self, middleware: std::sync::Arc<dyn crate::execute_middleware::ExecuteMiddleware>) -> std::result::Result<&mut Self, crate::error::HederaError> {
+                self.query.hooks.middleware.push(middleware);
+                Ok(self)
+            }
         }
         impl std::default::Default for #name {
             fn default() -> Self {
@@ -74,7 +74,7 @@ impl ToTokens for QueryExecute {
 
                     self.query.next_payment_transaction_index = 0;
                     self.query.payment_transactions = Vec::new();
-                    self.query.generate_payments_for_node_account_ids(client, cost)?;
+                    self.query.generate_payments_for_node_account_ids(client, cost).await?;
                     crate::query::query_make_request_header(&self.query, &mut self.header)?;
                     self.build_query()?;
 
@@ -48,7 +48,7 @@ impl ToTokens for QueryGetCost {
         tokens.extend(quote! {
             impl #ident {
                 pub async fn get_cost(&mut self, client: &crate::client::Client) -> std::result::Result<crate::hbar::Hbar, crate::error::HederaError> {
-                    crate::query::cost_query_make_request_header(&mut self.header, client)?;
+                    crate::query::cost_query_make_request_header(&mut self.header, client).await?;
                     self.query.node_account_ids = client.node_account_ids_for_execute().await;
                     self.build_query()?;
                 
@@ -23,11 +23,17 @@ pub fn gen_transaction_base(ast: &DeriveInput) -> TokenStream {
                 self.transaction.signatures()
             }
 
+            pub fn key_requirement(&self, key: &crate::key::Key) -> std::result::Result<std::collections::HashMap<crate::account_id::AccountId, crate::key::KeyRequirement>, crate::error::HederaError> {
+                self.transaction.key_requirement(key)
+            }
+
             pub fn sign(&mut self, private_key: &crate::crypto::PrivateKey) -> std::result::Result<&mut Self, crate::error::HederaError> {
                 self.transaction.sign(private_key)?;
                 Ok(self)
             }
 
+            /// Signs with a closure, without an async runtime. Keys held
+            /// outside the process use `sign_with_signer`.
             pub fn sign_with<F: Fn(&Vec<u8>) -> crate::crypto::Signature>(
                 &mut self,
                 public_key: &crate::crypto::PublicKey,
@@ -37,6 +43,12 @@ pub fn gen_transaction_base(ast: &DeriveInput) -> TokenStream {
                 Ok(self)
             }
 
+            /// Signs with a `Signer`, e.g. an HSM or a `RemoteSigner`.
+            pub async fn sign_with_signer(&mut self, signer: &dyn crate::signer::Signer) -> std::result::Result<&mut Self, crate::error::HederaError> {
+                self.transaction.sign_with_signer(signer).await?;
+                Ok(self)
+            }
+
             pub async fn sign_with_operator(&mut self, client: &crate::client::Client) -> std::result::Result<&mut Self, crate::error::HederaError> {
                 self.transaction.sign_with_operator(client).await?;
                 Ok(self)
@@ -132,6 +144,22 @@ pub fn gen_transaction_base(ast: &DeriveInput) -> TokenStream {
                 self.transaction.max_backoff = Some(backoff);
                 Ok(self)
             }
+            pub fn set_grpc_deadline(&mut self, deadline: std::time::Duration) -> std::result::Result<&mut Self, crate::error::HederaError> {
+                self.transaction.grpc_deadline = Some(deadline);
+                Ok(self)
+            }
+            pub fn set_request_timeout(&mut self, timeout: std::time::Duration) -> std::result::Result<&mut Self, crate::error::HederaError> {
+                self.transaction.request_timeout = Some(timeout);
+                Ok(self)
+            }
+            pub fn set_retry_policy(&mut self, retry_policy: std::sync::Arc<dyn crate::retry_policy::RetryPolicy>) -> std::result::Result<&mut Self, crate::error::HederaError> {
+                self.transaction.hooks.retry_policy = Some(retry_policy);
+                Ok(self)
+            }
+            pub fn add_middleware(&mut self, middleware: std::sync::Arc<dyn crate::execute_middleware::ExecuteMiddleware>) -> std::result::Result<&mut Self, crate::error::HederaError> {
+                self.transaction.hooks.middleware.push(middleware);
+                Ok(self)
+            }
         }
         impl std::fmt::Display for #name {
             fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
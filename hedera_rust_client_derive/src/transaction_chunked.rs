@@ -58,6 +58,70 @@ impl ToTokens for TransactionChunked {
                     self.transaction.set_transaction_body_data(Some(self.services.proto()?));
                     Ok(())
                 }
+
+                pub fn estimate_fee(
+                    &self,
+                    estimator: &crate::fee_estimator::FeeEstimator,
+                    signatures: usize
+                ) -> std::result::Result<crate::fee_estimator::FeeEstimate, crate::error::HederaError> {
+                    if self.is_frozen() {
+                        return estimator.estimate(&self.transaction, signatures);
+                    }
+                    // each chunk is its own transaction, and is charged as one
+                    let mut tx = self.clone();
+                    let data_len = tx.data.len();
+                    let required_chunks = ((data_len + (CHUNK_SIZE - 1)) / CHUNK_SIZE).max(1);
+                    let initial_transaction_id = match tx.transaction.transaction_id() {
+                        Ok(id) => id,
+                        Err(_) => crate::TransactionId::generate(crate::AccountId::simple(1001)),
+                    }.to_proto()?;
+                    let mut chunks = Vec::with_capacity(required_chunks);
+                    for i in 0..required_chunks {
+                        let start_index = i * CHUNK_SIZE;
+                        let end_index = (start_index + CHUNK_SIZE).min(data_len);
+                        tx.on_freeze_chunk(
+                            Some(initial_transaction_id.clone()),
+                            start_index,
+                            end_index,
+                            i,
+                            required_chunks
+                        )?;
+                        let mut transaction = tx.transaction.clone();
+                        transaction.set_transaction_body_data(Some(tx.services.proto()?));
+                        chunks.push(transaction);
+                    }
+                    estimator.estimate_chunks(&chunks, signatures)
+                }
+
+                /// Rebuilds the transaction decoded from bytes, joining the
+                /// contents of its chunks.
+                pub(crate) fn from_transaction(transaction: crate::transaction::Transaction) -> std::result::Result<Self, crate::error::HederaError> {
+                    let chunks = transaction.chunk_data()?;
+                    if chunks.is_empty() {
+                        return Err(crate::error::HederaError::UnsupportedTransactionBodyType);
+                    }
+                    let mut tx = Self::new();
+                    tx.max_chunks = tx.max_chunks.max(chunks.len());
+                    for (_, data) in chunks {
+                        tx.on_decode_chunk(Proto::from_data(data)?);
+                    }
+                    tx.transaction = transaction;
+                    Ok(tx)
+                }
+
+                /// What signing the transaction approves, see `TransactionDescription`.
+                /// The data is described whole, as a single chunk.
+                pub fn describe(&self) -> std::result::Result<crate::transaction_description::TransactionDescription, crate::error::HederaError> {
+                    let mut tx = self.clone();
+                    let data_len = tx.data.len();
+                    tx.on_freeze_chunk(None, 0, data_len, 0, 1)?;
+                    crate::transaction_description::TransactionDescription::new(stringify!(#ident), &tx.transaction, tx.services.proto()?)
+                }
+
+                pub(crate) fn serialize_data<S: serde::Serializer>(data: crate::proto::services::transaction_body::Data, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                    let services = Proto::from_data(data).map_err(serde::ser::Error::custom)?;
+                    serde::Serialize::serialize(&services, serializer)
+                }
     
                 pub async fn freeze_with(&mut self, client: Option<&crate::client::Client>) -> std::result::Result<&mut Self, crate::error::HederaError> {
                     if self.is_frozen() {
@@ -79,16 +143,33 @@ impl ToTokens for TransactionChunked {
                         return Err(crate::error::HederaError::MaxChunksExceeded(required_chunks, self.max_chunks));
                     }
     
+                    if self.transaction.node_account_ids_len() == 0 {
+                        match client {
+                            Some(cli) => self.transaction.set_node_account_ids(cli.node_account_ids_for_execute().await)?,
+                            None => return Err(crate::error::HederaError::TransactionNodeAccountIdOrClientNotSet),
+                        }
+                    }
+
                     let initial_transaction_id = self.transaction.transaction_id()?;
                     let mut next_transaction_id = initial_transaction_id.clone();
                     let initial_transaction_id = initial_transaction_id.to_proto()?;
                     
                     self.transaction.clear_transactions();
     
+                    // every chunk's id is added before the first chunk freezes the transaction
+                    let mut chunk_transaction_ids = Vec::with_capacity(required_chunks);
+                    for _ in 0..required_chunks {
+                        self.transaction.add_transaction_id(next_transaction_id.clone())?;
+                        chunk_transaction_ids.push(next_transaction_id.clone());
+                        if let Some(start) = next_transaction_id.transaction_valid_start {
+                            next_transaction_id.transaction_valid_start = Some(start + chrono::Duration::nanoseconds(1));
+                        }
+                    }
+
                     let mut start_index = 0usize;
                     let mut end_index = 0usize;
     
-                    for i in 0..required_chunks {
+                    for (i, chunk_transaction_id) in chunk_transaction_ids.into_iter().enumerate() {
                         start_index = i * CHUNK_SIZE;
                         end_index = start_index + CHUNK_SIZE;
     
@@ -96,8 +177,7 @@ impl ToTokens for TransactionChunked {
                             end_index = data_len;
                         }
     
-                        self.transaction.add_transaction_id(next_transaction_id.clone())?;
-                        self.transaction.set_transaction_body_transaction_id(next_transaction_id.clone());
+                        self.transaction.set_transaction_body_transaction_id(chunk_transaction_id);
     
                         // prepare inner services data
                         self.on_freeze_chunk(
@@ -114,10 +194,6 @@ impl ToTokens for TransactionChunked {
                         for node_account_id in self.transaction.node_account_ids().into_iter() {
                             self.transaction.freeze_with_account_id(node_account_id)?;
                         }
-    
-                        if let Some(start) = next_transaction_id.transaction_valid_start {
-                            next_transaction_id.transaction_valid_start = Some(start + chrono::Duration::nanoseconds(1));
-                        }
                     }
                     Ok(self)
                 }
@@ -139,6 +215,7 @@ impl ToTokens for TransactionChunked {
                     let mut list: Vec<crate::transaction_response::TransactionResponse> = Vec::with_capacity(size);
     
                     for i in 0..size {
+                        self.transaction.set_next_transaction_index(i);
                         let res = crate::executor::execute(
                             crate::executor::Request::Transaction(self.transaction.clone()),
                             client,
@@ -150,7 +227,7 @@ impl ToTokens for TransactionChunked {
                             #should_retry,
                             #map_response
                         ).await?;
-                        list[i] = res.to_transaction()?;
+                        list.push(res.to_transaction()?);
                     }
                     Ok(list)
                 }
@@ -163,6 +240,16 @@ impl ToTokens for TransactionChunked {
                     Ok(list[0].clone())
                 }
             }
+
+            // written with all of its data, as a single chunk
+            impl serde::Serialize for #ident {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                    let mut tx = self.clone();
+                    let data_len = tx.data.len();
+                    tx.on_freeze_chunk(None, 0, data_len, 0, 1).map_err(serde::ser::Error::custom)?;
+                    tx.transaction.serialize_with_data(stringify!(#ident), &tx.services, serializer)
+                }
+            }
         });
     }
 }
@@ -74,6 +74,43 @@ impl ToTokens for TransactionExecutable {
                     self.transaction.set_transaction_body_data(Some(self.services.proto()?));
                     Ok(())
                 }
+
+                pub fn estimate_fee(
+                    &self,
+                    estimator: &crate::fee_estimator::FeeEstimator,
+                    signatures: usize
+                ) -> std::result::Result<crate::fee_estimator::FeeEstimate, crate::error::HederaError> {
+                    if self.is_frozen() {
+                        return estimator.estimate(&self.transaction, signatures);
+                    }
+                    let mut transaction = self.transaction.clone();
+                    transaction.set_transaction_body_data(Some(self.services.proto()?));
+                    estimator.estimate(&transaction, signatures)
+                }
+
+                /// Rebuilds the transaction decoded from bytes, every node's body
+                /// having to carry the same data.
+                pub(crate) fn from_transaction(transaction: crate::transaction::Transaction) -> std::result::Result<Self, crate::error::HederaError> {
+                    let mut chunks = transaction.chunk_data()?.into_iter();
+                    let (_, data) = chunks.next().ok_or(crate::error::HederaError::UnsupportedTransactionBodyType)?;
+                    if let Some((i, _)) = chunks.find(|(_, chunk)| *chunk != data) {
+                        return Err(crate::error::HederaError::TransactionBodyMismatch(i));
+                    }
+                    let mut tx = Self::new();
+                    tx.services = Proto::from_data(data)?;
+                    tx.transaction = transaction;
+                    Ok(tx)
+                }
+
+                /// What signing the transaction approves, see `TransactionDescription`.
+                pub fn describe(&self) -> std::result::Result<crate::transaction_description::TransactionDescription, crate::error::HederaError> {
+                    crate::transaction_description::TransactionDescription::new(stringify!(#ident), &self.transaction, self.services.proto()?)
+                }
+
+                pub(crate) fn serialize_data<S: serde::Serializer>(data: crate::proto::services::transaction_body::Data, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                    let services = Proto::from_data(data).map_err(serde::ser::Error::custom)?;
+                    serde::Serialize::serialize(&services, serializer)
+                }
                 
                 pub async fn execute_async(&mut self, client: &crate::client::Client) -> std::result::Result<crate::executor::IntermediateResponse, crate::error::HederaError> {
                     if !self.is_frozen() {
@@ -102,6 +139,12 @@ impl ToTokens for TransactionExecutable {
                     Ok(res)
                 }
             }
+
+            impl serde::Serialize for #ident {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+                    self.transaction.serialize_with_data(stringify!(#ident), &self.services, serializer)
+                }
+            }
         });
     }
 }
@@ -16,6 +16,8 @@ pub struct FromStructField {
     #[darling(default)]
     to_proto_with_fn: Option<syn::Path>,
     #[darling(default)]
+    from_proto_with_fn: Option<syn::Path>,
+    #[darling(default)]
     rename: Option<syn::Ident>,
 }
 
@@ -49,7 +51,7 @@ impl ToTokens for TransactionProto {
 
         // Generate the actual values to fill the fromat string.
         let field_list = fields
-            .into_iter()
+            .iter()
             .enumerate()
             .map(|(i, f)| {
                 let field_ident = if let Some(field) = &f.rename {
@@ -79,8 +81,43 @@ impl ToTokens for TransactionProto {
             })
             .collect::<Vec<_>>();
 
+        // the inverse of `field_list`, for decoding bodies
+        let from_field_list = fields
+            .iter()
+            .map(|f| {
+                let self_ident = f.ident.as_ref().expect("Proto fields should be named");
+                let proto_ident = f.rename.as_ref().unwrap_or(self_ident);
+                if let Some(from_fn) = &f.from_proto_with_fn {
+                    quote!(#self_ident: #from_fn(body.#proto_ident)?)
+                } else if f.to_proto {
+                    quote!(#self_ident: std::convert::TryFrom::try_from(body.#proto_ident)?)
+                } else if f.to_option_proto {
+                    quote!(#self_ident: body.#proto_ident.map(std::convert::TryFrom::try_from).transpose()?)
+                } else if f.to_proto_vec.is_some() {
+                    quote!(#self_ident: body.#proto_ident.into_iter().map(std::convert::TryFrom::try_from).collect::<std::result::Result<Vec<_>, _>>()?)
+                } else if f.to_proto_with_fn.is_some() {
+                    panic!("to_proto_with_fn fields need a from_proto_with_fn")
+                } else {
+                    quote!(#self_ident: body.#proto_ident)
+                }
+            })
+            .collect::<Vec<_>>();
+
         tokens.extend(quote! {
             impl #ident {
+                pub fn from_proto(body: crate::proto::services::#proto_type) -> std::result::Result<Self, crate::error::HederaError> {
+                    Ok(#ident {
+                        #(#from_field_list),*
+                    })
+                }
+
+                pub fn from_data(data: crate::proto::services::transaction_body::Data) -> std::result::Result<Self, crate::error::HederaError> {
+                    match data {
+                        crate::proto::services::transaction_body::Data::#proto_enum(body) => Self::from_proto(body),
+                        _ => Err(crate::error::HederaError::UnsupportedTransactionBodyType),
+                    }
+                }
+
                 pub fn proto(&self) -> std::result::Result<crate::proto::services::transaction_body::Data, crate::error::HederaError> {
                     use crate::proto::ToProto;
                     let data = crate::proto::services::transaction_body::Data::#proto_enum(crate::proto::services::#proto_type {